@@ -4,6 +4,11 @@ use serde::{Deserialize, Serialize};
 use worker::*;
 
 const TICKTICK_TOKEN_URL: &str = "https://ticktick.com/oauth/token";
+/// Optional KV namespace holding per-IP request counters.
+const RATE_LIMIT_BINDING: &str = "RATE_LIMIT_KV";
+const DEFAULT_RATE_LIMIT: u32 = 30;
+/// Counters are fixed windows of this many seconds. KV TTLs cannot go below 60.
+const RATE_LIMIT_WINDOW_SECS: u64 = 60;
 
 #[derive(Debug, Deserialize)]
 struct ExchangeRequest {
@@ -29,25 +34,127 @@ struct TickTickTokenResponse {
     expires_in: Option<i64>,
 }
 
+/// OAuth error body TickTick returns from its token endpoint.
+#[derive(Debug, Default, Deserialize)]
+struct UpstreamErrorBody {
+    #[serde(default)]
+    error: Option<String>,
+    #[serde(default)]
+    error_description: Option<String>,
+}
+
+/// Error body the broker sends to the CLI. `upstream_status` is set when the failure came
+/// from TickTick rather than from the broker itself.
+#[derive(Debug, Serialize)]
+struct BrokerError {
+    error: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error_description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    upstream_status: Option<u16>,
+    request_id: String,
+}
+
+#[derive(Serialize)]
+struct LogEvent<'a> {
+    request_id: &'a str,
+    path: &'a str,
+    status: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    upstream_status: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'a str>,
+}
+
+/// One JSON line per request. Only ids, paths, and status codes are logged; request
+/// bodies and tokens never are.
+fn log_event(event: &LogEvent<'_>) {
+    if let Ok(line) = serde_json::to_string(event) {
+        console_log!("{}", line);
+    }
+}
+
+fn request_id(req: &Request) -> String {
+    ["cf-ray", "x-request-id"]
+        .iter()
+        .find_map(|name| req.headers().get(name).ok().flatten())
+        .filter(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| "-".to_string())
+}
+
+fn json_error(
+    status: u16,
+    error: &str,
+    error_description: Option<String>,
+    upstream_status: Option<u16>,
+    request_id: &str,
+) -> Result<Response> {
+    let body = BrokerError {
+        error: error.to_string(),
+        error_description,
+        upstream_status,
+        request_id: request_id.to_string(),
+    };
+    let mut response = Response::from_json(&body)?.with_status(status);
+    response.headers_mut().set("Cache-Control", "no-store")?;
+    Ok(response)
+}
+
+fn upstream_error(status: u16, body: &str) -> (String, Option<String>) {
+    let parsed = serde_json::from_str::<UpstreamErrorBody>(body).unwrap_or_default();
+    let error = parsed
+        .error
+        .filter(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| "upstream_error".to_string());
+    let description = parsed
+        .error_description
+        .filter(|value| !value.trim().is_empty())
+        .or_else(|| {
+            let body = body.trim();
+            (!body.is_empty() && !body.starts_with('{'))
+                .then(|| format!("TickTick token endpoint returned {}: {}", status, body))
+        });
+    (error, description)
+}
+
 #[event(fetch, respond_with_errors)]
 async fn fetch(req: Request, env: Env, _ctx: Context) -> Result<Response> {
     Router::new()
         .get_async("/health", |_req, _ctx| async move { Response::ok("ok") })
         .post_async("/v1/oauth/exchange", |mut req, ctx| async move {
-            if let Some(response) = authorize_request(&req, &ctx)? {
+            let request_id = request_id(&req);
+            let path = req.path();
+            if let Some(response) = authorize_request(&req, &ctx, &request_id, &path)? {
+                return Ok(response);
+            }
+            if let Some(response) = enforce_rate_limit(&req, &ctx, &request_id, &path).await? {
                 return Ok(response);
             }
 
             let payload = match req.json::<ExchangeRequest>().await {
                 Ok(payload) => payload,
-                Err(_) => return Response::error("Invalid JSON body", 400),
+                Err(_) => {
+                    return reject(
+                        400,
+                        "invalid_request",
+                        "Invalid JSON body",
+                        &request_id,
+                        &path,
+                    )
+                }
             };
 
             if is_blank(&payload.code)
                 || is_blank(&payload.code_verifier)
                 || is_blank(&payload.redirect_uri)
             {
-                return Response::error("Missing code, code_verifier, or redirect_uri", 400);
+                return reject(
+                    400,
+                    "invalid_request",
+                    "Missing code, code_verifier, or redirect_uri",
+                    &request_id,
+                    &path,
+                );
             }
 
             let body = format!(
@@ -57,23 +164,39 @@ async fn fetch(req: Request, env: Env, _ctx: Context) -> Result<Response> {
                 urlencoding::encode(payload.code_verifier.trim())
             );
 
-            let token = exchange_token(&ctx, body).await?;
-            let mut response = Response::from_json(&token)?;
-            response.headers_mut().set("Cache-Control", "no-store")?;
-            Ok(response)
+            exchange_token(&ctx, body, &request_id, &path).await
         })
         .post_async("/v1/oauth/refresh", |mut req, ctx| async move {
-            if let Some(response) = authorize_request(&req, &ctx)? {
+            let request_id = request_id(&req);
+            let path = req.path();
+            if let Some(response) = authorize_request(&req, &ctx, &request_id, &path)? {
+                return Ok(response);
+            }
+            if let Some(response) = enforce_rate_limit(&req, &ctx, &request_id, &path).await? {
                 return Ok(response);
             }
 
             let payload = match req.json::<RefreshRequest>().await {
                 Ok(payload) => payload,
-                Err(_) => return Response::error("Invalid JSON body", 400),
+                Err(_) => {
+                    return reject(
+                        400,
+                        "invalid_request",
+                        "Invalid JSON body",
+                        &request_id,
+                        &path,
+                    )
+                }
             };
 
             if is_blank(&payload.refresh_token) {
-                return Response::error("Missing refresh_token", 400);
+                return reject(
+                    400,
+                    "invalid_request",
+                    "Missing refresh_token",
+                    &request_id,
+                    &path,
+                );
             }
 
             let body = format!(
@@ -81,16 +204,41 @@ async fn fetch(req: Request, env: Env, _ctx: Context) -> Result<Response> {
                 urlencoding::encode(payload.refresh_token.trim())
             );
 
-            let token = exchange_token(&ctx, body).await?;
-            let mut response = Response::from_json(&token)?;
-            response.headers_mut().set("Cache-Control", "no-store")?;
-            Ok(response)
+            exchange_token(&ctx, body, &request_id, &path).await
         })
         .run(req, env)
         .await
 }
 
-fn authorize_request(req: &Request, ctx: &RouteContext<()>) -> Result<Option<Response>> {
+fn reject(
+    status: u16,
+    error: &str,
+    description: &str,
+    request_id: &str,
+    path: &str,
+) -> Result<Response> {
+    log_event(&LogEvent {
+        request_id,
+        path,
+        status,
+        upstream_status: None,
+        error: Some(error),
+    });
+    json_error(
+        status,
+        error,
+        Some(description.to_string()),
+        None,
+        request_id,
+    )
+}
+
+fn authorize_request(
+    req: &Request,
+    ctx: &RouteContext<()>,
+    request_id: &str,
+    path: &str,
+) -> Result<Option<Response>> {
     let expected_key = match ctx.var("BROKER_API_KEY") {
         Ok(value) => value.to_string(),
         Err(_) => return Ok(None),
@@ -104,15 +252,107 @@ fn authorize_request(req: &Request, ctx: &RouteContext<()>) -> Result<Option<Res
         .to_string();
 
     if provided != expected_key {
-        return Ok(Some(Response::error("Unauthorized", 401)?));
+        return reject(
+            401,
+            "unauthorized",
+            "Missing or invalid x-broker-key",
+            request_id,
+            path,
+        )
+        .map(Some);
     }
 
     Ok(None)
 }
 
-async fn exchange_token(ctx: &RouteContext<()>, body: String) -> Result<TickTickTokenResponse> {
-    let client_id = ctx.secret("TICKTICK_CLIENT_ID")?.to_string();
-    let client_secret = ctx.secret("TICKTICK_CLIENT_SECRET")?.to_string();
+fn rate_limit(ctx: &RouteContext<()>) -> u32 {
+    ctx.var("RATE_LIMIT")
+        .ok()
+        .and_then(|value| value.to_string().trim().parse::<u32>().ok())
+        .filter(|limit| *limit > 0)
+        .unwrap_or(DEFAULT_RATE_LIMIT)
+}
+
+/// Counts requests per `cf-connecting-ip` in fixed windows and answers 429 once the
+/// `RATE_LIMIT` threshold is reached. Without a KV binding the broker is not limited.
+/// KV is eventually consistent, so the limit is approximate under bursts.
+async fn enforce_rate_limit(
+    req: &Request,
+    ctx: &RouteContext<()>,
+    request_id: &str,
+    path: &str,
+) -> Result<Option<Response>> {
+    let Ok(store) = ctx.kv(RATE_LIMIT_BINDING) else {
+        return Ok(None);
+    };
+    let Some(ip) = req
+        .headers()
+        .get("cf-connecting-ip")?
+        .filter(|value| !value.trim().is_empty())
+    else {
+        return Ok(None);
+    };
+
+    let now = Date::now().as_millis() / 1000;
+    let window = now / RATE_LIMIT_WINDOW_SECS;
+    let key = format!("rate:{}:{}", ip.trim(), window);
+    let count = store
+        .get(&key)
+        .text()
+        .await
+        .ok()
+        .flatten()
+        .and_then(|value| value.parse::<u32>().ok())
+        .unwrap_or(0);
+
+    if count >= rate_limit(ctx) {
+        let retry_after = ((window + 1) * RATE_LIMIT_WINDOW_SECS)
+            .saturating_sub(now)
+            .max(1);
+        let mut response = reject(
+            429,
+            "rate_limited",
+            "Too many token requests; retry later",
+            request_id,
+            path,
+        )?;
+        response
+            .headers_mut()
+            .set("Retry-After", &retry_after.to_string())?;
+        return Ok(Some(response));
+    }
+
+    // A failed write only loosens the limit; it should not fail the token request.
+    if let Ok(put) = store.put(&key, (count + 1).to_string()) {
+        let _ = put
+            .expiration_ttl(RATE_LIMIT_WINDOW_SECS * 2)
+            .execute()
+            .await;
+    }
+    Ok(None)
+}
+
+async fn exchange_token(
+    ctx: &RouteContext<()>,
+    body: String,
+    request_id: &str,
+    path: &str,
+) -> Result<Response> {
+    let (client_id, client_secret) = match (
+        ctx.secret("TICKTICK_CLIENT_ID"),
+        ctx.secret("TICKTICK_CLIENT_SECRET"),
+    ) {
+        (Ok(client_id), Ok(client_secret)) => (client_id.to_string(), client_secret.to_string()),
+        _ => {
+            return reject(
+                500,
+                "broker_misconfigured",
+                "TICKTICK_CLIENT_ID and TICKTICK_CLIENT_SECRET must be set",
+                request_id,
+                path,
+            )
+        }
+    };
 
     let basic_auth = format!(
         "Basic {}",
@@ -133,20 +373,48 @@ async fn exchange_token(ctx: &RouteContext<()>, body: String) -> Result<TickTick
 
     let status = upstream.status_code();
     if status >= 400 {
-        let details = upstream
-            .text()
-            .await
-            .unwrap_or_else(|_| "Token exchange failed".to_string());
-        return Err(Error::RustError(format!(
-            "TickTick token endpoint returned {}: {}",
-            status, details
-        )));
+        let details = upstream.text().await.unwrap_or_default();
+        let (error, description) = upstream_error(status, &details);
+        log_event(&LogEvent {
+            request_id,
+            path,
+            status,
+            upstream_status: Some(status),
+            error: Some(&error),
+        });
+        return json_error(status, &error, description, Some(status), request_id);
     }
 
-    upstream
-        .json::<TickTickTokenResponse>()
-        .await
-        .map_err(|err| Error::RustError(format!("Failed to parse token response: {err}")))
+    let token = match upstream.json::<TickTickTokenResponse>().await {
+        Ok(token) => token,
+        Err(err) => {
+            log_event(&LogEvent {
+                request_id,
+                path,
+                status: 502,
+                upstream_status: Some(status),
+                error: Some("invalid_upstream_response"),
+            });
+            return json_error(
+                502,
+                "invalid_upstream_response",
+                Some(format!("Failed to parse token response: {err}")),
+                Some(status),
+                request_id,
+            );
+        }
+    };
+
+    log_event(&LogEvent {
+        request_id,
+        path,
+        status: 200,
+        upstream_status: Some(status),
+        error: None,
+    });
+    let mut response = Response::from_json(&token)?;
+    response.headers_mut().set("Cache-Control", "no-store")?;
+    Ok(response)
 }
 
 fn is_blank(value: &str) -> bool {
@@ -1,20 +1,87 @@
+use super::proxy::{http_client_builder, proxy_auth_required, send_error};
+use super::trace::{log_event, log_request, log_request_body};
 use crate::config::auth::AuthSettings;
 use crate::config::{AppConfig, Config};
-use crate::models::{Column, Project, ProjectData, Task};
+use crate::models::{Column, Habit, HabitCheckin, HabitCheckinReceipt, Project, ProjectData, Task};
 use anyhow::{anyhow, Context, Result};
 use reqwest::{header, Client, Response, StatusCode};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, Mutex};
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
 
 const BASE_URL: &str = "https://api.ticktick.com/open/v1";
 const USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
+/// `POST /task/completed` returns at most this many tasks per call, newest completion first.
+/// Older pages are requested by moving the `endDate` window bound back to the oldest
+/// `completedTime` seen so far.
+const COMPLETED_TASKS_PAGE_SIZE: usize = 100;
+const UNDOCUMENTED_HABIT_ENDPOINT: &str =
+    "TickTick's habit endpoints are undocumented and may have changed or be unavailable";
+
+/// A non-success HTTP response from the TickTick API.
+#[derive(Debug)]
+pub struct ApiError {
+    pub status: StatusCode,
+    pub body: String,
+}
+
+impl std::fmt::Display for ApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Request failed: {} - {}", self.status, self.body)
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+/// Whether any error in the chain is a 401 from the API.
+pub fn is_unauthorized(err: &anyhow::Error) -> bool {
+    err.chain().any(|cause| {
+        cause
+            .downcast_ref::<ApiError>()
+            .is_some_and(|api_error| api_error.status == StatusCode::UNAUTHORIZED)
+    })
+}
+
+/// Whether any error in the chain is a 404 from the API.
+pub fn is_not_found(err: &anyhow::Error) -> bool {
+    err.chain().any(|cause| {
+        cause
+            .downcast_ref::<ApiError>()
+            .is_some_and(|api_error| api_error.status == StatusCode::NOT_FOUND)
+    })
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CompletedTasksQuery<'a> {
+    project_ids: &'a [String],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    start_date: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    end_date: Option<&'a str>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct HabitCheckinsQuery<'a> {
+    habit_ids: &'a [String],
+    after_stamp: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    before_stamp: Option<i64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct HabitCheckinsResponse {
+    #[serde(default)]
+    checkins: HashMap<String, Vec<HabitCheckin>>,
+}
 
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct InboxProjectData {
-    #[allow(dead_code)]
     project: Option<Project>,
     tasks: Option<Vec<Task>>,
     #[allow(dead_code)]
@@ -25,12 +92,15 @@ struct InboxProjectData {
 pub struct TickTickClient {
     client: Client,
     config: Arc<Mutex<Config>>,
+    /// Held while refreshing so clones fetching in parallel refresh the token only once.
+    refresh_lock: Arc<tokio::sync::Mutex<()>>,
     app_config: AppConfig,
+    base_url: String,
 }
 
 impl TickTickClient {
     pub fn new(config: Config) -> Result<Self> {
-        let client = Client::builder()
+        let client = http_client_builder()?
             .user_agent(USER_AGENT)
             .build()
             .context("Failed to build HTTP client")?;
@@ -38,10 +108,19 @@ impl TickTickClient {
         Ok(Self {
             client,
             config: Arc::new(Mutex::new(config)),
+            refresh_lock: Arc::new(tokio::sync::Mutex::new(())),
             app_config: AppConfig::new()?,
+            base_url: BASE_URL.to_string(),
         })
     }
 
+    /// Sends every request to `base_url` instead of the TickTick API, for mock servers.
+    #[cfg(test)]
+    pub(crate) fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
     async fn request(
         &self,
         method: &str,
@@ -51,14 +130,23 @@ impl TickTickClient {
         validate_http_method(method)?;
         self.refresh_access_token_if_needed().await?;
 
-        let response = self.send_request(method, endpoint, body.as_ref()).await?;
-        if should_refresh_after_response(response.status()) {
-            self.refresh_access_token().await?;
-            let retry_response = self.send_request(method, endpoint, body.as_ref()).await?;
-            return response_to_result(retry_response).await;
+        let access_token = self.access_token()?;
+        let response = self
+            .send_request(method, endpoint, body.as_ref(), &access_token)
+            .await?;
+        if !should_refresh_after_response(response.status()) {
+            return response_to_result(response).await;
         }
 
-        response_to_result(response).await
+        // One refresh and one retry; a second 401 is returned as is.
+        if let Err(refresh_err) = self.refresh_rejected_token(&access_token).await {
+            return Err(rejected_token_error(response, refresh_err).await);
+        }
+        let access_token = self.access_token()?;
+        let retry_response = self
+            .send_request(method, endpoint, body.as_ref(), &access_token)
+            .await?;
+        response_to_result(retry_response).await
     }
 
     pub async fn get_projects(&self) -> Result<Vec<Project>> {
@@ -81,12 +169,72 @@ impl TickTickClient {
         Ok(data)
     }
 
+    /// Fetches tasks completed between `from` and `to` (TickTick timestamps, either side
+    /// open), following the `endDate` cursor until the endpoint returns a short page or
+    /// stops making progress.
+    pub async fn get_completed_tasks(
+        &self,
+        project_ids: &[String],
+        from: Option<&str>,
+        to: Option<&str>,
+    ) -> Result<Vec<Task>> {
+        let mut tasks = Vec::new();
+        let mut seen = HashSet::new();
+        let mut end_date: Option<String> = to.map(str::to_string);
+
+        loop {
+            let page = self
+                .get_completed_tasks_page(project_ids, from, end_date.as_deref())
+                .await?;
+            let cursor = next_completed_page_cursor(&page, end_date.as_deref());
+            for task in page {
+                let is_new = match task.id.as_deref() {
+                    Some(id) => seen.insert(id.to_string()),
+                    None => true,
+                };
+                if is_new {
+                    tasks.push(task);
+                }
+            }
+
+            match cursor {
+                Some(cursor) => end_date = Some(cursor),
+                None => break,
+            }
+        }
+
+        Ok(tasks)
+    }
+
+    async fn get_completed_tasks_page(
+        &self,
+        project_ids: &[String],
+        start_date: Option<&str>,
+        end_date: Option<&str>,
+    ) -> Result<Vec<Task>> {
+        let body = json!(CompletedTasksQuery {
+            project_ids,
+            start_date,
+            end_date,
+        });
+        let response = self.request("POST", "/task/completed", Some(body)).await?;
+        let tasks: Vec<Task> = response.json().await.context("Failed to parse response")?;
+        Ok(tasks)
+    }
+
     pub async fn get_inbox_tasks(&self) -> Result<Vec<Task>> {
         let response = self.request("GET", "/project/inbox/data", None).await?;
         let data: InboxProjectData = response.json().await.context("Failed to parse response")?;
         Ok(inbox_tasks_from_data(data))
     }
 
+    /// The inbox's own project id (`inbox<user id>`), which `/project` does not list.
+    pub async fn get_inbox_project_id(&self) -> Result<Option<String>> {
+        let response = self.request("GET", "/project/inbox/data", None).await?;
+        let data: InboxProjectData = response.json().await.context("Failed to parse response")?;
+        Ok(inbox_project_id_from_data(&data))
+    }
+
     pub async fn get_project_data_value(&self, project_id: &str) -> Result<serde_json::Value> {
         let endpoint = format!("/project/{}/data", project_id);
         let response = self.request("GET", &endpoint, None).await?;
@@ -101,9 +249,12 @@ impl TickTickClient {
         Ok(created)
     }
 
-    pub async fn update_project(&self, project_id: &str, project: &Project) -> Result<Project> {
+    pub async fn update_project<T>(&self, project_id: &str, project: &T) -> Result<Project>
+    where
+        T: Serialize + ?Sized,
+    {
         let endpoint = format!("/project/{}", project_id);
-        let body = json!(project);
+        let body = serde_json::to_value(project).context("Failed to serialize project update")?;
         let response = self.request("POST", &endpoint, Some(body)).await?;
         let updated: Project = response.json().await.context("Failed to parse response")?;
         Ok(updated)
@@ -122,11 +273,13 @@ impl TickTickClient {
         Ok(task)
     }
 
-    pub async fn create_task(&self, task: &Task) -> Result<Task> {
-        let body = json!(task);
-        let response = self.request("POST", "/task", Some(body)).await?;
-        let created: Task = response.json().await.context("Failed to parse response")?;
-        Ok(created)
+    pub async fn create_task<T>(&self, task: &T) -> Result<Task>
+    where
+        T: Serialize + ?Sized,
+    {
+        let body = serde_json::to_value(task).context("Failed to serialize task")?;
+        let response = self.request("POST", "/task", Some(body.clone())).await?;
+        task_or_payload(optional_json(response).await?, body)
     }
 
     pub async fn update_task<T>(&self, task_id: &str, task: &T) -> Result<Task>
@@ -135,9 +288,8 @@ impl TickTickClient {
     {
         let endpoint = format!("/task/{}", task_id);
         let body = serde_json::to_value(task).context("Failed to serialize task update")?;
-        let response = self.request("POST", &endpoint, Some(body)).await?;
-        let updated: Task = response.json().await.context("Failed to parse response")?;
-        Ok(updated)
+        let response = self.request("POST", &endpoint, Some(body.clone())).await?;
+        task_or_payload(optional_json(response).await?, body)
     }
 
     pub async fn complete_task(&self, project_id: &str, task_id: &str) -> Result<()> {
@@ -152,14 +304,122 @@ impl TickTickClient {
         Ok(())
     }
 
+    pub async fn get_habits(&self) -> Result<Vec<Habit>> {
+        let response = self.habit_request("GET", "/habit", None).await?;
+        let habits: Vec<Habit> = response.json().await.context("Failed to parse response")?;
+        Ok(habits)
+    }
+
+    /// Fetches check-ins recorded after `after_stamp` (a `YYYYMMDD` date), keyed by habit ID.
+    pub async fn get_habit_checkins(
+        &self,
+        habit_ids: &[String],
+        after_stamp: i64,
+    ) -> Result<HashMap<String, Vec<HabitCheckin>>> {
+        self.query_habit_checkins(HabitCheckinsQuery {
+            habit_ids,
+            after_stamp,
+            before_stamp: None,
+        })
+        .await
+    }
+
+    /// Fetches one habit's check-ins from `from_stamp` through `to_stamp` (`YYYYMMDD`, inclusive).
+    pub async fn get_habit_checkins_between(
+        &self,
+        habit_id: &str,
+        from_stamp: i64,
+        to_stamp: i64,
+    ) -> Result<Vec<HabitCheckin>> {
+        let habit_ids = [habit_id.to_string()];
+        let mut checkins = self
+            .query_habit_checkins(HabitCheckinsQuery {
+                habit_ids: &habit_ids,
+                after_stamp: from_stamp - 1,
+                before_stamp: Some(to_stamp + 1),
+            })
+            .await?
+            .remove(habit_id)
+            .unwrap_or_default();
+        // The stamps are only a hint to the server; keep the range exact either way.
+        checkins.retain(|checkin| (from_stamp..=to_stamp).contains(&checkin.checkin_stamp));
+        Ok(checkins)
+    }
+
+    async fn query_habit_checkins(
+        &self,
+        query: HabitCheckinsQuery<'_>,
+    ) -> Result<HashMap<String, Vec<HabitCheckin>>> {
+        let response = self
+            .habit_request("POST", "/habit/checkins/query", Some(json!(query)))
+            .await?;
+        let data: HabitCheckinsResponse =
+            response.json().await.context("Failed to parse response")?;
+        Ok(data.checkins)
+    }
+
+    /// Records the check-in for one day, returning whatever the API echoed back.
+    pub async fn habit_checkin(
+        &self,
+        habit_id: &str,
+        checkin: &HabitCheckin,
+    ) -> Result<Option<HabitCheckinReceipt>> {
+        let endpoint = format!("/habit/{}/checkin", habit_id);
+        let body = json!(checkin);
+        let response = self.habit_request("POST", &endpoint, Some(body)).await?;
+        optional_json(response).await
+    }
+
+    pub async fn create_habit(&self, habit: &Habit) -> Result<Habit> {
+        let body = json!(habit);
+        let response = self.habit_request("POST", "/habit", Some(body)).await?;
+        let created: Habit = response.json().await.context("Failed to parse response")?;
+        Ok(created)
+    }
+
+    pub async fn update_habit<T>(&self, habit_id: &str, habit: &T) -> Result<Habit>
+    where
+        T: Serialize + ?Sized,
+    {
+        let endpoint = format!("/habit/{}", habit_id);
+        let body = json!(habit);
+        let response = self.habit_request("POST", &endpoint, Some(body)).await?;
+        let updated: Habit = response.json().await.context("Failed to parse response")?;
+        Ok(updated)
+    }
+
+    pub async fn delete_habit(&self, habit_id: &str) -> Result<()> {
+        let endpoint = format!("/habit/{}", habit_id);
+        self.habit_request("DELETE", &endpoint, None).await?;
+        Ok(())
+    }
+
+    /// The `/habit` routes are not in the Open API docs, so a failure other than an
+    /// expired login may mean the endpoint itself changed; the error says so.
+    async fn habit_request(
+        &self,
+        method: &str,
+        endpoint: &str,
+        body: Option<serde_json::Value>,
+    ) -> Result<Response> {
+        self.request(method, endpoint, body).await.map_err(|err| {
+            if is_unauthorized(&err) {
+                err
+            } else {
+                err.context(UNDOCUMENTED_HABIT_ENDPOINT)
+            }
+        })
+    }
+
     async fn send_request(
         &self,
         method: &str,
         endpoint: &str,
         body: Option<&serde_json::Value>,
+        access_token: &str,
     ) -> Result<Response> {
-        let url = build_url(endpoint);
-        let access_token = self.access_token()?;
+        let url = build_url(&self.base_url, endpoint);
+        log_request_body(method, endpoint, body);
         let mut request = match method {
             "GET" => self.client.get(&url),
             "POST" => self.client.post(&url),
@@ -169,27 +429,58 @@ impl TickTickClient {
         };
 
         request = request
-            .header(header::AUTHORIZATION, bearer_token_value(&access_token))
+            .header(header::AUTHORIZATION, bearer_token_value(access_token))
             .header(header::CONTENT_TYPE, "application/json");
 
         if let Some(body) = body {
             request = request.json(body);
         }
 
-        request.send().await.context("Failed to send request")
+        let started = Instant::now();
+        match request.send().await {
+            Ok(response) => {
+                log_request(
+                    method,
+                    endpoint,
+                    &response.status().to_string(),
+                    started.elapsed(),
+                );
+                Ok(response)
+            }
+            Err(err) => {
+                log_request(method, endpoint, "send failed", started.elapsed());
+                Err(send_error(err, "Failed to send request"))
+            }
+        }
     }
 
     async fn refresh_access_token_if_needed(&self) -> Result<()> {
-        if self
-            .config_snapshot()?
-            .is_access_token_expired(current_timestamp()?)
-        {
-            self.refresh_access_token().await?;
+        let is_expired = |client: &Self| -> Result<bool> {
+            Ok(client
+                .config_snapshot()?
+                .is_access_token_expired(current_timestamp()?))
+        };
+        if !is_expired(self)? {
+            return Ok(());
         }
 
+        let _guard = self.refresh_lock.lock().await;
+        if is_expired(self)? {
+            self.refresh_access_token().await?;
+        }
         Ok(())
     }
 
+    /// Refreshes after `rejected` drew a 401, unless a parallel request already replaced
+    /// it, in which case the new token is simply retried.
+    async fn refresh_rejected_token(&self, rejected: &str) -> Result<()> {
+        let _guard = self.refresh_lock.lock().await;
+        if token_already_replaced(rejected, &self.access_token()?) {
+            return Ok(());
+        }
+        self.refresh_access_token().await
+    }
+
     async fn refresh_access_token(&self) -> Result<()> {
         let current_config = self.config_snapshot()?;
         if current_config.refresh_token.is_empty() {
@@ -199,6 +490,7 @@ impl TickTickClient {
         }
 
         let settings = auth_settings_from_env()?;
+        log_event("refreshing access token");
         let refreshed = settings
             .refresh_access_token(&current_config.refresh_token)
             .await
@@ -235,10 +527,47 @@ impl TickTickClient {
     }
 }
 
+/// Returns the `endDate` for the next completed-task page, or `None` when the crawl is done.
+fn next_completed_page_cursor(page: &[Task], previous: Option<&str>) -> Option<String> {
+    if page.len() < COMPLETED_TASKS_PAGE_SIZE {
+        return None;
+    }
+
+    let oldest = page
+        .iter()
+        .filter_map(|task| task.completed_time.clone())
+        .min()?;
+    if previous == Some(oldest.as_str()) {
+        return None;
+    }
+
+    Some(oldest)
+}
+
 fn inbox_tasks_from_data(data: InboxProjectData) -> Vec<Task> {
     data.tasks.unwrap_or_default()
 }
 
+/// The inbox id from its project record, or from a task's `projectId` when the record is
+/// missing.
+fn inbox_project_id_from_data(data: &InboxProjectData) -> Option<String> {
+    let project_id = data
+        .project
+        .as_ref()
+        .and_then(|project| project.id.as_deref());
+    let task_project_id = || {
+        data.tasks
+            .iter()
+            .flatten()
+            .find_map(|task| task.project_id.as_deref())
+    };
+    project_id
+        .or_else(task_project_id)
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+        .map(str::to_string)
+}
+
 fn validate_http_method(method: &str) -> Result<()> {
     match method {
         "GET" | "POST" | "PUT" | "DELETE" => Ok(()),
@@ -246,8 +575,8 @@ fn validate_http_method(method: &str) -> Result<()> {
     }
 }
 
-fn build_url(endpoint: &str) -> String {
-    format!("{}{}", BASE_URL, endpoint)
+fn build_url(base_url: &str, endpoint: &str) -> String {
+    format!("{}{}", base_url, endpoint)
 }
 
 fn bearer_token_value(access_token: &str) -> String {
@@ -264,8 +593,48 @@ async fn response_to_result(response: Response) -> Result<Response> {
     }
 
     let status = response.status();
-    let body_text = response.text().await.unwrap_or_default();
-    Err(anyhow!("Request failed: {} - {}", status, body_text))
+    let body = response.text().await.unwrap_or_default();
+    let err = anyhow::Error::from(ApiError { status, body });
+    if status == StatusCode::PROXY_AUTHENTICATION_REQUIRED {
+        return Err(err.context(proxy_auth_required()));
+    }
+    Err(err)
+}
+
+/// Parses a successful response body, or `None` when the API sent an empty 200.
+async fn optional_json<T: DeserializeOwned>(response: Response) -> Result<Option<T>> {
+    let text = response.text().await.context("Failed to read response")?;
+    if text.trim().is_empty() {
+        return Ok(None);
+    }
+    serde_json::from_str(&text)
+        .map(Some)
+        .context("Failed to parse response")
+}
+
+/// The task the API returned, or the payload that was sent when it returned nothing.
+fn task_or_payload(returned: Option<Task>, payload: serde_json::Value) -> Result<Task> {
+    match returned {
+        Some(task) => Ok(task),
+        None => serde_json::from_value(payload).context("Failed to parse response"),
+    }
+}
+
+fn token_already_replaced(rejected: &str, current: &str) -> bool {
+    rejected != current
+}
+
+/// The 401 we got, with why the refresh meant to recover from it failed. The
+/// [`ApiError`] stays in the chain so [`is_unauthorized`] still sees it.
+async fn rejected_token_error(response: Response, refresh_err: anyhow::Error) -> anyhow::Error {
+    let err = match response_to_result(response).await {
+        Err(err) => err,
+        Ok(_) => anyhow!("The access token was rejected"),
+    };
+    err.context(format!(
+        "The access token was rejected and could not be refreshed ({:#}). Run 'tt auth login' again.",
+        refresh_err
+    ))
 }
 
 fn current_timestamp() -> Result<i64> {
@@ -315,10 +684,147 @@ mod tests {
         assert_eq!(tasks[0].title, "Follow up");
     }
 
+    #[test]
+    fn inbox_project_id_prefers_the_project_record_over_tasks() {
+        let data: InboxProjectData = serde_json::from_value(json!({
+            "project": { "id": "inbox123", "name": "Inbox" },
+            "tasks": [{ "id": "task-1", "title": "a", "projectId": "inbox999" }]
+        }))
+        .unwrap();
+        assert_eq!(
+            inbox_project_id_from_data(&data).as_deref(),
+            Some("inbox123")
+        );
+
+        let data: InboxProjectData = serde_json::from_value(json!({
+            "tasks": [{ "id": "task-1", "title": "a", "projectId": "inbox999" }]
+        }))
+        .unwrap();
+        assert_eq!(
+            inbox_project_id_from_data(&data).as_deref(),
+            Some("inbox999")
+        );
+
+        let data: InboxProjectData = serde_json::from_value(json!({ "tasks": [] })).unwrap();
+        assert_eq!(inbox_project_id_from_data(&data), None);
+    }
+
+    fn completed_page(times: &[&str]) -> Vec<Task> {
+        times
+            .iter()
+            .enumerate()
+            .map(|(index, time)| Task {
+                id: Some(format!("task-{}", index)),
+                title: "Done".to_string(),
+                completed_time: Some(time.to_string()),
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn completed_page_cursor_stops_on_short_pages() {
+        let page = completed_page(&["2026-03-02T00:00:00.000+0000"]);
+        assert_eq!(next_completed_page_cursor(&page, None), None);
+    }
+
+    #[test]
+    fn completed_page_cursor_moves_to_oldest_completion_on_full_pages() {
+        let mut times = vec!["2026-03-05T00:00:00.000+0000"; COMPLETED_TASKS_PAGE_SIZE - 1];
+        times.push("2026-02-01T08:30:00.000+0000");
+        let page = completed_page(&times);
+
+        assert_eq!(
+            next_completed_page_cursor(&page, None).as_deref(),
+            Some("2026-02-01T08:30:00.000+0000")
+        );
+        assert_eq!(
+            next_completed_page_cursor(&page, Some("2026-02-01T08:30:00.000+0000")),
+            None
+        );
+    }
+
+    #[test]
+    fn completed_tasks_query_serializes_camel_case_window() {
+        let project_ids = vec!["project-1".to_string()];
+        let body = serde_json::to_value(CompletedTasksQuery {
+            project_ids: &project_ids,
+            start_date: None,
+            end_date: Some("2026-03-01T00:00:00.000+0000"),
+        })
+        .unwrap();
+
+        assert_eq!(
+            body,
+            json!({
+                "projectIds": ["project-1"],
+                "endDate": "2026-03-01T00:00:00.000+0000"
+            })
+        );
+    }
+
+    fn ok_response(body: &str) -> Response {
+        Response::from(http::Response::new(body.to_string()))
+    }
+
+    #[tokio::test]
+    async fn optional_json_treats_an_empty_200_as_no_body() {
+        let parsed: Option<Task> = optional_json(ok_response("")).await.unwrap();
+        assert!(parsed.is_none());
+        let parsed: Option<Task> = optional_json(ok_response(" \n")).await.unwrap();
+        assert!(parsed.is_none());
+
+        let parsed: Option<Task> = optional_json(ok_response(r#"{"id":"t1","title":"Milk"}"#))
+            .await
+            .unwrap();
+        assert_eq!(parsed.and_then(|task| task.id).as_deref(), Some("t1"));
+
+        let err = optional_json::<Task>(ok_response("OK")).await.unwrap_err();
+        assert_eq!(err.to_string(), "Failed to parse response");
+    }
+
+    #[tokio::test]
+    async fn task_writes_fall_back_to_the_payload_on_an_empty_200() {
+        let payload = json!({"id": "t1", "projectId": "p1", "title": "Milk"});
+        let returned = optional_json(ok_response("")).await.unwrap();
+        let task = task_or_payload(returned, payload).unwrap();
+        assert_eq!(task.id.as_deref(), Some("t1"));
+        assert_eq!(task.title, "Milk");
+
+        let returned = Some(Task {
+            title: "From API".to_string(),
+            ..Default::default()
+        });
+        let task = task_or_payload(returned, json!({"title": "Sent"})).unwrap();
+        assert_eq!(task.title, "From API");
+    }
+
+    #[test]
+    fn refresh_is_skipped_when_another_request_already_replaced_the_token() {
+        assert!(!token_already_replaced("old-token", "old-token"));
+        assert!(token_already_replaced("old-token", "new-token"));
+    }
+
+    #[tokio::test]
+    async fn failed_refresh_after_401_keeps_the_unauthorized_error() {
+        let mut response = http::Response::new("token revoked".to_string());
+        *response.status_mut() = StatusCode::UNAUTHORIZED;
+        let err = rejected_token_error(
+            Response::from(response),
+            anyhow!("Missing refresh token in saved config"),
+        )
+        .await;
+
+        assert!(is_unauthorized(&err));
+        let message = err.to_string();
+        assert!(message.contains("could not be refreshed (Missing refresh token in saved config)"));
+        assert!(message.contains("tt auth login"));
+    }
+
     #[test]
     fn build_url_joins_base_url_and_endpoint() {
         assert_eq!(
-            build_url("/project/inbox/data"),
+            build_url(BASE_URL, "/project/inbox/data"),
             "https://api.ticktick.com/open/v1/project/inbox/data"
         );
     }
@@ -335,6 +841,39 @@ mod tests {
         assert!(!should_refresh_after_response(StatusCode::FORBIDDEN));
     }
 
+    #[test]
+    fn is_unauthorized_detects_wrapped_401_responses() {
+        let err: anyhow::Error = ApiError {
+            status: StatusCode::UNAUTHORIZED,
+            body: "invalid_token".to_string(),
+        }
+        .into();
+        assert_eq!(
+            err.to_string(),
+            "Request failed: 401 Unauthorized - invalid_token"
+        );
+        assert!(is_unauthorized(&err.context("Failed to list projects")));
+
+        let forbidden: anyhow::Error = ApiError {
+            status: StatusCode::FORBIDDEN,
+            body: String::new(),
+        }
+        .into();
+        assert!(!is_unauthorized(&forbidden));
+        assert!(!is_unauthorized(&anyhow!("connection refused")));
+    }
+
+    #[test]
+    fn is_not_found_detects_wrapped_404_responses() {
+        let err: anyhow::Error = ApiError {
+            status: StatusCode::NOT_FOUND,
+            body: "project not found".to_string(),
+        }
+        .into();
+        assert!(is_not_found(&err.context("Failed to create task")));
+        assert!(!is_not_found(&anyhow!("connection refused")));
+    }
+
     #[test]
     fn current_timestamp_returns_unix_seconds() {
         assert!(current_timestamp().unwrap() > 0);
@@ -1,3 +1,7 @@
 pub mod client;
+pub mod proxy;
+pub mod trace;
 
-pub use client::TickTickClient;
+pub use client::{is_not_found, is_unauthorized, TickTickClient};
+pub use proxy::set_proxy_override;
+pub use trace::set_trace_level;
@@ -0,0 +1,106 @@
+use serde_json::Value;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::time::Duration;
+
+/// Body keys whose values are replaced before a body is logged.
+const REDACTED_KEYS: [&str; 5] = [
+    "access_token",
+    "accessToken",
+    "refresh_token",
+    "refreshToken",
+    "token",
+];
+
+static TRACE_LEVEL: AtomicU8 = AtomicU8::new(0);
+
+/// Sets how much the API client logs to stderr for the rest of the process (`-v`):
+/// 0 is silent, 1 logs one line per request, 2 and up also logs request bodies.
+pub fn set_trace_level(level: u8) {
+    TRACE_LEVEL.store(level, Ordering::Relaxed);
+}
+
+fn trace_level() -> u8 {
+    TRACE_LEVEL.load(Ordering::Relaxed)
+}
+
+fn redact(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                if REDACTED_KEYS.contains(&key.as_str()) {
+                    *value = Value::String("[redacted]".to_string());
+                } else {
+                    redact(value);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact),
+        _ => {}
+    }
+}
+
+fn request_line(method: &str, endpoint: &str, outcome: &str, elapsed: Duration) -> String {
+    format!(
+        "[tt] {} {} -> {} ({} ms)",
+        method,
+        endpoint,
+        outcome,
+        elapsed.as_millis()
+    )
+}
+
+fn body_line(method: &str, endpoint: &str, body: &Value) -> String {
+    let mut body = body.clone();
+    redact(&mut body);
+    format!("[tt] {} {} body: {}", method, endpoint, body)
+}
+
+/// Logs a finished request at `-v`. `outcome` is the status, or why the send failed.
+pub(super) fn log_request(method: &str, endpoint: &str, outcome: &str, elapsed: Duration) {
+    if trace_level() >= 1 {
+        eprintln!("{}", request_line(method, endpoint, outcome, elapsed));
+    }
+}
+
+/// Logs a request body at `-vv`, with token fields redacted.
+pub(super) fn log_request_body(method: &str, endpoint: &str, body: Option<&Value>) {
+    if let Some(body) = body.filter(|_| trace_level() >= 2) {
+        eprintln!("{}", body_line(method, endpoint, body));
+    }
+}
+
+pub(super) fn log_event(message: &str) {
+    if trace_level() >= 1 {
+        eprintln!("[tt] {}", message);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn request_line_shows_method_endpoint_status_and_elapsed_time() {
+        assert_eq!(
+            request_line("GET", "/project", "200 OK", Duration::from_millis(142)),
+            "[tt] GET /project -> 200 OK (142 ms)"
+        );
+    }
+
+    #[test]
+    fn body_line_redacts_token_fields_at_any_depth() {
+        let body = json!({
+            "title": "Buy milk",
+            "token": "abc",
+            "auth": {"access_token": "secret", "refreshToken": "other"},
+            "items": [{"accessToken": "nested"}],
+        });
+        let line = body_line("POST", "/task", &body);
+        assert!(line.starts_with("[tt] POST /task body: "));
+        assert!(line.contains("\"title\":\"Buy milk\""));
+        for secret in ["abc", "secret", "other", "nested"] {
+            assert!(!line.contains(secret), "leaked {} in {}", secret, line);
+        }
+    }
+}
@@ -6,10 +6,38 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 const PROJECT_CACHE_TTL_SECS: i64 = 15;
 const TASK_PROJECT_CACHE_TTL_SECS: i64 = 15 * 60;
+/// `tt project list --counts` makes one request per list, so its counts are kept longer.
+const PROJECT_COUNTS_CACHE_TTL_SECS: i64 = 60;
+/// `tt undo` only needs the most recent operations; older entries are dropped on write.
+const JOURNAL_MAX_ENTRIES: usize = 20;
+/// `--offline` reads still work past this age, but warn that `tt sync` is due.
+pub const SNAPSHOT_STALE_AFTER_SECS: i64 = 24 * 60 * 60;
+
+static JOURNAL_DISABLED: AtomicBool = AtomicBool::new(false);
+static OFFLINE: AtomicBool = AtomicBool::new(false);
+
+/// Turns off journal recording for the rest of the process (`--no-journal`).
+pub fn disable_journal() {
+    JOURNAL_DISABLED.store(true, Ordering::Relaxed);
+}
+
+pub fn journal_enabled() -> bool {
+    !JOURNAL_DISABLED.load(Ordering::Relaxed)
+}
+
+/// Serves task reads from the `tt sync` snapshot for the rest of the process (`--offline`).
+pub fn enable_offline() {
+    OFFLINE.store(true, Ordering::Relaxed);
+}
+
+pub fn offline_enabled() -> bool {
+    OFFLINE.load(Ordering::Relaxed)
+}
 
 #[derive(Debug, Clone)]
 pub struct CacheStore {
@@ -22,6 +50,19 @@ struct ProjectsCacheFile {
     projects: Vec<Project>,
 }
 
+/// Open and overdue task counts for one project.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProjectTaskCounts {
+    pub open: usize,
+    pub overdue: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ProjectCountsCacheFile {
+    updated_at: i64,
+    counts: HashMap<String, ProjectTaskCounts>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 struct TaskProjectCacheFile {
     tasks: HashMap<String, TaskProjectCacheEntry>,
@@ -33,6 +74,72 @@ struct TaskProjectCacheEntry {
     updated_at: i64,
 }
 
+/// A mutating command and the state needed to reverse it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum JournalOperation {
+    TaskDeleted { task: Task, project_id: String },
+    TaskCompleted { task_id: String, project_id: String },
+    TaskUpdated { previous: Task, project_id: String },
+    ProjectDeleted { project: Project },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub recorded_at: i64,
+    pub operation: JournalOperation,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+struct JournalFile {
+    entries: Vec<JournalEntry>,
+}
+
+/// Every project and open task as of the last `tt sync`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncSnapshot {
+    pub synced_at: i64,
+    pub projects: Vec<Project>,
+    pub tasks: Vec<Task>,
+}
+
+impl SyncSnapshot {
+    pub fn new(projects: Vec<Project>, tasks: Vec<Task>) -> Result<Self> {
+        Ok(Self {
+            synced_at: unix_timestamp()?,
+            projects,
+            tasks,
+        })
+    }
+
+    pub fn load(path: &Path) -> Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read sync snapshot {}", path.display()))?;
+        let snapshot = serde_json::from_str(&contents)
+            .with_context(|| format!("Failed to parse sync snapshot {}", path.display()))?;
+        Ok(Some(snapshot))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let contents = serde_json::to_string(self).context("Failed to serialize sync snapshot")?;
+        fs::write(path, contents)
+            .with_context(|| format!("Failed to write sync snapshot {}", path.display()))?;
+        Ok(())
+    }
+
+    pub fn age_secs(&self, now: i64) -> i64 {
+        now.saturating_sub(self.synced_at).max(0)
+    }
+
+    pub fn is_stale(&self, now: i64) -> bool {
+        !is_fresh(self.synced_at, SNAPSHOT_STALE_AFTER_SECS, now)
+    }
+}
+
 impl CacheStore {
     pub fn new() -> Result<Self> {
         let proj_dirs = ProjectDirs::from("", "", "ticktick-cli")
@@ -75,8 +182,40 @@ impl CacheStore {
         Ok(())
     }
 
+    /// Counts saved by the last `--counts` run, unless they are over a minute old.
+    pub fn load_project_counts(&self) -> Result<HashMap<String, ProjectTaskCounts>> {
+        let path = self.project_counts_path();
+        let Some(cache) = self.read_json::<ProjectCountsCacheFile>(&path)? else {
+            return Ok(HashMap::new());
+        };
+
+        if !is_fresh(
+            cache.updated_at,
+            PROJECT_COUNTS_CACHE_TTL_SECS,
+            unix_timestamp()?,
+        ) {
+            let _ = fs::remove_file(path);
+            return Ok(HashMap::new());
+        }
+
+        Ok(cache.counts)
+    }
+
+    pub fn save_project_counts(&self, counts: &HashMap<String, ProjectTaskCounts>) -> Result<()> {
+        let cache = ProjectCountsCacheFile {
+            updated_at: unix_timestamp()?,
+            counts: counts.clone(),
+        };
+        self.write_json(&self.project_counts_path(), &cache)
+    }
+
     pub fn clear_all(&self) -> Result<()> {
-        for path in [self.projects_path(), self.task_projects_path()] {
+        for path in [
+            self.projects_path(),
+            self.project_counts_path(),
+            self.task_projects_path(),
+            self.journal_path(),
+        ] {
             if path.exists() {
                 fs::remove_file(&path)
                     .with_context(|| format!("Failed to remove cache file {}", path.display()))?;
@@ -162,6 +301,41 @@ impl CacheStore {
         Ok(())
     }
 
+    /// Appends `operation` to the undo journal unless `--no-journal` is set.
+    pub fn record_operation(&self, operation: JournalOperation) -> Result<()> {
+        if !journal_enabled() {
+            return Ok(());
+        }
+
+        let mut journal = self.load_journal()?;
+        journal.entries.push(JournalEntry {
+            recorded_at: unix_timestamp()?,
+            operation,
+        });
+        let excess = journal.entries.len().saturating_sub(JOURNAL_MAX_ENTRIES);
+        journal.entries.drain(..excess);
+        self.write_json(&self.journal_path(), &journal)
+    }
+
+    pub fn last_operation(&self) -> Result<Option<JournalEntry>> {
+        Ok(self.load_journal()?.entries.pop())
+    }
+
+    /// Drops the newest journal entry once it has been undone.
+    pub fn remove_last_operation(&self) -> Result<()> {
+        let mut journal = self.load_journal()?;
+        if journal.entries.pop().is_some() {
+            self.write_json(&self.journal_path(), &journal)?;
+        }
+        Ok(())
+    }
+
+    fn load_journal(&self) -> Result<JournalFile> {
+        Ok(self
+            .read_json::<JournalFile>(&self.journal_path())?
+            .unwrap_or_default())
+    }
+
     fn load_task_project_cache(&self) -> Result<TaskProjectCacheFile> {
         Ok(self
             .read_json::<TaskProjectCacheFile>(&self.task_projects_path())?
@@ -176,10 +350,18 @@ impl CacheStore {
         self.cache_dir.join("projects.json")
     }
 
+    fn project_counts_path(&self) -> PathBuf {
+        self.cache_dir.join("project-counts.json")
+    }
+
     fn task_projects_path(&self) -> PathBuf {
         self.cache_dir.join("task-projects.json")
     }
 
+    fn journal_path(&self) -> PathBuf {
+        self.cache_dir.join("journal.json")
+    }
+
     fn read_json<T: for<'de> Deserialize<'de>>(&self, path: &Path) -> Result<Option<T>> {
         if !path.exists() {
             return Ok(None);
@@ -207,9 +389,8 @@ pub async fn get_projects_cached(
 ) -> Result<Vec<Project>> {
     if !force_refresh {
         if let Some(cache) = cache {
-            match cache.load_projects() {
-                Ok(Some(projects)) => return Ok(projects),
-                Ok(None) | Err(_) => {}
+            if let Ok(Some(projects)) = cache.load_projects() {
+                return Ok(projects);
             }
         }
     }
@@ -393,4 +574,99 @@ mod tests {
         assert!(!cache.projects_path().exists());
         assert!(!cache.task_projects_path().exists());
     }
+
+    #[test]
+    fn project_counts_round_trip_until_they_expire() {
+        let cache = CacheStore::from_dir(temp_cache_dir()).unwrap();
+        assert!(cache.load_project_counts().unwrap().is_empty());
+
+        let counts = HashMap::from([(
+            "p1".to_string(),
+            ProjectTaskCounts {
+                open: 4,
+                overdue: 1,
+            },
+        )]);
+        cache.save_project_counts(&counts).unwrap();
+        assert_eq!(cache.load_project_counts().unwrap(), counts);
+
+        cache
+            .write_json(
+                &cache.project_counts_path(),
+                &ProjectCountsCacheFile {
+                    updated_at: unix_timestamp().unwrap() - PROJECT_COUNTS_CACHE_TTL_SECS - 1,
+                    counts,
+                },
+            )
+            .unwrap();
+        assert!(cache.load_project_counts().unwrap().is_empty());
+        assert!(!cache.project_counts_path().exists());
+    }
+
+    #[test]
+    fn journal_keeps_only_the_newest_entries() {
+        let cache = CacheStore::from_dir(temp_cache_dir()).unwrap();
+        for index in 0..JOURNAL_MAX_ENTRIES + 5 {
+            cache
+                .record_operation(JournalOperation::TaskCompleted {
+                    task_id: format!("task-{}", index),
+                    project_id: "project-1".to_string(),
+                })
+                .unwrap();
+        }
+
+        assert_eq!(
+            cache.load_journal().unwrap().entries.len(),
+            JOURNAL_MAX_ENTRIES
+        );
+        let Some(JournalEntry {
+            operation: JournalOperation::TaskCompleted { task_id, .. },
+            ..
+        }) = cache.last_operation().unwrap()
+        else {
+            panic!("expected a completed-task entry");
+        };
+        assert_eq!(task_id, format!("task-{}", JOURNAL_MAX_ENTRIES + 4));
+
+        cache.remove_last_operation().unwrap();
+        let Some(JournalEntry {
+            operation: JournalOperation::TaskCompleted { task_id, .. },
+            ..
+        }) = cache.last_operation().unwrap()
+        else {
+            panic!("expected a completed-task entry");
+        };
+        assert_eq!(task_id, format!("task-{}", JOURNAL_MAX_ENTRIES + 3));
+    }
+
+    #[test]
+    fn sync_snapshot_round_trips_and_goes_stale() {
+        let path = temp_cache_dir().join("sync.json");
+        assert!(SyncSnapshot::load(&path).unwrap().is_none());
+
+        let snapshot = SyncSnapshot {
+            synced_at: 1_000,
+            projects: vec![Project {
+                id: Some("project-1".to_string()),
+                name: "Work".to_string(),
+                ..Default::default()
+            }],
+            tasks: vec![Task {
+                id: Some("task-1".to_string()),
+                project_id: Some("project-1".to_string()),
+                title: "Write report".to_string(),
+                ..Default::default()
+            }],
+        };
+        snapshot.save(&path).unwrap();
+
+        let loaded = SyncSnapshot::load(&path).unwrap().unwrap();
+        assert_eq!(loaded.synced_at, 1_000);
+        assert_eq!(loaded.projects[0].name, "Work");
+        assert_eq!(loaded.tasks[0].title, "Write report");
+
+        assert_eq!(loaded.age_secs(1_000 + 90), 90);
+        assert!(!loaded.is_stale(1_000 + SNAPSHOT_STALE_AFTER_SECS));
+        assert!(loaded.is_stale(1_000 + SNAPSHOT_STALE_AFTER_SECS + 1));
+    }
 }
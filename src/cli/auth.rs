@@ -1,9 +1,11 @@
 use super::bootstrap::{app_config, load_config};
+use crate::api::{is_unauthorized, TickTickClient};
 use crate::cache::CacheStore;
 use crate::config::auth::AuthSettings;
 use crate::config::Config;
+use crate::output::Verbosity;
 use anyhow::{anyhow, Result};
-use clap::Subcommand;
+use clap::{Args, Subcommand};
 use oauth2::{AuthorizationCode, CsrfToken};
 use std::sync::mpsc;
 use std::time::Duration;
@@ -17,17 +19,156 @@ struct LocalCallbackConfig {
     callback_path: String,
 }
 
+/// Read by `tt auth login` when `--token` is not given.
+const ACCESS_TOKEN_ENV: &str = "TICKTICK_ACCESS_TOKEN";
+/// Lifetime assumed for an imported token when `--expires-in` is not given.
+const DEFAULT_TOKEN_LIFETIME_SECS: i64 = 60 * 60;
+
 #[derive(Subcommand)]
 pub enum AuthCommands {
     #[command(alias = "signin")]
-    Login,
+    Login(LoginArgs),
     #[command(alias = "signout")]
     Logout,
     #[command(alias = "whoami")]
-    Status,
+    Status(StatusArgs),
+}
+
+#[derive(Args, Default)]
+pub struct StatusArgs {
+    #[arg(long, help = "Skip the API check and report only the stored token")]
+    no_verify: bool,
+}
+
+#[derive(Args, Default)]
+pub struct LoginArgs {
+    #[arg(
+        long,
+        value_name = "ACCESS_TOKEN",
+        help = "Save this access token instead of opening the browser (or set TICKTICK_ACCESS_TOKEN)"
+    )]
+    token: Option<String>,
+    #[arg(
+        long,
+        help = "Refresh token to store with --token, so it can be renewed"
+    )]
+    refresh_token: Option<String>,
+    #[arg(
+        long,
+        value_name = "SECS",
+        value_parser = clap::value_parser!(i64).range(1..),
+        help = "Seconds until the --token expires (default 3600)"
+    )]
+    expires_in: Option<i64>,
+}
+
+/// The token to import: `--token`, else a non-blank `TICKTICK_ACCESS_TOKEN`.
+fn resolve_login_token(flag: Option<&str>, env: Option<String>) -> Option<String> {
+    flag.map(str::to_string)
+        .or(env)
+        .map(|token| token.trim().to_string())
+        .filter(|token| !token.is_empty())
+}
+
+fn imported_token_config(
+    access_token: String,
+    refresh_token: Option<String>,
+    expires_in: Option<i64>,
+    now: i64,
+) -> Config {
+    Config {
+        access_token,
+        refresh_token: refresh_token.unwrap_or_default(),
+        expires_at: now + expires_in.unwrap_or(DEFAULT_TOKEN_LIFETIME_SECS),
+    }
+}
+
+/// Outcome of the authenticated request `tt auth status` makes to confirm the token works.
+#[derive(Debug)]
+pub(super) enum ApiProbe {
+    Verified { project_count: usize, inbox: bool },
+    Unauthorized,
+    Failed(String),
 }
 
-pub async fn login() -> Result<()> {
+pub(super) async fn probe_api_access(config: &Config) -> ApiProbe {
+    let client = match TickTickClient::new(config.clone()) {
+        Ok(client) => client,
+        Err(err) => return ApiProbe::Failed(err.to_string()),
+    };
+
+    match client.get_projects().await {
+        Ok(projects) => {
+            let inbox = projects.iter().any(|project| {
+                project.kind.as_deref() == Some("INBOX")
+                    || project.name.eq_ignore_ascii_case("inbox")
+            }) || client.get_inbox_tasks().await.is_ok();
+            ApiProbe::Verified {
+                project_count: projects.len(),
+                inbox,
+            }
+        }
+        Err(err) if is_unauthorized(&err) => ApiProbe::Unauthorized,
+        Err(err) => ApiProbe::Failed(err.to_string()),
+    }
+}
+
+pub async fn login(args: LoginArgs) -> Result<()> {
+    let env_token = std::env::var(ACCESS_TOKEN_ENV).ok();
+    match resolve_login_token(args.token.as_deref(), env_token) {
+        Some(token) => login_with_token(token, args).await,
+        None if args.token.is_some() => Err(anyhow!("--token is empty")),
+        None if args.refresh_token.is_some() || args.expires_in.is_some() => Err(anyhow!(
+            "--refresh-token and --expires-in only apply with --token or {}",
+            ACCESS_TOKEN_ENV
+        )),
+        None => login_with_browser().await,
+    }
+}
+
+/// Saves an existing token without the OAuth flow, for CI and headless machines. The
+/// token is checked with one API call first so a bad one is never stored.
+async fn login_with_token(access_token: String, args: LoginArgs) -> Result<()> {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::SystemTime::UNIX_EPOCH)?
+        .as_secs() as i64;
+    let config = imported_token_config(access_token, args.refresh_token, args.expires_in, now);
+
+    match probe_api_access(&config).await {
+        ApiProbe::Verified { .. } => {}
+        ApiProbe::Unauthorized => {
+            return Err(anyhow!(
+                "TickTick rejected the access token; nothing was saved."
+            ))
+        }
+        ApiProbe::Failed(err) => {
+            return Err(anyhow!(
+                "Could not verify the access token ({}); nothing was saved.",
+                err
+            ))
+        }
+    }
+
+    let app_config = app_config()?;
+    app_config.save(&config)?;
+    // Lists are per account, so forget the cached default and Inbox IDs too.
+    let _ = app_config.save_default_project_ids(None);
+    if let Ok(cache) = CacheStore::new() {
+        let _ = cache.clear_all();
+    }
+
+    println!("Access token verified and saved.");
+    if config.refresh_token.is_empty() {
+        println!("No refresh token given; run 'tt auth login' again when the token expires.");
+    }
+    println!(
+        "Session metadata stored in {}",
+        app_config.config_file_path().display()
+    );
+    Ok(())
+}
+
+async fn login_with_browser() -> Result<()> {
     println!("TickTick CLI Authentication");
     println!("=========================");
     println!();
@@ -60,6 +201,8 @@ pub async fn login() -> Result<()> {
 
     let app_config = app_config()?;
     app_config.save(&config)?;
+    // Lists are per account, so forget the cached default and Inbox IDs too.
+    let _ = app_config.save_default_project_ids(None);
     if let Ok(cache) = CacheStore::new() {
         let _ = cache.clear_all();
     }
@@ -118,20 +261,43 @@ pub async fn logout() -> Result<()> {
     Ok(())
 }
 
-pub async fn status() -> Result<()> {
+/// With the global `--quiet`, status is all confirmation, so it prints nothing and only
+/// the exit code says whether the token works.
+pub async fn status(args: StatusArgs, verbosity: Verbosity) -> Result<()> {
     let now = std::time::SystemTime::now()
         .duration_since(std::time::SystemTime::UNIX_EPOCH)?
         .as_secs() as i64;
 
     let config = load_config()?;
+    let probe = match config.as_ref() {
+        Some(config) if !args.no_verify => Some(probe_api_access(config).await),
+        _ => None,
+    };
 
-    for line in format_status_lines(config.as_ref(), now) {
+    if verbosity.is_quiet() {
+        let valid = match (&config, &probe) {
+            (_, Some(probe)) => matches!(probe, ApiProbe::Verified { .. }),
+            (Some(config), None) => config.expires_at > now,
+            (None, None) => false,
+        };
+        if !valid {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    for line in format_status_lines(config.as_ref(), now, probe.as_ref()) {
         println!("{}", line);
     }
 
     Ok(())
 }
 
+/// Checks that `redirect_uri` is one the local login callback server can listen on.
+pub(super) fn validate_redirect_uri(redirect_uri: &str) -> Result<()> {
+    LocalCallbackConfig::from_redirect_uri(redirect_uri).map(|_| ())
+}
+
 fn extract_callback_params(url: &str) -> (Option<String>, Option<String>) {
     let parsed = Url::parse(url).ok();
     let mut code: Option<String> = None;
@@ -216,23 +382,57 @@ fn normalize_callback_path(path: &str) -> String {
     }
 }
 
-fn format_status_lines(config: Option<&Config>, now: i64) -> Vec<String> {
+/// First and last 8 characters of the token. Tokens of 16 characters or fewer would be
+/// shown whole (a hand-edited config, say), so they get a fixed-length redaction instead.
+fn mask_access_token(token: &str) -> String {
+    let chars: Vec<char> = token.chars().collect();
+    if chars.is_empty() {
+        return "(empty)".to_string();
+    }
+    if chars.len() <= 16 {
+        return "********".to_string();
+    }
+    let head: String = chars[..8].iter().collect();
+    let tail: String = chars[chars.len() - 8..].iter().collect();
+    format!("{}...{}", head, tail)
+}
+
+fn format_status_lines(config: Option<&Config>, now: i64, probe: Option<&ApiProbe>) -> Vec<String> {
     match config {
         Some(config) => {
             let remaining = config.expires_at - now;
+            let status = match probe {
+                None | Some(ApiProbe::Verified { .. }) => "Status: Authenticated".to_string(),
+                Some(ApiProbe::Unauthorized) => "Status: Token invalid or expired".to_string(),
+                Some(ApiProbe::Failed(_)) => "Status: Unverified (API check failed)".to_string(),
+            };
             let mut lines = vec![
-                "Status: Authenticated".to_string(),
-                format!(
-                    "Access Token: {}...{}",
-                    &config.access_token[0..8],
-                    &config.access_token[config.access_token.len() - 8..]
-                ),
+                status,
+                format!("Access Token: {}", mask_access_token(&config.access_token)),
             ];
 
-            if remaining > 0 {
-                lines.push(format!("Token expires in: {} minutes", remaining / 60));
-            } else {
-                lines.push("Token expired! Please login again.".to_string());
+            match probe {
+                Some(ApiProbe::Unauthorized) => lines
+                    .push("The API rejected this token. Run 'tt auth login' again.".to_string()),
+                _ if remaining > 0 => {
+                    lines.push(format!("Token expires in: {} minutes", remaining / 60))
+                }
+                _ => lines.push("Token expired! Please login again.".to_string()),
+            }
+
+            match probe {
+                Some(ApiProbe::Verified {
+                    project_count,
+                    inbox,
+                }) => {
+                    lines.push(format!("Projects: {}", project_count));
+                    lines.push(format!(
+                        "Inbox: {}",
+                        if *inbox { "detected" } else { "not detected" }
+                    ));
+                }
+                Some(ApiProbe::Failed(message)) => lines.push(format!("API error: {}", message)),
+                _ => {}
             }
 
             lines
@@ -250,12 +450,62 @@ mod tests {
 
     fn sample_config(expires_at: i64) -> Config {
         Config {
-            access_token: "12345678abcdefgh".to_string(),
+            access_token: "12345678-middle-abcdefgh".to_string(),
             refresh_token: "refresh".to_string(),
             expires_at,
         }
     }
 
+    #[test]
+    fn login_token_comes_from_the_flag_before_the_environment() {
+        assert_eq!(
+            resolve_login_token(Some("flag-token"), Some("env-token".to_string())),
+            Some("flag-token".to_string())
+        );
+        assert_eq!(
+            resolve_login_token(None, Some(" env-token\n".to_string())),
+            Some("env-token".to_string())
+        );
+        assert_eq!(resolve_login_token(None, Some("  ".to_string())), None);
+        assert_eq!(resolve_login_token(None, None), None);
+    }
+
+    #[test]
+    fn imported_token_expires_after_the_given_lifetime_or_an_hour() {
+        let config = imported_token_config("token".to_string(), None, None, 1_000);
+        assert_eq!(config.expires_at, 1_000 + 3_600);
+        assert_eq!(config.refresh_token, "");
+
+        let config = imported_token_config(
+            "token".to_string(),
+            Some("refresh".to_string()),
+            Some(86_400),
+            1_000,
+        );
+        assert_eq!(config.expires_at, 87_400);
+        assert_eq!(config.refresh_token, "refresh");
+    }
+
+    #[test]
+    fn login_rejects_non_positive_expiry() {
+        use clap::Parser;
+
+        #[derive(Parser)]
+        struct LoginArgsCli {
+            #[command(flatten)]
+            args: LoginArgs,
+        }
+
+        let parsed = LoginArgsCli::try_parse_from(["tt", "--token", "abc", "--expires-in", "60"])
+            .unwrap()
+            .args;
+        assert_eq!(parsed.token.as_deref(), Some("abc"));
+        assert_eq!(parsed.expires_in, Some(60));
+        assert!(
+            LoginArgsCli::try_parse_from(["tt", "--token", "abc", "--expires-in", "0"]).is_err()
+        );
+    }
+
     #[test]
     fn extract_callback_params_returns_code_and_state() {
         let (code, state) =
@@ -318,19 +568,74 @@ mod tests {
 
     #[test]
     fn format_status_lines_for_authenticated_session() {
-        let lines = format_status_lines(Some(&sample_config(4_000)), 1_000);
+        let lines = format_status_lines(Some(&sample_config(4_000)), 1_000, None);
 
         assert_eq!(lines[0], "Status: Authenticated");
         assert_eq!(lines[1], "Access Token: 12345678...abcdefgh");
         assert_eq!(lines[2], "Token expires in: 50 minutes");
     }
 
+    #[test]
+    fn mask_access_token_never_slices_short_tokens() {
+        assert_eq!(
+            mask_access_token("12345678-middle-abcdefgh"),
+            "12345678...abcdefgh"
+        );
+        assert_eq!(mask_access_token("short-token"), "********");
+        assert_eq!(mask_access_token("12345678abcdefgh"), "********");
+        assert_eq!(
+            mask_access_token("12345678-abcdefgh"),
+            "12345678...abcdefgh"
+        );
+        assert_eq!(mask_access_token("ééééééééé"), "********");
+        assert_eq!(mask_access_token(""), "(empty)");
+
+        let mut config = sample_config(4_000);
+        config.access_token = "abc".to_string();
+        let lines = format_status_lines(Some(&config), 1_000, None);
+        assert_eq!(lines[1], "Access Token: ********");
+    }
+
+    #[test]
+    fn format_status_lines_include_probe_results() {
+        let verified = format_status_lines(
+            Some(&sample_config(4_000)),
+            1_000,
+            Some(&ApiProbe::Verified {
+                project_count: 7,
+                inbox: true,
+            }),
+        );
+        assert_eq!(verified[0], "Status: Authenticated");
+        assert_eq!(verified[3], "Projects: 7");
+        assert_eq!(verified[4], "Inbox: detected");
+
+        let rejected = format_status_lines(
+            Some(&sample_config(4_000)),
+            1_000,
+            Some(&ApiProbe::Unauthorized),
+        );
+        assert_eq!(rejected[0], "Status: Token invalid or expired");
+        assert_eq!(
+            rejected[2],
+            "The API rejected this token. Run 'tt auth login' again."
+        );
+
+        let failed = format_status_lines(
+            Some(&sample_config(4_000)),
+            1_000,
+            Some(&ApiProbe::Failed("connection refused".to_string())),
+        );
+        assert_eq!(failed[0], "Status: Unverified (API check failed)");
+        assert_eq!(failed[3], "API error: connection refused");
+    }
+
     #[test]
     fn format_status_lines_for_expired_or_missing_session() {
-        let expired = format_status_lines(Some(&sample_config(900)), 1_000);
+        let expired = format_status_lines(Some(&sample_config(900)), 1_000, None);
         assert_eq!(expired[2], "Token expired! Please login again.");
 
-        let missing = format_status_lines(None, 1_000);
+        let missing = format_status_lines(None, 1_000, None);
         assert_eq!(missing[0], "Status: Not authenticated");
         assert_eq!(missing[1], "Run 'tt auth login' to authenticate.");
     }
@@ -0,0 +1,346 @@
+use super::auth::{probe_api_access, validate_redirect_uri, ApiProbe};
+use super::bootstrap::app_config;
+use crate::config::auth::{check_broker_health, AuthSettings};
+use crate::config::Config;
+use crate::output::{MessageFormat, OutputContext};
+use anyhow::{anyhow, Result};
+use clap::{Args, Subcommand};
+use serde::Serialize;
+
+#[derive(Subcommand)]
+pub enum ConfigCommands {
+    #[command(alias = "check")]
+    Doctor(DoctorArgs),
+}
+
+#[derive(Args)]
+pub struct DoctorArgs {
+    #[arg(
+        long,
+        help = "Skip the broker and API requests and check local setup only"
+    )]
+    no_verify: bool,
+    #[arg(long, default_value = "human")]
+    output: MessageFormat,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum CheckStatus {
+    Ok,
+    Warn,
+    Fail,
+    Skipped,
+}
+
+impl CheckStatus {
+    fn mark(self) -> &'static str {
+        match self {
+            Self::Ok => "✓",
+            Self::Warn => "!",
+            Self::Fail => "✗",
+            Self::Skipped => "-",
+        }
+    }
+}
+
+/// One line of the `tt config doctor` report, with what to do about it when it is not ok.
+#[derive(Debug, Clone, Serialize)]
+struct DoctorCheck {
+    name: &'static str,
+    status: CheckStatus,
+    detail: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hint: Option<String>,
+}
+
+impl DoctorCheck {
+    fn new(name: &'static str, status: CheckStatus, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            status,
+            detail: detail.into(),
+            hint: None,
+        }
+    }
+
+    fn hint(mut self, hint: impl Into<String>) -> Self {
+        self.hint = Some(hint.into());
+        self
+    }
+}
+
+pub async fn config_doctor(args: DoctorArgs, context: &OutputContext) -> Result<()> {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::SystemTime::UNIX_EPOCH)?
+        .as_secs() as i64;
+    let mut checks = Vec::new();
+
+    let settings = AuthSettings::from_env();
+    checks.push(oauth_settings_check(settings.as_ref()));
+    if let Ok(settings) = settings.as_ref() {
+        checks.push(redirect_uri_check(
+            settings.redirect_uri(),
+            validate_redirect_uri(settings.redirect_uri()),
+        ));
+        if let Some(broker_url) = settings.broker_url() {
+            checks.push(if args.no_verify {
+                DoctorCheck::new("OAuth broker", CheckStatus::Skipped, "--no-verify")
+            } else {
+                broker_check(broker_url, check_broker_health(broker_url).await)
+            });
+        }
+    }
+
+    let app_config = app_config()?;
+    let path = app_config.config_file_path().display().to_string();
+    let config = app_config.load();
+    checks.push(config_file_check(&path, config.as_ref()));
+
+    let config = config.ok().flatten();
+    checks.push(token_check(config.as_ref(), now));
+    checks.push(match config.as_ref() {
+        None => DoctorCheck::new("API access", CheckStatus::Skipped, "no saved session"),
+        Some(_) if args.no_verify => {
+            DoctorCheck::new("API access", CheckStatus::Skipped, "--no-verify")
+        }
+        Some(config) => api_check(&probe_api_access(config).await),
+    });
+
+    print!("{}", format_doctor_report(&checks, args.output, context)?);
+
+    let failed = checks
+        .iter()
+        .filter(|check| check.status == CheckStatus::Fail)
+        .count();
+    if failed > 0 {
+        return Err(anyhow!("{} of {} checks failed", failed, checks.len()));
+    }
+    Ok(())
+}
+
+fn oauth_settings_check(settings: Result<&AuthSettings, &anyhow::Error>) -> DoctorCheck {
+    const NAME: &str = "OAuth settings";
+    match settings {
+        Err(err) => DoctorCheck::new(NAME, CheckStatus::Fail, err.to_string()).hint(
+            "Set TICKTICK_CLIENT_SECRET for your TICKTICK_CLIENT_ID, or TICKTICK_OAUTH_BROKER_URL",
+        ),
+        Ok(settings) if settings.custom_client_on_shared_broker() => DoctorCheck::new(
+            NAME,
+            CheckStatus::Warn,
+            "TICKTICK_CLIENT_ID is set but tokens go through the shared broker",
+        )
+        .hint("Set TICKTICK_CLIENT_SECRET, or point TICKTICK_OAUTH_BROKER_URL at your own broker"),
+        Ok(settings) => match settings.broker_url() {
+            Some(broker_url) => DoctorCheck::new(
+                NAME,
+                CheckStatus::Ok,
+                format!("using broker {}", broker_url),
+            ),
+            None => DoctorCheck::new(
+                NAME,
+                CheckStatus::Ok,
+                "using TICKTICK_CLIENT_ID and TICKTICK_CLIENT_SECRET",
+            ),
+        },
+    }
+}
+
+fn redirect_uri_check(redirect_uri: &str, valid: Result<()>) -> DoctorCheck {
+    const NAME: &str = "Redirect URI";
+    match valid {
+        Ok(()) => DoctorCheck::new(NAME, CheckStatus::Ok, redirect_uri),
+        Err(err) => DoctorCheck::new(NAME, CheckStatus::Fail, err.to_string())
+            .hint("Use a loopback URL with a port, e.g. http://localhost:8080/callback"),
+    }
+}
+
+fn broker_check(broker_url: &str, health: Result<()>) -> DoctorCheck {
+    const NAME: &str = "OAuth broker";
+    match health {
+        Ok(()) => DoctorCheck::new(
+            NAME,
+            CheckStatus::Ok,
+            format!("{} is reachable", broker_url),
+        ),
+        Err(err) => DoctorCheck::new(NAME, CheckStatus::Fail, format!("{:#}", err))
+            .hint("Check your network or --proxy/HTTPS_PROXY, or set TICKTICK_OAUTH_BROKER_URL"),
+    }
+}
+
+fn config_file_check(path: &str, config: Result<&Option<Config>, &anyhow::Error>) -> DoctorCheck {
+    const NAME: &str = "Config file";
+    match config {
+        Ok(Some(_)) => DoctorCheck::new(NAME, CheckStatus::Ok, path),
+        Ok(None) => DoctorCheck::new(NAME, CheckStatus::Fail, format!("{} not found", path))
+            .hint("Run 'tt auth login'"),
+        Err(err) => DoctorCheck::new(NAME, CheckStatus::Fail, format!("{:#}", err)).hint(format!(
+            "Run 'tt auth logout', or delete {}, then 'tt auth login'",
+            path
+        )),
+    }
+}
+
+fn token_check(config: Option<&Config>, now: i64) -> DoctorCheck {
+    const NAME: &str = "Access token";
+    let Some(config) = config else {
+        return DoctorCheck::new(NAME, CheckStatus::Skipped, "no saved session");
+    };
+
+    let remaining = config.expires_at - now;
+    if remaining > 0 {
+        DoctorCheck::new(
+            NAME,
+            CheckStatus::Ok,
+            format!("expires in {} minutes", remaining / 60),
+        )
+    } else if !config.refresh_token.is_empty() {
+        DoctorCheck::new(
+            NAME,
+            CheckStatus::Warn,
+            "expired; it is refreshed on the next request",
+        )
+    } else {
+        DoctorCheck::new(
+            NAME,
+            CheckStatus::Fail,
+            "expired and no refresh token is saved",
+        )
+        .hint("Run 'tt auth login'")
+    }
+}
+
+fn api_check(probe: &ApiProbe) -> DoctorCheck {
+    const NAME: &str = "API access";
+    match probe {
+        ApiProbe::Verified { project_count, .. } => DoctorCheck::new(
+            NAME,
+            CheckStatus::Ok,
+            format!("{} lists visible", project_count),
+        ),
+        ApiProbe::Unauthorized => DoctorCheck::new(
+            NAME,
+            CheckStatus::Fail,
+            "TickTick rejected the access token",
+        )
+        .hint("Run 'tt auth login'"),
+        ApiProbe::Failed(message) => DoctorCheck::new(NAME, CheckStatus::Fail, message.clone())
+            .hint("Check your network or --proxy/HTTPS_PROXY; run with -v to see the request"),
+    }
+}
+
+fn format_doctor_report(
+    checks: &[DoctorCheck],
+    format: MessageFormat,
+    context: &OutputContext,
+) -> Result<String> {
+    match format {
+        MessageFormat::Json => Ok(format!("{}\n", context.to_json_string(checks)?)),
+        MessageFormat::Human => Ok(checks
+            .iter()
+            .map(|check| {
+                let mut line =
+                    format!("{} {}: {}\n", check.status.mark(), check.name, check.detail);
+                if let Some(hint) = check.hint.as_deref() {
+                    line.push_str(&format!("    {}\n", hint));
+                }
+                line
+            })
+            .collect()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(expires_at: i64, refresh_token: &str) -> Config {
+        Config {
+            access_token: "access-token-value".to_string(),
+            refresh_token: refresh_token.to_string(),
+            expires_at,
+        }
+    }
+
+    #[test]
+    fn token_check_warns_when_an_expired_token_can_be_refreshed() {
+        assert_eq!(
+            token_check(Some(&config(4_000, "")), 1_000).status,
+            CheckStatus::Ok
+        );
+        assert_eq!(
+            token_check(Some(&config(900, "refresh")), 1_000).status,
+            CheckStatus::Warn
+        );
+
+        let stuck = token_check(Some(&config(900, "")), 1_000);
+        assert_eq!(stuck.status, CheckStatus::Fail);
+        assert_eq!(stuck.hint.as_deref(), Some("Run 'tt auth login'"));
+        assert_eq!(token_check(None, 1_000).status, CheckStatus::Skipped);
+    }
+
+    #[test]
+    fn config_file_check_separates_missing_and_unreadable_files() {
+        let ok = config_file_check("/tmp/config.toml", Ok(&Some(config(1, ""))));
+        assert_eq!(ok.status, CheckStatus::Ok);
+
+        let missing = config_file_check("/tmp/config.toml", Ok(&None));
+        assert_eq!(missing.detail, "/tmp/config.toml not found");
+
+        let err = anyhow!("expected `=`").context("Failed to parse config file");
+        let broken = config_file_check("/tmp/config.toml", Err(&err));
+        assert_eq!(broken.status, CheckStatus::Fail);
+        assert_eq!(broken.detail, "Failed to parse config file: expected `=`");
+    }
+
+    #[test]
+    fn api_check_maps_each_probe_outcome() {
+        let verified = api_check(&ApiProbe::Verified {
+            project_count: 4,
+            inbox: true,
+        });
+        assert_eq!(verified.status, CheckStatus::Ok);
+        assert_eq!(verified.detail, "4 lists visible");
+        assert_eq!(api_check(&ApiProbe::Unauthorized).status, CheckStatus::Fail);
+        assert_eq!(
+            api_check(&ApiProbe::Failed("connection refused".to_string())).detail,
+            "connection refused"
+        );
+    }
+
+    #[test]
+    fn redirect_uri_check_reports_validation_errors() {
+        let uri = "http://localhost:8080/callback";
+        assert_eq!(
+            redirect_uri_check(uri, validate_redirect_uri(uri)).status,
+            CheckStatus::Ok
+        );
+
+        let remote = "http://example.com:8080/callback";
+        let check = redirect_uri_check(remote, validate_redirect_uri(remote));
+        assert_eq!(check.status, CheckStatus::Fail);
+        assert!(check.detail.contains("loopback host"));
+    }
+
+    #[test]
+    fn doctor_report_marks_each_check_and_indents_hints() {
+        let checks = vec![
+            DoctorCheck::new("Config file", CheckStatus::Ok, "/tmp/config.toml"),
+            DoctorCheck::new("API access", CheckStatus::Fail, "rejected")
+                .hint("Run 'tt auth login'"),
+            DoctorCheck::new("OAuth broker", CheckStatus::Skipped, "--no-verify"),
+        ];
+        assert_eq!(
+            format_doctor_report(&checks, MessageFormat::Human, &OutputContext::default()).unwrap(),
+            "✓ Config file: /tmp/config.toml\n✗ API access: rejected\n    Run 'tt auth login'\n- OAuth broker: --no-verify\n"
+        );
+
+        let json: serde_json::Value = serde_json::from_str(
+            &format_doctor_report(&checks, MessageFormat::Json, &OutputContext::default()).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(json[1]["status"], "fail");
+        assert_eq!(json[1]["hint"], "Run 'tt auth login'");
+        assert!(json[0].get("hint").is_none());
+    }
+}
@@ -0,0 +1,234 @@
+use super::task::{all_tasks, count_tags, TaskSource};
+use crate::models::{Habit, Project, Task};
+use crate::output::{MessageFormat, OutputContext, TagCount, Verbosity};
+use anyhow::{Context, Result};
+use chrono::{DateTime, SecondsFormat, Utc};
+use clap::{Args, ValueEnum};
+use serde::Serialize;
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// Bumped whenever a field of [`Export`] is renamed, removed, or changes meaning.
+pub const EXPORT_SCHEMA_VERSION: u32 = 1;
+
+#[derive(Clone, Copy, Debug, Default, ValueEnum)]
+pub enum ExportFormat {
+    #[default]
+    Json,
+}
+
+#[derive(Args)]
+pub struct ExportArgs {
+    #[arg(value_enum, default_value = "json", help = "Document format")]
+    format: ExportFormat,
+    #[arg(long, help = "Write the document to this file instead of stdout")]
+    out: Option<PathBuf>,
+    #[arg(
+        long,
+        default_value = "human",
+        help = "Format of the --out confirmation"
+    )]
+    output: MessageFormat,
+}
+
+/// Everything in the account as one document. TickTick has no folder or tag endpoints:
+/// folders are the projects' `groupId`s and tags are counted from the tasks.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Export {
+    pub schema_version: u32,
+    pub exported_at: String,
+    pub tool: ExportTool,
+    pub projects: Vec<Project>,
+    pub folders: Vec<ExportFolder>,
+    pub tasks: Vec<Task>,
+    pub tags: Vec<TagCount>,
+    pub habits: Vec<Habit>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExportTool {
+    pub name: &'static str,
+    pub version: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportFolder {
+    pub id: String,
+    pub project_ids: Vec<String>,
+}
+
+impl Export {
+    fn new(
+        projects: Vec<Project>,
+        tasks: Vec<Task>,
+        habits: Vec<Habit>,
+        exported_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            schema_version: EXPORT_SCHEMA_VERSION,
+            exported_at: exported_at.to_rfc3339_opts(SecondsFormat::Secs, true),
+            tool: ExportTool {
+                name: env!("CARGO_PKG_NAME"),
+                version: env!("CARGO_PKG_VERSION"),
+            },
+            folders: export_folders(&projects),
+            tags: count_tags(&tasks),
+            projects,
+            tasks,
+            habits,
+        }
+    }
+}
+
+/// One folder per `groupId`, listing its projects in their original order; sorted by ID.
+fn export_folders(projects: &[Project]) -> Vec<ExportFolder> {
+    let mut folders: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+    for project in projects {
+        let Some(group_id) = project.group_id.as_deref().filter(|id| !id.is_empty()) else {
+            continue;
+        };
+        folders
+            .entry(group_id)
+            .or_default()
+            .extend(project.id.clone());
+    }
+    folders
+        .into_iter()
+        .map(|(id, project_ids)| ExportFolder {
+            id: id.to_string(),
+            project_ids,
+        })
+        .collect()
+}
+
+/// `GET /habit` is not a documented endpoint, so when it fails the backup still goes
+/// ahead with no habits and a warning on stderr.
+fn habits_or_warn(habits: Result<Vec<Habit>>) -> Vec<Habit> {
+    habits.unwrap_or_else(|err| {
+        eprintln!("Warning: habits were not exported: {:#}", err);
+        Vec::new()
+    })
+}
+
+/// Fetches lists, tasks, and habits at the same time and prints one JSON document, or
+/// writes it to `--out` and confirms with the counts.
+pub async fn export(args: ExportArgs, verbosity: Verbosity, context: &OutputContext) -> Result<()> {
+    let (client, cache) = TaskSource::online("export")?;
+
+    let (lists, habits) = tokio::join!(
+        async { tokio::try_join!(client.get_projects(), all_tasks(&client, cache.as_ref())) },
+        client.get_habits(),
+    );
+    let (projects, tasks) = lists?;
+    let document = Export::new(projects, tasks, habits_or_warn(habits), Utc::now());
+    let rendered = match args.format {
+        ExportFormat::Json => format!("{}\n", context.to_json_string(&document)?),
+    };
+
+    let Some(path) = args.out else {
+        print!("{}", rendered);
+        return Ok(());
+    };
+    std::fs::write(&path, rendered)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+
+    let confirmation = match args.output {
+        MessageFormat::Json => format!(
+            "{}\n",
+            context.to_json_string(&json!({
+                "path": path.display().to_string(),
+                "projects": document.projects.len(),
+                "tasks": document.tasks.len(),
+                "habits": document.habits.len(),
+            }))?
+        ),
+        MessageFormat::Human => {
+            format!(
+                "Exported {} lists, {} tasks, and {} habits to {}\n",
+                document.projects.len(),
+                document.tasks.len(),
+                document.habits.len(),
+                path.display()
+            )
+        }
+    };
+    verbosity.confirm(&confirmation, args.output);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn project(id: &str, group_id: Option<&str>) -> Project {
+        Project {
+            id: Some(id.to_string()),
+            group_id: group_id.map(str::to_string),
+            ..Default::default()
+        }
+    }
+
+    fn tagged_task(id: &str, tags: &[&str]) -> Task {
+        Task {
+            id: Some(id.to_string()),
+            tags: Some(tags.iter().map(|tag| tag.to_string()).collect()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn export_derives_folders_and_tags_and_stamps_the_document() {
+        let projects = vec![
+            project("p1", Some("g2")),
+            project("p2", None),
+            project("p3", Some("g1")),
+            project("p4", Some("g2")),
+        ];
+        let tasks = vec![
+            tagged_task("t1", &["work"]),
+            tagged_task("t2", &["Work", "home"]),
+        ];
+        let exported_at = Utc.with_ymd_and_hms(2026, 10, 15, 12, 30, 0).unwrap();
+
+        let document = Export::new(projects, tasks, Vec::new(), exported_at);
+        let value = serde_json::to_value(&document).unwrap();
+
+        assert_eq!(value["schemaVersion"], EXPORT_SCHEMA_VERSION);
+        assert_eq!(value["exportedAt"], "2026-10-15T12:30:00Z");
+        assert_eq!(value["tool"]["name"], env!("CARGO_PKG_NAME"));
+        assert_eq!(value["tool"]["version"], env!("CARGO_PKG_VERSION"));
+        assert_eq!(
+            value["folders"],
+            json!([
+                {"id": "g1", "projectIds": ["p3"]},
+                {"id": "g2", "projectIds": ["p1", "p4"]},
+            ])
+        );
+        assert_eq!(
+            value["tags"],
+            json!([
+                {"tag": "home", "open": 1, "total": 1},
+                {"tag": "work", "open": 2, "total": 2},
+            ])
+        );
+        assert_eq!(value["projects"].as_array().unwrap().len(), 4);
+        assert_eq!(value["tasks"].as_array().unwrap().len(), 2);
+        assert_eq!(value["habits"], json!([]));
+    }
+
+    #[test]
+    fn failed_habit_fetch_exports_no_habits() {
+        assert!(habits_or_warn(Err(anyhow::anyhow!("404 Not Found"))).is_empty());
+        let habit = Habit {
+            id: Some("h1".to_string()),
+            ..Default::default()
+        };
+        let habits = habits_or_warn(Ok(vec![habit]));
+        assert_eq!(habits.len(), 1);
+        assert_eq!(habits[0].id.as_deref(), Some("h1"));
+    }
+}
@@ -1,15 +1,43 @@
-use super::bootstrap::authenticated_client;
-use crate::output::{print_habits, OutputFormat};
-use anyhow::Result;
+use super::task::{ensure_can_prompt, TaskSource};
+use crate::api::TickTickClient;
+use crate::models::{Habit, HabitCheckin};
+use crate::output::{
+    print_habit_checkin, print_habit_stats, print_habits, HabitCheckinOutcome, HabitRate,
+    HabitStats, HabitSummary, MessageFormat, OutputContext, OutputFormat, Verbosity,
+};
+use anyhow::{anyhow, Result};
+use atty::Stream;
+use chrono::{Datelike, Duration, Local, NaiveDate, Weekday};
 use clap::{Args, Subcommand};
-use uuid::Uuid;
+use std::collections::HashMap;
+
+/// How far back `habit list` looks for check-ins; longer streaks are capped here.
+const CHECKIN_HISTORY_DAYS: i64 = 365;
+/// Days shown in the `habit stats` strip, oldest first.
+const HEAT_STRIP_DAYS: i64 = 14;
 
 #[derive(Subcommand)]
 pub enum HabitCommands {
     Add(HabitAddArgs),
+    #[command(alias = "ls")]
     List(HabitListArgs),
     Update(HabitUpdateArgs),
+    #[command(alias = "done")]
+    Checkin(HabitCheckinArgs),
+    #[command(alias = "show")]
+    Stats(HabitStatsArgs),
+    #[command(alias = "rm")]
     Delete(HabitDeleteArgs),
+    Archive(HabitArchiveArgs),
+    #[command(alias = "restore")]
+    Unarchive(HabitUnarchiveArgs),
+}
+
+fn parse_habit_days(value: &str) -> Vec<i32> {
+    value
+        .split(',')
+        .filter_map(|s| s.trim().parse().ok())
+        .collect()
 }
 
 #[derive(Args)]
@@ -18,7 +46,7 @@ pub struct HabitAddArgs {
     #[arg(long)]
     content: Option<String>,
     #[arg(long)]
-    goal: Option<i32>,
+    goal: Option<f64>,
     #[arg(long)]
     unit: Option<String>,
     #[arg(long)]
@@ -28,61 +56,524 @@ pub struct HabitAddArgs {
     #[arg(long)]
     repeat: Option<String>,
     #[arg(long, default_value = "human")]
-    output: OutputFormat,
+    output: MessageFormat,
 }
 
-pub async fn habit_add(args: HabitAddArgs) -> Result<()> {
-    let client = authenticated_client()?;
+pub async fn habit_add(
+    args: HabitAddArgs,
+    verbosity: Verbosity,
+    context: &OutputContext,
+) -> Result<()> {
+    let (client, _) = TaskSource::online("habit add")?;
 
-    let days = args
-        .days
-        .map(|d| d.split(',').filter_map(|s| s.trim().parse().ok()).collect());
-
-    let habit = crate::models::Habit {
-        id: Uuid::new_v4().to_string(),
+    let habit = Habit {
         title: args.title,
         content: args.content,
         goal: args.goal,
         unit: args.unit,
-        days,
+        days: args.days.as_deref().map(parse_habit_days),
         remind: args.remind,
         repeat: args.repeat,
         repeated: Some(true),
         ..Default::default()
     };
 
-    client.create_habit(&habit).await?;
+    let created = client.create_habit(&habit).await?;
 
     match args.output {
-        OutputFormat::Json => {
-            println!("{}", serde_json::to_string_pretty(&habit)?);
+        MessageFormat::Json => {
+            println!("{}", context.to_json_string(&created)?);
+        }
+        MessageFormat::Human if verbosity.is_quiet() => {
+            println!("{}", created.id.as_deref().unwrap_or_default());
+        }
+        MessageFormat::Human => {
+            println!("Habit created: {}", created.title);
+        }
+    }
+
+    Ok(())
+}
+
+fn checkin_stamp(date: NaiveDate) -> i64 {
+    i64::from(date.year()) * 10_000 + i64::from(date.month()) * 100 + i64::from(date.day())
+}
+
+fn completed_on(checkins: &[HabitCheckin], date: NaiveDate) -> bool {
+    let stamp = checkin_stamp(date);
+    checkins
+        .iter()
+        .any(|checkin| checkin.checkin_stamp == stamp && checkin.is_completed())
+}
+
+/// Counts consecutive completed days ending today, or yesterday when today has not been
+/// checked in yet, so an unfinished day does not reset the streak.
+fn habit_streak(checkins: &[HabitCheckin], today: NaiveDate) -> u32 {
+    let mut day = if completed_on(checkins, today) {
+        today
+    } else {
+        today - Duration::days(1)
+    };
+
+    let mut streak = 0;
+    while streak < CHECKIN_HISTORY_DAYS as u32 && completed_on(checkins, day) {
+        streak += 1;
+        day -= Duration::days(1);
+    }
+    streak
+}
+
+/// Which weekdays a habit is due. `days` holds weekday numbers where 0 and 7 are Sunday
+/// and 1-6 are Monday to Saturday; a `BYDAY` list in the repeat rule is used when `days`
+/// is empty. Habits with neither are due every day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct HabitSchedule {
+    weekdays: Option<Vec<Weekday>>,
+}
+
+impl HabitSchedule {
+    fn for_habit(habit: &Habit) -> Self {
+        let from_days: Vec<Weekday> = habit
+            .days
+            .as_deref()
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|day| match day.rem_euclid(7) {
+                0 => Some(Weekday::Sun),
+                day => Weekday::try_from(day as u8 - 1).ok(),
+            })
+            .collect();
+        let weekdays = if from_days.is_empty() {
+            habit.repeat.as_deref().and_then(rrule_weekdays)
+        } else {
+            Some(from_days)
+        };
+        Self { weekdays }
+    }
+
+    fn is_scheduled(&self, date: NaiveDate) -> bool {
+        self.weekdays
+            .as_ref()
+            .is_none_or(|weekdays| weekdays.contains(&date.weekday()))
+    }
+
+    /// `daily`, or the scheduled weekdays from Monday on, e.g. `Mon Wed Fri`.
+    fn label(&self) -> String {
+        let Some(weekdays) = self.weekdays.as_ref() else {
+            return "daily".to_string();
+        };
+        let mut days: Vec<Weekday> = weekdays.clone();
+        days.sort_by_key(Weekday::num_days_from_monday);
+        days.dedup();
+        if days.len() == 7 {
+            return "daily".to_string();
+        }
+        days.iter()
+            .map(|day| day.to_string())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+fn rrule_weekdays(rule: &str) -> Option<Vec<Weekday>> {
+    let byday = rule
+        .trim_start_matches("RRULE:")
+        .split(';')
+        .find_map(|part| part.strip_prefix("BYDAY="))?;
+    let weekdays: Vec<Weekday> = byday
+        .split(',')
+        .filter_map(|day| match day.trim().to_ascii_uppercase().as_str() {
+            "MO" => Some(Weekday::Mon),
+            "TU" => Some(Weekday::Tue),
+            "WE" => Some(Weekday::Wed),
+            "TH" => Some(Weekday::Thu),
+            "FR" => Some(Weekday::Fri),
+            "SA" => Some(Weekday::Sat),
+            "SU" => Some(Weekday::Sun),
+            _ => None,
+        })
+        .collect();
+    (!weekdays.is_empty()).then_some(weekdays)
+}
+
+/// Like [`habit_streak`], but days the habit is not scheduled neither extend nor break the
+/// run unless they were checked in anyway.
+fn scheduled_streak(
+    checkins: &[HabitCheckin],
+    schedule: &HabitSchedule,
+    today: NaiveDate,
+    history_days: i64,
+) -> u32 {
+    let oldest = today - Duration::days(history_days);
+    let mut day = today;
+    let mut streak = 0;
+    while day > oldest {
+        if completed_on(checkins, day) {
+            streak += 1;
+        } else if schedule.is_scheduled(day) && day != today {
+            break;
+        }
+        day -= Duration::days(1);
+    }
+    streak
+}
+
+fn longest_streak(
+    checkins: &[HabitCheckin],
+    schedule: &HabitSchedule,
+    from: NaiveDate,
+    today: NaiveDate,
+) -> u32 {
+    let (mut longest, mut run) = (0, 0);
+    let mut day = from;
+    while day <= today {
+        if completed_on(checkins, day) {
+            run += 1;
+            longest = longest.max(run);
+        } else if schedule.is_scheduled(day) && day != today {
+            run = 0;
         }
-        OutputFormat::Human => {
-            println!("Habit created: {}", habit.title);
+        day += Duration::days(1);
+    }
+    longest
+}
+
+/// Completed scheduled days out of scheduled days over the last `days` days. Today only
+/// counts once it is done, so an unfinished day does not drag the rate down.
+fn completion_rate(
+    checkins: &[HabitCheckin],
+    schedule: &HabitSchedule,
+    today: NaiveDate,
+    days: i64,
+) -> HabitRate {
+    let mut rate = HabitRate {
+        days,
+        completed: 0,
+        scheduled: 0,
+    };
+    for offset in 0..days {
+        let day = today - Duration::days(offset);
+        let done = completed_on(checkins, day);
+        if !schedule.is_scheduled(day) || (day == today && !done) {
+            continue;
+        }
+        rate.scheduled += 1;
+        if done {
+            rate.completed += 1;
         }
     }
+    rate
+}
+
+/// `✓` for a completed day, `·` for a missed scheduled day, `-` for a day off, oldest first.
+fn heat_strip(
+    checkins: &[HabitCheckin],
+    schedule: &HabitSchedule,
+    today: NaiveDate,
+    days: i64,
+) -> String {
+    (0..days)
+        .rev()
+        .map(|offset| {
+            let day = today - Duration::days(offset);
+            if completed_on(checkins, day) {
+                '✓'
+            } else if schedule.is_scheduled(day) {
+                '·'
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
+
+fn compute_habit_stats(habit: &Habit, checkins: &[HabitCheckin], today: NaiveDate) -> HabitStats {
+    let schedule = HabitSchedule::for_habit(habit);
+    let from = today - Duration::days(CHECKIN_HISTORY_DAYS - 1);
+    HabitStats {
+        habit_id: habit.id.clone().unwrap_or_default(),
+        title: habit.title.clone(),
+        current_streak: scheduled_streak(checkins, &schedule, today, CHECKIN_HISTORY_DAYS),
+        longest_streak: longest_streak(checkins, &schedule, from, today),
+        last_30_days: completion_rate(checkins, &schedule, today, 30),
+        last_90_days: completion_rate(checkins, &schedule, today, 90),
+        recent: heat_strip(checkins, &schedule, today, HEAT_STRIP_DAYS),
+        done_today: completed_on(checkins, today),
+    }
+}
+
+#[derive(Args)]
+pub struct HabitStatsArgs {
+    #[arg(help = "Habit name or ID")]
+    habit: String,
+    #[arg(long, default_value = "human")]
+    output: MessageFormat,
+}
+
+pub async fn habit_stats(args: HabitStatsArgs, context: &OutputContext) -> Result<()> {
+    let (client, _) = TaskSource::online("habit stats")?;
+    let habit = match_habit(client.get_habits().await?, &args.habit)?;
+    let habit_id = habit
+        .id
+        .clone()
+        .ok_or_else(|| anyhow!("Habit '{}' has no ID", habit.title))?;
 
+    let today = Local::now().date_naive();
+    let checkins = client
+        .get_habit_checkins_between(
+            &habit_id,
+            checkin_stamp(today - Duration::days(CHECKIN_HISTORY_DAYS - 1)),
+            checkin_stamp(today),
+        )
+        .await?;
+
+    print_habit_stats(
+        &compute_habit_stats(&habit, &checkins, today),
+        args.output,
+        context,
+    );
     Ok(())
 }
 
+fn summarize_habit(
+    habit: Habit,
+    checkins: Option<&[HabitCheckin]>,
+    today: NaiveDate,
+) -> HabitSummary {
+    let streak = habit
+        .current_streak
+        .or_else(|| checkins.map(|checkins| habit_streak(checkins, today)));
+    let checked_in_today = checkins.map(|checkins| completed_on(checkins, today));
+    let schedule = HabitSchedule::for_habit(&habit);
+
+    HabitSummary {
+        schedule: schedule.label(),
+        scheduled_today: schedule.is_scheduled(today),
+        habit,
+        streak,
+        checked_in_today,
+    }
+}
+
 #[derive(Args)]
 pub struct HabitListArgs {
     #[arg(long)]
     name: Option<String>,
+    #[arg(
+        long,
+        conflicts_with = "archived",
+        help = "Only habits that are not archived"
+    )]
+    active: bool,
+    #[arg(long, help = "Only archived habits")]
+    archived: bool,
+    #[arg(
+        long,
+        conflicts_with = "archived",
+        help = "Only active habits scheduled for today, with whether each is checked in"
+    )]
+    due_today: bool,
     #[arg(long, default_value = "human")]
     output: OutputFormat,
 }
 
-pub async fn habit_list(args: HabitListArgs) -> Result<()> {
-    let client = authenticated_client()?;
+pub async fn habit_list(args: HabitListArgs, context: &OutputContext) -> Result<()> {
+    let (client, _) = TaskSource::online("habit list")?;
 
     let mut habits = client.get_habits().await?;
 
     if let Some(name) = args.name {
-        habits.retain(|h| h.title.contains(&name));
+        let needle = name.to_lowercase();
+        habits.retain(|h| h.title.to_lowercase().contains(&needle));
+    }
+    if args.active || args.due_today {
+        habits.retain(|habit| !habit.is_archived());
+    }
+    if args.archived {
+        habits.retain(Habit::is_archived);
+    }
+
+    let today = Local::now().date_naive();
+    let habit_ids: Vec<String> = habits.iter().filter_map(|h| h.id.clone()).collect();
+    // Check-in stats are best effort: the list still renders if the query fails.
+    let checkins: Option<HashMap<String, Vec<HabitCheckin>>> = if habit_ids.is_empty() {
+        None
+    } else {
+        client
+            .get_habit_checkins(
+                &habit_ids,
+                checkin_stamp(today - Duration::days(CHECKIN_HISTORY_DAYS)),
+            )
+            .await
+            .ok()
+    };
+
+    let mut summaries: Vec<HabitSummary> = habits
+        .into_iter()
+        .map(|habit| {
+            let habit_checkins = checkins.as_ref().map(|checkins| {
+                habit
+                    .id
+                    .as_ref()
+                    .and_then(|id| checkins.get(id))
+                    .map_or(&[][..], Vec::as_slice)
+            });
+            summarize_habit(habit, habit_checkins, today)
+        })
+        .collect();
+    if args.due_today {
+        summaries.retain(|summary| summary.scheduled_today);
+    }
+
+    print_habits(&summaries, args.output, context);
+    Ok(())
+}
+
+async fn find_habit(client: &TickTickClient, habit_id: &str) -> Result<Habit> {
+    client
+        .get_habits()
+        .await?
+        .into_iter()
+        .find(|h| h.id.as_deref() == Some(habit_id))
+        .ok_or_else(|| anyhow!("Habit not found: {}", habit_id))
+}
+
+/// Picks a habit by exact ID, then by title: an exact (case-insensitive) title first,
+/// otherwise the only title containing `query`.
+fn match_habit(habits: Vec<Habit>, query: &str) -> Result<Habit> {
+    let needle = query.trim().to_lowercase();
+    let mut by_title = Vec::new();
+    for habit in habits {
+        if habit.id.as_deref() == Some(query) || habit.title.to_lowercase() == needle {
+            return Ok(habit);
+        }
+        if habit.title.to_lowercase().contains(&needle) {
+            by_title.push(habit);
+        }
     }
 
-    print_habits(&habits, args.output);
+    match by_title.len() {
+        0 => Err(anyhow!("Habit not found: {}", query)),
+        1 => Ok(by_title.remove(0)),
+        _ => Err(anyhow!(
+            "'{}' matches several habits: {}. Use the full name or the ID.",
+            query,
+            by_title
+                .iter()
+                .map(|habit| habit.title.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )),
+    }
+}
+
+fn parse_checkin_date(value: &str) -> std::result::Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d")
+        .map_err(|_| format!("Invalid date '{}'. Use YYYY-MM-DD", value))
+}
+
+#[derive(Args)]
+pub struct HabitCheckinArgs {
+    #[arg(help = "Habit name or ID")]
+    habit: String,
+    #[arg(
+        long,
+        value_parser = parse_checkin_date,
+        help = "Day to check in (YYYY-MM-DD, default today)"
+    )]
+    date: Option<NaiveDate>,
+    #[arg(
+        long,
+        help = "Amount to add to the day's progress on a goal-based habit (default 1)"
+    )]
+    value: Option<f64>,
+    #[arg(long, default_value = "human")]
+    output: MessageFormat,
+}
+
+/// The check-in to send for `date`, or `None` when the day is already complete and no
+/// extra `--value` was given, so checking in twice does not double count.
+fn next_checkin(
+    habit: &Habit,
+    existing: Option<&HabitCheckin>,
+    date: NaiveDate,
+    value: Option<f64>,
+) -> Option<HabitCheckin> {
+    if value.is_none() && existing.is_some_and(HabitCheckin::is_completed) {
+        return None;
+    }
+
+    let goal = habit.goal.filter(|goal| *goal > 0.0).unwrap_or(1.0);
+    let logged = existing.and_then(|checkin| checkin.value).unwrap_or(0.0);
+    let total = logged + value.unwrap_or(1.0);
+    Some(HabitCheckin {
+        habit_id: habit.id.clone(),
+        checkin_stamp: checkin_stamp(date),
+        value: Some(total),
+        goal: Some(goal),
+        status: Some(if total >= goal { 2 } else { 0 }),
+    })
+}
+
+pub async fn habit_checkin(
+    args: HabitCheckinArgs,
+    verbosity: Verbosity,
+    context: &OutputContext,
+) -> Result<()> {
+    let today = Local::now().date_naive();
+    let date = args.date.unwrap_or(today);
+    if date > today {
+        return Err(anyhow!("Cannot check in for a future date: {}", date));
+    }
+
+    let (client, _) = TaskSource::online("habit checkin")?;
+    let habit = match_habit(client.get_habits().await?, &args.habit)?;
+    let habit_id = habit
+        .id
+        .clone()
+        .ok_or_else(|| anyhow!("Habit '{}' has no ID", habit.title))?;
+
+    let existing = client
+        .get_habit_checkins(
+            std::slice::from_ref(&habit_id),
+            checkin_stamp(date - Duration::days(1)),
+        )
+        .await?
+        .remove(&habit_id)
+        .unwrap_or_default()
+        .into_iter()
+        .find(|checkin| checkin.checkin_stamp == checkin_stamp(date));
+
+    let mut outcome = HabitCheckinOutcome {
+        habit_id: habit_id.clone(),
+        title: habit.title.clone(),
+        date: date.format("%Y-%m-%d").to_string(),
+        value: existing.as_ref().and_then(|checkin| checkin.value),
+        goal: habit.goal,
+        unit: habit.unit.clone(),
+        completed: true,
+        already_checked_in: true,
+        current_streak: None,
+        total_check_ins: None,
+    };
+
+    if let Some(checkin) = next_checkin(&habit, existing.as_ref(), date, args.value) {
+        let receipt = client
+            .habit_checkin(&habit_id, &checkin)
+            .await?
+            .unwrap_or_default();
+        outcome.value = receipt.value.or(checkin.value);
+        outcome.goal = receipt.goal.or(checkin.goal);
+        outcome.completed = receipt.status.or(checkin.status) == Some(2);
+        outcome.already_checked_in = false;
+        outcome.current_streak = receipt.current_streak;
+        outcome.total_check_ins = receipt.total_check_ins;
+    }
+
+    if args.output == MessageFormat::Human && verbosity.is_quiet() {
+        return Ok(());
+    }
+    print_habit_checkin(&outcome, args.output, context);
     Ok(())
 }
 
@@ -94,7 +585,7 @@ pub struct HabitUpdateArgs {
     #[arg(long)]
     content: Option<String>,
     #[arg(long)]
-    goal: Option<i32>,
+    goal: Option<f64>,
     #[arg(long)]
     unit: Option<String>,
     #[arg(long)]
@@ -105,14 +596,10 @@ pub struct HabitUpdateArgs {
     repeat: Option<String>,
 }
 
-pub async fn habit_update(args: HabitUpdateArgs) -> Result<()> {
-    let client = authenticated_client()?;
+pub async fn habit_update(args: HabitUpdateArgs, verbosity: Verbosity) -> Result<()> {
+    let (client, _) = TaskSource::online("habit update")?;
 
-    let habits = client.get_habits().await?;
-    let mut habit = habits
-        .into_iter()
-        .find(|h| h.id == args.habit_id)
-        .ok_or_else(|| anyhow::anyhow!("Habit not found: {}", args.habit_id))?;
+    let mut habit = find_habit(&client, &args.habit_id).await?;
 
     if let Some(title) = args.title {
         habit.title = title;
@@ -127,11 +614,7 @@ pub async fn habit_update(args: HabitUpdateArgs) -> Result<()> {
         habit.unit = Some(unit);
     }
     if let Some(days) = args.days {
-        habit.days = Some(
-            days.split(',')
-                .filter_map(|s| s.trim().parse().ok())
-                .collect(),
-        );
+        habit.days = Some(parse_habit_days(&days));
     }
     if let Some(remind) = args.remind {
         habit.remind = Some(remind);
@@ -140,8 +623,10 @@ pub async fn habit_update(args: HabitUpdateArgs) -> Result<()> {
         habit.repeat = Some(repeat);
     }
 
-    client.update_habit(&habit).await?;
-    println!("Habit updated: {}", habit.title);
+    client.update_habit(&args.habit_id, &habit).await?;
+    if !verbosity.is_quiet() {
+        println!("Habit updated: {}", habit.title);
+    }
     Ok(())
 }
 
@@ -150,31 +635,437 @@ pub struct HabitDeleteArgs {
     habit_id: String,
     #[arg(long, default_value = "true")]
     confirm: bool,
+    #[arg(
+        long,
+        visible_alias = "yes",
+        short = 'y',
+        help = "Skip the confirmation prompt"
+    )]
+    force: bool,
 }
 
-pub async fn habit_delete(args: HabitDeleteArgs) -> Result<()> {
-    let client = authenticated_client()?;
+pub async fn habit_delete(args: HabitDeleteArgs, verbosity: Verbosity) -> Result<()> {
+    let (client, _) = TaskSource::online("habit delete")?;
 
-    let habits = client.get_habits().await?;
-    let habit = habits
-        .into_iter()
-        .find(|h| h.id == args.habit_id)
-        .ok_or_else(|| anyhow::anyhow!("Habit not found: {}", args.habit_id))?;
+    let habit = find_habit(&client, &args.habit_id).await?;
 
-    if args.confirm {
-        println!(
+    if args.confirm && !args.force {
+        ensure_can_prompt(atty::is(Stream::Stdin), MessageFormat::Human)?;
+        eprintln!(
             "Are you sure you want to delete habit '{}'? [y/N]",
             habit.title
         );
         let mut input = String::new();
         std::io::stdin().read_line(&mut input)?;
         if !input.trim().eq_ignore_ascii_case("y") {
-            println!("Cancelled.");
+            eprintln!("Cancelled.");
             return Ok(());
         }
     }
 
     client.delete_habit(&args.habit_id).await?;
-    println!("Habit deleted: {}", habit.title);
+    if !verbosity.is_quiet() {
+        println!("Habit deleted: {}", habit.title);
+    }
+    Ok(())
+}
+
+#[derive(Args)]
+pub struct HabitArchiveArgs {
+    #[arg(help = "Habit name or ID")]
+    habit: String,
+    #[arg(long, default_value = "true")]
+    confirm: bool,
+    #[arg(
+        long,
+        visible_alias = "yes",
+        short = 'y',
+        help = "Skip the confirmation prompt"
+    )]
+    force: bool,
+    #[arg(long, default_value = "human")]
+    output: MessageFormat,
+}
+
+#[derive(Args)]
+pub struct HabitUnarchiveArgs {
+    #[arg(help = "Habit name or ID")]
+    habit: String,
+    #[arg(long, default_value = "human")]
+    output: MessageFormat,
+}
+
+pub async fn habit_archive(
+    args: HabitArchiveArgs,
+    verbosity: Verbosity,
+    context: &OutputContext,
+) -> Result<()> {
+    set_habit_archived(
+        &args.habit,
+        true,
+        args.confirm && !args.force,
+        args.output,
+        verbosity,
+        context,
+    )
+    .await
+}
+
+pub async fn habit_unarchive(
+    args: HabitUnarchiveArgs,
+    verbosity: Verbosity,
+    context: &OutputContext,
+) -> Result<()> {
+    set_habit_archived(&args.habit, false, false, args.output, verbosity, context).await
+}
+
+/// The habit as an update body. Unarchiving sends `archivedTime` as an explicit null,
+/// since the field is skipped when unset.
+fn habit_update_payload(habit: &Habit) -> Result<serde_json::Value> {
+    let mut payload = serde_json::to_value(habit)?;
+    if !habit.is_archived() {
+        payload["archivedTime"] = serde_json::Value::Null;
+    }
+    Ok(payload)
+}
+
+async fn set_habit_archived(
+    habit_ref: &str,
+    archived: bool,
+    confirm: bool,
+    output: MessageFormat,
+    verbosity: Verbosity,
+    context: &OutputContext,
+) -> Result<()> {
+    let command = if archived {
+        "habit archive"
+    } else {
+        "habit unarchive"
+    };
+    let (client, _) = TaskSource::online(command)?;
+    let mut habit = match_habit(client.get_habits().await?, habit_ref)?;
+    let habit_id = habit
+        .id
+        .clone()
+        .ok_or_else(|| anyhow!("Habit '{}' has no ID", habit.title))?;
+    if habit.is_archived() == archived {
+        let rendered = match output {
+            MessageFormat::Json => format!("{}\n", context.to_json_string(&habit)?),
+            MessageFormat::Human => {
+                let state = if archived { "archived" } else { "active" };
+                format!("Habit '{}' is already {}.\n", habit.title, state)
+            }
+        };
+        verbosity.confirm(&rendered, output);
+        return Ok(());
+    }
+
+    if confirm {
+        ensure_can_prompt(atty::is(Stream::Stdin), output)?;
+        eprintln!(
+            "Archive habit '{}'? It stops showing up for check-ins; its history is kept. [y/N]",
+            habit.title
+        );
+        let mut input = String::new();
+        std::io::stdin().read_line(&mut input)?;
+        if !input.trim().eq_ignore_ascii_case("y") {
+            eprintln!("Cancelled.");
+            return Ok(());
+        }
+    }
+
+    let now = chrono::Utc::now()
+        .format("%Y-%m-%dT%H:%M:%S%.3f%z")
+        .to_string();
+    habit.set_archived(archived, &now);
+    client
+        .update_habit(&habit_id, &habit_update_payload(&habit)?)
+        .await?;
+
+    let rendered = match output {
+        MessageFormat::Json => format!("{}\n", context.to_json_string(&habit)?),
+        MessageFormat::Human if archived => {
+            format!("Habit archived: {}\n", habit.title)
+        }
+        MessageFormat::Human => {
+            format!("Habit unarchived: {}\n", habit.title)
+        }
+    };
+    verbosity.confirm(&rendered, output);
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, month, day).unwrap()
+    }
+
+    fn done(date: NaiveDate) -> HabitCheckin {
+        HabitCheckin {
+            checkin_stamp: checkin_stamp(date),
+            status: Some(2),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn checkin_stamp_formats_yyyymmdd() {
+        assert_eq!(checkin_stamp(day(3, 7)), 20260307);
+    }
+
+    #[test]
+    fn habit_streak_counts_back_from_today_across_month_boundaries() {
+        let checkins = vec![done(day(3, 2)), done(day(3, 1)), done(day(2, 28))];
+        assert_eq!(habit_streak(&checkins, day(3, 2)), 3);
+    }
+
+    #[test]
+    fn habit_streak_keeps_yesterdays_run_until_today_is_missed() {
+        let checkins = vec![done(day(3, 1)), done(day(2, 28)), done(day(2, 26))];
+        assert_eq!(habit_streak(&checkins, day(3, 2)), 2);
+        assert_eq!(habit_streak(&checkins, day(3, 3)), 0);
+    }
+
+    fn habit(title: &str, goal: Option<f64>) -> Habit {
+        Habit {
+            id: Some(format!("id-{}", title.to_lowercase())),
+            title: title.to_string(),
+            goal,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn match_habit_accepts_ids_exact_titles_and_unique_fragments() {
+        let habits = || {
+            vec![
+                habit("Read", None),
+                habit("Read news", None),
+                habit("Drink water", Some(8.0)),
+            ]
+        };
+        assert_eq!(
+            match_habit(habits(), "id-read news").unwrap().title,
+            "Read news"
+        );
+        assert_eq!(match_habit(habits(), "read").unwrap().title, "Read");
+        assert_eq!(match_habit(habits(), "water").unwrap().title, "Drink water");
+
+        let err = match_habit(habits(), "rea").unwrap_err().to_string();
+        assert!(err.contains("matches several habits: Read, Read news"));
+        assert_eq!(
+            match_habit(habits(), "yoga").unwrap_err().to_string(),
+            "Habit not found: yoga"
+        );
+    }
+
+    #[test]
+    fn next_checkin_completes_yes_no_habits_once() {
+        let read = habit("Read", None);
+        let first = next_checkin(&read, None, day(3, 2), None).unwrap();
+        assert_eq!(first.checkin_stamp, 20260302);
+        assert_eq!(first.value, Some(1.0));
+        assert_eq!(first.status, Some(2));
+
+        assert!(next_checkin(&read, Some(&done(day(3, 2))), day(3, 2), None).is_none());
+    }
+
+    #[test]
+    fn next_checkin_adds_value_toward_the_goal() {
+        let water = habit("Drink water", Some(8.0));
+        let partial = HabitCheckin {
+            checkin_stamp: checkin_stamp(day(3, 2)),
+            value: Some(5.0),
+            goal: Some(8.0),
+            status: Some(0),
+            ..Default::default()
+        };
+
+        let step = next_checkin(&water, Some(&partial), day(3, 2), None).unwrap();
+        assert_eq!(step.value, Some(6.0));
+        assert_eq!(step.status, Some(0));
+
+        let finish = next_checkin(&water, Some(&partial), day(3, 2), Some(3.0)).unwrap();
+        assert_eq!(finish.value, Some(8.0));
+        assert_eq!(finish.status, Some(2));
+
+        let reached = HabitCheckin {
+            value: Some(8.0),
+            ..partial
+        };
+        assert!(next_checkin(&water, Some(&reached), day(3, 2), None).is_none());
+        let extra = next_checkin(&water, Some(&reached), day(3, 2), Some(2.0)).unwrap();
+        assert_eq!(extra.value, Some(10.0));
+    }
+
+    fn scheduled(days: &[i32]) -> HabitSchedule {
+        HabitSchedule::for_habit(&Habit {
+            days: Some(days.to_vec()),
+            ..Default::default()
+        })
+    }
+
+    // 2026-03-02 is a Monday.
+    #[test]
+    fn habit_schedule_reads_weekday_numbers_or_the_byday_rule() {
+        let weekdays = scheduled(&[1, 3, 5]);
+        assert!(weekdays.is_scheduled(day(3, 2)));
+        assert!(!weekdays.is_scheduled(day(3, 3)));
+
+        assert!(scheduled(&[0]).is_scheduled(day(3, 1)));
+        assert!(scheduled(&[7]).is_scheduled(day(3, 1)));
+
+        let from_rule = HabitSchedule::for_habit(&Habit {
+            repeat: Some("RRULE:FREQ=WEEKLY;BYDAY=SA,SU".to_string()),
+            ..Default::default()
+        });
+        assert!(from_rule.is_scheduled(day(3, 7)));
+        assert!(!from_rule.is_scheduled(day(3, 2)));
+        assert!(HabitSchedule::for_habit(&Habit::default()).is_scheduled(day(3, 3)));
+    }
+
+    #[test]
+    fn scheduled_streak_skips_days_off() {
+        // Mon/Wed/Fri habit, done on each of them for two weeks.
+        let schedule = scheduled(&[1, 3, 5]);
+        let checkins: Vec<HabitCheckin> = [(2, 20), (2, 23), (2, 25), (2, 27), (3, 2)]
+            .into_iter()
+            .map(|(month, date)| done(day(month, date)))
+            .collect();
+
+        assert_eq!(scheduled_streak(&checkins, &schedule, day(3, 2), 365), 5);
+        // Tuesday is a day off and Wednesday is still open.
+        assert_eq!(scheduled_streak(&checkins, &schedule, day(3, 4), 365), 5);
+        // Missing Wednesday ends the run on Thursday.
+        assert_eq!(scheduled_streak(&checkins, &schedule, day(3, 5), 365), 0);
+
+        let daily = HabitSchedule::for_habit(&Habit::default());
+        assert_eq!(scheduled_streak(&checkins, &daily, day(3, 2), 365), 1);
+    }
+
+    #[test]
+    fn longest_streak_and_rates_ignore_unscheduled_days() {
+        let schedule = scheduled(&[1, 3, 5]);
+        let checkins: Vec<HabitCheckin> = [(2, 2), (2, 4), (2, 6), (2, 9), (2, 13), (3, 2)]
+            .into_iter()
+            .map(|(month, date)| done(day(month, date)))
+            .collect();
+
+        // Feb 2-9 is four scheduled days in a row; Feb 11 was missed.
+        assert_eq!(
+            longest_streak(&checkins, &schedule, day(2, 1), day(3, 2)),
+            4
+        );
+
+        let rate = completion_rate(&checkins, &schedule, day(3, 2), 14);
+        assert_eq!((rate.completed, rate.scheduled), (1, 6));
+
+        // An open today is left out of the rate until it is checked in.
+        let rate = completion_rate(&checkins, &schedule, day(3, 4), 3);
+        assert_eq!((rate.completed, rate.scheduled), (1, 1));
+    }
+
+    #[test]
+    fn heat_strip_marks_done_missed_and_off_days_oldest_first() {
+        let schedule = scheduled(&[1, 3, 5]);
+        let checkins = vec![done(day(2, 27)), done(day(3, 1)), done(day(3, 2))];
+        // Wed 25 .. Mon 2: Wed missed, Thu off, Fri done, Sat off, Sun done anyway, Mon done.
+        assert_eq!(heat_strip(&checkins, &schedule, day(3, 2), 6), "·-✓-✓✓");
+    }
+
+    #[test]
+    fn compute_habit_stats_combines_streaks_rates_and_strip() {
+        let habit = Habit {
+            id: Some("h1".to_string()),
+            title: "Run".to_string(),
+            days: Some(vec![1, 3, 5]),
+            ..Default::default()
+        };
+        let checkins = vec![done(day(2, 27)), done(day(3, 2))];
+        let stats = compute_habit_stats(&habit, &checkins, day(3, 2));
+        assert_eq!(stats.current_streak, 2);
+        assert_eq!(stats.longest_streak, 2);
+        assert_eq!(stats.recent.chars().count(), 14);
+        assert!(stats.done_today);
+    }
+
+    #[test]
+    fn habit_update_payload_clears_archived_time_when_unarchiving() {
+        let mut habit = habit("Read", None);
+        habit.set_archived(true, "2026-03-02T08:00:00.000+0000");
+        let archived = habit_update_payload(&habit).unwrap();
+        assert_eq!(archived["status"], 1);
+        assert_eq!(archived["archivedTime"], "2026-03-02T08:00:00.000+0000");
+
+        habit.set_archived(false, "2026-03-03T08:00:00.000+0000");
+        let active = habit_update_payload(&habit).unwrap();
+        assert_eq!(active["status"], 0);
+        assert!(active["archivedTime"].is_null());
+        assert!(active.get("archivedTime").is_some());
+    }
+
+    #[test]
+    fn summarize_habit_prefers_api_streak_and_leaves_missing_stats_blank() {
+        let habit = Habit {
+            id: Some("h1".to_string()),
+            title: "Read".to_string(),
+            current_streak: Some(12),
+            ..Default::default()
+        };
+        let checkins = vec![done(day(3, 2))];
+
+        let summary = summarize_habit(habit.clone(), Some(&checkins), day(3, 2));
+        assert_eq!(summary.streak, Some(12));
+        assert_eq!(summary.checked_in_today, Some(true));
+        assert!(summary.scheduled_today);
+        assert_eq!(summary.schedule, "daily");
+
+        let without_stats = Habit {
+            current_streak: None,
+            ..habit
+        };
+        let summary = summarize_habit(without_stats, None, day(3, 2));
+        assert_eq!(summary.streak, None);
+        assert_eq!(summary.checked_in_today, None);
+    }
+
+    #[test]
+    fn summarize_habit_flags_whether_today_is_scheduled() {
+        let habit = Habit {
+            title: "Run".to_string(),
+            days: Some(vec![5, 1, 3]),
+            ..Default::default()
+        };
+        let monday = summarize_habit(habit.clone(), Some(&[]), day(3, 2));
+        assert!(monday.scheduled_today);
+        assert_eq!(monday.checked_in_today, Some(false));
+        assert_eq!(monday.schedule, "Mon Wed Fri");
+
+        let tuesday = summarize_habit(habit, None, day(3, 3));
+        assert!(!tuesday.scheduled_today);
+        assert_eq!(scheduled(&[0, 1, 2, 3, 4, 5, 6]).label(), "daily");
+    }
+
+    #[test]
+    fn habit_list_flags_conflict_with_archived() {
+        use clap::Parser;
+
+        #[derive(Parser)]
+        struct Cli {
+            #[command(flatten)]
+            args: HabitListArgs,
+        }
+
+        assert!(
+            Cli::try_parse_from(["tt", "--due-today"])
+                .unwrap()
+                .args
+                .due_today
+        );
+        assert!(Cli::try_parse_from(["tt", "--archived", "--due-today"]).is_err());
+        assert!(Cli::try_parse_from(["tt", "--archived", "--active"]).is_err());
+    }
+}
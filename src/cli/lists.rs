@@ -0,0 +1,233 @@
+use crate::api::TickTickClient;
+use crate::cache::{get_projects_cached, CacheStore};
+use crate::models::{Column, Project};
+use anyhow::{anyhow, Result};
+
+pub(super) fn normalize_list_name(value: &str) -> String {
+    value
+        .chars()
+        .filter(|ch| ch.is_alphanumeric() || ch.is_whitespace())
+        .flat_map(|ch| ch.to_lowercase())
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn ambiguous_name_error(name: &str, matches: &[&Project]) -> anyhow::Error {
+    let candidates = matches
+        .iter()
+        .map(|project| {
+            format!(
+                "{} ({})",
+                project.name,
+                project.id.as_deref().unwrap_or("no ID")
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    anyhow!(
+        "'{}' matches more than one list: {}. Use the project ID instead.",
+        name,
+        candidates
+    )
+}
+
+/// Finds the project called `name`. An exact (case-insensitive) name beats one that
+/// only matches once emoji and punctuation are stripped; two projects matching at the
+/// same level is an error naming both.
+pub(super) fn find_project_by_name<'a>(
+    projects: &'a [Project],
+    name: &str,
+) -> Result<Option<&'a Project>> {
+    let exact: Vec<&Project> = projects
+        .iter()
+        .filter(|project| project.name.eq_ignore_ascii_case(name))
+        .collect();
+    let matches = if exact.is_empty() {
+        let needle = normalize_list_name(name);
+        if needle.is_empty() {
+            return Ok(None);
+        }
+        projects
+            .iter()
+            .filter(|project| normalize_list_name(&project.name) == needle)
+            .collect()
+    } else {
+        exact
+    };
+
+    match matches.as_slice() {
+        [] => Ok(None),
+        [project] => Ok(Some(project)),
+        _ => Err(ambiguous_name_error(name, &matches)),
+    }
+}
+
+/// TickTick project IDs are 24 hex characters.
+fn looks_like_project_id(value: &str) -> bool {
+    value.len() == 24 && value.chars().all(|ch| ch.is_ascii_hexdigit())
+}
+
+/// Resolves a `tt project` argument that may be an ID or a list name. Known IDs win;
+/// then names; an unknown value shaped like an ID is passed through for the API to
+/// judge, since the cached project list can be behind.
+pub(super) fn resolve_project_ref(projects: &[Project], value: &str) -> Result<String> {
+    let value = value.trim();
+    if projects
+        .iter()
+        .any(|project| project.id.as_deref() == Some(value))
+    {
+        return Ok(value.to_string());
+    }
+    if let Some(project) = find_project_by_name(projects, value)? {
+        return project
+            .id
+            .clone()
+            .ok_or_else(|| anyhow!("List '{}' has no project ID", project.name));
+    }
+    if looks_like_project_id(value) {
+        return Ok(value.to_string());
+    }
+    Err(anyhow!(
+        "Project not found: {}. Use a list name or ID from 'tt project list'.",
+        value
+    ))
+}
+
+pub(super) async fn resolve_project_arg(
+    client: &TickTickClient,
+    cache: Option<&CacheStore>,
+    value: &str,
+) -> Result<String> {
+    let projects = get_projects_cached(client, cache, false).await?;
+    resolve_project_ref(&projects, value)
+}
+
+/// Finds a board column by name, ignoring case. Unknown names list the columns the
+/// project has.
+pub(super) fn find_column_id(columns: &[Column], name: &str) -> Result<String> {
+    let name = name.trim();
+    if columns.is_empty() {
+        return Err(anyhow!(
+            "This list has no columns; only lists shown as a board have them."
+        ));
+    }
+    if let Some(column) = columns
+        .iter()
+        .find(|column| column.name.eq_ignore_ascii_case(name))
+    {
+        return Ok(column.id.clone());
+    }
+
+    let mut sorted: Vec<&Column> = columns.iter().collect();
+    sorted.sort_by_key(|column| column.sort_order.unwrap_or(i64::MAX));
+    let available = sorted
+        .iter()
+        .map(|column| column.name.as_str())
+        .collect::<Vec<_>>()
+        .join(", ");
+    Err(anyhow!(
+        "Column '{}' not found. Available columns: {}",
+        name,
+        available
+    ))
+}
+
+pub(super) async fn resolve_column_arg(
+    client: &TickTickClient,
+    project_id: &str,
+    name: &str,
+) -> Result<String> {
+    let data = client.get_project_data(project_id).await?;
+    find_column_id(&data.columns.unwrap_or_default(), name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project(id: &str, name: &str) -> Project {
+        Project {
+            id: Some(id.to_string()),
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn find_project_by_name_prefers_exact_names_then_normalized_ones() {
+        let projects = vec![
+            project("p1", "🛒 Groceries"),
+            project("p2", "Work"),
+            project("p3", "work!"),
+        ];
+
+        let found = |name| {
+            find_project_by_name(&projects, name)
+                .unwrap()
+                .and_then(|project| project.id.as_deref())
+        };
+        assert_eq!(found("groceries"), Some("p1"));
+        assert_eq!(found("WORK"), Some("p2"));
+        assert_eq!(found("Home"), None);
+        assert_eq!(found("🛒"), None);
+    }
+
+    #[test]
+    fn find_project_by_name_lists_candidates_when_ambiguous() {
+        let projects = vec![project("p1", "📚 Reading"), project("p2", "Reading!")];
+
+        let err = find_project_by_name(&projects, "reading")
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("📚 Reading (p1), Reading! (p2)"),
+            "unexpected error: {}",
+            err
+        );
+    }
+
+    #[test]
+    fn find_column_id_ignores_case_and_lists_columns_when_missing() {
+        let column = |id: &str, name: &str, sort_order: i64| Column {
+            id: id.to_string(),
+            project_id: "p1".to_string(),
+            name: name.to_string(),
+            sort_order: Some(sort_order),
+        };
+        let columns = vec![column("c2", "Doing", 2), column("c1", "To do", 1)];
+
+        assert_eq!(find_column_id(&columns, "doing").unwrap(), "c2");
+        assert_eq!(
+            find_column_id(&columns, "Done").unwrap_err().to_string(),
+            "Column 'Done' not found. Available columns: To do, Doing"
+        );
+        assert!(find_column_id(&[], "Doing")
+            .unwrap_err()
+            .to_string()
+            .contains("no columns"));
+    }
+
+    #[test]
+    fn resolve_project_ref_accepts_ids_or_names() {
+        let projects = vec![
+            project("64f0c0ffee0000000000abcd", "Groceries"),
+            project("p2", "Work"),
+        ];
+
+        assert_eq!(resolve_project_ref(&projects, "p2").unwrap(), "p2");
+        assert_eq!(
+            resolve_project_ref(&projects, "groceries").unwrap(),
+            "64f0c0ffee0000000000abcd"
+        );
+        assert_eq!(
+            resolve_project_ref(&projects, "0123456789abcdef01234567").unwrap(),
+            "0123456789abcdef01234567"
+        );
+        assert!(resolve_project_ref(&projects, "Garden")
+            .unwrap_err()
+            .to_string()
+            .starts_with("Project not found: Garden"));
+    }
+}
@@ -1,20 +1,101 @@
 mod auth;
 mod bootstrap;
+mod config;
+mod export;
+mod habit;
+mod lists;
 mod project;
+mod tag;
 mod task;
+mod undo;
+mod weblink;
 
 pub use auth::*;
+pub use config::*;
+pub use export::*;
+pub use habit::*;
 pub use project::*;
+pub use tag::*;
 pub use task::*;
+pub use undo::*;
 
-use clap::{Parser, Subcommand};
+use crate::output::{render_error_json, ColorChoice, OutputContext, OutputFormat, Verbosity};
+use clap::{ArgMatches, CommandFactory, FromArgMatches, Parser, Subcommand, ValueEnum};
 
 #[derive(Parser)]
 #[command(name = "tt")]
 #[command(bin_name = "tt")]
 #[command(about = "A fast, snappy TickTick CLI tool", long_about = None)]
 #[command(version = env!("CARGO_PKG_VERSION"))]
+#[command(arg_required_else_help = true)]
+#[command(
+    after_help = "Anything that is not a command is added as a task: tt buy milk tomorrow !high ~Errands"
+)]
 struct Cli {
+    #[arg(
+        short,
+        long,
+        global = true,
+        help = "Suppress confirmation messages; data output and errors are still printed (tt status prints nothing and only sets the exit code)"
+    )]
+    quiet: bool,
+    #[arg(
+        short,
+        long,
+        global = true,
+        action = clap::ArgAction::Count,
+        help = "Log API requests to stderr; repeat (-vv) to include request bodies"
+    )]
+    verbose: u8,
+    #[arg(
+        long,
+        global = true,
+        help = "Don't record this command in the journal used by `tt undo`"
+    )]
+    no_journal: bool,
+    #[arg(
+        long,
+        global = true,
+        help = "Read task lists and task info from the last `tt sync` instead of the API"
+    )]
+    offline: bool,
+    #[arg(
+        long,
+        global = true,
+        value_name = "URL",
+        help = "Send requests through this proxy instead of HTTPS_PROXY/HTTP_PROXY"
+    )]
+    proxy: Option<String>,
+    #[arg(
+        long,
+        global = true,
+        help = "Print --output json on a single line instead of pretty-printed"
+    )]
+    compact: bool,
+    #[arg(
+        long,
+        global = true,
+        value_name = "FIELDS",
+        value_delimiter = ',',
+        value_parser = crate::output::parse_field_name,
+        help = "Columns for --output csv/tsv, in order, e.g. id,title,priority,due,project,tags"
+    )]
+    fields: Vec<String>,
+    #[arg(
+        long,
+        global = true,
+        help = "Leave the header row out of --output csv/tsv"
+    )]
+    no_header: bool,
+    #[arg(
+        long,
+        global = true,
+        value_enum,
+        value_name = "WHEN",
+        default_value = "auto",
+        help = "Color human output: auto (a terminal without NO_COLOR), always, or never"
+    )]
+    color: ColorChoice,
     #[command(subcommand)]
     command: Commands,
 }
@@ -25,6 +106,10 @@ enum Commands {
         #[command(subcommand)]
         subcommand: auth::AuthCommands,
     },
+    Config {
+        #[command(subcommand)]
+        subcommand: config::ConfigCommands,
+    },
     #[command(alias = "tasks")]
     Task {
         #[command(subcommand)]
@@ -34,6 +119,21 @@ enum Commands {
         #[command(subcommand)]
         subcommand: project::ProjectCommands,
     },
+    #[command(alias = "habits")]
+    Habit {
+        #[command(subcommand)]
+        subcommand: habit::HabitCommands,
+    },
+    #[command(alias = "tags")]
+    Tag {
+        #[command(subcommand)]
+        subcommand: tag::TagCommands,
+    },
+    #[command(alias = "templates")]
+    Template {
+        #[command(subcommand)]
+        subcommand: task::TemplateCommands,
+    },
     #[command(name = "ls", aliases = ["list"])]
     Ls(task::TaskListArgs),
     #[command(alias = "new")]
@@ -44,50 +144,192 @@ enum Commands {
     Done(task::TaskCompleteArgs),
     #[command(name = "rm", aliases = ["delete", "del"])]
     Rm(task::TaskDeleteArgs),
+    #[command(name = "search", alias = "find")]
+    Search(task::TaskSearchArgs),
+    Today(task::AgendaArgs),
+    #[command(alias = "week")]
+    Upcoming(task::UpcomingArgs),
     #[command(name = "projects", alias = "lists")]
     Projects(project::ProjectListArgs),
     #[command(name = "login")]
-    Login,
+    Login(auth::LoginArgs),
     #[command(name = "logout")]
     Logout,
-    #[command(name = "status")]
-    Status,
+    #[command(name = "status", alias = "whoami")]
+    Status(auth::StatusArgs),
+    Undo(undo::UndoArgs),
+    Sync(task::SyncArgs),
+    Export(export::ExportArgs),
+    #[command(external_subcommand)]
+    QuickAdd(Vec<String>),
+}
+
+/// Finds the `--output` value of the innermost subcommand that defines one.
+fn selected_output_format(matches: &ArgMatches) -> OutputFormat {
+    let mut current = matches;
+    loop {
+        if let Ok(Some(format)) = current.try_get_one::<OutputFormat>("output") {
+            return *format;
+        }
+        match current.subcommand() {
+            Some((_, subcommand)) => current = subcommand,
+            None => return OutputFormat::default(),
+        }
+    }
+}
+
+/// `--output` among quick-add words, which clap hands over unparsed.
+fn quick_add_output_format(words: &[String]) -> OutputFormat {
+    words
+        .iter()
+        .enumerate()
+        .find_map(|(index, word)| match word.strip_prefix("--output=") {
+            Some(value) => Some(value),
+            None if word == "--output" => words.get(index + 1).map(String::as_str),
+            None => None,
+        })
+        .and_then(|value| OutputFormat::from_str(value, true).ok())
+        .unwrap_or_default()
 }
 
 pub async fn run() -> anyhow::Result<()> {
-    let cli = Cli::parse();
+    let matches = Cli::command().get_matches();
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|err| err.exit());
+    let output = match &cli.command {
+        Commands::QuickAdd(words) => quick_add_output_format(words),
+        _ => selected_output_format(&matches),
+    };
+    let context = OutputContext {
+        compact_json: cli.compact,
+        fields: cli.fields.clone(),
+        no_header: cli.no_header,
+        ..OutputContext::stdout(cli.color)
+    };
+
+    match dispatch(cli, &context).await {
+        Err(err) if matches!(output, OutputFormat::Json) => {
+            eprint!("{}", render_error_json(&err));
+            std::process::exit(1);
+        }
+        result => result,
+    }
+}
+
+async fn dispatch(cli: Cli, context: &OutputContext) -> anyhow::Result<()> {
+    crate::api::set_trace_level(cli.verbose);
+    if cli.no_journal {
+        crate::cache::disable_journal();
+    }
+    if cli.offline {
+        crate::cache::enable_offline();
+    }
+    if let Some(proxy) = cli.proxy.as_deref() {
+        crate::api::set_proxy_override(proxy)?;
+    }
+    let verbosity = if cli.quiet {
+        Verbosity::Quiet
+    } else {
+        Verbosity::Normal
+    };
 
     match cli.command {
         Commands::Auth { subcommand } => match subcommand {
-            auth::AuthCommands::Login => login().await,
+            auth::AuthCommands::Login(args) => login(args).await,
             auth::AuthCommands::Logout => logout().await,
-            auth::AuthCommands::Status => status().await,
+            auth::AuthCommands::Status(args) => status(args, verbosity).await,
+        },
+        Commands::Config { subcommand } => match subcommand {
+            config::ConfigCommands::Doctor(args) => config_doctor(args, context).await,
         },
         Commands::Task { subcommand } => match subcommand {
-            task::TaskCommands::Add(args) => task_add(args).await,
-            task::TaskCommands::List(args) => task_list(args).await,
-            task::TaskCommands::Info(args) => task_info(args).await,
-            task::TaskCommands::Update(args) => task_update(args).await,
-            task::TaskCommands::Complete(args) => task_complete(args).await,
-            task::TaskCommands::Delete(args) => task_delete(args).await,
+            task::TaskCommands::Add(args) => task_add(args, verbosity, context).await,
+            task::TaskCommands::List(args) => task_list(args, context).await,
+            task::TaskCommands::Count(args) => task_count(args, context).await,
+            task::TaskCommands::Info(args) => task_info(args, context).await,
+            task::TaskCommands::Update(args) => task_update(args, verbosity, context).await,
+            task::TaskCommands::Complete(args) => task_complete(args, verbosity, context).await,
+            task::TaskCommands::Uncomplete(args) => task_uncomplete(args, verbosity, context).await,
+            task::TaskCommands::Delete(args) => task_delete(args, verbosity, context).await,
+            task::TaskCommands::Search(args) => task_search(args, context).await,
+            task::TaskCommands::Postpone(args) => task_postpone(args, verbosity, context).await,
+            task::TaskCommands::Duplicate(args) => task_duplicate(args, verbosity, context).await,
+            task::TaskCommands::Defer(args) => task_defer(args, verbosity, context).await,
+            task::TaskCommands::Prio(args) => task_prio(args, verbosity, context).await,
+            task::TaskCommands::Tag(args) => task_tag(args, verbosity, context).await,
+            task::TaskCommands::BatchUpdate(args) => task_batch_update(args, context).await,
+            task::TaskCommands::Open(args) => task_open(args, verbosity).await,
+            task::TaskCommands::Items { subcommand } => {
+                task_items(subcommand, verbosity, context).await
+            }
         },
         Commands::Project { subcommand } => match subcommand {
-            project::ProjectCommands::Add(args) => project_add(args).await,
-            project::ProjectCommands::List(args) => project_list(args).await,
-            project::ProjectCommands::Get(args) => project_get(args).await,
-            project::ProjectCommands::Data(args) => project_data(args).await,
-            project::ProjectCommands::Update(args) => project_update(args).await,
-            project::ProjectCommands::Delete(args) => project_delete(args).await,
+            project::ProjectCommands::Add(args) => project_add(args, verbosity, context).await,
+            project::ProjectCommands::List(args) => project_list(args, context).await,
+            project::ProjectCommands::Get(args) => project_get(args, context).await,
+            project::ProjectCommands::Data(args) => project_data(args, context).await,
+            project::ProjectCommands::Update(args) => {
+                project_update(args, verbosity, context).await
+            }
+            project::ProjectCommands::Delete(args) => {
+                project_delete(args, verbosity, context).await
+            }
+            project::ProjectCommands::Move(args) => project_move(args, verbosity, context).await,
+            project::ProjectCommands::Duplicate(args) => {
+                project_duplicate(args, verbosity, context).await
+            }
+            project::ProjectCommands::Stats(args) => project_stats(args, context).await,
+            project::ProjectCommands::Archive(args) => {
+                project_archive(args, verbosity, context).await
+            }
+            project::ProjectCommands::Reopen(args) => {
+                project_reopen(args, verbosity, context).await
+            }
+            project::ProjectCommands::Open(args) => project_open(args, verbosity).await,
+            project::ProjectCommands::Column { subcommand } => match subcommand {
+                project::ColumnCommands::List(args) => column_list(args, context).await,
+            },
         },
-        Commands::Ls(args) => task_list(args).await,
-        Commands::Add(args) => task_add(args).await,
-        Commands::Edit(args) => task_update(args).await,
-        Commands::Done(args) => task_complete(args).await,
-        Commands::Rm(args) => task_delete(args).await,
-        Commands::Projects(args) => project_list(args).await,
-        Commands::Login => login().await,
+        Commands::Tag { subcommand } => match subcommand {
+            tag::TagCommands::Add(args) => tag_add(args, verbosity, context),
+            tag::TagCommands::List(args) => tag_list(args, context).await,
+            tag::TagCommands::Tasks(args) => tag_tasks(*args, context).await,
+            tag::TagCommands::Rename(args) => tag_rename(args, context).await,
+            tag::TagCommands::Merge(args) => tag_merge(args, context).await,
+            tag::TagCommands::Delete(args) => tag_delete(args, context).await,
+        },
+        Commands::Habit { subcommand } => match subcommand {
+            habit::HabitCommands::Add(args) => habit_add(args, verbosity, context).await,
+            habit::HabitCommands::List(args) => habit_list(args, context).await,
+            habit::HabitCommands::Update(args) => habit_update(args, verbosity).await,
+            habit::HabitCommands::Checkin(args) => habit_checkin(args, verbosity, context).await,
+            habit::HabitCommands::Stats(args) => habit_stats(args, context).await,
+            habit::HabitCommands::Delete(args) => habit_delete(args, verbosity).await,
+            habit::HabitCommands::Archive(args) => habit_archive(args, verbosity, context).await,
+            habit::HabitCommands::Unarchive(args) => {
+                habit_unarchive(args, verbosity, context).await
+            }
+        },
+        Commands::Template { subcommand } => match subcommand {
+            task::TemplateCommands::Save(args) => template_save(args, verbosity, context).await,
+            task::TemplateCommands::List(args) => template_list(args, context).await,
+            task::TemplateCommands::Delete(args) => template_delete(args, verbosity, context).await,
+        },
+        Commands::Ls(args) => task_list(args, context).await,
+        Commands::Add(args) => task_add(args, verbosity, context).await,
+        Commands::Edit(args) => task_update(args, verbosity, context).await,
+        Commands::Done(args) => task_complete(args, verbosity, context).await,
+        Commands::Rm(args) => task_delete(args, verbosity, context).await,
+        Commands::Search(args) => task_search(args, context).await,
+        Commands::Today(args) => today(args, context).await,
+        Commands::Upcoming(args) => upcoming(args, context).await,
+        Commands::Projects(args) => project_list(args, context).await,
+        Commands::Login(args) => login(args).await,
         Commands::Logout => logout().await,
-        Commands::Status => status().await,
+        Commands::Status(args) => status(args, verbosity).await,
+        Commands::Undo(args) => undo(args, verbosity, context).await,
+        Commands::Sync(args) => sync(args, verbosity, context).await,
+        Commands::Export(args) => export(args, verbosity, context).await,
+        Commands::QuickAdd(words) => task_add(quick_add_args(words)?, verbosity, context).await,
     }
 }
 
@@ -101,12 +343,127 @@ mod tests {
         assert!(matches!(
             auth_cli.command,
             Commands::Auth {
-                subcommand: auth::AuthCommands::Login
+                subcommand: auth::AuthCommands::Login(_)
             }
         ));
 
         let login_cli = Cli::try_parse_from(["tt", "login"]).unwrap();
-        assert!(matches!(login_cli.command, Commands::Login));
+        assert!(matches!(login_cli.command, Commands::Login(_)));
+    }
+
+    #[test]
+    fn parses_config_doctor() {
+        let cli = Cli::try_parse_from(["tt", "config", "doctor", "--no-verify"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Commands::Config {
+                subcommand: config::ConfigCommands::Doctor(_)
+            }
+        ));
+    }
+
+    #[test]
+    fn unknown_words_become_a_quick_add_without_shadowing_commands() {
+        let cli = Cli::try_parse_from(["tt", "buy", "milk", "tomorrow", "!high", "--list", "Home"])
+            .unwrap();
+        let Commands::QuickAdd(words) = cli.command else {
+            panic!("expected a quick add");
+        };
+        assert_eq!(
+            words,
+            ["buy", "milk", "tomorrow", "!high", "--list", "Home"]
+        );
+        assert!(quick_add_args(words).is_ok());
+
+        let cli = Cli::try_parse_from(["tt", "buy milk ~Errands #shop"]).unwrap();
+        let Commands::QuickAdd(words) = cli.command else {
+            panic!("expected a quick add");
+        };
+        assert!(quick_add_args(words).is_ok());
+
+        for command in ["task", "project", "auth", "ls", "today"] {
+            let cli = Cli::try_parse_from(["tt", command, "--help"]);
+            assert!(
+                cli.is_err_and(|err| err.kind() == clap::error::ErrorKind::DisplayHelp),
+                "{}",
+                command
+            );
+        }
+
+        let err = Cli::try_parse_from(["tt"]).err().unwrap();
+        assert_eq!(
+            err.kind(),
+            clap::error::ErrorKind::DisplayHelpOnMissingArgumentOrSubcommand
+        );
+
+        let err = quick_add_args(vec!["lst".to_string()]).err().unwrap();
+        assert!(err.to_string().contains("tt add lst"), "{}", err);
+    }
+
+    #[test]
+    fn quick_add_reports_parse_errors_instead_of_exiting() {
+        let words = vec!["buy".to_string(), "milk".to_string(), "--bogus".to_string()];
+        let err = quick_add_args(words).err().unwrap();
+        assert!(
+            err.to_string().starts_with("unexpected argument '--bogus'"),
+            "{}",
+            err
+        );
+
+        let words = ["buy", "--output=json", "--bogus"].map(String::from);
+        assert!(matches!(
+            quick_add_output_format(&words),
+            OutputFormat::Json
+        ));
+        let words = ["buy", "--output", "json"].map(String::from);
+        assert!(matches!(
+            quick_add_output_format(&words),
+            OutputFormat::Json
+        ));
+        let words = ["buy".to_string()];
+        assert!(matches!(
+            quick_add_output_format(&words),
+            OutputFormat::Human
+        ));
+    }
+
+    #[test]
+    fn parses_tag_delete_with_yes() {
+        let cli = Cli::try_parse_from(["tt", "tag", "rm", "#errand", "--yes"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Commands::Tag {
+                subcommand: tag::TagCommands::Delete(_)
+            }
+        ));
+        assert!(Cli::try_parse_from(["tt", "tag", "delete", "errand", "-y", "--dry-run"]).is_ok());
+        assert!(Cli::try_parse_from(["tt", "tag", "delete", "#"]).is_err());
+        assert!(Cli::try_parse_from(["tt", "tag", "merge", "errand", "#errands"]).is_ok());
+    }
+
+    #[test]
+    fn parses_yes_on_every_confirming_command() {
+        for args in [
+            &["tt", "task", "delete", "task-1", "--yes"][..],
+            &["tt", "project", "delete", "Work", "-y"],
+            &["tt", "project", "archive", "Work", "--force"],
+            &["tt", "habit", "delete", "habit-1", "--yes"],
+            &["tt", "habit", "archive", "Read", "-y"],
+        ] {
+            assert!(Cli::try_parse_from(args).is_ok(), "{:?}", args);
+        }
+    }
+
+    #[test]
+    fn keeps_tag_add_and_the_with_counts_flag() {
+        let cli = Cli::try_parse_from(["tt", "tag", "add", "#errand"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Commands::Tag {
+                subcommand: tag::TagCommands::Add(_)
+            }
+        ));
+        assert!(Cli::try_parse_from(["tt", "tag", "list", "--with-counts"]).is_ok());
     }
 
     #[test]
@@ -114,6 +471,14 @@ mod tests {
         let list_cli = Cli::try_parse_from(["tt", "ls", "inbox"]).unwrap();
         assert!(matches!(list_cli.command, Commands::Ls(_)));
 
+        let today_cli = Cli::try_parse_from(["tt", "today", "--list", "Work"]).unwrap();
+        assert!(matches!(today_cli.command, Commands::Today(_)));
+        let upcoming_cli =
+            Cli::try_parse_from(["tt", "upcoming", "3", "--tags", "work", "--output", "json"])
+                .unwrap();
+        assert!(matches!(upcoming_cli.command, Commands::Upcoming(_)));
+        assert!(Cli::try_parse_from(["tt", "upcoming", "0"]).is_err());
+
         let task_info_cli = Cli::try_parse_from(["tt", "task", "info", "task-1"]).unwrap();
         assert!(matches!(
             task_info_cli.command,
@@ -126,6 +491,119 @@ mod tests {
         assert!(matches!(projects_cli.command, Commands::Projects(_)));
     }
 
+    #[test]
+    fn parses_undo_and_global_no_journal_flag() {
+        let undo_cli = Cli::try_parse_from(["tt", "undo", "--max-age", "2h"]).unwrap();
+        assert!(matches!(undo_cli.command, Commands::Undo(_)));
+        assert!(!undo_cli.no_journal);
+
+        let rm_cli = Cli::try_parse_from(["tt", "rm", "task-1", "--no-journal"]).unwrap();
+        assert!(matches!(rm_cli.command, Commands::Rm(_)));
+        assert!(rm_cli.no_journal);
+    }
+
+    #[test]
+    fn parses_sync_and_global_offline_flag() {
+        let sync_cli = Cli::try_parse_from(["tt", "sync"]).unwrap();
+        assert!(matches!(sync_cli.command, Commands::Sync(_)));
+        assert!(!sync_cli.offline);
+
+        let ls_cli = Cli::try_parse_from(["tt", "--offline", "ls", "today"]).unwrap();
+        assert!(matches!(ls_cli.command, Commands::Ls(_)));
+        assert!(ls_cli.offline);
+    }
+
+    #[test]
+    fn parses_export_with_out_path() {
+        let cli = Cli::try_parse_from(["tt", "export", "json", "--out", "backup.json"]).unwrap();
+        assert!(matches!(cli.command, Commands::Export(_)));
+        assert!(Cli::try_parse_from(["tt", "export"]).is_ok());
+        assert!(Cli::try_parse_from(["tt", "export", "xml"]).is_err());
+    }
+
+    #[test]
+    fn parses_global_color_flag() {
+        let choice = |argv: &[&str]| Cli::try_parse_from(argv).unwrap().color;
+        assert_eq!(choice(&["tt", "ls"]), ColorChoice::Auto);
+        assert_eq!(
+            choice(&["tt", "--color", "never", "ls"]),
+            ColorChoice::Never
+        );
+        assert_eq!(
+            choice(&["tt", "--color=always", "today"]),
+            ColorChoice::Always
+        );
+        assert_eq!(
+            choice(&["tt", "task", "ls", "--color", "never"]),
+            ColorChoice::Never
+        );
+        assert_eq!(
+            choice(&["tt", "project", "list", "--color", "always"]),
+            ColorChoice::Always
+        );
+        assert!(Cli::try_parse_from(["tt", "--color", "sometimes", "ls"]).is_err());
+        assert!(Cli::try_parse_from(["tt", "ls", "--color", "sometimes"]).is_err());
+
+        // The list's own color is a separate flag, so --color means the same everywhere.
+        let update = Cli::try_parse_from([
+            "tt",
+            "project",
+            "update",
+            "Work",
+            "--project-color",
+            "#ff0000",
+            "--color",
+            "never",
+        ])
+        .unwrap();
+        assert_eq!(update.color, ColorChoice::Never);
+        assert!(
+            Cli::try_parse_from(["tt", "project", "add", "Home", "--color", "#ff0000"]).is_err()
+        );
+    }
+
+    #[test]
+    fn parses_global_proxy_flag() {
+        let cli = Cli::try_parse_from(["tt", "ls", "--proxy", "http://proxy.corp:8080"]).unwrap();
+        assert_eq!(cli.proxy.as_deref(), Some("http://proxy.corp:8080"));
+
+        let cli = Cli::try_parse_from(["tt", "ls"]).unwrap();
+        assert_eq!(cli.proxy, None);
+    }
+
+    #[test]
+    fn counts_repeated_global_verbose_flags() {
+        let cli = Cli::try_parse_from(["tt", "-vv", "task", "list"]).unwrap();
+        assert_eq!(cli.verbose, 2);
+
+        let cli = Cli::try_parse_from(["tt", "projects", "--verbose"]).unwrap();
+        assert_eq!(cli.verbose, 1);
+
+        let cli = Cli::try_parse_from(["tt", "projects"]).unwrap();
+        assert_eq!(cli.verbose, 0);
+    }
+
+    #[test]
+    fn parses_global_quiet_flag_before_or_after_the_command() {
+        let before = Cli::try_parse_from(["tt", "-q", "done", "task-1"]).unwrap();
+        assert!(before.quiet);
+
+        let after = Cli::try_parse_from(["tt", "task", "update", "task-1", "--quiet"]).unwrap();
+        assert!(after.quiet);
+
+        let default = Cli::try_parse_from(["tt", "done", "task-1"]).unwrap();
+        assert!(!default.quiet);
+
+        // `tt status` has no --quiet of its own; the global flag is its script mode.
+        for args in [
+            ["tt", "-q", "auth", "status"].as_slice(),
+            ["tt", "status", "--quiet"].as_slice(),
+        ] {
+            let cli = Cli::try_parse_from(args).unwrap();
+            assert!(cli.quiet, "{:?}", args);
+        }
+    }
+
     #[test]
     fn parses_json_output_for_mutating_commands() {
         let done_cli = Cli::try_parse_from(["tt", "done", "task-1", "--output", "json"]).unwrap();
@@ -148,5 +626,71 @@ mod tests {
                 subcommand: project::ProjectCommands::Update(_)
             }
         ));
+
+        // Confirmations have no rows to put in a table.
+        for format in ["csv", "tsv", "markdown"] {
+            assert!(Cli::try_parse_from(["tt", "done", "task-1", "--output", format]).is_err());
+        }
+        assert!(Cli::try_parse_from(["tt", "task", "list", "--output", "csv"]).is_ok());
+    }
+
+    fn output_for(args: &[&str]) -> OutputFormat {
+        selected_output_format(&Cli::command().try_get_matches_from(args).unwrap())
+    }
+
+    #[test]
+    fn selects_output_format_from_nested_subcommands() {
+        assert!(matches!(
+            output_for(&["tt", "task", "info", "task-1", "--output", "json"]),
+            OutputFormat::Json
+        ));
+        assert!(matches!(
+            output_for(&["tt", "projects", "--output", "json"]),
+            OutputFormat::Json
+        ));
+        assert!(matches!(
+            output_for(&["tt", "ls", "inbox"]),
+            OutputFormat::Human
+        ));
+        assert!(matches!(
+            output_for(&["tt", "status", "--quiet"]),
+            OutputFormat::Human
+        ));
+    }
+
+    #[test]
+    fn parses_search_with_negated_terms_and_flags() {
+        let cli = Cli::try_parse_from([
+            "tt", "search", "--regex", "--limit", "5", "report", "-draft",
+        ])
+        .unwrap();
+        assert!(matches!(cli.command, Commands::Search(_)));
+
+        let task_cli = Cli::try_parse_from(["tt", "task", "find", "\"weekly review\""]).unwrap();
+        assert!(matches!(
+            task_cli.command,
+            Commands::Task {
+                subcommand: task::TaskCommands::Search(_)
+            }
+        ));
+    }
+
+    #[test]
+    fn parses_checklist_item_commands() {
+        let done_cli = Cli::try_parse_from(["tt", "task", "items", "done", "task-1", "2"]).unwrap();
+        assert!(matches!(
+            done_cli.command,
+            Commands::Task {
+                subcommand: task::TaskCommands::Items {
+                    subcommand: task::TaskItemCommands::Done(_)
+                }
+            }
+        ));
+
+        let add_cli = Cli::try_parse_from([
+            "tt", "add", "Pack", "--item", "Passport", "--item", "Chargers",
+        ])
+        .unwrap();
+        assert!(matches!(add_cli.command, Commands::Add(_)));
     }
 }
@@ -1,12 +1,80 @@
-use super::bootstrap::authenticated_client;
-use crate::cache::{get_projects_cached, CacheStore};
-use crate::models::{Project, ProjectData};
-use crate::output::{print_projects, OutputFormat};
-use anyhow::Result;
+use super::lists::resolve_project_arg;
+use super::task::{ensure_can_prompt, format_project_stats, summarize_project, TaskSource};
+use super::weblink::{open_or_print, project_web_url};
+use crate::api::TickTickClient;
+use crate::cache::{get_projects_cached, journal_enabled, CacheStore, JournalOperation};
+use crate::models::{Column, Project, ProjectData, Task, TaskStatus};
+use crate::output::{
+    print_columns, print_project_summaries, print_project_tree, print_projects, render_task_groups,
+    render_tasks, MessageFormat, OutputContext, OutputFormat, ProjectFolder, ProjectSummary,
+    TableLayout, TaskGroup, TaskListOptions, Verbosity,
+};
+use anyhow::{anyhow, Result};
+use atty::Stream;
+use chrono::Local;
 use clap::{Args, Subcommand};
+use serde::Serialize;
 
-fn cache_store() -> Option<CacheStore> {
-    CacheStore::new().ok()
+fn record_project_deleted(cache: Option<&CacheStore>, project: Project) {
+    if let Some(cache) = cache {
+        let _ = cache.record_operation(JournalOperation::ProjectDeleted { project });
+    }
+}
+
+const NAMED_PROJECT_COLORS: &[(&str, &str)] = &[
+    ("red", "#FF6161"),
+    ("orange", "#FFAC38"),
+    ("yellow", "#FFD324"),
+    ("green", "#35D870"),
+    ("teal", "#4AC3B4"),
+    ("blue", "#4CA1FF"),
+    ("purple", "#9F7AEA"),
+    ("pink", "#F279D6"),
+    ("gray", "#B4B4B4"),
+];
+
+fn parse_color(value: &str) -> Result<String> {
+    let trimmed = value.trim();
+
+    if let Some(hex) = trimmed.strip_prefix('#') {
+        if hex.chars().all(|ch| ch.is_ascii_hexdigit()) {
+            match hex.len() {
+                6 => return Ok(format!("#{}", hex.to_ascii_uppercase())),
+                3 => {
+                    let expanded: String = hex
+                        .chars()
+                        .flat_map(|ch| [ch, ch])
+                        .collect::<String>()
+                        .to_ascii_uppercase();
+                    return Ok(format!("#{}", expanded));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let normalized = trimmed.to_ascii_lowercase();
+    let normalized = if normalized == "grey" {
+        "gray".to_string()
+    } else {
+        normalized
+    };
+    if let Some((_, hex)) = NAMED_PROJECT_COLORS
+        .iter()
+        .find(|(name, _)| *name == normalized)
+    {
+        return Ok((*hex).to_string());
+    }
+
+    Err(anyhow!(
+        "Invalid color '{}'. Use #RRGGBB, #RGB, or one of: {}",
+        value,
+        NAMED_PROJECT_COLORS
+            .iter()
+            .map(|(name, _)| *name)
+            .collect::<Vec<_>>()
+            .join(", ")
+    ))
 }
 
 #[derive(Subcommand)]
@@ -21,13 +89,40 @@ pub enum ProjectCommands {
     Update(ProjectUpdateArgs),
     #[command(aliases = ["rm", "del"])]
     Delete(ProjectDeleteArgs),
+    #[command(alias = "move-to-folder")]
+    Move(ProjectMoveArgs),
+    #[command(aliases = ["copy", "dup"])]
+    Duplicate(ProjectDuplicateArgs),
+    #[command(alias = "summary")]
+    Stats(ProjectStatsArgs),
+    #[command(alias = "close")]
+    Archive(ProjectArchiveArgs),
+    #[command(alias = "unarchive")]
+    Reopen(ProjectReopenArgs),
+    #[command(aliases = ["web", "browse"])]
+    Open(ProjectOpenArgs),
+    #[command(alias = "columns")]
+    Column {
+        #[command(subcommand)]
+        subcommand: ColumnCommands,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ColumnCommands {
+    #[command(alias = "ls")]
+    List(ColumnListArgs),
 }
 
 #[derive(Args)]
 pub struct ProjectAddArgs {
     name: String,
-    #[arg(long)]
-    color: Option<String>,
+    #[arg(
+        long,
+        value_parser = parse_color,
+        help = "List color: hex (#RRGGBB or #RGB) or a palette name"
+    )]
+    project_color: Option<String>,
     #[arg(long)]
     view_mode: Option<String>,
     #[arg(long)]
@@ -35,12 +130,15 @@ pub struct ProjectAddArgs {
     #[arg(long)]
     group_id: Option<String>,
     #[arg(long, default_value = "human")]
-    output: OutputFormat,
+    output: MessageFormat,
 }
 
-pub async fn project_add(args: ProjectAddArgs) -> Result<()> {
-    let client = authenticated_client()?;
-    let cache = cache_store();
+pub async fn project_add(
+    args: ProjectAddArgs,
+    verbosity: Verbosity,
+    context: &OutputContext,
+) -> Result<()> {
+    let (client, cache) = TaskSource::online("project add")?;
 
     let project = build_project_from_add_args(&args);
 
@@ -49,120 +147,621 @@ pub async fn project_add(args: ProjectAddArgs) -> Result<()> {
         let _ = cache.invalidate_projects();
     }
 
-    print!("{}", format_project_create_output(&created, args.output)?);
+    if verbosity.is_quiet() && args.output == MessageFormat::Human {
+        println!("{}", created.id.as_deref().unwrap_or_default());
+    } else {
+        print!(
+            "{}",
+            format_project_create_output(&created, args.output, context)?
+        );
+    }
+
+    Ok(())
+}
+
+#[derive(Args)]
+pub struct ProjectStatsArgs {
+    #[arg(help = "Project ID or list name")]
+    project_id: String,
+    #[arg(long, default_value = "human")]
+    output: MessageFormat,
+}
 
+pub async fn project_stats(args: ProjectStatsArgs, context: &OutputContext) -> Result<()> {
+    let source = TaskSource::open()?;
+
+    let project_id = source.resolve_project(&args.project_id).await?;
+    let data = source.project_data(&project_id).await?;
+    let open = data.tasks.unwrap_or_default();
+    // The snapshot only holds open tasks, so offline stats have no completion history.
+    let completed = match &source {
+        TaskSource::Api { client, .. } => client
+            .get_completed_tasks(std::slice::from_ref(&project_id), None, None)
+            .await
+            .ok(),
+        TaskSource::Snapshot(_) => None,
+    };
+
+    let stats = summarize_project(
+        &data.project.name,
+        &open,
+        completed.as_deref(),
+        Local::now().date_naive(),
+        &Local,
+    );
+    print!("{}", format_project_stats(&stats, args.output, context)?);
     Ok(())
 }
 
+#[derive(Args)]
+pub struct ProjectDuplicateArgs {
+    #[arg(help = "Project ID or list name to copy")]
+    project_id: String,
+    #[arg(long, help = "Name of the new list")]
+    name: String,
+    #[arg(long, help = "Copy only open tasks, not completed ones")]
+    tasks_only_open: bool,
+    #[arg(
+        long,
+        help = "Keep start and due dates, reminders, and repeats (dropped by default)"
+    )]
+    keep_dates: bool,
+    #[arg(long, default_value = "human")]
+    output: MessageFormat,
+}
+
+/// A new copy of `task` for the list `project_id`. Its place in the list is kept; the
+/// board column is not, since the new list has its own columns. A completed task stays
+/// completed, with its completion time.
+fn task_copy_for_project(task: &Task, project_id: &str, keep_dates: bool) -> Task {
+    let mut copy = task.duplicate();
+    copy.project_id = Some(project_id.to_string());
+    copy.sort_order = task.sort_order;
+    copy.column_id = None;
+    if task.status == Some(TaskStatus::Completed) {
+        copy.status = task.status;
+        copy.completed_time = task.completed_time.clone();
+    }
+    if !keep_dates {
+        copy.start_date = None;
+        copy.due_date = None;
+        copy.reminders = None;
+        copy.repeat_flag = None;
+    }
+    copy
+}
+
+pub async fn project_duplicate(
+    args: ProjectDuplicateArgs,
+    verbosity: Verbosity,
+    context: &OutputContext,
+) -> Result<()> {
+    let (client, cache) = TaskSource::online("project duplicate")?;
+
+    let source_id = resolve_project_arg(&client, cache.as_ref(), &args.project_id).await?;
+    let data = client.get_project_data(&source_id).await?;
+    let source = data.project;
+    let mut tasks = data.tasks.unwrap_or_default();
+    if !args.tasks_only_open {
+        tasks.extend(
+            client
+                .get_completed_tasks(std::slice::from_ref(&source_id), None, None)
+                .await?,
+        );
+    }
+
+    let created = client
+        .create_project(&Project {
+            name: args.name.clone(),
+            color: source.color.clone(),
+            view_mode: source.view_mode.clone(),
+            kind: source.kind.clone(),
+            group_id: source.group_id.clone(),
+            ..Default::default()
+        })
+        .await?;
+    if let Some(cache) = cache.as_ref() {
+        let _ = cache.invalidate_projects();
+    }
+    let project_id = created
+        .id
+        .clone()
+        .ok_or_else(|| anyhow!("TickTick did not return an ID for the new list"))?;
+
+    let mut failed = 0;
+    for task in &tasks {
+        let copy = task_copy_for_project(task, &project_id, args.keep_dates);
+        if let Err(err) = create_task_copy(&client, &copy, &project_id).await {
+            failed += 1;
+            eprintln!("Warning: could not copy '{}': {}", task.title, err);
+        }
+    }
+
+    let copied = tasks.len() - failed;
+    verbosity.confirm(
+        &format_project_duplicate_output(&source, &created, copied, failed, args.output, context)?,
+        args.output,
+    );
+    if failed > 0 {
+        return Err(anyhow!(
+            "{} of {} tasks were not copied",
+            failed,
+            tasks.len()
+        ));
+    }
+    Ok(())
+}
+
+/// Creates `copy`, then completes it if it should be completed and the API created it open.
+async fn create_task_copy(client: &TickTickClient, copy: &Task, project_id: &str) -> Result<()> {
+    let created = client.create_task(copy).await?;
+    if copy.status != Some(TaskStatus::Completed) || created.status == Some(TaskStatus::Completed) {
+        return Ok(());
+    }
+    let task_id = created
+        .id
+        .ok_or_else(|| anyhow!("Created copy of '{}' has no ID", copy.title))?;
+    client.complete_task(project_id, &task_id).await
+}
+
+fn format_project_duplicate_output(
+    source: &Project,
+    created: &Project,
+    copied: usize,
+    failed: usize,
+    format: MessageFormat,
+    context: &OutputContext,
+) -> Result<String> {
+    match format {
+        MessageFormat::Json => Ok(format!(
+            "{}\n",
+            context.to_json_string(&serde_json::json!({
+                "project": created,
+                "copied": copied,
+                "failed": failed,
+            }))?
+        )),
+        MessageFormat::Human => Ok(format!(
+            "Project duplicated: {} -> {} ({} {} copied)\nID: {}\n",
+            source.name,
+            created.name,
+            copied,
+            if copied == 1 { "task" } else { "tasks" },
+            created.id.as_deref().unwrap_or_default()
+        )),
+    }
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProjectKind {
+    Task,
+    Note,
+    Inbox,
+}
+
+impl ProjectKind {
+    fn matches(self, project: &Project) -> bool {
+        let kind = project.kind.as_deref().unwrap_or("TASK");
+        match self {
+            Self::Task => kind.eq_ignore_ascii_case("TASK"),
+            Self::Note => kind.eq_ignore_ascii_case("NOTE"),
+            Self::Inbox => kind.eq_ignore_ascii_case("INBOX"),
+        }
+    }
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProjectSort {
+    Name,
+    #[value(alias = "sort-order")]
+    Order,
+}
+
 #[derive(Args)]
 pub struct ProjectListArgs {
     #[arg(long)]
     name: Option<String>,
+    #[arg(
+        long,
+        overrides_with = "open",
+        help = "Include closed (archived) lists"
+    )]
+    closed: bool,
+    #[arg(long, overrides_with = "closed", help = "Only open lists (default)")]
+    open: bool,
+    #[arg(long, value_enum, help = "Only lists of this kind")]
+    kind: Option<ProjectKind>,
+    #[arg(
+        long,
+        value_name = "FOLDER",
+        help = "Only lists in this folder (its groupId)"
+    )]
+    group: Option<String>,
+    #[arg(
+        long,
+        value_enum,
+        help = "Sort by name or by TickTick's sort order (default: as returned)"
+    )]
+    sort: Option<ProjectSort>,
+    #[arg(
+        long,
+        help = "Add open and overdue task counts (fetches every listed project)"
+    )]
+    counts: bool,
+    #[arg(
+        long,
+        help = "Nest lists under their folders, in sort order within each folder (add --counts for task counts)"
+    )]
+    tree: bool,
     #[arg(long, default_value = "human")]
     output: OutputFormat,
 }
 
-pub async fn project_list(args: ProjectListArgs) -> Result<()> {
-    let client = authenticated_client()?;
-    let cache = cache_store();
+pub async fn project_list(args: ProjectListArgs, context: &OutputContext) -> Result<()> {
+    let source = TaskSource::open()?;
 
-    let mut projects = get_projects_cached(&client, cache.as_ref(), false).await?;
+    let mut projects = source.projects().await?;
+    let group_id = match args.group.as_deref() {
+        Some(folder) => Some(resolve_folder_id(&projects, folder)?),
+        None => None,
+    };
     filter_projects_by_name(&mut projects, args.name.as_deref());
+    projects.retain(|project| {
+        args.kind.is_none_or(|kind| kind.matches(project))
+            && project_in_group(project, group_id.as_deref())
+    });
+    let hidden = if args.closed {
+        0
+    } else {
+        hide_closed_projects(&mut projects)
+    };
+    if let Some(sort) = args.sort {
+        sort_projects(&mut projects, sort);
+    }
+    // Markdown lists are meant for notes, where the counts are the point.
+    let counts = args.counts || matches!(args.output, OutputFormat::Markdown);
 
-    print_projects(&projects, args.output);
+    if args.tree {
+        let sort = args.sort.unwrap_or(ProjectSort::Order);
+        let mut folders = project_tree(projects, sort);
+        if counts {
+            let project_ids: Vec<String> = folders
+                .iter()
+                .flat_map(|folder| &folder.projects)
+                .filter_map(|summary| summary.project.id.clone())
+                .collect();
+            let counts = source
+                .project_task_counts(&project_ids, show_count_progress)
+                .await?;
+            for summary in folders.iter_mut().flat_map(|folder| &mut folder.projects) {
+                let counts = summary
+                    .project
+                    .id
+                    .as_ref()
+                    .and_then(|id| counts.get(id).copied());
+                summary.open_count = counts.map(|counts| counts.open);
+                summary.overdue_count = counts.map(|counts| counts.overdue);
+            }
+        }
+        print_project_tree(&folders, args.output, context);
+    } else if counts {
+        let summaries = project_summaries(&source, projects).await?;
+        print_project_summaries(&summaries, args.output, context);
+    } else {
+        print_projects(&projects, args.output, context);
+    }
+    if matches!(args.output, OutputFormat::Human) {
+        print!("{}", closed_projects_footer(hidden));
+    }
     Ok(())
 }
 
+/// Shows "Counting tasks 8/24" on a terminal's stderr while project data is fetched.
+fn show_count_progress(done: usize, total: usize) {
+    if !atty::is(Stream::Stderr) {
+        return;
+    }
+    if done < total {
+        eprint!("\rCounting tasks {}/{}", done, total);
+    } else {
+        eprint!("\r\x1b[K");
+    }
+}
+
+async fn project_summaries(
+    source: &TaskSource,
+    projects: Vec<Project>,
+) -> Result<Vec<ProjectSummary>> {
+    let project_ids: Vec<String> = projects
+        .iter()
+        .filter_map(|project| project.id.clone())
+        .collect();
+    let counts = source
+        .project_task_counts(&project_ids, show_count_progress)
+        .await?;
+    Ok(projects
+        .into_iter()
+        .map(|project| {
+            let counts = project.id.as_ref().and_then(|id| counts.get(id).copied());
+            ProjectSummary {
+                project,
+                open_count: counts.map(|counts| counts.open),
+                overdue_count: counts.map(|counts| counts.overdue),
+            }
+        })
+        .collect())
+}
+
+fn project_in_group(project: &Project, group_id: Option<&str>) -> bool {
+    group_id.is_none_or(|group_id| project.group_id.as_deref() == Some(group_id))
+}
+
+/// Drops closed lists and returns how many were dropped.
+fn hide_closed_projects(projects: &mut Vec<Project>) -> usize {
+    let before = projects.len();
+    projects.retain(|project| !project.closed.unwrap_or(false));
+    before - projects.len()
+}
+
+fn sort_projects(projects: &mut [Project], sort: ProjectSort) {
+    match sort {
+        ProjectSort::Name => {
+            projects.sort_by_key(|project| project.name.to_lowercase());
+        }
+        ProjectSort::Order => {
+            projects.sort_by_key(|project| project.sort_order.unwrap_or(i64::MAX));
+        }
+    }
+}
+
+/// Groups lists by folder, folders in order of their first list and lists in `sort` order
+/// within each, with lists outside any folder last. The Open API has no folder listing,
+/// so a folder is named by its groupId and only folders holding a list can appear.
+fn project_tree(mut projects: Vec<Project>, sort: ProjectSort) -> Vec<ProjectFolder> {
+    sort_projects(&mut projects, sort);
+    let mut folders: Vec<ProjectFolder> = Vec::new();
+    let mut ungrouped = Vec::new();
+
+    for project in projects {
+        let group_id = project.group_id.clone().filter(|id| !id.trim().is_empty());
+        let project = ProjectSummary::without_counts(project);
+        let Some(group_id) = group_id else {
+            ungrouped.push(project);
+            continue;
+        };
+        match folders
+            .iter_mut()
+            .find(|folder| folder.folder_id.as_deref() == Some(group_id.as_str()))
+        {
+            Some(folder) => folder.projects.push(project),
+            None => folders.push(ProjectFolder {
+                folder_id: Some(group_id.clone()),
+                name: group_id,
+                projects: vec![project],
+            }),
+        }
+    }
+
+    if !ungrouped.is_empty() {
+        folders.push(ProjectFolder {
+            folder_id: None,
+            name: "(no folder)".to_string(),
+            projects: ungrouped,
+        });
+    }
+    folders
+}
+
+fn closed_projects_footer(hidden: usize) -> String {
+    match hidden {
+        0 => String::new(),
+        1 => "\n1 closed list hidden, use --closed\n".to_string(),
+        count => format!("\n{} closed lists hidden, use --closed\n", count),
+    }
+}
+
 #[derive(Args)]
 pub struct ProjectGetArgs {
+    #[arg(help = "Project ID or list name")]
+    project_id: String,
+    #[arg(long, default_value = "human")]
+    output: MessageFormat,
+}
+
+pub async fn project_get(args: ProjectGetArgs, context: &OutputContext) -> Result<()> {
+    let source = TaskSource::open()?;
+
+    let project_id = source.resolve_project(&args.project_id).await?;
+    let project = match &source {
+        TaskSource::Api { client, .. } => client.get_project(&project_id).await?,
+        TaskSource::Snapshot(_) => source.project_data(&project_id).await?.project,
+    };
+    print!(
+        "{}",
+        format_project_detail_output(&project, args.output, context)?
+    );
+
+    Ok(())
+}
+
+#[derive(Args)]
+pub struct ColumnListArgs {
+    #[arg(help = "Project ID or list name")]
     project_id: String,
     #[arg(long, default_value = "human")]
     output: OutputFormat,
 }
 
-pub async fn project_get(args: ProjectGetArgs) -> Result<()> {
-    let client = authenticated_client()?;
+pub async fn column_list(args: ColumnListArgs, context: &OutputContext) -> Result<()> {
+    let (client, cache) = TaskSource::online("project column list")?;
+
+    let project_id = resolve_project_arg(&client, cache.as_ref(), &args.project_id).await?;
+    let data = client.get_project_data(&project_id).await?;
+    let mut columns = data.columns.unwrap_or_default();
+    columns.sort_by_key(|column| column.sort_order.unwrap_or(i64::MAX));
+    print_columns(&columns, args.output, context);
+    Ok(())
+}
 
-    let project = client.get_project(&args.project_id).await?;
-    print!("{}", format_project_detail_output(&project, args.output)?);
+#[derive(Args)]
+pub struct ProjectOpenArgs {
+    #[arg(help = "Project ID or list name")]
+    project_id: String,
+    #[arg(long, help = "Print the web app URL instead of opening it")]
+    print: bool,
+}
 
+pub async fn project_open(args: ProjectOpenArgs, verbosity: Verbosity) -> Result<()> {
+    let project_id = TaskSource::open()?
+        .resolve_project(&args.project_id)
+        .await?;
+    open_or_print(&project_web_url(&project_id), args.print, verbosity);
     Ok(())
 }
 
 #[derive(Args)]
 pub struct ProjectDataArgs {
+    #[arg(help = "Project ID or list name")]
     project_id: String,
+    #[arg(
+        long,
+        alias = "columns",
+        help = "List tasks under their kanban columns (a flat list when the project has none)"
+    )]
+    by_column: bool,
     #[arg(long, default_value = "human")]
     output: OutputFormat,
 }
 
-pub async fn project_data(args: ProjectDataArgs) -> Result<()> {
-    let client = authenticated_client()?;
+pub async fn project_data(args: ProjectDataArgs, context: &OutputContext) -> Result<()> {
+    // Only --by-column lists tasks; without it this prints a summary.
+    let summary = if args.by_column {
+        None
+    } else {
+        Some(MessageFormat::for_command(args.output, "project data")?)
+    };
+    let source = TaskSource::open()?;
 
-    let data = client.get_project_data(&args.project_id).await?;
-    print!("{}", format_project_data_output(&data, args.output)?);
+    let project_id = source.resolve_project(&args.project_id).await?;
+    let data = source.project_data(&project_id).await?;
+    match summary {
+        Some(output) => print!("{}", format_project_data_output(&data, output, context)?),
+        None => print!(
+            "{}",
+            format_project_columns_output(&data, args.output, context)?
+        ),
+    }
 
     Ok(())
 }
 
 #[derive(Args)]
 pub struct ProjectUpdateArgs {
+    #[arg(help = "Project ID or list name")]
     project_id: String,
     #[arg(long)]
     name: Option<String>,
-    #[arg(long)]
-    color: Option<String>,
+    #[arg(
+        long,
+        value_parser = parse_color,
+        help = "List color: hex (#RRGGBB or #RGB) or a palette name"
+    )]
+    project_color: Option<String>,
     #[arg(long)]
     view_mode: Option<String>,
     #[arg(long)]
     kind: Option<String>,
     #[arg(long)]
     sort_order: Option<i64>,
+    #[arg(
+        long,
+        value_name = "FOLDER",
+        conflicts_with = "ungroup",
+        help = "Folder ID (groupId) to place the project in; matched case-insensitively"
+    )]
+    group: Option<String>,
+    #[arg(long, help = "Take the project out of its folder")]
+    ungroup: bool,
     #[arg(long, default_value = "human")]
-    output: OutputFormat,
+    output: MessageFormat,
 }
 
-pub async fn project_update(args: ProjectUpdateArgs) -> Result<()> {
-    let client = authenticated_client()?;
-    let cache = cache_store();
+pub async fn project_update(
+    args: ProjectUpdateArgs,
+    verbosity: Verbosity,
+    context: &OutputContext,
+) -> Result<()> {
+    let mut args = args;
+    let (client, cache) = TaskSource::online("project update")?;
 
+    if let Some(folder) = args.group.take() {
+        let projects = get_projects_cached(&client, cache.as_ref(), false).await?;
+        args.group = Some(resolve_folder_id(&projects, &folder)?);
+    }
+    args.project_id = resolve_project_arg(&client, cache.as_ref(), &args.project_id).await?;
     let mut project = client.get_project(&args.project_id).await?;
     apply_project_update_args(&mut project, &args);
 
-    let updated = client.update_project(&args.project_id, &project).await?;
+    let payload = project_update_payload(&project, args.ungroup)?;
+    let updated = client.update_project(&args.project_id, &payload).await?;
     if let Some(cache) = cache.as_ref() {
         let _ = cache.invalidate_projects();
     }
-    print!("{}", format_project_update_output(&updated, args.output)?);
+    verbosity.confirm(
+        &format_project_update_output(&updated, args.output, context)?,
+        args.output,
+    );
     Ok(())
 }
 
 #[derive(Args)]
 pub struct ProjectDeleteArgs {
+    #[arg(help = "Project ID or list name")]
     project_id: String,
     #[arg(long, default_value = "true")]
     confirm: bool,
+    #[arg(
+        long,
+        visible_alias = "yes",
+        short = 'y',
+        help = "Skip the confirmation prompt"
+    )]
+    force: bool,
     #[arg(long, default_value = "human")]
-    output: OutputFormat,
+    output: MessageFormat,
 }
 
-pub async fn project_delete(args: ProjectDeleteArgs) -> Result<()> {
+pub async fn project_delete(
+    args: ProjectDeleteArgs,
+    verbosity: Verbosity,
+    context: &OutputContext,
+) -> Result<()> {
     let ProjectDeleteArgs {
         project_id,
         confirm,
+        force,
         output,
     } = args;
-    let client = authenticated_client()?;
-    let cache = cache_store();
+    let confirm = confirm && !force;
+    let (client, cache) = TaskSource::online("project delete")?;
+    let project_id = resolve_project_arg(&client, cache.as_ref(), &project_id).await?;
 
     if !confirm {
+        let snapshot = if journal_enabled() {
+            client.get_project(&project_id).await.ok()
+        } else {
+            None
+        };
         client.delete_project(&project_id).await?;
         if let Some(cache) = cache.as_ref() {
             let _ = cache.invalidate_projects();
         }
-        print!(
-            "{}",
-            format_project_delete_output(&project_id, None, output)?
+        if let Some(project) = snapshot {
+            record_project_deleted(cache.as_ref(), project);
+        }
+        verbosity.confirm(
+            &format_project_delete_output(&project_id, None, output, context)?,
+            output,
         );
         return Ok(());
     }
@@ -170,14 +769,15 @@ pub async fn project_delete(args: ProjectDeleteArgs) -> Result<()> {
     let project = client.get_project(&project_id).await?;
 
     if confirm {
-        println!(
+        ensure_can_prompt(atty::is(Stream::Stdin), output)?;
+        eprintln!(
             "Are you sure you want to delete project '{}'? [y/N]",
             project.name
         );
         let mut input = String::new();
         std::io::stdin().read_line(&mut input)?;
         if !input.trim().eq_ignore_ascii_case("y") {
-            println!("Cancelled.");
+            eprintln!("Cancelled.");
             return Ok(());
         }
     }
@@ -186,18 +786,275 @@ pub async fn project_delete(args: ProjectDeleteArgs) -> Result<()> {
     if let Some(cache) = cache.as_ref() {
         let _ = cache.invalidate_projects();
     }
-    print!(
-        "{}",
-        format_project_delete_output(&project_id, Some(project.name.as_str()), output)?
+    verbosity.confirm(
+        &format_project_delete_output(&project_id, Some(project.name.as_str()), output, context)?,
+        output,
+    );
+    record_project_deleted(cache.as_ref(), project);
+    Ok(())
+}
+
+#[derive(Args)]
+pub struct ProjectMoveArgs {
+    #[arg(help = "Project ID or list name")]
+    project_id: String,
+    #[arg(
+        long,
+        required_unless_present = "none",
+        conflicts_with = "none",
+        help = "Folder ID to place the project in"
+    )]
+    folder: Option<String>,
+    #[arg(long, help = "Take the project out of its folder")]
+    none: bool,
+    #[arg(long, default_value = "human")]
+    output: MessageFormat,
+}
+
+pub async fn project_move(
+    args: ProjectMoveArgs,
+    verbosity: Verbosity,
+    context: &OutputContext,
+) -> Result<()> {
+    let (client, cache) = TaskSource::online("project move")?;
+
+    let group_id = match args.folder.as_deref() {
+        Some(folder) => {
+            let projects = get_projects_cached(&client, cache.as_ref(), false).await?;
+            Some(resolve_folder_id(&projects, folder)?)
+        }
+        None => None,
+    };
+
+    let project_id = resolve_project_arg(&client, cache.as_ref(), &args.project_id).await?;
+    let mut project = client.get_project(&project_id).await?;
+    project.id = Some(project_id.clone());
+    project.group_id = group_id;
+    let payload = project_update_payload(&project, project.group_id.is_none())?;
+
+    let updated = client.update_project(&project_id, &payload).await?;
+    if let Some(cache) = cache.as_ref() {
+        let _ = cache.invalidate_projects();
+    }
+    verbosity.confirm(
+        &format_project_move_output(
+            &updated.name,
+            project.group_id.as_deref(),
+            args.output,
+            context,
+        )?,
+        args.output,
+    );
+    Ok(())
+}
+
+#[derive(Args)]
+pub struct ProjectArchiveArgs {
+    #[arg(help = "Project ID or list name")]
+    project_id: String,
+    #[arg(long, default_value = "true")]
+    confirm: bool,
+    #[arg(
+        long,
+        visible_alias = "yes",
+        short = 'y',
+        help = "Skip the confirmation prompt"
+    )]
+    force: bool,
+    #[arg(long, default_value = "human")]
+    output: MessageFormat,
+}
+
+#[derive(Args)]
+pub struct ProjectReopenArgs {
+    #[arg(help = "Project ID or list name")]
+    project_id: String,
+    #[arg(long, default_value = "human")]
+    output: MessageFormat,
+}
+
+pub async fn project_archive(
+    args: ProjectArchiveArgs,
+    verbosity: Verbosity,
+    context: &OutputContext,
+) -> Result<()> {
+    set_project_closed(
+        &args.project_id,
+        true,
+        args.confirm && !args.force,
+        args.output,
+        verbosity,
+        context,
+    )
+    .await
+}
+
+pub async fn project_reopen(
+    args: ProjectReopenArgs,
+    verbosity: Verbosity,
+    context: &OutputContext,
+) -> Result<()> {
+    set_project_closed(
+        &args.project_id,
+        false,
+        false,
+        args.output,
+        verbosity,
+        context,
+    )
+    .await
+}
+
+async fn set_project_closed(
+    project_ref: &str,
+    closed: bool,
+    confirm: bool,
+    output: MessageFormat,
+    verbosity: Verbosity,
+    context: &OutputContext,
+) -> Result<()> {
+    let command = if closed {
+        "project archive"
+    } else {
+        "project reopen"
+    };
+    let (client, cache) = TaskSource::online(command)?;
+
+    let project_id = resolve_project_arg(&client, cache.as_ref(), project_ref).await?;
+    let mut project = client.get_project(&project_id).await?;
+    project.id = Some(project_id.clone());
+    if project.closed.unwrap_or(false) == closed {
+        verbosity.confirm(
+            &format_project_unchanged_output(&project, closed, output, context)?,
+            output,
+        );
+        return Ok(());
+    }
+
+    if confirm {
+        ensure_can_prompt(atty::is(Stream::Stdin), output)?;
+        eprintln!(
+            "Archive project '{}'? It will be hidden from your lists in every TickTick app. [y/N]",
+            project.name
+        );
+        let mut input = String::new();
+        std::io::stdin().read_line(&mut input)?;
+        if !input.trim().eq_ignore_ascii_case("y") {
+            eprintln!("Cancelled.");
+            return Ok(());
+        }
+    }
+
+    project.closed = Some(closed);
+    let updated = client.update_project(&project_id, &project).await?;
+    if let Some(cache) = cache.as_ref() {
+        let _ = cache.invalidate_projects();
+    }
+    verbosity.confirm(
+        &format_project_closed_output(&updated, closed, output, context)?,
+        output,
     );
     Ok(())
 }
 
+fn format_project_closed_output(
+    project: &Project,
+    closed: bool,
+    format: MessageFormat,
+    context: &OutputContext,
+) -> Result<String> {
+    match format {
+        MessageFormat::Json => Ok(format!("{}\n", context.to_json_string(project)?)),
+        MessageFormat::Human if closed => Ok(format!("Project archived: {}\n", project.name)),
+        MessageFormat::Human => Ok(format!("Project reopened: {}\n", project.name)),
+    }
+}
+
+/// For a project already in the requested state; JSON gets the project as it stands.
+fn format_project_unchanged_output(
+    project: &Project,
+    closed: bool,
+    format: MessageFormat,
+    context: &OutputContext,
+) -> Result<String> {
+    let state = if closed { "archived" } else { "open" };
+    match format {
+        MessageFormat::Json => Ok(format!("{}\n", context.to_json_string(project)?)),
+        MessageFormat::Human => Ok(format!(
+            "Project '{}' is already {}.\n",
+            project.name, state
+        )),
+    }
+}
+
+/// The project as an update body. With `clear_group`, `groupId` is sent as an explicit
+/// null: it is skipped when unset, and TickTick only clears it on a null.
+fn project_update_payload(project: &Project, clear_group: bool) -> Result<serde_json::Value> {
+    let mut payload = serde_json::to_value(project)?;
+    if clear_group {
+        payload["groupId"] = serde_json::Value::Null;
+    }
+    Ok(payload)
+}
+
+/// The Open API has no folder listing, so folders are known only through the
+/// `groupId` of the projects already in them.
+fn resolve_folder_id(projects: &[Project], folder: &str) -> Result<String> {
+    let folder = folder.trim();
+    let mut known: Vec<&str> = projects
+        .iter()
+        .filter_map(|project| project.group_id.as_deref())
+        .filter(|group_id| !group_id.trim().is_empty())
+        .collect();
+    if let Some(group_id) = known
+        .iter()
+        .find(|group_id| group_id.eq_ignore_ascii_case(folder))
+    {
+        return Ok(group_id.to_string());
+    }
+
+    known.sort_unstable();
+    known.dedup();
+    let available = if known.is_empty() {
+        "No project is in a folder yet.".to_string()
+    } else {
+        format!("Known folders: {}.", known.join(", "))
+    };
+    Err(anyhow!(
+        "Unknown folder '{}'. {} Use the groupId of a project already in that folder (see `tt project list --tree`)",
+        folder,
+        available
+    ))
+}
+
+fn format_project_move_output(
+    project_name: &str,
+    group_id: Option<&str>,
+    format: MessageFormat,
+    context: &OutputContext,
+) -> Result<String> {
+    match format {
+        MessageFormat::Json => Ok(format!(
+            "{}\n",
+            context.to_json_string(&serde_json::json!({
+                "name": project_name,
+                "groupId": group_id,
+            }))?
+        )),
+        MessageFormat::Human => Ok(match group_id {
+            Some(group_id) => {
+                format!("Project moved: {} -> folder {}\n", project_name, group_id)
+            }
+            None => format!("Project moved: {} -> no folder\n", project_name),
+        }),
+    }
+}
+
 fn build_project_from_add_args(args: &ProjectAddArgs) -> Project {
     Project {
         id: None,
         name: args.name.clone(),
-        color: args.color.clone(),
+        color: args.project_color.clone(),
         view_mode: args.view_mode.clone(),
         kind: args.kind.clone(),
         group_id: args.group_id.clone(),
@@ -211,10 +1068,14 @@ fn filter_projects_by_name(projects: &mut Vec<Project>, name: Option<&str>) {
     }
 }
 
-fn format_project_create_output(project: &Project, format: OutputFormat) -> Result<String> {
+fn format_project_create_output(
+    project: &Project,
+    format: MessageFormat,
+    context: &OutputContext,
+) -> Result<String> {
     match format {
-        OutputFormat::Json => Ok(format!("{}\n", serde_json::to_string_pretty(project)?)),
-        OutputFormat::Human => Ok(format!(
+        MessageFormat::Json => Ok(format!("{}\n", context.to_json_string(project)?)),
+        MessageFormat::Human => Ok(format!(
             "Project created: {}\nID: {}\n",
             project.name,
             project.id.clone().unwrap_or_default()
@@ -222,10 +1083,14 @@ fn format_project_create_output(project: &Project, format: OutputFormat) -> Resu
     }
 }
 
-fn format_project_detail_output(project: &Project, format: OutputFormat) -> Result<String> {
+fn format_project_detail_output(
+    project: &Project,
+    format: MessageFormat,
+    context: &OutputContext,
+) -> Result<String> {
     match format {
-        OutputFormat::Json => Ok(format!("{}\n", serde_json::to_string_pretty(project)?)),
-        OutputFormat::Human => Ok(format!(
+        MessageFormat::Json => Ok(format!("{}\n", context.to_json_string(project)?)),
+        MessageFormat::Human => Ok(format!(
             "Project: {}\nID: {}\n",
             project.name,
             project.id.clone().unwrap_or_default()
@@ -233,38 +1098,47 @@ fn format_project_detail_output(project: &Project, format: OutputFormat) -> Resu
     }
 }
 
-fn format_project_update_output(project: &Project, format: OutputFormat) -> Result<String> {
+fn format_project_update_output(
+    project: &Project,
+    format: MessageFormat,
+    context: &OutputContext,
+) -> Result<String> {
     match format {
-        OutputFormat::Json => Ok(format!("{}\n", serde_json::to_string_pretty(project)?)),
-        OutputFormat::Human => Ok(format!("Project updated: {}\n", project.name)),
+        MessageFormat::Json => Ok(format!("{}\n", context.to_json_string(project)?)),
+        MessageFormat::Human => Ok(format!("Project updated: {}\n", project.name)),
     }
 }
 
 fn format_project_delete_output(
     project_id: &str,
     project_name: Option<&str>,
-    format: OutputFormat,
+    format: MessageFormat,
+    context: &OutputContext,
 ) -> Result<String> {
     match format {
-        OutputFormat::Json => Ok(format!(
+        MessageFormat::Json => Ok(format!(
             "{}\n",
-            serde_json::to_string_pretty(&serde_json::json!({
+            context.to_json_string(&serde_json::json!({
                 "status": "deleted",
                 "projectId": project_id,
                 "name": project_name,
             }))?
         )),
-        OutputFormat::Human => Ok(match project_name {
+        MessageFormat::Human => Ok(match project_name {
             Some(project_name) => format!("Project deleted: {}\n", project_name),
             None => format!("Project deleted: {}\n", project_id),
         }),
     }
 }
 
-fn format_project_data_output(data: &ProjectData, format: OutputFormat) -> Result<String> {
+fn format_project_data_output(
+    data: &ProjectData,
+    format: MessageFormat,
+    context: &OutputContext,
+) -> Result<String> {
     match format {
-        OutputFormat::Json => Ok(format!("{}\n", serde_json::to_string_pretty(data)?)),
-        OutputFormat::Human => {
+        MessageFormat::Json => Ok(format!("{}\n", context.to_json_string(data)?)),
+        MessageFormat::Human => {
             let mut output = format!("Project: {}\n", data.project.name);
             if let Some(tasks) = data.tasks.as_ref() {
                 output.push_str(&format!("Tasks: {}\n", tasks.len()));
@@ -277,11 +1151,108 @@ fn format_project_data_output(data: &ProjectData, format: OutputFormat) -> Resul
     }
 }
 
+/// Tasks whose `columnId` matches none of the project's columns.
+const UNASSIGNED_COLUMN: &str = "No column";
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ColumnTasks {
+    id: Option<String>,
+    name: String,
+    tasks: Vec<Task>,
+}
+
+/// Sorts the project's tasks into its columns, in board order. Empty columns are kept so
+/// the board reads the same as in TickTick; unmatched tasks go last under "No column".
+fn tasks_by_column(data: &ProjectData) -> Vec<ColumnTasks> {
+    let mut columns: Vec<&Column> = data.columns.iter().flatten().collect();
+    columns.sort_by_key(|column| column.sort_order.unwrap_or(i64::MAX));
+
+    let mut groups: Vec<ColumnTasks> = columns
+        .iter()
+        .map(|column| ColumnTasks {
+            id: Some(column.id.clone()),
+            name: column.name.clone(),
+            tasks: Vec::new(),
+        })
+        .collect();
+    let mut unassigned = Vec::new();
+    for task in data.tasks.iter().flatten() {
+        let column = groups
+            .iter_mut()
+            .find(|group| group.id.is_some() && group.id == task.column_id);
+        match column {
+            Some(column) => column.tasks.push(task.clone()),
+            None => unassigned.push(task.clone()),
+        }
+    }
+    if !unassigned.is_empty() {
+        groups.push(ColumnTasks {
+            id: None,
+            name: UNASSIGNED_COLUMN.to_string(),
+            tasks: unassigned,
+        });
+    }
+    groups
+}
+
+fn format_project_columns_output(
+    data: &ProjectData,
+    format: OutputFormat,
+    context: &OutputContext,
+) -> Result<String> {
+    let has_columns = data
+        .columns
+        .as_ref()
+        .is_some_and(|columns| !columns.is_empty());
+    let tasks = data.tasks.clone().unwrap_or_default();
+    let options = TaskListOptions {
+        table: TableLayout::for_terminal(false),
+        ..Default::default()
+    };
+
+    match format {
+        OutputFormat::Json if has_columns => Ok(format!(
+            "{}\n",
+            context.to_json_string(&serde_json::json!({
+                "project": data.project,
+                "columns": tasks_by_column(data),
+            }))?
+        )),
+        OutputFormat::Json => Ok(format!(
+            "{}\n",
+            context.to_json_string(&serde_json::json!({
+                "project": data.project,
+                "tasks": tasks,
+            }))?
+        )),
+        OutputFormat::Human | OutputFormat::Csv | OutputFormat::Tsv | OutputFormat::Markdown => {
+            let body = if has_columns {
+                let groups: Vec<TaskGroup> = tasks_by_column(data)
+                    .into_iter()
+                    .map(|column| TaskGroup {
+                        key: column.name,
+                        tasks: column.tasks,
+                    })
+                    .collect();
+                render_task_groups(&groups, format, options, context)
+            } else {
+                render_tasks(&tasks, format, options, context)
+            };
+            // A heading line would not parse as a CSV/TSV row.
+            if matches!(format, OutputFormat::Csv | OutputFormat::Tsv) {
+                return Ok(body);
+            }
+            Ok(format!("Project: {}\n\n{}", data.project.name, body))
+        }
+    }
+}
+
 fn apply_project_update_args(project: &mut Project, args: &ProjectUpdateArgs) {
     if let Some(name) = args.name.as_ref() {
         project.name = name.clone();
     }
-    if let Some(color) = args.color.as_ref() {
+    if let Some(color) = args.project_color.as_ref() {
         project.color = Some(color.clone());
     }
     if let Some(view_mode) = args.view_mode.as_ref() {
@@ -293,6 +1264,12 @@ fn apply_project_update_args(project: &mut Project, args: &ProjectUpdateArgs) {
     if let Some(sort_order) = args.sort_order {
         project.sort_order = Some(sort_order);
     }
+    if let Some(group) = args.group.as_ref() {
+        project.group_id = Some(group.clone());
+    }
+    if args.ungroup {
+        project.group_id = None;
+    }
 
     project.id = Some(args.project_id.clone());
 }
@@ -300,7 +1277,7 @@ fn apply_project_update_args(project: &mut Project, args: &ProjectUpdateArgs) {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::{Column, Task};
+    use crate::models::TaskStatus;
 
     fn sample_project() -> Project {
         Project {
@@ -316,11 +1293,11 @@ mod tests {
     fn build_project_from_add_args_copies_fields() {
         let args = ProjectAddArgs {
             name: "Work".to_string(),
-            color: Some("#ffffff".to_string()),
+            project_color: Some("#ffffff".to_string()),
             view_mode: Some("kanban".to_string()),
             kind: Some("TASK".to_string()),
             group_id: Some("group-1".to_string()),
-            output: OutputFormat::Human,
+            output: MessageFormat::Human,
         };
 
         let project = build_project_from_add_args(&args);
@@ -350,22 +1327,227 @@ mod tests {
         assert_eq!(projects[0].name, "Work");
     }
 
+    fn listed_projects() -> Vec<Project> {
+        let project = |name: &str, kind: Option<&str>, closed: bool, group: Option<&str>| Project {
+            id: Some(name.to_lowercase()),
+            name: name.to_string(),
+            kind: kind.map(str::to_string),
+            closed: Some(closed),
+            group_id: group.map(str::to_string),
+            ..Default::default()
+        };
+        vec![
+            Project {
+                sort_order: Some(30),
+                ..project("work", Some("TASK"), false, Some("g1"))
+            },
+            Project {
+                sort_order: Some(10),
+                ..project("Journal", Some("NOTE"), false, None)
+            },
+            Project {
+                sort_order: Some(20),
+                ..project("Archive", None, true, Some("g1"))
+            },
+            project("Old notes", Some("NOTE"), true, None),
+        ]
+    }
+
+    fn names(projects: &[Project]) -> Vec<&str> {
+        projects
+            .iter()
+            .map(|project| project.name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn project_kind_treats_missing_kind_as_task() {
+        let projects = listed_projects();
+        let of_kind = |kind: ProjectKind| -> Vec<&str> {
+            projects
+                .iter()
+                .filter(|project| kind.matches(project))
+                .map(|project| project.name.as_str())
+                .collect()
+        };
+        assert_eq!(of_kind(ProjectKind::Task), vec!["work", "Archive"]);
+        assert_eq!(of_kind(ProjectKind::Note), vec!["Journal", "Old notes"]);
+        assert!(of_kind(ProjectKind::Inbox).is_empty());
+    }
+
+    #[test]
+    fn closed_projects_are_hidden_and_counted() {
+        let mut projects = listed_projects();
+        assert_eq!(hide_closed_projects(&mut projects), 2);
+        assert_eq!(names(&projects), vec!["work", "Journal"]);
+
+        assert_eq!(closed_projects_footer(0), "");
+        assert_eq!(
+            closed_projects_footer(1),
+            "\n1 closed list hidden, use --closed\n"
+        );
+        assert_eq!(
+            closed_projects_footer(3),
+            "\n3 closed lists hidden, use --closed\n"
+        );
+    }
+
+    #[test]
+    fn projects_filter_by_group_and_sort_by_name_or_order() {
+        let projects = listed_projects();
+        let in_group: Vec<&str> = projects
+            .iter()
+            .filter(|project| project_in_group(project, Some("g1")))
+            .map(|project| project.name.as_str())
+            .collect();
+        assert_eq!(in_group, vec!["work", "Archive"]);
+        assert!(projects
+            .iter()
+            .all(|project| project_in_group(project, None)));
+
+        let mut sorted = listed_projects();
+        sort_projects(&mut sorted, ProjectSort::Name);
+        assert_eq!(
+            names(&sorted),
+            vec!["Archive", "Journal", "Old notes", "work"]
+        );
+        sort_projects(&mut sorted, ProjectSort::Order);
+        assert_eq!(
+            names(&sorted),
+            vec!["Journal", "Archive", "work", "Old notes"]
+        );
+    }
+
+    #[test]
+    fn task_copies_get_the_new_list_and_drop_dates_unless_kept() {
+        let source = Task {
+            id: Some("t1".to_string()),
+            project_id: Some("old".to_string()),
+            title: "Water plants".to_string(),
+            content: Some("Both balconies".to_string()),
+            tags: Some(vec!["home".to_string()]),
+            priority: Some(3),
+            due_date: Some("2026-10-16T09:00:00.000+0000".to_string()),
+            reminders: Some(vec!["TRIGGER:PT0S".to_string()]),
+            repeat_flag: Some("RRULE:FREQ=WEEKLY".to_string()),
+            sort_order: Some(-42),
+            column_id: Some("c1".to_string()),
+            status: Some(TaskStatus::Completed),
+            completed_time: Some("2026-10-10T09:00:00.000+0000".to_string()),
+            ..Default::default()
+        };
+
+        let copy = task_copy_for_project(&source, "new", false);
+        assert_eq!(copy.id, None);
+        assert_eq!(copy.project_id.as_deref(), Some("new"));
+        assert_eq!(copy.title, "Water plants");
+        assert_eq!(copy.content.as_deref(), Some("Both balconies"));
+        assert_eq!(copy.tags, source.tags);
+        assert_eq!(copy.priority, Some(3));
+        assert_eq!(copy.sort_order, Some(-42));
+        assert_eq!(copy.status, Some(TaskStatus::Completed));
+        assert_eq!(copy.completed_time, source.completed_time);
+        assert!(copy.column_id.is_none());
+        assert!(copy.due_date.is_none() && copy.reminders.is_none() && copy.repeat_flag.is_none());
+
+        let dated = task_copy_for_project(&source, "new", true);
+        assert_eq!(dated.due_date, source.due_date);
+        assert_eq!(dated.repeat_flag, source.repeat_flag);
+
+        let open = task_copy_for_project(
+            &Task {
+                status: Some(TaskStatus::Normal),
+                completed_time: None,
+                ..source.clone()
+            },
+            "new",
+            false,
+        );
+        assert_eq!(open.status, Some(TaskStatus::Normal));
+        assert!(open.completed_time.is_none());
+    }
+
+    #[test]
+    fn reopening_sends_closed_false_explicitly() {
+        let project = Project {
+            id: Some("p1".to_string()),
+            name: "Archive".to_string(),
+            closed: Some(false),
+            ..Default::default()
+        };
+        let payload = serde_json::to_value(&project).unwrap();
+        assert_eq!(payload["closed"], serde_json::Value::Bool(false));
+
+        let archived = format_project_closed_output(
+            &Project {
+                closed: Some(true),
+                ..project.clone()
+            },
+            true,
+            MessageFormat::Human,
+            &OutputContext::default(),
+        )
+        .unwrap();
+        assert_eq!(archived, "Project archived: Archive\n");
+        assert_eq!(
+            format_project_closed_output(
+                &project,
+                false,
+                MessageFormat::Human,
+                &OutputContext::default(),
+            )
+            .unwrap(),
+            "Project reopened: Archive\n"
+        );
+        assert_eq!(
+            format_project_unchanged_output(
+                &project,
+                false,
+                MessageFormat::Human,
+                &OutputContext::default(),
+            )
+            .unwrap(),
+            "Project 'Archive' is already open.\n"
+        );
+        let unchanged = format_project_unchanged_output(
+            &project,
+            false,
+            MessageFormat::Json,
+            &OutputContext::default(),
+        )
+        .unwrap();
+        let json: serde_json::Value = serde_json::from_str(&unchanged).unwrap();
+        assert_eq!(json["id"], "p1");
+        assert_eq!(json["closed"], false);
+    }
+
     #[test]
     fn format_project_outputs_match_selected_mode() {
         let project = sample_project();
 
-        let created = format_project_create_output(&project, OutputFormat::Human).unwrap();
+        let created =
+            format_project_create_output(&project, MessageFormat::Human, &OutputContext::default())
+                .unwrap();
         assert!(created.contains("Project created: Inbox"));
         assert!(created.contains("ID: project-1"));
 
-        let detail_json = format_project_detail_output(&project, OutputFormat::Json).unwrap();
+        let detail_json =
+            format_project_detail_output(&project, MessageFormat::Json, &OutputContext::default())
+                .unwrap();
         assert!(detail_json.contains("\"name\": \"Inbox\""));
 
-        let updated = format_project_update_output(&project, OutputFormat::Human).unwrap();
+        let updated =
+            format_project_update_output(&project, MessageFormat::Human, &OutputContext::default())
+                .unwrap();
         assert!(updated.contains("Project updated: Inbox"));
 
-        let deleted =
-            format_project_delete_output("project-1", Some("Inbox"), OutputFormat::Json).unwrap();
+        let deleted = format_project_delete_output(
+            "project-1",
+            Some("Inbox"),
+            MessageFormat::Json,
+            &OutputContext::default(),
+        )
+        .unwrap();
         assert!(deleted.contains("\"status\": \"deleted\""));
         assert!(deleted.contains("\"projectId\": \"project-1\""));
         assert!(deleted.contains("\"name\": \"Inbox\""));
@@ -387,26 +1569,176 @@ mod tests {
             }]),
         };
 
-        let output = format_project_data_output(&data, OutputFormat::Human).unwrap();
+        let output =
+            format_project_data_output(&data, MessageFormat::Human, &OutputContext::default())
+                .unwrap();
         assert!(output.contains("Project: Inbox"));
         assert!(output.contains("Tasks: 1"));
         assert!(output.contains("Columns: 1"));
     }
 
+    fn kanban_data() -> ProjectData {
+        let column = |id: &str, name: &str, sort_order: i64| Column {
+            id: id.to_string(),
+            project_id: "project-1".to_string(),
+            name: name.to_string(),
+            sort_order: Some(sort_order),
+        };
+        let task = |title: &str, column_id: Option<&str>| Task {
+            id: Some(title.to_lowercase()),
+            title: title.to_string(),
+            column_id: column_id.map(str::to_string),
+            ..Default::default()
+        };
+        ProjectData {
+            project: sample_project(),
+            tasks: Some(vec![
+                task("Ship", Some("col-done")),
+                task("Draft", Some("col-todo")),
+                task("Loose", None),
+                task("Review", Some("col-todo")),
+            ]),
+            columns: Some(vec![
+                column("col-done", "Done", 2),
+                column("col-todo", "To Do", 1),
+                column("col-empty", "Blocked", 3),
+            ]),
+        }
+    }
+
+    #[test]
+    fn tasks_by_column_follows_board_order_and_collects_unassigned_tasks() {
+        let groups = tasks_by_column(&kanban_data());
+        let summary: Vec<(&str, Vec<&str>)> = groups
+            .iter()
+            .map(|group| {
+                (
+                    group.name.as_str(),
+                    group.tasks.iter().map(|task| task.title.as_str()).collect(),
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            summary,
+            vec![
+                ("To Do", vec!["Draft", "Review"]),
+                ("Done", vec!["Ship"]),
+                ("Blocked", vec![]),
+                ("No column", vec!["Loose"]),
+            ]
+        );
+        assert_eq!(groups[3].id, None);
+    }
+
+    #[test]
+    fn project_columns_output_sections_tasks_and_nests_them_in_json() {
+        let data = kanban_data();
+
+        let human =
+            format_project_columns_output(&data, OutputFormat::Human, &OutputContext::default())
+                .unwrap();
+        assert!(human.starts_with("Project: Inbox\n\n# To Do (2)\n"));
+        assert!(human.contains("# Blocked (0)"));
+
+        let json: serde_json::Value = serde_json::from_str(
+            &format_project_columns_output(&data, OutputFormat::Json, &OutputContext::default())
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(json["columns"][0]["name"], "To Do");
+        assert_eq!(json["columns"][0]["tasks"][1]["title"], "Review");
+        assert_eq!(json["columns"][3]["id"], serde_json::Value::Null);
+    }
+
+    #[test]
+    fn project_columns_output_falls_back_to_a_flat_list_without_columns() {
+        let data = ProjectData {
+            columns: None,
+            ..kanban_data()
+        };
+
+        let human =
+            format_project_columns_output(&data, OutputFormat::Human, &OutputContext::default())
+                .unwrap();
+        assert!(human.starts_with("Project: Inbox\n\n"));
+        assert!(!human.contains("# "));
+        assert!(human.contains("Draft"));
+
+        let json: serde_json::Value = serde_json::from_str(
+            &format_project_columns_output(&data, OutputFormat::Json, &OutputContext::default())
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(json["tasks"].as_array().unwrap().len(), 4);
+        assert!(json.get("columns").is_none());
+    }
+
+    #[test]
+    fn parse_color_normalizes_hex_values() {
+        assert_eq!(parse_color("#a1b2c3").unwrap(), "#A1B2C3");
+        assert_eq!(parse_color(" #FF6161 ").unwrap(), "#FF6161");
+    }
+
+    #[test]
+    fn parse_color_expands_shorthand_hex() {
+        assert_eq!(parse_color("#abc").unwrap(), "#AABBCC");
+        assert_eq!(parse_color("#0F0").unwrap(), "#00FF00");
+    }
+
+    #[test]
+    fn parse_color_maps_named_palette_colors() {
+        assert_eq!(parse_color("red").unwrap(), "#FF6161");
+        assert_eq!(parse_color("Blue").unwrap(), "#4CA1FF");
+        assert_eq!(parse_color("grey").unwrap(), "#B4B4B4");
+    }
+
+    #[test]
+    fn parse_color_rejects_invalid_values_with_valid_names() {
+        for value in ["#12345", "#GGGGGG", "123456", "chartreuse", ""] {
+            let err = parse_color(value).unwrap_err().to_string();
+            assert!(err.contains("Invalid color"), "{}", err);
+            assert!(err.contains("red, orange, yellow"), "{}", err);
+        }
+    }
+
+    #[test]
+    fn project_color_flag_is_validated_at_parse_time() {
+        use clap::Parser;
+
+        #[derive(Parser)]
+        struct ProjectAddArgsCli {
+            #[command(flatten)]
+            args: ProjectAddArgs,
+        }
+
+        let parsed = ProjectAddArgsCli::try_parse_from(["tt", "Work", "--project-color", "#f00"])
+            .unwrap()
+            .args;
+        assert_eq!(parsed.project_color.as_deref(), Some("#FF0000"));
+
+        assert!(
+            ProjectAddArgsCli::try_parse_from(["tt", "Work", "--project-color", "mauve"]).is_err()
+        );
+    }
+
     #[test]
     fn apply_project_update_args_overrides_selected_fields() {
         let mut project = sample_project();
         let args = ProjectUpdateArgs {
             project_id: "project-99".to_string(),
             name: Some("Renamed".to_string()),
-            color: Some("#654321".to_string()),
+            project_color: Some("#654321".to_string()),
             view_mode: Some("kanban".to_string()),
             kind: Some("TASK".to_string()),
             sort_order: Some(7),
-            output: OutputFormat::Human,
+            group: Some("group-2".to_string()),
+            ungroup: false,
+            output: MessageFormat::Human,
         };
 
         apply_project_update_args(&mut project, &args);
+        assert_eq!(project.group_id.as_deref(), Some("group-2"));
 
         assert_eq!(project.id.as_deref(), Some("project-99"));
         assert_eq!(project.name, "Renamed");
@@ -415,4 +1747,139 @@ mod tests {
         assert_eq!(project.kind.as_deref(), Some("TASK"));
         assert_eq!(project.sort_order, Some(7));
     }
+
+    #[test]
+    fn project_tree_nests_lists_by_folder_in_sort_order() {
+        let project = |id: &str, group: Option<&str>, order: i64| Project {
+            id: Some(id.to_string()),
+            name: id.to_string(),
+            group_id: group.map(str::to_string),
+            sort_order: Some(order),
+            ..Default::default()
+        };
+        let tree = project_tree(
+            vec![
+                project("loose", None, 0),
+                project("b2", Some("g-b"), 5),
+                project("a1", Some("g-a"), 3),
+                project("b1", Some("g-b"), 1),
+                project("blank", Some(" "), 2),
+            ],
+            ProjectSort::Order,
+        );
+
+        let summary: Vec<(Option<&str>, Vec<&str>)> = tree
+            .iter()
+            .map(|folder| {
+                (
+                    folder.folder_id.as_deref(),
+                    folder
+                        .projects
+                        .iter()
+                        .map(|summary| summary.project.name.as_str())
+                        .collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (Some("g-b"), vec!["b1", "b2"]),
+                (Some("g-a"), vec!["a1"]),
+                (None, vec!["loose", "blank"]),
+            ]
+        );
+        assert_eq!(tree[2].name, "(no folder)");
+        assert!(project_tree(Vec::new(), ProjectSort::Order).is_empty());
+    }
+
+    #[test]
+    fn resolve_folder_id_matches_folders_used_by_projects() {
+        let projects = vec![
+            sample_project(),
+            Project {
+                group_id: Some("Group-1".to_string()),
+                ..sample_project()
+            },
+        ];
+
+        assert_eq!(resolve_folder_id(&projects, "group-1").unwrap(), "Group-1");
+        let err = resolve_folder_id(&projects, "missing")
+            .unwrap_err()
+            .to_string();
+        assert!(err.starts_with("Unknown folder 'missing'. Known folders: Group-1."));
+        assert!(resolve_folder_id(&[sample_project()], "missing")
+            .unwrap_err()
+            .to_string()
+            .contains("No project is in a folder yet."));
+    }
+
+    #[test]
+    fn ungrouping_sends_an_explicit_null_group_id() {
+        let mut project = Project {
+            group_id: Some("group-1".to_string()),
+            ..sample_project()
+        };
+        let kept = project_update_payload(&project, false).unwrap();
+        assert_eq!(kept["groupId"], "group-1");
+
+        project.group_id = None;
+        let cleared = project_update_payload(&project, true).unwrap();
+        assert!(cleared.get("groupId").unwrap().is_null());
+        assert!(project_update_payload(&project, false)
+            .unwrap()
+            .get("groupId")
+            .is_none());
+    }
+
+    #[test]
+    fn project_update_group_conflicts_with_ungroup() {
+        use clap::Parser;
+
+        #[derive(Parser)]
+        struct ProjectUpdateArgsCli {
+            #[command(flatten)]
+            args: ProjectUpdateArgs,
+        }
+
+        let parsed = ProjectUpdateArgsCli::try_parse_from(["tt", "Work", "--ungroup"]).unwrap();
+        assert!(parsed.args.ungroup);
+        assert!(
+            ProjectUpdateArgsCli::try_parse_from(["tt", "Work", "--group", "g", "--ungroup"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn project_move_requires_a_folder_or_none() {
+        use clap::Parser;
+
+        #[derive(Parser)]
+        struct ProjectMoveArgsCli {
+            #[command(flatten)]
+            args: ProjectMoveArgs,
+        }
+
+        assert!(ProjectMoveArgsCli::try_parse_from(["tt", "project-1"]).is_err());
+        assert!(
+            ProjectMoveArgsCli::try_parse_from(["tt", "project-1", "--folder", "g", "--none"])
+                .is_err()
+        );
+        assert!(
+            ProjectMoveArgsCli::try_parse_from(["tt", "project-1", "--none"])
+                .unwrap()
+                .args
+                .none
+        );
+        assert_eq!(
+            format_project_move_output(
+                "Work",
+                None,
+                MessageFormat::Human,
+                &OutputContext::default()
+            )
+            .unwrap(),
+            "Project moved: Work -> no folder\n"
+        );
+    }
 }
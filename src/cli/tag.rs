@@ -1,86 +1,178 @@
-use super::bootstrap::authenticated_client;
-use crate::output::{print_tags, OutputFormat};
-use anyhow::Result;
+use super::task::{retag_tasks, tag_counts, task_list, TaskListArgs};
+use crate::output::{print_tag_counts, MessageFormat, OutputContext, OutputFormat, Verbosity};
+use anyhow::{anyhow, Result};
 use clap::{Args, Subcommand};
+use serde_json::json;
 
 #[derive(Subcommand)]
 pub enum TagCommands {
     Add(TagAddArgs),
+    #[command(alias = "ls")]
     List(TagListArgs),
+    Tasks(Box<TagTasksArgs>),
+    #[command(alias = "mv")]
+    Rename(TagRenameArgs),
+    Merge(TagMergeArgs),
+    #[command(alias = "rm")]
     Delete(TagDeleteArgs),
 }
 
 #[derive(Args)]
-pub struct TagAddArgs {
-    tag: String,
+pub struct TagListArgs {
+    #[arg(
+        long,
+        help = "Only tags whose name contains this text (case-insensitive)"
+    )]
+    contains: Option<String>,
+    /// Counts are always shown now; the flag is still accepted so older scripts parse.
+    #[arg(long, hide = true)]
+    #[allow(dead_code)]
+    with_counts: bool,
     #[arg(long, default_value = "human")]
     output: OutputFormat,
 }
 
-pub async fn tag_add(args: TagAddArgs) -> Result<()> {
-    let _client = authenticated_client()?;
+pub async fn tag_list(args: TagListArgs, context: &OutputContext) -> Result<()> {
+    let counts = tag_counts(args.contains.as_deref()).await?;
+    print_tag_counts(&counts, args.output, context);
+    Ok(())
+}
+
+#[derive(Args)]
+pub struct TagTasksArgs {
+    #[arg(help = "Tag name, with or without the leading #")]
+    tag: String,
+    #[command(flatten)]
+    list: TaskListArgs,
+}
+
+/// `tt tag tasks <tag>` is `tt task list --tags <tag>`, so every list filter and output
+/// option works the same way.
+pub async fn tag_tasks(args: TagTasksArgs, context: &OutputContext) -> Result<()> {
+    task_list(args.list.with_tag(&args.tag), context).await
+}
 
-    println!("Note: Tags are added by including them in task titles or using task update");
-    println!("Tag example: {}", args.tag);
+/// A tag name from the command line: without the leading `#`, and non-empty.
+fn tag_name_arg(value: &str) -> std::result::Result<String, String> {
+    let name = value.trim().trim_start_matches('#');
+    if name.is_empty() || name.chars().any(char::is_whitespace) {
+        return Err(format!("Invalid tag '{}': use a single word", value));
+    }
+    Ok(name.to_string())
+}
 
-    match args.output {
-        OutputFormat::Json => {
-            println!(
-                "{}",
-                serde_json::to_string_pretty(&serde_json::json!({"tag": args.tag}))?
-            );
+#[derive(Args)]
+pub struct TagAddArgs {
+    #[arg(value_parser = tag_name_arg, help = "Tag name, with or without the leading #")]
+    tag: String,
+    #[arg(long, default_value = "human")]
+    output: MessageFormat,
+}
+
+/// There is nothing to create: a tag exists once a task carries it, so this says how.
+pub fn tag_add(args: TagAddArgs, verbosity: Verbosity, context: &OutputContext) -> Result<()> {
+    let rendered = match args.output {
+        MessageFormat::Json => {
+            format!("{}\n", context.to_json_string(&json!({ "tag": args.tag }))?)
         }
-        OutputFormat::Human => {
-            println!(
-                "To use this tag, add it to a task: tt task add 'Buy groceries #{}'",
+        MessageFormat::Human => {
+            format!(
+                "Tags live on tasks. To use #{0}, add it to one: tt task add 'Buy groceries #{0}'\n",
                 args.tag
-            );
+            )
         }
-    }
-
+    };
+    verbosity.confirm(&rendered, args.output);
     Ok(())
 }
 
 #[derive(Args)]
-pub struct TagListArgs {
-    #[arg(long)]
-    contains: Option<String>,
-    #[arg(long, default_value = "true")]
-    with_counts: bool,
+pub struct TagRenameArgs {
+    #[arg(value_parser = tag_name_arg, help = "Tag to rename (matched ignoring case)")]
+    old: String,
+    #[arg(value_parser = tag_name_arg, help = "New tag name; merged if a task already has it")]
+    new: String,
+    #[arg(long, help = "List the tasks that would change without updating them")]
+    dry_run: bool,
     #[arg(long, default_value = "human")]
-    output: OutputFormat,
+    output: MessageFormat,
 }
 
-pub async fn tag_list(args: TagListArgs) -> Result<()> {
-    let client = authenticated_client()?;
+/// Rewrites the tag on every open task that carries it, one update per task.
+pub async fn tag_rename(args: TagRenameArgs, context: &OutputContext) -> Result<()> {
+    if args.old == args.new {
+        return Err(anyhow!("#{} already has that name", args.old));
+    }
+    retag_tasks(
+        "tag rename",
+        std::slice::from_ref(&args.old),
+        Some(&args.new),
+        args.dry_run,
+        false,
+        args.output,
+        context,
+    )
+    .await
+}
 
-    let mut tags = client.get_tags().await?;
+#[derive(Args)]
+pub struct TagMergeArgs {
+    #[arg(value_parser = tag_name_arg, help = "Tag to fold in and drop (matched ignoring case)")]
+    from: String,
+    #[arg(value_parser = tag_name_arg, help = "Tag to keep")]
+    to: String,
+    #[arg(long, help = "List the tasks that would change without updating them")]
+    dry_run: bool,
+    #[arg(long, default_value = "human")]
+    output: MessageFormat,
+}
 
-    if let Some(contains) = args.contains {
-        tags.retain(|t| t.contains(&contains));
+/// Swaps `from` for `to` on every open task; tasks that had both just lose `from`. Tasks
+/// already merged no longer carry `from`, so re-running after failures only retries those.
+pub async fn tag_merge(args: TagMergeArgs, context: &OutputContext) -> Result<()> {
+    if args.from.eq_ignore_ascii_case(&args.to) {
+        return Err(anyhow!("Cannot merge #{} into itself", args.from));
     }
-
-    print_tags(&tags, args.output);
-    Ok(())
+    retag_tasks(
+        "tag merge",
+        std::slice::from_ref(&args.from),
+        Some(&args.to),
+        args.dry_run,
+        false,
+        args.output,
+        context,
+    )
+    .await
 }
 
 #[derive(Args)]
 pub struct TagDeleteArgs {
+    #[arg(value_parser = tag_name_arg, help = "Tag to remove (matched ignoring case)")]
     tag: String,
-    #[arg(long)]
+    #[arg(
+        long,
+        visible_alias = "yes",
+        short = 'y',
+        help = "Skip the confirmation prompt"
+    )]
     force: bool,
+    #[arg(long, help = "List the tasks that would change without updating them")]
+    dry_run: bool,
+    #[arg(long, default_value = "human")]
+    output: MessageFormat,
 }
 
-pub async fn tag_delete(args: TagDeleteArgs) -> Result<()> {
-    let _client = authenticated_client()?;
-
-    if args.force {
-        println!("Tag deletion is not directly supported by the API.");
-        println!("To remove a tag, update tasks that use it.");
-    } else {
-        println!("Tag deletion is not directly supported by the API.");
-        println!("To remove a tag, update tasks that use it.");
-    }
-
-    Ok(())
+/// TickTick has no tag endpoint: a tag exists while some task carries it, so deleting one
+/// strips it from every open task after asking once for the whole batch.
+pub async fn tag_delete(args: TagDeleteArgs, context: &OutputContext) -> Result<()> {
+    retag_tasks(
+        "tag delete",
+        std::slice::from_ref(&args.tag),
+        None,
+        args.dry_run,
+        !args.force,
+        args.output,
+        context,
+    )
+    .await
 }
@@ -1,33 +1,111 @@
+mod agenda;
+mod batch;
 mod dates;
+mod editor;
 mod filters;
+mod format;
+mod groups;
+mod import;
+mod items;
+mod offline;
+mod picker;
+mod postpone;
 mod projects;
+mod reminders;
+mod repeat;
+mod search;
+mod stats;
+mod tags;
+mod template;
+mod watch;
 
 #[cfg(test)]
 mod tests;
 
+pub use self::agenda::{today, upcoming, AgendaArgs, UpcomingArgs};
+use self::batch::{
+    apply_batch_update, batch_update_failed_ids, batch_update_failures, format_batch_update_report,
+    parse_task_ids, BatchUpdatePlan, BatchUpdateResult, BatchUpdateStatus,
+};
 use self::dates::{
-    extract_due_date_from_input, format_ticktick_due_date, normalize_task_datetime_input,
-    task_matches_when_filter, TaskWhenFilter,
+    extract_due_datetime_from_input, format_ticktick_datetime, format_ticktick_due_date,
+    format_ticktick_local_datetime, normalize_task_datetime_input, parse_due_filter_value,
+    parse_due_format, parse_modified_after_value, sort_tasks, task_completed_at,
+    task_matches_due_range, task_matches_when_filter, task_modified_since, tasks_completed_since,
+    DueFormat, TaskSort, TaskWhenFilter,
 };
+use self::editor::edit_task_in_editor;
+pub(crate) use self::filters::priority_title;
 use self::filters::{
-    extract_implicit_list_from_terms, is_inbox_list_name, merge_tags, parse_priority_value,
-    parse_shorthand, parse_task_add_shorthand, parse_task_status_value, task_has_all_tags,
+    apply_tag_changes, compile_task_list_regex, extract_implicit_list_from_terms,
+    is_inbox_list_name, merge_tags, parse_list_regex, parse_priority_change, parse_priority_value,
+    parse_shorthand, parse_tag_change, parse_task_add_shorthand, parse_task_status_value,
+    priority_name, step_priority, task_has_all_tags, task_has_any_tag, task_meets_priority,
+    task_search_haystack, PriorityChange, TagChange,
+};
+use self::format::{parse_task_format, render_formatted_tasks, TaskFormat};
+use self::groups::{group_tasks, task_project_label, TaskGroupBy};
+use self::import::{
+    format_import_report, import_failures, parse_task_import_file, task_from_import, ImportResult,
+    ImportStatus,
+};
+use self::items::{
+    complete_checklist_item, find_checklist_item, format_checklist_lines, new_checklist_items,
+    push_checklist_item,
+};
+pub(crate) use self::offline::TaskSource;
+use self::offline::{snapshot_list_project_id, snapshot_task, snapshot_tasks_in_scope};
+pub use self::offline::{sync, SyncArgs};
+use self::picker::{missing_task_id, pick_task};
+use self::postpone::{
+    defer_task, describe_task_due, local_task_date, parse_defer_spec, parse_postpone_target,
+    postpone_task, DeferSpec,
 };
 use self::projects::{
-    cache_store, forget_task_project_id, get_tasks_across_projects, get_tasks_for_project,
-    infer_default_project_id, remember_task, remember_task_project_id, remember_tasks,
-    resolve_project_id, resolve_task_project_id,
+    dedupe_tasks_by_id, default_project_config, forget_default_project_id, forget_task_project_id,
+    get_completed_tasks_in_scope, get_tasks_across_projects, get_tasks_for_project,
+    get_tasks_in_scope, infer_default_project_id, normalize_project_id, record_operation,
+    remember_task, remember_task_project_id, remember_tasks, resolve_create_project_id,
+    resolve_project_id, resolve_task_project_id, ListScope,
 };
-use super::bootstrap::authenticated_client;
-use crate::models::{Task, TaskStatus};
-use crate::output::{print_tasks, OutputFormat};
-use anyhow::{anyhow, Result};
+use self::reminders::{
+    describe_reminder, extract_reminder_from_input, parse_reminder_at, parse_reminder_value,
+    reminder_at_trigger, ReminderAt,
+};
+use self::repeat::{
+    describe_repeat_flag, extract_repeat_from_input, parse_repeat_value, validate_rrule,
+};
+use self::search::parse_search_query;
+pub(crate) use self::stats::{format_project_stats, summarize_project};
+use self::stats::{format_task_stats, task_stats};
+pub(crate) use self::tags::count_tags;
+use self::tags::{filter_tag_counts, replace_task_tags, save_retagged_tasks};
+use self::template::{load_template, resolve_template_date, task_value_from_template};
+pub use self::template::{template_delete, template_list, template_save, TemplateCommands};
+use self::watch::{parse_watch_interval, watch_task_list};
+use super::lists::resolve_column_arg;
+use super::weblink::{open_or_print, task_web_url};
+use crate::api::{is_not_found, TickTickClient};
+use crate::cache::{get_projects_cached, journal_enabled, CacheStore, JournalOperation};
+use crate::config::AppConfig;
+use crate::models::{ChecklistItem, Task, TaskStatus};
+use crate::output::{
+    due_ansi, paint, print_batch_add_results, print_task_search_results, priority_ansi,
+    render_task_groups, render_task_markdown, render_tasks, BatchAddResult, MessageFormat,
+    OutputContext, OutputFormat, TableLayout, TagCount, TaskListOptions, TaskSearchResult,
+    Verbosity,
+};
+use anyhow::{anyhow, Context, Result};
 use atty::Stream;
-use chrono::Local;
+use chrono::{Local, NaiveDate, Utc};
 use clap::{Args, Subcommand};
 use iana_time_zone::get_timezone;
+use regex::Regex;
 use serde_json::Value;
+use std::collections::HashMap;
 use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 #[derive(Subcommand)]
 pub enum TaskCommands {
@@ -35,14 +113,51 @@ pub enum TaskCommands {
     Add(TaskAddArgs),
     #[command(alias = "ls")]
     List(TaskListArgs),
+    #[command(alias = "stats")]
+    Count(TaskListArgs),
     #[command(aliases = ["get", "show"])]
     Info(TaskInfoArgs),
     #[command(alias = "edit")]
     Update(TaskUpdateArgs),
     #[command(alias = "done")]
     Complete(TaskCompleteArgs),
+    #[command(aliases = ["reopen", "undone"])]
+    Uncomplete(TaskCompleteArgs),
     #[command(aliases = ["rm", "del"])]
     Delete(TaskDeleteArgs),
+    #[command(alias = "find")]
+    Search(TaskSearchArgs),
+    #[command(alias = "snooze")]
+    Postpone(TaskPostponeArgs),
+    #[command(aliases = ["copy", "dup"])]
+    Duplicate(TaskDuplicateArgs),
+    #[command(alias = "bump")]
+    Defer(TaskDeferArgs),
+    #[command(alias = "priority")]
+    Prio(TaskPrioArgs),
+    #[command(alias = "tags")]
+    Tag(TaskTagArgs),
+    #[command(alias = "bulk-update")]
+    BatchUpdate(TaskBatchUpdateArgs),
+    #[command(aliases = ["web", "browse"])]
+    Open(TaskOpenArgs),
+    #[command(aliases = ["item", "checklist", "subtasks"])]
+    Items {
+        #[command(subcommand)]
+        subcommand: TaskItemCommands,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum TaskItemCommands {
+    #[command(alias = "new")]
+    Add(TaskItemAddArgs),
+    #[command(alias = "ls")]
+    List(TaskItemListArgs),
+    #[command(alias = "complete")]
+    Done(TaskItemTargetArgs),
+    #[command(aliases = ["delete", "del"])]
+    Rm(TaskItemTargetArgs),
 }
 
 fn resolve_task_note_fields(
@@ -56,6 +171,55 @@ fn resolve_task_note_fields(
     }
 }
 
+/// `--content-file`/`--desc-file`: a file's contents, or stdin for `-`, without the
+/// trailing newline editors add.
+fn read_note_file(path: &Path) -> Result<String> {
+    let mut contents = if path == Path::new("-") {
+        let mut buffer = String::new();
+        io::stdin()
+            .read_to_string(&mut buffer)
+            .context("Failed to read the note from stdin")?;
+        buffer
+    } else {
+        std::fs::read_to_string(path)
+            .map_err(|err| anyhow!("Failed to read {}: {}", path.display(), err))?
+    };
+    let trimmed = contents.trim_end_matches(['\n', '\r']).len();
+    contents.truncate(trimmed);
+    Ok(contents)
+}
+
+/// Fills `content`/`desc` from their `--*-file` flags; clap already rejects a flag given
+/// with its file form. Only one of them can read stdin.
+fn read_task_note_files(
+    content: &mut Option<String>,
+    content_file: Option<&Path>,
+    desc: &mut Option<String>,
+    desc_file: Option<&Path>,
+) -> Result<()> {
+    if content_file.is_some_and(|path| path == Path::new("-"))
+        && desc_file.is_some_and(|path| path == Path::new("-"))
+    {
+        return Err(anyhow!(
+            "--content-file and --desc-file cannot both read stdin ('-')"
+        ));
+    }
+    if let Some(path) = content_file {
+        *content = Some(read_note_file(path)?);
+    }
+    if let Some(path) = desc_file {
+        *desc = Some(read_note_file(path)?);
+    }
+    Ok(())
+}
+
+fn note_file_reads_stdin(content_file: Option<&Path>, desc_file: Option<&Path>) -> bool {
+    [content_file, desc_file]
+        .into_iter()
+        .flatten()
+        .any(|path| path == Path::new("-"))
+}
+
 fn sync_task_note_fields(task: &mut Task) {
     match (&task.content, &task.desc) {
         (Some(content), None) => {
@@ -86,6 +250,8 @@ fn apply_system_time_zone_default(task: &mut Task) -> Result<()> {
 
 #[derive(Debug, Default, Clone, Copy)]
 struct TaskUpdateClearFlags {
+    content: bool,
+    desc: bool,
     start_date: bool,
     due_date: bool,
     time_zone: bool,
@@ -95,20 +261,67 @@ struct TaskUpdateClearFlags {
     sort_order: bool,
 }
 
-#[derive(Args)]
+impl TaskUpdateClearFlags {
+    /// Clears every field `previous` did not have, so restoring it also undoes values
+    /// an update added.
+    fn restoring(previous: &Task) -> Self {
+        Self {
+            content: previous.content.is_none(),
+            desc: previous.desc.is_none(),
+            start_date: previous.start_date.is_none(),
+            due_date: previous.due_date.is_none(),
+            time_zone: previous.time_zone.is_none(),
+            tags: previous.tags.is_none(),
+            reminders: previous.reminders.is_none(),
+            repeat_flag: previous.repeat_flag.is_none(),
+            sort_order: previous.sort_order.is_none(),
+        }
+    }
+}
+
+#[derive(Args, Clone)]
 pub struct TaskAddArgs {
+    #[arg(
+        help = "Task title; a trailing date/time like 'tomorrow at 3pm' sets the due date. A bare 'at 9' means 9am ('at 15' is 3pm); a time alone means today"
+    )]
     title: Vec<String>,
+    #[arg(
+        long,
+        visible_alias = "no-infer-date",
+        help = "Keep the title word for word: no date, time, reminder, or repeat phrases and no !priority/~list/#tag shorthand (flags still apply)"
+    )]
+    literal: bool,
     #[arg(long, help = "Visible task note shown in TickTick")]
     content: Option<String>,
+    #[arg(
+        long,
+        value_name = "PATH",
+        conflicts_with_all = ["content", "stdin", "batch"],
+        help = "Read the note from a file ('-' for stdin; give the title as arguments)"
+    )]
+    content_file: Option<PathBuf>,
     #[arg(
         long,
         help = "Secondary TickTick API description field; mirrored to content when used alone"
     )]
     desc: Option<String>,
+    #[arg(
+        long,
+        value_name = "PATH",
+        conflicts_with_all = ["desc", "stdin", "batch"],
+        help = "Read the desc field from a file ('-' for stdin)"
+    )]
+    desc_file: Option<PathBuf>,
     #[arg(long)]
     project_id: Option<String>,
     #[arg(long)]
     list: Option<String>,
+    #[arg(
+        long,
+        conflicts_with_all = ["project_id", "list"],
+        help = "Create the task in the Inbox (same as ~inbox in the title)"
+    )]
+    inbox: bool,
     #[arg(long, value_parser = normalize_task_datetime_input)]
     start_date: Option<String>,
     #[arg(long, value_parser = normalize_task_datetime_input)]
@@ -117,26 +330,162 @@ pub struct TaskAddArgs {
     time_zone: Option<String>,
     #[arg(long)]
     all_day: Option<bool>,
-    #[arg(long, value_parser = parse_priority_value)]
+    #[arg(
+        long,
+        value_parser = parse_priority_value,
+        help = "Priority: none, low, medium, high (or 0, 1, 3, 5)"
+    )]
     priority: Option<i32>,
     #[arg(long)]
     tags: Vec<String>,
-    #[arg(long)]
+    #[arg(
+        long,
+        value_parser = parse_reminder_value,
+        help = "Reminder before the due time: 0/at-time, 30m, 1h, 1d, 1h30m (repeatable)"
+    )]
     reminders: Vec<String>,
-    #[arg(long)]
+    #[arg(
+        long,
+        value_name = "TIME",
+        value_parser = parse_reminder_at,
+        conflicts_with_all = ["template", "from_file"],
+        help = "Remind at a time on the due date (9am, 21:15) or at a date and time (2026-10-14T08:00); repeatable"
+    )]
+    reminder_at: Vec<ReminderAt>,
+    #[arg(
+        long,
+        value_name = "NAME",
+        conflicts_with_all = ["template", "from_file", "batch"],
+        help = "Put the task in this board column (by name)"
+    )]
+    column: Option<String>,
+    #[arg(
+        long,
+        value_name = "TASK_ID",
+        conflicts_with_all = ["template", "from_file", "batch"],
+        help = "Make the task a subtask of this one; it goes in the parent's list unless a list is given"
+    )]
+    parent: Option<String>,
+    #[arg(long, value_parser = validate_rrule, conflicts_with = "repeat")]
     repeat_flag: Option<String>,
+    #[arg(
+        long,
+        value_parser = parse_repeat_value,
+        help = "Recurrence such as daily, every monday, every 2 weeks, every weekday, or monthly on the 15th"
+    )]
+    repeat: Option<String>,
     #[arg(long)]
     sort_order: Option<i64>,
+    #[arg(
+        long = "item",
+        help = "Checklist item to create with the task (repeatable)"
+    )]
+    items: Vec<String>,
     #[arg(long)]
     stdin: bool,
+    #[arg(
+        long,
+        conflicts_with = "title",
+        help = "Read stdin as one task per line ('#' lines are skipped); flags apply to every task"
+    )]
+    batch: bool,
+    #[arg(
+        long,
+        value_name = "PATH",
+        conflicts_with_all = ["title", "stdin", "batch", "template"],
+        help = "Create every task in a JSON or TOML file; --list/--project-id set the default list"
+    )]
+    from_file: Option<PathBuf>,
+    #[arg(
+        long,
+        requires = "from_file",
+        help = "Validate the file and resolve lists without creating anything"
+    )]
+    dry_run: bool,
+    #[arg(
+        long,
+        value_name = "NAME",
+        conflicts_with = "batch",
+        help = "Start from a saved template; a title and shorthand given here override it"
+    )]
+    template: Option<String>,
     #[arg(long, default_value = "human")]
     output: OutputFormat,
 }
 
-pub async fn task_add(args: TaskAddArgs) -> Result<()> {
+/// Parses `tt <words...>` (no subcommand) as `tt add <words...>`. A lone bare word is
+/// refused so a mistyped command such as `tt lst` never becomes a task.
+pub fn quick_add_args(words: Vec<String>) -> Result<TaskAddArgs> {
+    #[derive(clap::Parser)]
+    #[command(name = "tt", no_binary_name = true)]
+    struct QuickAddCli {
+        #[command(flatten)]
+        args: TaskAddArgs,
+    }
+
+    if let [word] = words.as_slice() {
+        if !word.trim().contains(char::is_whitespace) && parse_shorthand(word).terms.len() == 1 {
+            return Err(anyhow!(
+                "Unknown command '{}'. To add a task with that title, run 'tt add {}'.",
+                word,
+                word
+            ));
+        }
+    }
+    let parsed = <QuickAddCli as clap::Parser>::try_parse_from(words).map_err(|err| {
+        let rendered = err.render().to_string();
+        anyhow!("{}", rendered.trim().trim_start_matches("error: "))
+    })?;
+    Ok(parsed.args)
+}
+
+pub async fn task_add(
+    args: TaskAddArgs,
+    verbosity: Verbosity,
+    context: &OutputContext,
+) -> Result<()> {
     let mut args = args;
-    let client = authenticated_client()?;
-    let cache = cache_store();
+    if note_file_reads_stdin(args.content_file.as_deref(), args.desc_file.as_deref())
+        && args.title.is_empty()
+        && args.from_file.is_none()
+        && args.template.is_none()
+    {
+        return Err(anyhow!(
+            "Give the task title as arguments when the note is read from stdin ('-')"
+        ));
+    }
+    let (content_file, desc_file) = (args.content_file.take(), args.desc_file.take());
+    read_task_note_files(
+        &mut args.content,
+        content_file.as_deref(),
+        &mut args.desc,
+        desc_file.as_deref(),
+    )?;
+    let (client, cache) = TaskSource::online("task add")?;
+    let defaults = default_project_config();
+
+    if args.batch {
+        return task_add_batch(&client, cache.as_ref(), defaults.as_ref(), args, context).await;
+    }
+    // Only --batch lists rows; every other path reports what it created.
+    let output = MessageFormat::for_command(args.output, "task add")?;
+    if let Some(path) = args.from_file.take() {
+        return task_add_from_file(
+            &client,
+            cache.as_ref(),
+            defaults.as_ref(),
+            &path,
+            args,
+            output,
+            context,
+        )
+        .await;
+    }
+    if let Some(name) = args.template.take() {
+        let created =
+            task_add_from_template(&client, cache.as_ref(), defaults.as_ref(), &name, args).await?;
+        return print_created_task(&created, output, verbosity, context);
+    }
 
     let raw_input = if args.stdin || (!atty::is(Stream::Stdin) && args.title.is_empty()) {
         let mut buffer = String::new();
@@ -146,51 +495,206 @@ pub async fn task_add(args: TaskAddArgs) -> Result<()> {
         args.title.join(" ")
     };
 
+    let title = apply_task_add_input(&mut args, &raw_input, Local::now().date_naive())?;
+    if title.is_empty() {
+        return Err(anyhow!("Task title required or provide stdin"));
+    }
+    let parent = match args.parent.as_deref() {
+        Some(parent_id) => {
+            Some(fetch_task_with_project(&client, cache.as_ref(), parent_id, None, None).await?)
+        }
+        None => None,
+    };
+    let (inbox, explicit_id, list) = (args.inbox, args.project_id.clone(), args.list.clone());
+    let resolve = || async {
+        match resolve_create_project_id(
+            &client,
+            cache.as_ref(),
+            defaults.as_ref(),
+            inbox,
+            explicit_id.clone(),
+            list.clone(),
+        )
+        .await?
+        {
+            Some(project_id) => Ok::<_, anyhow::Error>(project_id),
+            None => match parent.as_ref() {
+                Some((_, parent_project_id)) => Ok(parent_project_id.clone()),
+                None => infer_default_project_id(&client, cache.as_ref(), defaults.as_ref()).await,
+            },
+        }
+    };
+    let mut project_id = resolve().await?;
+
+    let column_id = match args.column.as_deref() {
+        Some(name) => Some(resolve_column_arg(&client, &project_id, name).await?),
+        None => None,
+    };
+    let mut task = task_from_add_args(args, title, &project_id)?;
+    task.column_id = column_id;
+    let created = match client.create_task(&task).await {
+        // A cached default list that has since been deleted: look it up again once.
+        Err(err)
+            if is_not_found(&err) && forget_default_project_id(defaults.as_ref(), &project_id) =>
+        {
+            project_id = resolve().await?;
+            task.project_id = Some(project_id.clone());
+            client.create_task(&task).await?
+        }
+        result => result?,
+    };
+    remember_task(cache.as_ref(), &created, Some(&project_id));
+    print_created_task(&created, output, verbosity, context)?;
+    if let Some((parent, _)) = parent.as_ref() {
+        if output == MessageFormat::Human {
+            verbosity.confirm(&format_subtask_of(parent), output);
+        }
+    }
+    Ok(())
+}
+
+/// The line under "Task created" for `tt task add --parent`.
+fn format_subtask_of(parent: &Task) -> String {
+    format!(
+        "Subtask of: {} ({})\n",
+        parent.title,
+        parent.id.as_deref().unwrap_or_default()
+    )
+}
+
+fn print_created_task(
+    created: &Task,
+    output: MessageFormat,
+    verbosity: Verbosity,
+    context: &OutputContext,
+) -> Result<()> {
+    if verbosity.is_quiet() && output == MessageFormat::Human {
+        println!("{}", created.id.as_deref().unwrap_or_default());
+    } else {
+        print!("{}", format_task_create_output(created, output, context)?);
+    }
+    Ok(())
+}
+
+async fn task_add_from_template(
+    client: &TickTickClient,
+    cache: Option<&CacheStore>,
+    defaults: Option<&AppConfig>,
+    name: &str,
+    mut args: TaskAddArgs,
+) -> Result<Task> {
+    let template = load_template(name)?;
     let today = Local::now().date_naive();
-    let (input_without_due_date, inferred_due_date) =
-        extract_due_date_from_input(&raw_input, today);
+    let raw_input = args.title.join(" ");
+    let title = apply_task_add_input(&mut args, &raw_input, today)?;
+    let (mut task, template_list) = task_value_from_template(&template, &args, &title, today)?;
+
+    let template_project_id = task
+        .get("projectId")
+        .and_then(Value::as_str)
+        .map(str::to_string);
+    let project_id = match resolve_create_project_id(
+        client,
+        cache,
+        defaults,
+        args.inbox,
+        args.project_id.clone(),
+        args.list.clone().or(template_list),
+    )
+    .await?
+    {
+        Some(project_id) => project_id,
+        None => match template_project_id {
+            Some(project_id) => project_id,
+            None => infer_default_project_id(client, cache, defaults).await?,
+        },
+    };
+    task.insert("projectId".to_string(), Value::String(project_id.clone()));
+
+    let mut probe: Task = serde_json::from_value(Value::Object(task.clone()))
+        .context("Template is not a valid task")?;
+    apply_system_time_zone_default(&mut probe)?;
+    if let Some(time_zone) = probe.time_zone {
+        task.insert("timeZone".to_string(), Value::String(time_zone));
+    }
+
+    let created = client.create_task(&task).await?;
+    remember_task(cache, &created, Some(&project_id));
+    Ok(created)
+}
+
+/// Folds the reminder, recurrence, due date, and `!priority`/`~list`/`#tag` shorthand in
+/// `raw_input` into `args` (explicit flags win) and returns the remaining title, which
+/// may be empty. With `--literal` nothing is read from the input: it is all title.
+fn apply_task_add_input(
+    args: &mut TaskAddArgs,
+    raw_input: &str,
+    today: NaiveDate,
+) -> Result<String> {
+    if args.literal {
+        if args.repeat_flag.is_none() {
+            args.repeat_flag = args.repeat.take();
+        }
+        return Ok(raw_input.trim().to_string());
+    }
+
+    let (input_without_reminder, inferred_reminder) = extract_reminder_from_input(raw_input);
+    args.reminders.extend(inferred_reminder);
+
+    // Recurrence goes first so "every monday" is not read as a one-off due date.
+    let (input_without_repeat, inferred_repeat) =
+        extract_repeat_from_input(&input_without_reminder);
+    if args.repeat_flag.is_none() {
+        args.repeat_flag = args.repeat.take().or(inferred_repeat);
+    }
+
+    let (input_without_due_date, inferred_due_date, inferred_due_time) =
+        extract_due_datetime_from_input(&input_without_repeat, today);
     let shorthand = parse_task_add_shorthand(&input_without_due_date);
 
     if args.priority.is_none() {
         args.priority = shorthand.priority;
     }
-    if args.list.is_none() {
-        args.list = shorthand.list;
+    match shorthand.list {
+        Some(list) if is_inbox_list_name(&list) => {
+            args.inbox |= args.project_id.is_none() && args.list.is_none();
+        }
+        list => {
+            if args.list.is_none() && !args.inbox {
+                args.list = list;
+            }
+        }
     }
     if args.due_date.is_none() {
         if let Some(date) = inferred_due_date {
-            let formatted = format_ticktick_due_date(date)
-                .ok_or_else(|| anyhow!("Failed to format inferred due date '{}'", date))?;
+            let formatted = match inferred_due_time {
+                Some(time) => format_ticktick_local_datetime(date.and_time(time)),
+                None => format_ticktick_due_date(date),
+            }
+            .ok_or_else(|| anyhow!("Failed to format inferred due date '{}'", date))?;
             args.due_date = Some(formatted.clone());
             if args.start_date.is_none() {
                 args.start_date = Some(formatted);
             }
             if args.all_day.is_none() {
-                args.all_day = Some(true);
+                args.all_day = Some(inferred_due_time.is_none());
             }
         }
     }
     merge_tags(&mut args.tags, shorthand.tags);
 
-    let title = shorthand.terms.join(" ").trim().to_string();
-    if title.is_empty() {
-        return Err(anyhow!("Task title required or provide stdin"));
-    }
-
-    let project_id =
-        match resolve_project_id(&client, cache.as_ref(), args.project_id, args.list).await? {
-            Some(project_id) => project_id,
-            None => infer_default_project_id(&client, cache.as_ref()).await?,
-        };
+    Ok(shorthand.terms.join(" ").trim().to_string())
+}
 
+fn task_from_add_args(args: TaskAddArgs, title: String, project_id: &str) -> Result<Task> {
     let (content, desc) = resolve_task_note_fields(args.content, args.desc);
 
-    let task = Task {
+    let mut task = Task {
         id: None,
         title,
         content,
         desc,
-        project_id: Some(project_id.clone()),
+        project_id: Some(project_id.to_string()),
         start_date: args.start_date,
         due_date: args.due_date,
         time_zone: args.time_zone,
@@ -208,519 +712,2274 @@ pub async fn task_add(args: TaskAddArgs) -> Result<()> {
         },
         repeat_flag: args.repeat_flag,
         sort_order: args.sort_order,
-        kind: Some("TASK".to_string()),
+        parent_id: args.parent,
+        items: new_checklist_items(&args.items)?,
+        kind: Some(
+            if args.items.is_empty() {
+                "TASK"
+            } else {
+                "CHECKLIST"
+            }
+            .to_string(),
+        ),
         ..Default::default()
     };
-    let mut task = task;
+    add_reminders_at(&mut task, &args.reminder_at)?;
     sync_task_note_fields(&mut task);
     apply_system_time_zone_default(&mut task)?;
+    Ok(task)
+}
 
-    let created = client.create_task(&task).await?;
-    remember_task(cache.as_ref(), &created, Some(&project_id));
-
-    print!("{}", format_task_create_output(&created, args.output)?);
-
+/// Appends a reminder for each `--reminder-at`, measured from the task's due date.
+fn add_reminders_at(task: &mut Task, reminder_at: &[ReminderAt]) -> Result<()> {
+    for at in reminder_at {
+        let trigger = reminder_at_trigger(*at, task.due_date.as_deref(), &Local)?;
+        let reminders = task.reminders.get_or_insert_with(Vec::new);
+        if !reminders.contains(&trigger) {
+            reminders.push(trigger);
+        }
+    }
     Ok(())
 }
 
-#[derive(Args)]
-pub struct TaskListArgs {
-    #[arg(long)]
-    project_id: Option<String>,
-    #[arg(long)]
-    list: Option<String>,
-    #[arg(long)]
-    status: Option<String>,
-    #[arg(long, value_parser = parse_priority_value)]
-    priority: Option<i32>,
-    #[arg(long)]
-    tags: Vec<String>,
-    #[arg(long, value_enum)]
-    when: Option<TaskWhenFilter>,
-    #[arg(long, default_value = "0")]
-    limit: usize,
-    #[arg(long, default_value = "human")]
-    output: OutputFormat,
-    query: Vec<String>,
+/// Non-empty stdin lines that are not `#` comments, with their 1-based line numbers.
+fn batch_input_lines(input: &str) -> Vec<(usize, &str)> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .collect()
 }
 
-pub async fn task_list(args: TaskListArgs) -> Result<()> {
-    let mut args = args;
-    let client = authenticated_client()?;
-    let cache = cache_store();
-
-    let shorthand = parse_shorthand(&args.query.join(" "));
-    if args.priority.is_none() {
-        args.priority = shorthand.priority;
-    }
-    if args.project_id.is_none() && args.list.is_none() {
-        args.list = shorthand.list;
-    }
-    if args.when.is_none() {
-        args.when = shorthand.when;
-    }
-    merge_tags(&mut args.tags, shorthand.tags);
-    let mut search_terms = shorthand.terms;
-
-    if args.project_id.is_none() && args.list.is_none() {
-        if let Some(list_name) = extract_implicit_list_from_terms(&mut search_terms) {
-            args.list = Some(list_name);
-        }
-    }
-
-    if args.project_id.is_none()
-        && args.list.is_none()
-        && search_terms.len() == 1
-        && search_terms
-            .first()
-            .is_some_and(|term| is_inbox_list_name(term))
-    {
-        args.list = search_terms.pop();
+async fn task_add_batch(
+    client: &TickTickClient,
+    cache: Option<&CacheStore>,
+    defaults: Option<&AppConfig>,
+    args: TaskAddArgs,
+    context: &OutputContext,
+) -> Result<()> {
+    let mut input = String::new();
+    io::stdin().read_to_string(&mut input)?;
+    let lines = batch_input_lines(&input);
+    if lines.is_empty() {
+        return Err(anyhow!("No tasks on stdin"));
     }
 
-    let inbox_only =
-        args.project_id.is_none() && args.list.as_deref().is_some_and(is_inbox_list_name);
-
-    let project_id = if inbox_only {
-        None
-    } else {
-        resolve_project_id(&client, cache.as_ref(), args.project_id, args.list.clone()).await?
-    };
-
-    let mut tasks = if inbox_only {
-        get_tasks_for_project(&client, "").await?
-    } else if let Some(ref project_id) = project_id {
-        get_tasks_for_project(&client, project_id).await?
-    } else {
-        get_tasks_across_projects(&client, cache.as_ref()).await?
-    };
-    remember_tasks(cache.as_ref(), &tasks, project_id.as_deref());
-
-    if let Some(status) = args.status {
-        let normalized = status.to_ascii_lowercase();
-        let is_done = match normalized.as_str() {
-            "done" | "completed" | "complete" => true,
-            "todo" | "open" | "normal" | "active" => false,
-            _ => {
-                return Err(anyhow!(
-                    "Unsupported status '{}'. Use one of: done, completed, todo, open",
-                    status
-                ));
+    let today = Local::now().date_naive();
+    let mut resolved_projects: HashMap<(bool, Option<String>, Option<String>), String> =
+        HashMap::new();
+    let mut results = Vec::with_capacity(lines.len());
+
+    for (line, raw_input) in lines {
+        let mut line_args = args.clone();
+        let outcome = async {
+            let title = apply_task_add_input(&mut line_args, raw_input, today)?;
+            if title.is_empty() {
+                return Err(anyhow!("Task title required"));
             }
-        };
+            let key = (
+                line_args.inbox,
+                line_args.project_id.clone(),
+                line_args.list.as_deref().map(str::to_lowercase),
+            );
+            let project_id = match resolved_projects.get(&key) {
+                Some(project_id) => project_id.clone(),
+                None => {
+                    let resolved = match resolve_create_project_id(
+                        client,
+                        cache,
+                        defaults,
+                        line_args.inbox,
+                        line_args.project_id.clone(),
+                        line_args.list.clone(),
+                    )
+                    .await?
+                    {
+                        Some(project_id) => project_id,
+                        None => infer_default_project_id(client, cache, defaults).await?,
+                    };
+                    resolved_projects.insert(key, resolved.clone());
+                    resolved
+                }
+            };
 
-        tasks.retain(|task| {
-            if is_done {
-                task_is_completed(task)
-            } else {
-                !task_is_completed(task)
-            }
+            let task = task_from_add_args(line_args, title, &project_id)?;
+            let created = client.create_task(&task).await?;
+            remember_task(cache, &created, Some(&project_id));
+            Ok::<_, anyhow::Error>(created)
+        }
+        .await;
+
+        results.push(match outcome {
+            Ok(created) => BatchAddResult {
+                line,
+                title: created.title,
+                id: created.id,
+                project_id: created.project_id,
+                error: None,
+            },
+            Err(err) => BatchAddResult {
+                line,
+                title: raw_input.to_string(),
+                id: None,
+                project_id: None,
+                error: Some(err.to_string()),
+            },
         });
     }
 
-    if let Some(prio) = args.priority {
-        tasks.retain(|task| task.priority.unwrap_or(0) == prio);
-    }
+    print_batch_add_results(&results, args.output, context);
 
-    if !args.tags.is_empty() {
-        tasks.retain(|task| task_has_all_tags(task, &args.tags));
+    let failed = results
+        .iter()
+        .filter(|result| result.error.is_some())
+        .count();
+    if failed > 0 {
+        return Err(anyhow!("{} of {} tasks failed", failed, results.len()));
     }
+    Ok(())
+}
 
-    if let Some(when) = args.when {
-        let today = Local::now().date_naive();
-        tasks.retain(|task| task_matches_when_filter(task, when, today));
-    }
+async fn task_add_from_file(
+    client: &TickTickClient,
+    cache: Option<&CacheStore>,
+    defaults: Option<&AppConfig>,
+    path: &std::path::Path,
+    args: TaskAddArgs,
+    output: MessageFormat,
+    context: &OutputContext,
+) -> Result<()> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|err| anyhow!("Failed to read {}: {}", path.display(), err))?;
+    let entries = parse_task_import_file(path, &contents)?;
+
+    // Entries usually share a handful of lists; resolve each name once.
+    let mut resolved_projects: HashMap<(bool, Option<String>, Option<String>), String> =
+        HashMap::new();
+    let mut results = Vec::with_capacity(entries.len());
+
+    for (index, entry) in entries.iter().enumerate() {
+        // An entry naming its own list overrides --inbox.
+        let inbox = args.inbox && entry.project_id.is_none() && entry.list.is_none();
+        let project_id = entry.project_id.clone().or(args.project_id.clone());
+        let list = entry.list.clone().or(args.list.clone());
+        let key = (
+            inbox,
+            project_id.clone(),
+            list.as_deref().map(str::to_lowercase),
+        );
+
+        let outcome = async {
+            let project_id = match resolved_projects.get(&key) {
+                Some(project_id) => project_id.clone(),
+                None => {
+                    let resolved = match resolve_create_project_id(
+                        client,
+                        cache,
+                        defaults,
+                        inbox,
+                        project_id,
+                        list.clone(),
+                    )
+                    .await?
+                    {
+                        Some(project_id) => project_id,
+                        None => infer_default_project_id(client, cache, defaults).await?,
+                    };
+                    resolved_projects.insert(key, resolved.clone());
+                    resolved
+                }
+            };
 
-    if !search_terms.is_empty() {
-        let needles: Vec<String> = search_terms
-            .into_iter()
-            .map(|term| term.to_ascii_lowercase())
-            .collect();
-        tasks.retain(|task| {
-            let haystack = format!(
-                "{} {} {}",
-                task.title,
-                task.content.as_deref().unwrap_or_default(),
-                task.desc.as_deref().unwrap_or_default()
-            )
-            .to_ascii_lowercase();
-            needles.iter().all(|needle| haystack.contains(needle))
+            let mut task = task_from_import(entry, &project_id)?;
+            sync_task_note_fields(&mut task);
+            apply_system_time_zone_default(&mut task)?;
+            if args.dry_run {
+                return Ok::<_, anyhow::Error>((None, project_id));
+            }
+
+            let created = client.create_task(&task).await?;
+            remember_task(cache, &created, Some(&project_id));
+            Ok((created.id, project_id))
+        }
+        .await;
+
+        let title = entry.title.trim().to_string();
+        results.push(match outcome {
+            Ok((id, project_id)) => ImportResult {
+                index: index + 1,
+                title,
+                status: if args.dry_run {
+                    ImportStatus::Valid
+                } else {
+                    ImportStatus::Created
+                },
+                id,
+                list: list.clone(),
+                project_id: Some(project_id),
+                error: None,
+            },
+            Err(err) => ImportResult {
+                index: index + 1,
+                title,
+                status: ImportStatus::Failed,
+                id: None,
+                list: list.clone(),
+                project_id: None,
+                error: Some(err.to_string()),
+            },
         });
     }
 
-    if args.limit > 0 {
-        tasks = tasks.into_iter().take(args.limit).collect();
+    print!(
+        "{}",
+        format_import_report(
+            &results,
+            args.dry_run,
+            output == MessageFormat::Json,
+            context
+        )?
+    );
+
+    let failed = import_failures(&results);
+    if failed > 0 {
+        return Err(anyhow!(
+            "{} of {} tasks in {} failed",
+            failed,
+            results.len(),
+            path.display()
+        ));
     }
 
-    print_tasks(&tasks, args.output);
+    Ok(())
+}
+
+#[derive(Args, Clone, Default)]
+pub struct TaskListArgs {
+    #[arg(long)]
+    project_id: Option<String>,
+    #[arg(long)]
+    list: Option<String>,
+    #[arg(
+        long,
+        conflicts_with_all = ["project_id", "list"],
+        help = "List only Inbox tasks (same as ~inbox)"
+    )]
+    inbox: bool,
+    #[arg(
+        long,
+        alias = "list-pattern",
+        value_name = "REGEX",
+        value_parser = parse_list_regex,
+        conflicts_with_all = ["project_id", "list", "inbox"],
+        help = "List tasks from every list whose name matches this regex (case-insensitive); not with --list or --project-id"
+    )]
+    list_regex: Option<Regex>,
+    #[arg(
+        long,
+        help = "Only open (todo, open) or only completed (done, completed) tasks; open is the default"
+    )]
+    status: Option<String>,
+    #[arg(
+        long,
+        conflicts_with = "status",
+        help = "Keep completed tasks the lists return alongside open ones instead of hiding them"
+    )]
+    all_status: bool,
+    #[arg(
+        long,
+        value_parser = parse_priority_value,
+        help = "Priority: none, low, medium, high (or 0, 1, 3, 5)"
+    )]
+    priority: Option<i32>,
+    #[arg(
+        long,
+        value_name = "PRIORITY",
+        value_parser = parse_priority_value,
+        conflicts_with = "priority",
+        help = "Keep tasks at this priority or higher, e.g. --priority-min medium"
+    )]
+    priority_min: Option<i32>,
+    #[arg(long)]
+    tags: Vec<String>,
+    #[arg(
+        long,
+        help = "Keep tasks with any of the --tags (and #tag shorthand) instead of all of them"
+    )]
+    tag_any: bool,
+    #[arg(long, value_enum)]
+    when: Option<TaskWhenFilter>,
+    #[arg(
+        long,
+        value_parser = parse_due_filter_value,
+        help = "Only tasks due before this date (YYYY-MM-DD, eom, next month, in 3 days, ...)"
+    )]
+    due_before: Option<NaiveDate>,
+    #[arg(
+        long,
+        value_parser = parse_due_filter_value,
+        help = "Only tasks due after this date; accepts the same phrases as --due-before"
+    )]
+    due_after: Option<NaiveDate>,
+    #[arg(
+        long,
+        value_parser = parse_due_filter_value,
+        conflicts_with_all = ["all", "status"],
+        help = "List only tasks completed on or after this date, oldest first (queries the completed-task endpoint)"
+    )]
+    completed_since: Option<NaiveDate>,
+    #[arg(
+        long,
+        alias = "since",
+        value_parser = parse_modified_after_value,
+        help = "Only tasks changed on or after this date (YYYY-MM-DD), by TickTick's modified time"
+    )]
+    modified_after: Option<NaiveDate>,
+    #[arg(
+        long,
+        help = "Match the search terms as one regular expression (case-insensitive) instead of substrings"
+    )]
+    regex: bool,
+    #[arg(long, requires = "regex", help = "Make --regex case-sensitive")]
+    case_sensitive: bool,
+    #[arg(
+        long,
+        value_enum,
+        help = "Newest first by created or completed time; tasks without one come last"
+    )]
+    sort: Option<TaskSort>,
+    #[arg(long, default_value = "0")]
+    limit: usize,
+    #[arg(
+        long,
+        value_enum,
+        help = "Render tasks in sections by project, due bucket, priority, or tag"
+    )]
+    group_by: Option<TaskGroupBy>,
+    #[arg(
+        long,
+        help = "Include completed tasks (crawled in pages of 100, slower) and closed lists, with a status column"
+    )]
+    all: bool,
+    #[arg(
+        long,
+        overrides_with = "no_subtasks",
+        help = "Show checklist items as indented rows under each task"
+    )]
+    show_subtasks: bool,
+    #[arg(
+        long,
+        overrides_with = "show_subtasks",
+        help = "Hide checklist items (default)"
+    )]
+    no_subtasks: bool,
+    #[arg(
+        long,
+        help = "Wrap long cells onto extra lines instead of truncating them to the terminal width"
+    )]
+    wrap: bool,
+    #[arg(
+        long,
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "60",
+        value_name = "INTERVAL",
+        value_parser = parse_watch_interval,
+        help = "Redraw the list every INTERVAL (default 60s; e.g. --watch=30s, --watch=5m) until Ctrl-C"
+    )]
+    watch: Option<Duration>,
+    #[arg(
+        long,
+        alias = "template",
+        value_name = "FORMAT",
+        value_parser = parse_task_format,
+        conflicts_with_all = ["group_by", "output"],
+        help = "Print one line per task from a format string, e.g. \"{due} {title} ({list})\"; fields: {id} {title} {due} {priority} {list} {tags}"
+    )]
+    format: Option<TaskFormat>,
+    #[arg(
+        long,
+        value_name = "PATTERN",
+        value_parser = parse_due_format,
+        help = "Show the Due column with a strftime pattern in local time, e.g. \"%b %d\" or \"%a %H:%M\""
+    )]
+    due_format: Option<DueFormat>,
+    #[arg(
+        long,
+        conflicts_with_all = ["format", "group_by", "watch"],
+        help = "Print only task IDs, one per line, whatever the terminal or --output"
+    )]
+    ids_only: bool,
+    #[arg(long, default_value = "human")]
+    output: OutputFormat,
+    query: Vec<String>,
+}
+
+async fn fetch_task_list(
+    client: &TickTickClient,
+    cache: Option<&CacheStore>,
+    args: &TaskListArgs,
+    inbox_only: bool,
+) -> Result<Vec<Task>> {
+    let project_id = if inbox_only {
+        None
+    } else {
+        resolve_project_id(client, cache, args.project_id.clone(), args.list.clone()).await?
+    };
+    let scope = match args.list_regex.as_ref() {
+        Some(pattern) => Some(ListScope::matching(
+            &get_projects_cached(client, cache, false).await?,
+            pattern,
+            args.all,
+        )),
+        None => None,
+    };
+
+    let mut tasks = if let Some(since) = args.completed_since {
+        get_tasks_completed_since(client, cache, project_id.as_deref(), inbox_only, since).await?
+    } else if let Some(scope) = scope.as_ref() {
+        get_tasks_in_scope(client, cache, scope).await?
+    } else if inbox_only {
+        get_tasks_for_project(client, "").await?
+    } else if let Some(ref project_id) = project_id {
+        get_tasks_for_project(client, project_id).await?
+    } else {
+        get_tasks_across_projects(client, cache, args.all).await?
+    };
+    if args.completed_since.is_none() {
+        remember_tasks(cache, &tasks, project_id.as_deref());
+    }
+
+    if args.all {
+        let completed = get_completed_tasks_in_scope(
+            client,
+            cache,
+            project_id.as_deref(),
+            !inbox_only && project_id.is_none() && scope.is_none(),
+            &tasks,
+            None,
+        )
+        .await?;
+        tasks.extend(completed);
+        dedupe_tasks_by_id(&mut tasks);
+    }
+    // The completed-task crawls cover every list; keep the matched ones.
+    if let Some(scope) = scope.as_ref() {
+        tasks.retain(|task| scope.contains(task));
+    }
+    Ok(tasks)
+}
+
+/// Fetches and filters tasks the way `tt task list` does: shorthand, list scope,
+/// completion state, and every filter flag in `args`. Shared by `task list` and
+/// `task count` so both see the same tasks. Also reports whether a status column applies.
+async fn filter_task_list(
+    source: &TaskSource,
+    args: &mut TaskListArgs,
+) -> Result<(Vec<Task>, bool)> {
+    if args.inbox {
+        args.list = Some("inbox".to_string());
+    }
+    let shorthand = parse_shorthand(&args.query.join(" "));
+    if shorthand.priority.is_some() && args.priority_min.is_some() {
+        return Err(anyhow!(
+            "A !priority in the query cannot be combined with --priority-min"
+        ));
+    }
+    if args.priority.is_none() {
+        args.priority = shorthand.priority;
+    }
+    if shorthand.list.is_some() && args.list_regex.is_some() {
+        return Err(anyhow!(
+            "A ~list in the query cannot be combined with --list-regex"
+        ));
+    }
+    if args.project_id.is_none() && args.list.is_none() {
+        args.list = shorthand.list;
+    }
+    if args.when.is_none() {
+        args.when = shorthand.when;
+    }
+    merge_tags(&mut args.tags, shorthand.tags);
+    let mut search_terms = shorthand.terms;
+
+    if args.project_id.is_none() && args.list.is_none() && args.list_regex.is_none() {
+        if let Some(list_name) = extract_implicit_list_from_terms(&mut search_terms) {
+            args.list = Some(list_name);
+        }
+    }
+
+    if args.project_id.is_none()
+        && args.list.is_none()
+        && args.list_regex.is_none()
+        && search_terms.len() == 1
+        && search_terms
+            .first()
+            .is_some_and(|term| is_inbox_list_name(term))
+    {
+        args.list = search_terms.pop();
+    }
+
+    let search_regex = if args.regex && !search_terms.is_empty() {
+        Some(compile_task_list_regex(&search_terms, args.case_sensitive)?)
+    } else {
+        None
+    };
+
+    let inbox_only =
+        args.project_id.is_none() && args.list.as_deref().is_some_and(is_inbox_list_name);
+
+    let show_status =
+        args.all || args.all_status || args.status.is_some() || args.completed_since.is_some();
+    let mut tasks = match source {
+        TaskSource::Api { client, cache } => {
+            fetch_task_list(client, cache.as_ref(), args, inbox_only).await?
+        }
+        TaskSource::Snapshot(snapshot) => {
+            if args.all || args.completed_since.is_some() {
+                return Err(anyhow!(
+                    "Offline data only includes open tasks; drop --offline to use --all or --completed-since"
+                ));
+            }
+            if let Some(pattern) = args.list_regex.as_ref() {
+                let scope = ListScope::matching(&snapshot.projects, pattern, false);
+                let mut tasks = snapshot_tasks_in_scope(snapshot, None);
+                tasks.retain(|task| scope.contains(task));
+                tasks
+            } else {
+                let project_id = if inbox_only {
+                    Some(String::new())
+                } else if let Some(project_id) = args.project_id.clone() {
+                    Some(project_id)
+                } else if let Some(list) = args.list.as_deref() {
+                    Some(snapshot_list_project_id(snapshot, list)?)
+                } else {
+                    None
+                };
+                snapshot_tasks_in_scope(snapshot, project_id.as_deref())
+            }
+        }
+    };
+
+    // Project data sometimes carries completed tasks and sometimes not, so open tasks are
+    // the default; --all, --all-status, and --completed-since keep whatever was fetched.
+    let keep_completed = match args.status.as_deref() {
+        Some(status) => match status.to_ascii_lowercase().as_str() {
+            "done" | "completed" | "complete" => Some(true),
+            "todo" | "open" | "normal" | "active" => Some(false),
+            _ => {
+                return Err(anyhow!(
+                    "Unsupported status '{}'. Use one of: done, completed, todo, open",
+                    status
+                ));
+            }
+        },
+        None if args.all || args.all_status || args.completed_since.is_some() => None,
+        None => Some(false),
+    };
+    if let Some(is_done) = keep_completed {
+        tasks.retain(|task| task_is_completed(task) == is_done);
+    }
+
+    if let Some(prio) = args.priority {
+        tasks.retain(|task| task.priority.unwrap_or(0) == prio);
+    }
+    if let Some(min) = args.priority_min {
+        tasks.retain(|task| task_meets_priority(task, min));
+    }
+
+    if !args.tags.is_empty() {
+        if args.tag_any {
+            tasks.retain(|task| task_has_any_tag(task, &args.tags));
+        } else {
+            tasks.retain(|task| task_has_all_tags(task, &args.tags));
+        }
+    }
+
+    if let Some(when) = args.when {
+        let today = Local::now().date_naive();
+        tasks.retain(|task| task_matches_when_filter(task, when, today));
+    }
+
+    if args.due_before.is_some() || args.due_after.is_some() {
+        tasks.retain(|task| task_matches_due_range(task, args.due_before, args.due_after));
+    }
+
+    if let Some(since) = args.modified_after {
+        tasks.retain(|task| task_modified_since(task, since, &Local));
+    }
+
+    if let Some(pattern) = search_regex {
+        tasks.retain(|task| pattern.is_match(&task_search_haystack(task)));
+    } else if !search_terms.is_empty() {
+        let needles: Vec<String> = search_terms
+            .into_iter()
+            .map(|term| term.to_ascii_lowercase())
+            .collect();
+        tasks.retain(|task| {
+            let haystack = task_search_haystack(task).to_ascii_lowercase();
+            needles.iter().all(|needle| haystack.contains(needle))
+        });
+    }
+
+    if let Some(sort) = args.sort {
+        sort_tasks(&mut tasks, sort, &Local);
+    }
+
+    if args.limit > 0 {
+        tasks = tasks.into_iter().take(args.limit).collect();
+    }
+
+    Ok((tasks, show_status))
+}
+
+pub async fn task_list(args: TaskListArgs, context: &OutputContext) -> Result<()> {
+    if let Some(interval) = args.watch {
+        return watch_task_list(args, interval, context).await;
+    }
+    let mut args = args;
+    let source = TaskSource::open()?;
+
+    let (_, rendered) = render_task_list(&source, &mut args, context).await?;
+    let _ = io::Write::write_all(&mut io::stdout(), rendered.as_bytes());
+    Ok(())
+}
+
+/// Runs `tt task list` and renders the result as it would be printed. The tasks are
+/// returned too so `--watch` can compare refreshes.
+async fn render_task_list(
+    source: &TaskSource,
+    args: &mut TaskListArgs,
+    context: &OutputContext,
+) -> Result<(Vec<Task>, String)> {
+    let (tasks, show_status) = filter_task_list(source, args).await?;
+
+    if args.ids_only {
+        let rendered = render_task_ids(&tasks);
+        return Ok((tasks, rendered));
+    }
+
+    if let Some(format) = args.format.as_ref() {
+        let project_names = if format.uses_list() {
+            source.project_names().await?
+        } else {
+            HashMap::new()
+        };
+        let rendered = render_formatted_tasks(&tasks, format, &project_names);
+        return Ok((tasks, rendered));
+    }
+
+    let due_cell = args
+        .due_format
+        .as_ref()
+        .map(|due_format| move |task: &Task| due_format.render(task, &Local));
+    // Only listings that can hold completed tasks get the column, and only when one does.
+    let completed_cell = |task: &Task| {
+        task_completed_at(task, &Local)
+            .map(|completed_at| completed_at.date().to_string())
+            .unwrap_or_default()
+    };
+    let show_completed = show_status && tasks.iter().any(task_is_completed);
+    let list_options = TaskListOptions {
+        show_subtasks: args.show_subtasks && !args.no_subtasks,
+        show_status,
+        table: TableLayout::for_terminal(args.wrap),
+        due_cell: due_cell
+            .as_ref()
+            .map(|due_cell| due_cell as &dyn Fn(&Task) -> String),
+        completed_cell: show_completed.then_some(&completed_cell as &dyn Fn(&Task) -> String),
+    };
+
+    if let Some(group_by) = args.group_by {
+        let project_names = if group_by == TaskGroupBy::Project {
+            source.project_names().await?
+        } else {
+            HashMap::new()
+        };
+        let today = Local::now().date_naive();
+        let rendered = render_task_groups(
+            &group_tasks(tasks.clone(), group_by, &project_names, today),
+            args.output,
+            list_options,
+            context,
+        );
+        return Ok((tasks, rendered));
+    }
+
+    let rendered = render_tasks(&tasks, args.output, list_options, context);
+    Ok((tasks, rendered))
+}
+
+/// `--ids-only` output: one ID per line, and nothing at all when no tasks match.
+fn render_task_ids(tasks: &[Task]) -> String {
+    tasks
+        .iter()
+        .filter_map(|task| task.id.as_deref())
+        .map(|id| format!("{}\n", id))
+        .collect()
+}
+
+impl TaskListArgs {
+    /// Adds `tag` to the `--tags` filter, for `tt tag tasks`.
+    pub(crate) fn with_tag(mut self, tag: &str) -> Self {
+        self.tags.push(tag.trim_start_matches('#').to_string());
+        self
+    }
+}
+
+/// Per-tag counts over every open list, completed tasks included (the snapshot under
+/// `--offline` only has open ones). The API has no tag endpoint, so tags come from tasks.
+pub(crate) async fn tag_counts(contains: Option<&str>) -> Result<Vec<TagCount>> {
+    let tasks = match TaskSource::open()? {
+        TaskSource::Api { client, cache } => {
+            let mut tasks = get_tasks_across_projects(&client, cache.as_ref(), false).await?;
+            let completed =
+                get_completed_tasks_in_scope(&client, cache.as_ref(), None, true, &tasks, None)
+                    .await?;
+            tasks.extend(completed);
+            dedupe_tasks_by_id(&mut tasks);
+            tasks
+        }
+        TaskSource::Snapshot(snapshot) => snapshot_tasks_in_scope(&snapshot, None),
+    };
+
+    let mut counts = count_tags(&tasks);
+    filter_tag_counts(&mut counts, contains);
+    Ok(counts)
+}
+
+/// Every task in every list for `tt export`: closed lists and completed tasks included.
+pub(crate) async fn all_tasks(
+    client: &TickTickClient,
+    cache: Option<&CacheStore>,
+) -> Result<Vec<Task>> {
+    let mut tasks = get_tasks_across_projects(client, cache, true).await?;
+    let completed = get_completed_tasks_in_scope(client, cache, None, true, &tasks, None).await?;
+    tasks.extend(completed);
+    dedupe_tasks_by_id(&mut tasks);
+    Ok(tasks)
+}
+
+/// A y/N prompt would block on piped stdin or end up next to JSON output, so both need
+/// `--yes` instead.
+pub(crate) fn ensure_can_prompt(stdin_is_tty: bool, output: MessageFormat) -> Result<()> {
+    if !stdin_is_tty {
+        return Err(anyhow!(
+            "Cannot ask for confirmation without a terminal; pass --yes"
+        ));
+    }
+    if output == MessageFormat::Json {
+        return Err(anyhow!(
+            "Cannot ask for confirmation with --output json; pass --yes"
+        ));
+    }
+    Ok(())
+}
+
+/// Replaces the `from` tags with `to` (or removes them when `to` is `None`) on every open
+/// task carrying one, for `tt tag rename`. Each failed update is reported and makes the
+/// command fail at the end; `dry_run` only lists the tasks.
+pub(crate) async fn retag_tasks(
+    command: &str,
+    from: &[String],
+    to: Option<&str>,
+    dry_run: bool,
+    confirm: bool,
+    output: MessageFormat,
+    context: &OutputContext,
+) -> Result<()> {
+    let (client, cache) = TaskSource::online(command)?;
+    let tasks = get_tasks_across_projects(&client, cache.as_ref(), false).await?;
+    let planned: Vec<(Task, String)> = tasks
+        .into_iter()
+        .filter_map(|mut task| replace_task_tags(&mut task, from, to).map(|change| (task, change)))
+        .collect();
+
+    if confirm && !dry_run && !planned.is_empty() {
+        ensure_can_prompt(atty::is(Stream::Stdin), output)?;
+        let tags: Vec<String> = from.iter().map(|tag| format!("#{}", tag)).collect();
+        eprintln!(
+            "Remove {} from {} open task{}? [y/N]",
+            tags.join(" and "),
+            planned.len(),
+            if planned.len() == 1 { "" } else { "s" }
+        );
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        if !input.trim().eq_ignore_ascii_case("y") {
+            eprintln!("Cancelled.");
+            return Ok(());
+        }
+    }
+
+    let results = if dry_run {
+        planned
+            .into_iter()
+            .map(|(task, change)| BatchUpdateResult {
+                id: task.id.unwrap_or_default(),
+                title: Some(task.title),
+                status: BatchUpdateStatus::Updated,
+                changes: vec![change],
+                error: None,
+            })
+            .collect()
+    } else {
+        save_retagged_tasks(&client, cache.as_ref(), planned).await?
+    };
+
+    if results.is_empty() && output == MessageFormat::Human {
+        let tags: Vec<String> = from.iter().map(|tag| format!("#{}", tag)).collect();
+        println!("No open tasks are tagged {}.", tags.join(" or "));
+        return Ok(());
+    }
+    print!(
+        "{}",
+        format_batch_update_report(&results, dry_run, output == MessageFormat::Json, context)?
+    );
+    let failed = batch_update_failures(&results);
+    if failed > 0 {
+        if output == MessageFormat::Human {
+            println!(
+                "Failed task IDs: {}",
+                batch_update_failed_ids(&results).join(" ")
+            );
+        }
+        return Err(anyhow!("{} of {} tasks failed", failed, results.len()));
+    }
+    Ok(())
+}
+
+pub async fn task_count(args: TaskListArgs, context: &OutputContext) -> Result<()> {
+    let mut args = args;
+    let output = MessageFormat::for_command(args.output, "task count")?;
+    let source = TaskSource::open()?;
+
+    let (tasks, _) = filter_task_list(&source, &mut args).await?;
+    let project_names = source.project_names().await?;
+    let stats = task_stats(&tasks, &project_names, Local::now().date_naive());
+    print!("{}", format_task_stats(&stats, output, context)?);
+    Ok(())
+}
+
+/// The `--completed-since` path: only the completed-task endpoint is queried, so the
+/// open-task listing stays untouched. The inbox needs its open tasks to learn its id.
+async fn get_tasks_completed_since(
+    client: &TickTickClient,
+    cache: Option<&CacheStore>,
+    project_id: Option<&str>,
+    inbox_only: bool,
+    since: NaiveDate,
+) -> Result<Vec<Task>> {
+    let from = format_ticktick_due_date(since)
+        .ok_or_else(|| anyhow!("Failed to format date '{}'", since))?;
+    let inbox_tasks = if inbox_only {
+        get_tasks_for_project(client, "").await?
+    } else {
+        Vec::new()
+    };
+
+    let completed = get_completed_tasks_in_scope(
+        client,
+        cache,
+        project_id,
+        !inbox_only && project_id.is_none(),
+        &inbox_tasks,
+        Some(&from),
+    )
+    .await?;
+    Ok(tasks_completed_since(completed, since, &Local))
+}
+
+#[derive(Args)]
+pub struct TaskSearchArgs {
+    #[arg(
+        long,
+        help = "Treat each search term as a case-insensitive regular expression"
+    )]
+    regex: bool,
+    #[arg(long, default_value = "0")]
+    limit: usize,
+    #[arg(long, default_value = "human")]
+    output: OutputFormat,
+    #[arg(
+        required = true,
+        allow_hyphen_values = true,
+        help = "Terms to match; use \"quoted phrases\", a | b for either, -term to exclude (options go before the query)"
+    )]
+    query: Vec<String>,
+}
+
+pub async fn task_search(args: TaskSearchArgs, context: &OutputContext) -> Result<()> {
+    let query = parse_search_query(&args.query.join(" "), args.regex)?;
+    let source = TaskSource::open()?;
+
+    let tasks = match &source {
+        TaskSource::Api { client, cache } => {
+            let tasks = get_tasks_across_projects(client, cache.as_ref(), true).await?;
+            remember_tasks(cache.as_ref(), &tasks, None);
+            tasks
+        }
+        TaskSource::Snapshot(snapshot) => snapshot.tasks.clone(),
+    };
+    let project_names = source.project_names().await?;
+
+    let mut results: Vec<TaskSearchResult> = tasks
+        .into_iter()
+        .filter(|task| query.matches_task(task))
+        .map(|task| TaskSearchResult {
+            project_name: task_project_label(&task, &project_names),
+            task,
+        })
+        .collect();
+
+    if args.limit > 0 {
+        results.truncate(args.limit);
+    }
+
+    print_task_search_results(&results, args.output, context);
+    Ok(())
+}
+
+#[derive(Args)]
+pub struct TaskItemAddArgs {
+    task_id: String,
+    #[arg(required = true)]
+    title: Vec<String>,
+    #[arg(long)]
+    project_id: Option<String>,
+    #[arg(long)]
+    list: Option<String>,
+    #[arg(long, default_value = "human")]
+    output: MessageFormat,
+}
+
+#[derive(Args)]
+pub struct TaskItemListArgs {
+    task_id: String,
+    #[arg(long)]
+    project_id: Option<String>,
+    #[arg(long)]
+    list: Option<String>,
+    #[arg(long, default_value = "human")]
+    output: MessageFormat,
+}
+
+#[derive(Args)]
+pub struct TaskItemTargetArgs {
+    task_id: String,
+    #[arg(help = "Checklist item position (1-based), ID, or title")]
+    item: String,
+    #[arg(long)]
+    project_id: Option<String>,
+    #[arg(long)]
+    list: Option<String>,
+    #[arg(long, default_value = "human")]
+    output: MessageFormat,
+}
+
+async fn fetch_task_with_project(
+    client: &TickTickClient,
+    cache: Option<&CacheStore>,
+    task_id: &str,
+    project_id: Option<String>,
+    list: Option<String>,
+) -> Result<(Task, String)> {
+    let explicit_scope = project_id.is_some() || list.is_some();
+    let mut resolved = resolve_task_project_id(client, cache, task_id, project_id, list).await?;
+
+    let task = match client.get_task(&resolved.project_id, task_id).await {
+        Ok(task) => task,
+        Err(_) if resolved.from_cache && !explicit_scope => {
+            forget_task_project_id(cache, task_id);
+            resolved = resolve_task_project_id(client, cache, task_id, None, None).await?;
+            client.get_task(&resolved.project_id, task_id).await?
+        }
+        Err(err) => return Err(err),
+    };
+
+    Ok((task, resolved.project_id))
+}
+
+pub async fn task_items(
+    command: TaskItemCommands,
+    verbosity: Verbosity,
+    context: &OutputContext,
+) -> Result<()> {
+    let (task_id, project_id, list, output) = match &command {
+        TaskItemCommands::Add(args) => (&args.task_id, &args.project_id, &args.list, args.output),
+        TaskItemCommands::List(args) => (&args.task_id, &args.project_id, &args.list, args.output),
+        TaskItemCommands::Done(args) | TaskItemCommands::Rm(args) => {
+            (&args.task_id, &args.project_id, &args.list, args.output)
+        }
+    };
+    let task_id = task_id.clone();
+    let (client, cache) = match &command {
+        TaskItemCommands::List(_) => match TaskSource::open()? {
+            TaskSource::Api { client, cache } => (client, cache),
+            TaskSource::Snapshot(snapshot) => {
+                let task = snapshot_task(&snapshot, &task_id)?;
+                let items = task.items.as_deref().unwrap_or_default();
+                print!("{}", format_checklist_output(items, None, output, context)?);
+                return Ok(());
+            }
+        },
+        TaskItemCommands::Add(_) => TaskSource::online("task items add")?,
+        TaskItemCommands::Done(_) => TaskSource::online("task items done")?,
+        TaskItemCommands::Rm(_) => TaskSource::online("task items rm")?,
+    };
+    let (mut task, project_id) = fetch_task_with_project(
+        &client,
+        cache.as_ref(),
+        &task_id,
+        project_id.clone(),
+        list.clone(),
+    )
+    .await?;
+    remember_task(cache.as_ref(), &task, Some(&project_id));
+
+    let mut items = task.items.take().unwrap_or_default();
+    let message = match command {
+        TaskItemCommands::List(_) => {
+            print!(
+                "{}",
+                format_checklist_output(&items, None, output, context)?
+            );
+            return Ok(());
+        }
+        TaskItemCommands::Add(args) => {
+            let title = args.title.join(" ");
+            push_checklist_item(&mut items, &title)?;
+            if task.kind.as_deref() != Some("NOTE") {
+                task.kind = Some("CHECKLIST".to_string());
+            }
+            format!("Checklist item added: {}", title.trim())
+        }
+        TaskItemCommands::Done(args) => {
+            let index = find_checklist_item(&items, &args.item)?;
+            complete_checklist_item(&mut items[index], format_ticktick_datetime(Utc::now()));
+            format!(
+                "Checklist item completed: {}",
+                items[index].title.as_deref().unwrap_or_default()
+            )
+        }
+        TaskItemCommands::Rm(args) => {
+            let index = find_checklist_item(&items, &args.item)?;
+            let removed = items.remove(index);
+            format!(
+                "Checklist item removed: {}",
+                removed.title.as_deref().unwrap_or_default()
+            )
+        }
+    };
+
+    let task_id = task.id.clone().unwrap_or(task_id);
+    // An empty array (not a missing field) is what clears the last item server-side.
+    task.items = Some(items);
+    let payload = build_task_update_payload(&task, TaskUpdateClearFlags::default())?;
+    let updated = client.update_task(&task_id, &payload).await?;
+    remember_task(cache.as_ref(), &updated, Some(&project_id));
+
+    print!(
+        "{}",
+        format_checklist_output(
+            updated.items.as_deref().unwrap_or_default(),
+            (!verbosity.is_quiet()).then_some(message.as_str()),
+            output,
+            context
+        )?
+    );
+    Ok(())
+}
+
+/// The task a command acts on: `task_id` when given, else one picked interactively. A
+/// picked task also pins `project_id` so it is not looked up again.
+async fn task_id_or_pick(
+    client: &TickTickClient,
+    cache: Option<&CacheStore>,
+    task_id: Option<String>,
+    project_id: &mut Option<String>,
+    list: &mut Option<String>,
+) -> Result<String> {
+    if let Some(task_id) = task_id {
+        return Ok(task_id);
+    }
+    let (task_id, picked_project_id) =
+        pick_task(client, cache, project_id.take(), list.take()).await?;
+    *project_id = normalize_project_id(Some(picked_project_id));
+    Ok(task_id)
+}
+
+#[derive(Args)]
+pub struct TaskInfoArgs {
+    #[arg(help = "Task ID; omit it in a terminal to pick an open task interactively")]
+    task_id: Option<String>,
+    #[arg(long)]
+    project_id: Option<String>,
+    #[arg(long)]
+    list: Option<String>,
+    #[arg(long, default_value = "human")]
+    output: OutputFormat,
+}
+
+pub async fn task_info(args: TaskInfoArgs, context: &OutputContext) -> Result<()> {
+    let TaskInfoArgs {
+        task_id,
+        mut project_id,
+        mut list,
+        output,
+    } = args;
+
+    let (client, cache) = match TaskSource::open()? {
+        TaskSource::Api { client, cache } => (client, cache),
+        TaskSource::Snapshot(snapshot) => {
+            let task_id = task_id.ok_or_else(missing_task_id)?;
+            print!(
+                "{}",
+                format_task_info_output(&snapshot_task(&snapshot, &task_id)?, output, context,)?
+            );
+            return Ok(());
+        }
+    };
+    let task_id =
+        task_id_or_pick(&client, cache.as_ref(), task_id, &mut project_id, &mut list).await?;
+    let (task, project_id) =
+        fetch_task_with_project(&client, cache.as_ref(), &task_id, project_id, list).await?;
+
+    remember_task(cache.as_ref(), &task, Some(&project_id));
+    print!("{}", format_task_info_output(&task, output, context)?);
+
+    Ok(())
+}
+
+#[derive(Debug, Args)]
+pub struct TaskUpdateArgs {
+    #[arg(help = "Task ID; omit it in a terminal to pick an open task interactively")]
+    task_id: Option<String>,
+    #[arg(long)]
+    project_id: Option<String>,
+    #[arg(long)]
+    list: Option<String>,
+    #[arg(long)]
+    title: Option<String>,
+    #[arg(
+        long,
+        conflicts_with = "clear_content",
+        help = "Visible task note shown in TickTick"
+    )]
+    content: Option<String>,
+    #[arg(
+        long,
+        conflicts_with_all = ["clear_content", "clear_desc"],
+        help = "Secondary TickTick API description field; mirrored to content when used alone"
+    )]
+    desc: Option<String>,
+    #[arg(
+        long,
+        value_name = "PATH",
+        conflicts_with_all = ["content", "clear_content"],
+        help = "Read the note from a file ('-' for stdin)"
+    )]
+    content_file: Option<PathBuf>,
+    #[arg(
+        long,
+        value_name = "PATH",
+        conflicts_with_all = ["desc", "clear_content", "clear_desc"],
+        help = "Read the desc field from a file ('-' for stdin)"
+    )]
+    desc_file: Option<PathBuf>,
+    #[arg(
+        long,
+        value_parser = normalize_task_datetime_input,
+        conflicts_with = "clear_start_date"
+    )]
+    start_date: Option<String>,
+    #[arg(
+        long,
+        value_parser = normalize_task_datetime_input,
+        conflicts_with = "clear_due_date"
+    )]
+    due_date: Option<String>,
+    #[arg(long, conflicts_with = "clear_time_zone")]
+    time_zone: Option<String>,
+    #[arg(long)]
+    all_day: Option<bool>,
+    #[arg(
+        long,
+        value_parser = parse_priority_value,
+        help = "Priority: none, low, medium, high (or 0, 1, 3, 5)"
+    )]
+    priority: Option<i32>,
+    #[arg(
+        long,
+        conflicts_with = "clear_tags",
+        help = "Replace all tags (repeatable)"
+    )]
+    tags: Vec<String>,
+    #[arg(
+        long = "add-tag",
+        conflicts_with_all = ["tags", "clear_tags"],
+        help = "Add a tag, keeping the existing ones (repeatable)"
+    )]
+    add_tag: Vec<String>,
+    #[arg(
+        long = "remove-tag",
+        conflicts_with_all = ["tags", "clear_tags"],
+        help = "Remove a tag, ignoring case (repeatable)"
+    )]
+    remove_tag: Vec<String>,
+    #[arg(
+        long,
+        conflicts_with = "clear_reminders",
+        value_parser = parse_reminder_value,
+        help = "Reminder before the due time: 0/at-time, 30m, 1h, 1d, 1h30m (repeatable)"
+    )]
+    reminders: Vec<String>,
+    #[arg(
+        long,
+        value_name = "TIME",
+        value_parser = parse_reminder_at,
+        conflicts_with = "clear_reminders",
+        help = "Add a reminder at a time on the due date (9am, 21:15) or at a date and time (2026-10-14T08:00); repeatable"
+    )]
+    reminder_at: Vec<ReminderAt>,
+    #[arg(
+        long,
+        value_name = "NAME",
+        help = "Move the task to this board column (by name)"
+    )]
+    column: Option<String>,
+    #[arg(long, value_parser = parse_task_status_value)]
+    status: Option<TaskStatus>,
+    #[arg(
+        long,
+        value_parser = validate_rrule,
+        conflicts_with_all = ["clear_repeat_flag", "repeat"]
+    )]
+    repeat_flag: Option<String>,
+    #[arg(
+        long,
+        value_parser = parse_repeat_value,
+        conflicts_with = "clear_repeat_flag",
+        help = "Recurrence such as daily, every monday, every 2 weeks, every weekday, or monthly on the 15th"
+    )]
+    repeat: Option<String>,
+    #[arg(long, conflicts_with = "clear_sort_order")]
+    sort_order: Option<i64>,
+    #[arg(long, help = "Remove the note (content and its desc mirror)")]
+    clear_content: bool,
+    #[arg(long, help = "Remove only the desc field")]
+    clear_desc: bool,
+    #[arg(long, alias = "clear-start")]
+    clear_start_date: bool,
+    #[arg(long, alias = "clear-due")]
+    clear_due_date: bool,
+    #[arg(long)]
+    clear_time_zone: bool,
+    #[arg(long)]
+    clear_tags: bool,
+    #[arg(long)]
+    clear_reminders: bool,
+    #[arg(long, alias = "clear-repeat")]
+    clear_repeat_flag: bool,
+    #[arg(long)]
+    clear_sort_order: bool,
+    #[arg(
+        long,
+        help = "Open the task in $VISUAL/$EDITOR as TOML after applying any other flags"
+    )]
+    editor: bool,
+    #[arg(long, default_value = "human")]
+    output: MessageFormat,
+}
+
+fn build_task_update_payload(task: &Task, clear_flags: TaskUpdateClearFlags) -> Result<Value> {
+    let mut payload = serde_json::to_value(task)?;
+    let object = payload
+        .as_object_mut()
+        .ok_or_else(|| anyhow!("Failed to encode task update payload as JSON object"))?;
+
+    // TickTick keeps the old note when these are null, so they are blanked instead.
+    if clear_flags.content {
+        object.insert("content".to_string(), Value::String(String::new()));
+    }
+    if clear_flags.desc {
+        object.insert("desc".to_string(), Value::String(String::new()));
+    }
+    if clear_flags.start_date {
+        object.insert("startDate".to_string(), Value::Null);
+    }
+    if clear_flags.due_date {
+        object.insert("dueDate".to_string(), Value::Null);
+    }
+    if clear_flags.time_zone {
+        object.insert("timeZone".to_string(), Value::Null);
+    }
+    if clear_flags.tags {
+        object.insert("tags".to_string(), Value::Array(Vec::new()));
+    }
+    if clear_flags.reminders {
+        object.insert("reminders".to_string(), Value::Array(Vec::new()));
+    }
+    if clear_flags.repeat_flag {
+        object.insert("repeatFlag".to_string(), Value::Null);
+    }
+    if clear_flags.sort_order {
+        object.insert("sortOrder".to_string(), Value::Null);
+    }
+
+    Ok(payload)
+}
+
+pub async fn task_update(
+    args: TaskUpdateArgs,
+    verbosity: Verbosity,
+    context: &OutputContext,
+) -> Result<()> {
+    let TaskUpdateArgs {
+        task_id,
+        mut project_id,
+        mut list,
+        title,
+        mut content,
+        content_file,
+        mut desc,
+        desc_file,
+        start_date,
+        due_date,
+        time_zone,
+        all_day,
+        priority,
+        tags,
+        add_tag,
+        remove_tag,
+        reminders,
+        reminder_at,
+        column,
+        status,
+        repeat_flag,
+        repeat,
+        sort_order,
+        clear_content,
+        clear_desc,
+        mut clear_start_date,
+        mut clear_due_date,
+        clear_time_zone,
+        mut clear_tags,
+        clear_reminders,
+        clear_repeat_flag,
+        clear_sort_order,
+        editor,
+        output,
+    } = args;
+    read_task_note_files(
+        &mut content,
+        content_file.as_deref(),
+        &mut desc,
+        desc_file.as_deref(),
+    )?;
+
+    let (client, cache) = TaskSource::online("task update")?;
+    let task_id =
+        task_id_or_pick(&client, cache.as_ref(), task_id, &mut project_id, &mut list).await?;
+    let (mut task, project_id) =
+        fetch_task_with_project(&client, cache.as_ref(), &task_id, project_id, list).await?;
+    let previous = task.clone();
+
+    if let Some(title) = title {
+        task.title = title;
+    }
+    if clear_content {
+        task.content = None;
+        task.desc = None;
+    }
+    if clear_desc {
+        task.desc = None;
+    }
+    let note_fields_were_updated = content.is_some() || desc.is_some();
+    let (content, desc) = resolve_task_note_fields(content, desc);
+    if let Some(content) = content {
+        task.content = Some(content);
+    }
+    if let Some(desc) = desc {
+        task.desc = Some(desc);
+    }
+    if clear_start_date {
+        task.start_date = None;
+    }
+    if let Some(start_date) = start_date {
+        task.start_date = Some(start_date);
+    }
+    if clear_due_date {
+        task.due_date = None;
+    }
+    if let Some(due_date) = due_date {
+        task.due_date = Some(due_date);
+    }
+    if clear_time_zone {
+        task.time_zone = None;
+    }
+    if let Some(time_zone) = time_zone {
+        task.time_zone = Some(time_zone);
+    }
+    if let Some(all_day) = all_day {
+        task.is_all_day = Some(all_day);
+    }
+    if let Some(priority) = priority {
+        task.priority = Some(priority);
+    }
+    if clear_tags {
+        task.tags = None;
+    }
+    if !tags.is_empty() {
+        task.tags = Some(tags);
+    }
+    if !add_tag.is_empty() || !remove_tag.is_empty() {
+        apply_tag_changes(&mut task, add_tag, &remove_tag);
+    }
+    if clear_reminders {
+        task.reminders = None;
+    }
+    if !reminders.is_empty() {
+        task.reminders = Some(reminders);
+    }
+    add_reminders_at(&mut task, &reminder_at)?;
+    if let Some(name) = column.as_deref() {
+        task.column_id = Some(resolve_column_arg(&client, &project_id, name).await?);
+    }
+    if let Some(status) = status {
+        task.status = Some(status);
+    }
+    if clear_repeat_flag {
+        task.repeat_flag = None;
+    }
+    if let Some(repeat_flag) = repeat_flag.or(repeat) {
+        task.repeat_flag = Some(repeat_flag);
+    }
+    if clear_sort_order {
+        task.sort_order = None;
+    }
+    if let Some(sort_order) = sort_order {
+        task.sort_order = Some(sort_order);
+    }
+    if editor {
+        let completed_time = format_ticktick_datetime(Utc::now());
+        let Some(edited) = edit_task_in_editor(&task, &Local, &completed_time)? else {
+            eprintln!("Cancelled.");
+            return Ok(());
+        };
+        clear_start_date |= task.start_date.is_some() && edited.start_date.is_none();
+        clear_due_date |= task.due_date.is_some() && edited.due_date.is_none();
+        clear_tags |= task.tags.is_some() && edited.tags.is_none();
+        task = edited;
+
+        if serde_json::to_value(&task)? == serde_json::to_value(&previous)? {
+            eprintln!("No changes; task not updated.");
+            return Ok(());
+        }
+    }
+    if !clear_time_zone {
+        apply_system_time_zone_default(&mut task)?;
+    }
+    if note_fields_were_updated {
+        if task.content.is_none() {
+            task.content = task.desc.clone();
+        }
+        if task.desc.is_none() {
+            task.desc = task.content.clone();
+        }
+    } else if !clear_desc {
+        sync_task_note_fields(&mut task);
+    }
+
+    let payload = build_task_update_payload(
+        &task,
+        TaskUpdateClearFlags {
+            content: clear_content && task.content.is_none(),
+            desc: (clear_content || clear_desc) && task.desc.is_none(),
+            start_date: clear_start_date,
+            due_date: clear_due_date,
+            time_zone: clear_time_zone,
+            tags: clear_tags,
+            reminders: clear_reminders,
+            repeat_flag: clear_repeat_flag,
+            sort_order: clear_sort_order,
+        },
+    )?;
+    let updated = client.update_task(&task_id, &payload).await?;
+    remember_task(cache.as_ref(), &updated, Some(&project_id));
+    record_operation(
+        cache.as_ref(),
+        JournalOperation::TaskUpdated {
+            previous,
+            project_id: project_id.clone(),
+        },
+    );
+
+    verbosity.confirm(
+        &format_task_update_output(&updated, output, context)?,
+        output,
+    );
+
     Ok(())
 }
 
 #[derive(Args)]
-pub struct TaskInfoArgs {
-    task_id: String,
+pub struct TaskCompleteArgs {
+    #[arg(help = "Task ID; omit it in a terminal to pick an open task interactively")]
+    task_id: Option<String>,
     #[arg(long)]
     project_id: Option<String>,
     #[arg(long)]
     list: Option<String>,
     #[arg(long, default_value = "human")]
-    output: OutputFormat,
+    output: MessageFormat,
 }
 
-pub async fn task_info(args: TaskInfoArgs) -> Result<()> {
-    let TaskInfoArgs {
+pub async fn task_complete(
+    args: TaskCompleteArgs,
+    verbosity: Verbosity,
+    context: &OutputContext,
+) -> Result<()> {
+    let TaskCompleteArgs {
         task_id,
-        project_id,
-        list,
+        mut project_id,
+        mut list,
         output,
     } = args;
-
-    let client = authenticated_client()?;
-    let cache = cache_store();
+    let (client, cache) = TaskSource::online("task complete")?;
+    let task_id =
+        task_id_or_pick(&client, cache.as_ref(), task_id, &mut project_id, &mut list).await?;
     let explicit_scope = project_id.is_some() || list.is_some();
 
     let mut resolved =
         resolve_task_project_id(&client, cache.as_ref(), &task_id, project_id, list).await?;
 
-    let task = match client.get_task(&resolved.project_id, &task_id).await {
-        Ok(task) => task,
-        Err(_) if resolved.from_cache && !explicit_scope => {
+    if let Err(err) = client.complete_task(&resolved.project_id, &task_id).await {
+        if resolved.from_cache && !explicit_scope {
             forget_task_project_id(cache.as_ref(), &task_id);
             resolved =
                 resolve_task_project_id(&client, cache.as_ref(), &task_id, None, None).await?;
-            client.get_task(&resolved.project_id, &task_id).await?
+            client.complete_task(&resolved.project_id, &task_id).await?;
+        } else {
+            return Err(err);
         }
-        Err(err) => return Err(err),
-    };
-
-    remember_task(cache.as_ref(), &task, Some(&resolved.project_id));
-    print!("{}", format_task_info_output(&task, output)?);
+    }
+    remember_task_project_id(cache.as_ref(), &task_id, &resolved.project_id);
+    record_operation(
+        cache.as_ref(),
+        JournalOperation::TaskCompleted {
+            task_id: task_id.clone(),
+            project_id: resolved.project_id.clone(),
+        },
+    );
+    verbosity.confirm(
+        &format_task_action_output(&task_id, &resolved.project_id, "completed", output, context)?,
+        output,
+    );
 
     Ok(())
 }
 
-#[derive(Debug, Args)]
-pub struct TaskUpdateArgs {
-    task_id: String,
+/// Completed tasks are not always returned by the single-task endpoint, so fall back to
+/// the completed-task crawl for the lists in scope.
+async fn fetch_task_for_reopen(
+    client: &TickTickClient,
+    cache: Option<&CacheStore>,
+    task_id: &str,
+    project_id: Option<String>,
+    list: Option<String>,
+) -> Result<(Task, String)> {
+    let err =
+        match fetch_task_with_project(client, cache, task_id, project_id.clone(), list.clone())
+            .await
+        {
+            Ok(found) => return Ok(found),
+            Err(err) => err,
+        };
+
+    let scoped_project_id = resolve_project_id(client, cache, project_id, list).await?;
+    let completed = get_completed_tasks_in_scope(
+        client,
+        cache,
+        scoped_project_id.as_deref(),
+        scoped_project_id.is_none(),
+        &[],
+        None,
+    )
+    .await?;
+
+    completed
+        .into_iter()
+        .find(|task| task.id.as_deref() == Some(task_id))
+        .and_then(|task| {
+            let project_id = task.project_id.clone().or(scoped_project_id.clone())?;
+            Some((task, project_id))
+        })
+        .ok_or(err)
+}
+
+/// Reopens a completed task. Returns the task, its project ID, and whether it changed;
+/// tasks that are already open are left alone.
+pub(super) async fn reopen_task(
+    client: &TickTickClient,
+    cache: Option<&CacheStore>,
+    task_id: &str,
+    project_id: Option<String>,
+    list: Option<String>,
+) -> Result<(Task, String, bool)> {
+    let (mut task, project_id) =
+        fetch_task_for_reopen(client, cache, task_id, project_id, list).await?;
+    if task.status != Some(TaskStatus::Completed) {
+        return Ok((task, project_id, false));
+    }
+
+    task.reopen();
+    let payload = build_task_update_payload(&task, TaskUpdateClearFlags::default())?;
+    let updated = client.update_task(task_id, &payload).await?;
+    remember_task(cache, &updated, Some(&project_id));
+    Ok((updated, project_id, true))
+}
+
+/// Writes `previous` back over the current task, clearing fields it did not have.
+pub(super) async fn restore_task(
+    client: &TickTickClient,
+    cache: Option<&CacheStore>,
+    previous: &Task,
+    project_id: &str,
+) -> Result<Task> {
+    let task_id = previous
+        .id
+        .as_deref()
+        .ok_or_else(|| anyhow!("Journaled task '{}' has no ID", previous.title))?;
+    let payload = build_task_update_payload(previous, TaskUpdateClearFlags::restoring(previous))?;
+    let restored = client.update_task(task_id, &payload).await?;
+    remember_task(cache, &restored, Some(project_id));
+    Ok(restored)
+}
+
+/// Creates a fresh copy of a deleted task in its original list. TickTick assigns a new ID.
+pub(super) async fn recreate_task(
+    client: &TickTickClient,
+    cache: Option<&CacheStore>,
+    task: &Task,
+    project_id: &str,
+) -> Result<Task> {
+    let mut copy = task.clone();
+    copy.id = None;
+    copy.project_id = Some(project_id.to_string());
+    if let Some(items) = copy.items.as_mut() {
+        for item in items {
+            item.id = None;
+        }
+    }
+
+    let created = client.create_task(&copy).await?;
+    remember_task(cache, &created, Some(project_id));
+    Ok(created)
+}
+
+/// Parses `--due` for a duplicate: an exact date or datetime, or a phrase like
+/// `next friday`. Also reports whether the result is all-day.
+fn parse_duplicate_due(value: &str) -> std::result::Result<(String, bool), String> {
+    if let Ok(formatted) = normalize_task_datetime_input(value) {
+        return Ok((formatted, !value.contains('T')));
+    }
+    resolve_template_date(value, Local::now().date_naive()).map_err(|err| err.to_string())
+}
+
+/// Gives the copy a new due date. A start date that matched the old due date moves with
+/// it; any other start is dropped so the copy cannot start after it is due.
+fn apply_duplicate_due(task: &mut Task, due_date: String, all_day: bool) {
+    if task.start_date.is_some() {
+        task.start_date = (task.start_date == task.due_date).then(|| due_date.clone());
+    }
+    task.due_date = Some(due_date);
+    task.is_all_day = Some(all_day);
+}
+
+#[derive(Args)]
+pub struct TaskDuplicateArgs {
+    #[arg(help = "Task ID; omit it in a terminal to pick an open task interactively")]
+    task_id: Option<String>,
     #[arg(long)]
     project_id: Option<String>,
     #[arg(long)]
     list: Option<String>,
-    #[arg(long)]
+    #[arg(long, help = "Title for the copy (defaults to the original title)")]
     title: Option<String>,
-    #[arg(long, help = "Visible task note shown in TickTick")]
-    content: Option<String>,
-    #[arg(
-        long,
-        help = "Secondary TickTick API description field; mirrored to content when used alone"
-    )]
-    desc: Option<String>,
     #[arg(
         long,
-        value_parser = normalize_task_datetime_input,
-        conflicts_with = "clear_start_date"
+        value_parser = parse_duplicate_due,
+        help = "Due date for the copy: YYYY-MM-DD, ISO 8601, or a phrase like 'next friday'"
     )]
-    start_date: Option<String>,
+    due: Option<(String, bool)>,
     #[arg(
-        long,
-        value_parser = normalize_task_datetime_input,
-        conflicts_with = "clear_due_date"
+        long = "to",
+        value_name = "LIST",
+        help = "Create the copy in this list instead of the original's"
     )]
-    due_date: Option<String>,
-    #[arg(long, conflicts_with = "clear_time_zone")]
-    time_zone: Option<String>,
-    #[arg(long)]
-    all_day: Option<bool>,
-    #[arg(long, value_parser = parse_priority_value)]
-    priority: Option<i32>,
-    #[arg(long, conflicts_with = "clear_tags")]
-    tags: Vec<String>,
-    #[arg(long, conflicts_with = "clear_reminders")]
-    reminders: Vec<String>,
-    #[arg(long, value_parser = parse_task_status_value)]
-    status: Option<TaskStatus>,
-    #[arg(long, conflicts_with = "clear_repeat_flag")]
-    repeat_flag: Option<String>,
-    #[arg(long, conflicts_with = "clear_sort_order")]
-    sort_order: Option<i64>,
-    #[arg(long)]
-    clear_start_date: bool,
-    #[arg(long)]
-    clear_due_date: bool,
-    #[arg(long)]
-    clear_time_zone: bool,
-    #[arg(long)]
-    clear_tags: bool,
-    #[arg(long)]
-    clear_reminders: bool,
-    #[arg(long)]
-    clear_repeat_flag: bool,
-    #[arg(long)]
-    clear_sort_order: bool,
+    to: Option<String>,
     #[arg(long, default_value = "human")]
-    output: OutputFormat,
+    output: MessageFormat,
 }
 
-fn build_task_update_payload(task: &Task, clear_flags: TaskUpdateClearFlags) -> Result<Value> {
-    let mut payload = serde_json::to_value(task)?;
-    let object = payload
-        .as_object_mut()
-        .ok_or_else(|| anyhow!("Failed to encode task update payload as JSON object"))?;
-
-    if clear_flags.start_date {
-        object.insert("startDate".to_string(), Value::Null);
-    }
-    if clear_flags.due_date {
-        object.insert("dueDate".to_string(), Value::Null);
-    }
-    if clear_flags.time_zone {
-        object.insert("timeZone".to_string(), Value::Null);
-    }
-    if clear_flags.tags {
-        object.insert("tags".to_string(), Value::Array(Vec::new()));
+pub async fn task_duplicate(
+    args: TaskDuplicateArgs,
+    verbosity: Verbosity,
+    context: &OutputContext,
+) -> Result<()> {
+    let TaskDuplicateArgs {
+        task_id,
+        mut project_id,
+        mut list,
+        title,
+        due,
+        to,
+        output,
+    } = args;
+    let (client, cache) = TaskSource::online("task duplicate")?;
+    let task_id =
+        task_id_or_pick(&client, cache.as_ref(), task_id, &mut project_id, &mut list).await?;
+
+    let (source, source_project_id) =
+        fetch_task_with_project(&client, cache.as_ref(), &task_id, project_id, list).await?;
+    let target_project_id = resolve_project_id(&client, cache.as_ref(), None, to)
+        .await?
+        .unwrap_or(source_project_id);
+
+    let mut copy = source.duplicate();
+    copy.project_id = Some(target_project_id.clone());
+    if let Some(title) = title {
+        if title.trim().is_empty() {
+            return Err(anyhow!("--title cannot be empty"));
+        }
+        copy.title = title.trim().to_string();
     }
-    if clear_flags.reminders {
-        object.insert("reminders".to_string(), Value::Array(Vec::new()));
+    if let Some((due_date, all_day)) = due {
+        apply_duplicate_due(&mut copy, due_date, all_day);
+        apply_system_time_zone_default(&mut copy)?;
     }
-    if clear_flags.repeat_flag {
-        object.insert("repeatFlag".to_string(), Value::Null);
+
+    let created = client.create_task(&copy).await?;
+    remember_task(cache.as_ref(), &created, Some(&target_project_id));
+    print_created_task(&created, output, verbosity, context)
+}
+
+pub async fn task_uncomplete(
+    args: TaskCompleteArgs,
+    verbosity: Verbosity,
+    context: &OutputContext,
+) -> Result<()> {
+    let TaskCompleteArgs {
+        task_id,
+        project_id,
+        list,
+        output,
+    } = args;
+    // The picker lists open tasks, so reopening still needs an explicit ID.
+    let task_id = task_id.ok_or_else(missing_task_id)?;
+    let (client, cache) = TaskSource::online("task uncomplete")?;
+
+    let (_, project_id, reopened) =
+        reopen_task(&client, cache.as_ref(), &task_id, project_id, list).await?;
+    let status = if reopened { "reopened" } else { "already open" };
+    verbosity.confirm(
+        &format_task_action_output(&task_id, &project_id, status, output, context)?,
+        output,
+    );
+
+    Ok(())
+}
+
+#[derive(Args)]
+pub struct TaskPostponeArgs {
+    #[arg(required = true, num_args = 1.., help = "One or more task IDs")]
+    task_ids: Vec<String>,
+    #[arg(help = "Offset like 3h, 1d, 2w, or a date like tomorrow, 'next monday', 2026-03-01")]
+    target: String,
+    #[arg(long)]
+    project_id: Option<String>,
+    #[arg(long)]
+    list: Option<String>,
+    #[arg(long, default_value = "human")]
+    output: MessageFormat,
+}
+
+pub async fn task_postpone(
+    args: TaskPostponeArgs,
+    verbosity: Verbosity,
+    context: &OutputContext,
+) -> Result<()> {
+    let target = parse_postpone_target(&args.target, Local::now().date_naive())
+        .map_err(|err| anyhow!(err))?;
+    let (client, cache) = TaskSource::online("task postpone")?;
+
+    let mut updated_tasks = Vec::with_capacity(args.task_ids.len());
+    for task_id in &args.task_ids {
+        let (mut task, project_id) = fetch_task_with_project(
+            &client,
+            cache.as_ref(),
+            task_id,
+            args.project_id.clone(),
+            args.list.clone(),
+        )
+        .await?;
+
+        postpone_task(&mut task, target, &Local)?;
+        let payload = build_task_update_payload(&task, TaskUpdateClearFlags::default())?;
+        let updated = client.update_task(task_id, &payload).await?;
+        remember_task(cache.as_ref(), &updated, Some(&project_id));
+        updated_tasks.push(updated);
     }
-    if clear_flags.sort_order {
-        object.insert("sortOrder".to_string(), Value::Null);
+
+    verbosity.confirm(
+        &format_task_postpone_output(&updated_tasks, args.output, context)?,
+        args.output,
+    );
+    Ok(())
+}
+
+fn format_task_postpone_output(
+    tasks: &[Task],
+    format: MessageFormat,
+    context: &OutputContext,
+) -> Result<String> {
+    match format {
+        MessageFormat::Json => Ok(format!("{}\n", context.to_json_string(tasks)?)),
+        MessageFormat::Human => Ok(tasks
+            .iter()
+            .map(|task| {
+                let due = task
+                    .due_date
+                    .as_deref()
+                    .and_then(|due| local_task_date(&Local, due))
+                    .map(|date| date.to_string())
+                    .unwrap_or_default();
+                format!("Task postponed: {} -> {}\n", task.title, due)
+            })
+            .collect()),
     }
+}
+
+#[derive(Args)]
+pub struct TaskDeferArgs {
+    task_id: String,
+    #[arg(
+        value_parser = parse_defer_spec,
+        help = "How far to push the due date, from the current due date (or today): 1d, 1w, tomorrow, friday"
+    )]
+    spec: DeferSpec,
+    #[arg(long)]
+    project_id: Option<String>,
+    #[arg(long)]
+    list: Option<String>,
+    #[arg(long, default_value = "human")]
+    output: MessageFormat,
+}
+
+pub async fn task_defer(
+    args: TaskDeferArgs,
+    verbosity: Verbosity,
+    context: &OutputContext,
+) -> Result<()> {
+    let (client, cache) = TaskSource::online("task defer")?;
+    let (mut task, project_id) = fetch_task_with_project(
+        &client,
+        cache.as_ref(),
+        &args.task_id,
+        args.project_id,
+        args.list,
+    )
+    .await?;
 
-    Ok(payload)
-}
+    let previous_due = task.due_date.clone();
+    let previous = describe_task_due(&task, &Local);
+    defer_task(&mut task, args.spec, Local::now().date_naive(), &Local)?;
+    let payload = build_task_update_payload(&task, TaskUpdateClearFlags::default())?;
+    let updated = client.update_task(&args.task_id, &payload).await?;
+    remember_task(cache.as_ref(), &updated, Some(&project_id));
 
-pub async fn task_update(args: TaskUpdateArgs) -> Result<()> {
-    let TaskUpdateArgs {
-        task_id,
-        project_id,
-        list,
-        title,
-        content,
-        desc,
-        start_date,
-        due_date,
-        time_zone,
-        all_day,
-        priority,
-        tags,
-        reminders,
-        status,
-        repeat_flag,
-        sort_order,
-        clear_start_date,
-        clear_due_date,
-        clear_time_zone,
-        clear_tags,
-        clear_reminders,
-        clear_repeat_flag,
-        clear_sort_order,
-        output,
-    } = args;
+    let rendered = match args.output {
+        MessageFormat::Json => format!(
+            "{}\n",
+            context.to_json_string(&serde_json::json!({
+                "task": updated,
+                "previousDueDate": previous_due,
+            }))?
+        ),
+        MessageFormat::Human => {
+            format!(
+                "Task deferred: {} ({} -> {})\n",
+                updated.title,
+                previous,
+                describe_task_due(&updated, &Local)
+            )
+        }
+    };
+    verbosity.confirm(&rendered, args.output);
+    Ok(())
+}
 
-    let client = authenticated_client()?;
-    let cache = cache_store();
-    let explicit_scope = project_id.is_some() || list.is_some();
+#[derive(Args)]
+pub struct TaskPrioArgs {
+    task_id: String,
+    #[arg(
+        value_parser = parse_priority_change,
+        help = "up or down one level, or a level: high, medium, low, none"
+    )]
+    change: PriorityChange,
+    #[arg(long)]
+    project_id: Option<String>,
+    #[arg(long)]
+    list: Option<String>,
+    #[arg(long, default_value = "human")]
+    output: MessageFormat,
+}
 
-    let mut resolved = resolve_task_project_id(
+pub async fn task_prio(
+    args: TaskPrioArgs,
+    verbosity: Verbosity,
+    context: &OutputContext,
+) -> Result<()> {
+    let (client, cache) = TaskSource::online("task prio")?;
+    let (mut task, project_id) = fetch_task_with_project(
         &client,
         cache.as_ref(),
-        &task_id,
-        project_id.clone(),
-        list.clone(),
+        &args.task_id,
+        args.project_id,
+        args.list,
     )
     .await?;
 
-    let mut task = match client.get_task(&resolved.project_id, &task_id).await {
-        Ok(task) => task,
-        Err(_) if resolved.from_cache && !explicit_scope => {
-            forget_task_project_id(cache.as_ref(), &task_id);
-            resolved =
-                resolve_task_project_id(&client, cache.as_ref(), &task_id, None, None).await?;
-            client.get_task(&resolved.project_id, &task_id).await?
+    let previous = task.priority.unwrap_or(0);
+    task.priority = Some(step_priority(previous, args.change));
+    let payload = build_task_update_payload(&task, TaskUpdateClearFlags::default())?;
+    let updated = client.update_task(&args.task_id, &payload).await?;
+    remember_task(cache.as_ref(), &updated, Some(&project_id));
+
+    let rendered = match args.output {
+        MessageFormat::Json => format!("{}\n", context.to_json_string(&updated)?),
+        MessageFormat::Human => {
+            format!(
+                "Priority changed: {} ({} -> {})\n",
+                updated.title,
+                priority_name(previous),
+                priority_name(updated.priority.unwrap_or(0))
+            )
         }
-        Err(err) => return Err(err),
     };
+    verbosity.confirm(&rendered, args.output);
+    Ok(())
+}
 
-    if let Some(title) = title {
-        task.title = title;
-    }
-    let note_fields_were_updated = content.is_some() || desc.is_some();
-    let (content, desc) = resolve_task_note_fields(content, desc);
-    if let Some(content) = content {
-        task.content = Some(content);
-    }
-    if let Some(desc) = desc {
-        task.desc = Some(desc);
-    }
-    if clear_start_date {
-        task.start_date = None;
-    }
-    if let Some(start_date) = start_date {
-        task.start_date = Some(start_date);
-    }
-    if clear_due_date {
-        task.due_date = None;
-    }
-    if let Some(due_date) = due_date {
-        task.due_date = Some(due_date);
-    }
-    if clear_time_zone {
-        task.time_zone = None;
-    }
-    if let Some(time_zone) = time_zone {
-        task.time_zone = Some(time_zone);
-    }
-    if let Some(all_day) = all_day {
-        task.is_all_day = Some(all_day);
-    }
-    if let Some(priority) = priority {
-        task.priority = Some(priority);
-    }
-    if clear_tags {
-        task.tags = None;
-    }
-    if !tags.is_empty() {
-        task.tags = Some(tags);
-    }
-    if clear_reminders {
-        task.reminders = None;
-    }
-    if !reminders.is_empty() {
-        task.reminders = Some(reminders);
-    }
-    if let Some(status) = status {
-        task.status = Some(status);
-    }
-    if clear_repeat_flag {
-        task.repeat_flag = None;
-    }
-    if let Some(repeat_flag) = repeat_flag {
-        task.repeat_flag = Some(repeat_flag);
-    }
-    if clear_sort_order {
-        task.sort_order = None;
-    }
-    if let Some(sort_order) = sort_order {
-        task.sort_order = Some(sort_order);
-    }
-    if !clear_time_zone {
-        apply_system_time_zone_default(&mut task)?;
-    }
-    if note_fields_were_updated {
-        if task.content.is_none() {
-            task.content = task.desc.clone();
+#[derive(Args)]
+pub struct TaskOpenArgs {
+    task_id: String,
+    #[arg(long)]
+    project_id: Option<String>,
+    #[arg(long)]
+    list: Option<String>,
+    #[arg(long, help = "Print the web app URL instead of opening it")]
+    print: bool,
+}
+
+pub async fn task_open(args: TaskOpenArgs, verbosity: Verbosity) -> Result<()> {
+    let (task, project_id) = match TaskSource::open()? {
+        TaskSource::Api { client, cache } => {
+            fetch_task_with_project(
+                &client,
+                cache.as_ref(),
+                &args.task_id,
+                args.project_id,
+                args.list,
+            )
+            .await?
         }
-        if task.desc.is_none() {
-            task.desc = task.content.clone();
+        TaskSource::Snapshot(snapshot) => {
+            let task = snapshot_task(&snapshot, &args.task_id)?;
+            let project_id = task.project_id.clone().unwrap_or_default();
+            (task, project_id)
         }
-    } else {
-        sync_task_note_fields(&mut task);
-    }
-
-    let payload = build_task_update_payload(
-        &task,
-        TaskUpdateClearFlags {
-            start_date: clear_start_date,
-            due_date: clear_due_date,
-            time_zone: clear_time_zone,
-            tags: clear_tags,
-            reminders: clear_reminders,
-            repeat_flag: clear_repeat_flag,
-            sort_order: clear_sort_order,
-        },
-    )?;
-    let updated = client.update_task(&task_id, &payload).await?;
-    remember_task(cache.as_ref(), &updated, Some(&resolved.project_id));
-
-    print!("{}", format_task_update_output(&updated, output)?);
+    };
 
+    let project_id = task
+        .project_id
+        .as_deref()
+        .filter(|id| !id.is_empty())
+        .unwrap_or(&project_id);
+    open_or_print(
+        &task_web_url(project_id, &args.task_id),
+        args.print,
+        verbosity,
+    );
     Ok(())
 }
 
 #[derive(Args)]
-pub struct TaskCompleteArgs {
+pub struct TaskTagArgs {
     task_id: String,
+    #[arg(
+        required = true,
+        allow_hyphen_values = true,
+        value_parser = parse_tag_change,
+        help = "Tags to add (+#tag) or remove (-#tag); other tags are kept"
+    )]
+    changes: Vec<TagChange>,
     #[arg(long)]
     project_id: Option<String>,
     #[arg(long)]
     list: Option<String>,
     #[arg(long, default_value = "human")]
-    output: OutputFormat,
+    output: MessageFormat,
 }
 
-pub async fn task_complete(args: TaskCompleteArgs) -> Result<()> {
-    let TaskCompleteArgs {
-        task_id,
-        project_id,
-        list,
-        output,
-    } = args;
-    let client = authenticated_client()?;
-    let cache = cache_store();
-    let explicit_scope = project_id.is_some() || list.is_some();
+pub async fn task_tag(
+    args: TaskTagArgs,
+    verbosity: Verbosity,
+    context: &OutputContext,
+) -> Result<()> {
+    let (client, cache) = TaskSource::online("task tag")?;
+    let (mut task, project_id) = fetch_task_with_project(
+        &client,
+        cache.as_ref(),
+        &args.task_id,
+        args.project_id,
+        args.list,
+    )
+    .await?;
 
-    let mut resolved =
-        resolve_task_project_id(&client, cache.as_ref(), &task_id, project_id, list).await?;
+    let (mut additions, mut removals) = (Vec::new(), Vec::new());
+    for change in args.changes {
+        match change {
+            TagChange::Add(tag) => additions.push(tag),
+            TagChange::Remove(tag) => removals.push(tag),
+        }
+    }
+    apply_tag_changes(&mut task, additions, &removals);
+    let payload = build_task_update_payload(&task, TaskUpdateClearFlags::default())?;
+    let updated = client.update_task(&args.task_id, &payload).await?;
+    remember_task(cache.as_ref(), &updated, Some(&project_id));
+
+    let rendered = match args.output {
+        MessageFormat::Json => format!("{}\n", context.to_json_string(&updated)?),
+        MessageFormat::Human => {
+            let tags = updated.tags.as_deref().unwrap_or_default();
+            let tags = if tags.is_empty() {
+                "no tags".to_string()
+            } else {
+                tags.iter()
+                    .map(|tag| format!("#{}", tag))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            };
+            format!("Tags updated: {} ({})\n", updated.title, tags)
+        }
+    };
+    verbosity.confirm(&rendered, args.output);
+    Ok(())
+}
 
-    if let Err(err) = client.complete_task(&resolved.project_id, &task_id).await {
-        if resolved.from_cache && !explicit_scope {
-            forget_task_project_id(cache.as_ref(), &task_id);
-            resolved =
-                resolve_task_project_id(&client, cache.as_ref(), &task_id, None, None).await?;
-            client.complete_task(&resolved.project_id, &task_id).await?;
-        } else {
-            return Err(err);
+#[derive(Args)]
+pub struct TaskBatchUpdateArgs {
+    #[arg(
+        long,
+        value_delimiter = ',',
+        help = "Comma-separated task IDs; without it, IDs are read from stdin one per line"
+    )]
+    ids: Vec<String>,
+    #[arg(
+        long,
+        value_parser = parse_priority_value,
+        help = "Priority: none, low, medium, high (or 0, 1, 3, 5)"
+    )]
+    priority: Option<i32>,
+    #[arg(long, help = "Add a tag, keeping the existing ones (repeatable)")]
+    tag_add: Vec<String>,
+    #[arg(long, help = "Remove a tag, ignoring case (repeatable)")]
+    tag_remove: Vec<String>,
+    #[arg(long, value_name = "LIST", help = "Move every task to this list")]
+    to_list: Option<String>,
+    #[arg(long, help = "Show what would change without updating anything")]
+    dry_run: bool,
+    #[arg(long, default_value = "human")]
+    output: MessageFormat,
+}
+
+pub async fn task_batch_update(args: TaskBatchUpdateArgs, context: &OutputContext) -> Result<()> {
+    let mut plan = BatchUpdatePlan {
+        priority: args.priority,
+        tag_add: args
+            .tag_add
+            .iter()
+            .map(|tag| tag.trim_start_matches('#').to_string())
+            .collect(),
+        tag_remove: args
+            .tag_remove
+            .iter()
+            .map(|tag| tag.trim_start_matches('#').to_string())
+            .collect(),
+        to_project: None,
+    };
+    if plan.is_empty() && args.to_list.is_none() {
+        return Err(anyhow!(
+            "Nothing to change; pass --priority, --tag-add, --tag-remove, or --to-list"
+        ));
+    }
+
+    let ids = if args.ids.is_empty() {
+        if atty::is(Stream::Stdin) {
+            return Err(anyhow!(
+                "Pass --ids or pipe task IDs on stdin, one per line"
+            ));
         }
+        let mut input = String::new();
+        io::stdin().read_to_string(&mut input)?;
+        parse_task_ids(&input)
+    } else {
+        parse_task_ids(&args.ids.join("\n"))
+    };
+    if ids.is_empty() {
+        return Err(anyhow!("No task IDs given"));
     }
-    remember_task_project_id(cache.as_ref(), &task_id, &resolved.project_id);
+
+    let (client, cache) = TaskSource::online("task batch-update")?;
+    if let Some(list) = args.to_list {
+        let project_id = resolve_create_project_id(
+            &client,
+            cache.as_ref(),
+            None,
+            false,
+            None,
+            Some(list.clone()),
+        )
+        .await?
+        .ok_or_else(|| anyhow!("List not found: {}", list))?;
+        plan.to_project = Some((project_id, list));
+    }
+
+    let mut results = Vec::with_capacity(ids.len());
+    for id in ids {
+        let outcome = async {
+            let (mut task, project_id) =
+                fetch_task_with_project(&client, cache.as_ref(), &id, None, None).await?;
+            let title = task.title.clone();
+            let changes = apply_batch_update(&mut task, &plan);
+            if changes.is_empty() || args.dry_run {
+                return Ok::<_, anyhow::Error>((title, changes));
+            }
+
+            let payload = build_task_update_payload(&task, TaskUpdateClearFlags::default())?;
+            let updated = client.update_task(&id, &payload).await?;
+            let project_id = updated
+                .project_id
+                .clone()
+                .filter(|id| !id.is_empty())
+                .unwrap_or(project_id);
+            remember_task(cache.as_ref(), &updated, Some(&project_id));
+            Ok((title, changes))
+        }
+        .await;
+
+        results.push(match outcome {
+            Ok((title, changes)) => BatchUpdateResult {
+                id,
+                title: Some(title),
+                status: if changes.is_empty() {
+                    BatchUpdateStatus::Unchanged
+                } else {
+                    BatchUpdateStatus::Updated
+                },
+                changes,
+                error: None,
+            },
+            Err(err) => BatchUpdateResult {
+                id,
+                title: None,
+                status: BatchUpdateStatus::Failed,
+                changes: Vec::new(),
+                error: Some(err.to_string()),
+            },
+        });
+    }
+
     print!(
         "{}",
-        format_task_action_output(&task_id, &resolved.project_id, "completed", output)?
+        format_batch_update_report(
+            &results,
+            args.dry_run,
+            args.output == MessageFormat::Json,
+            context
+        )?
     );
 
+    let failed = batch_update_failures(&results);
+    if failed > 0 {
+        return Err(anyhow!("{} of {} tasks failed", failed, results.len()));
+    }
     Ok(())
 }
 
 #[derive(Args)]
 pub struct TaskDeleteArgs {
-    task_id: String,
+    #[arg(help = "Task ID; omit it in a terminal to pick an open task interactively")]
+    task_id: Option<String>,
     #[arg(long)]
     project_id: Option<String>,
     #[arg(long)]
     list: Option<String>,
     #[arg(long, default_value = "true")]
     confirm: bool,
+    #[arg(
+        long,
+        visible_alias = "yes",
+        short = 'y',
+        help = "Skip the confirmation prompt"
+    )]
+    force: bool,
     #[arg(long, default_value = "human")]
-    output: OutputFormat,
+    output: MessageFormat,
 }
 
-pub async fn task_delete(args: TaskDeleteArgs) -> Result<()> {
+pub async fn task_delete(
+    args: TaskDeleteArgs,
+    verbosity: Verbosity,
+    context: &OutputContext,
+) -> Result<()> {
     let TaskDeleteArgs {
         task_id,
-        project_id,
-        list,
+        mut project_id,
+        mut list,
         confirm,
+        force,
         output,
     } = args;
-    let client = authenticated_client()?;
-    let cache = cache_store();
+    let confirm = confirm && !force;
+    let (client, cache) = TaskSource::online("task delete")?;
+    let task_id =
+        task_id_or_pick(&client, cache.as_ref(), task_id, &mut project_id, &mut list).await?;
     let explicit_scope = project_id.is_some() || list.is_some();
     let mut resolved =
         resolve_task_project_id(&client, cache.as_ref(), &task_id, project_id, list).await?;
 
     if confirm {
-        println!("Are you sure you want to delete task '{}'? [y/N]", task_id);
+        ensure_can_prompt(atty::is(Stream::Stdin), output)?;
+        eprintln!("Are you sure you want to delete task '{}'? [y/N]", task_id);
         let mut input = String::new();
         io::stdin().read_line(&mut input)?;
         if !input.trim().eq_ignore_ascii_case("y") {
-            println!("Cancelled.");
+            eprintln!("Cancelled.");
             return Ok(());
         }
     }
 
+    // The journal needs the full task to recreate it; a stale project ID just skips it.
+    let snapshot = if journal_enabled() {
+        client.get_task(&resolved.project_id, &task_id).await.ok()
+    } else {
+        None
+    };
+
     if let Err(err) = client.delete_task(&resolved.project_id, &task_id).await {
         if resolved.from_cache && !explicit_scope {
             forget_task_project_id(cache.as_ref(), &task_id);
@@ -732,18 +2991,31 @@ pub async fn task_delete(args: TaskDeleteArgs) -> Result<()> {
         }
     }
     forget_task_project_id(cache.as_ref(), &task_id);
-    print!(
-        "{}",
-        format_task_action_output(&task_id, &resolved.project_id, "deleted", output)?
+    if let Some(task) = snapshot {
+        record_operation(
+            cache.as_ref(),
+            JournalOperation::TaskDeleted {
+                task,
+                project_id: resolved.project_id.clone(),
+            },
+        );
+    }
+    verbosity.confirm(
+        &format_task_action_output(&task_id, &resolved.project_id, "deleted", output, context)?,
+        output,
     );
 
     Ok(())
 }
 
-fn format_task_create_output(task: &Task, format: OutputFormat) -> Result<String> {
+fn format_task_create_output(
+    task: &Task,
+    format: MessageFormat,
+    context: &OutputContext,
+) -> Result<String> {
     match format {
-        OutputFormat::Json => Ok(format!("{}\n", serde_json::to_string_pretty(task)?)),
-        OutputFormat::Human => Ok(format!(
+        MessageFormat::Json => Ok(format!("{}\n", context.to_json_string(task)?)),
+        MessageFormat::Human => Ok(format!(
             "Task created: {}\nID: {}\n",
             task.title,
             task.id.clone().unwrap_or_default()
@@ -751,32 +3023,79 @@ fn format_task_create_output(task: &Task, format: OutputFormat) -> Result<String
     }
 }
 
-fn format_task_update_output(task: &Task, format: OutputFormat) -> Result<String> {
+fn format_task_update_output(
+    task: &Task,
+    format: MessageFormat,
+    context: &OutputContext,
+) -> Result<String> {
+    match format {
+        MessageFormat::Json => Ok(format!("{}\n", context.to_json_string(task)?)),
+        MessageFormat::Human => Ok(format!("Task updated: {}\n", task.title)),
+    }
+}
+
+fn format_checklist_output(
+    items: &[ChecklistItem],
+    message: Option<&str>,
+    format: MessageFormat,
+    context: &OutputContext,
+) -> Result<String> {
     match format {
-        OutputFormat::Json => Ok(format!("{}\n", serde_json::to_string_pretty(task)?)),
-        OutputFormat::Human => Ok(format!("Task updated: {}\n", task.title)),
+        MessageFormat::Json => Ok(format!("{}\n", context.to_json_string(items)?)),
+        MessageFormat::Human => Ok(match message {
+            Some(message) => format!("{}\n", message),
+            None => format_checklist_lines(items),
+        }),
     }
 }
 
-fn format_task_info_output(task: &Task, format: OutputFormat) -> Result<String> {
+fn format_task_info_output(
+    task: &Task,
+    format: OutputFormat,
+    context: &OutputContext,
+) -> Result<String> {
     match format {
-        OutputFormat::Json => Ok(format!("{}\n", serde_json::to_string_pretty(task)?)),
-        OutputFormat::Human => Ok(format_task_info_human(task)),
+        OutputFormat::Json => Ok(format!("{}\n", context.to_json_string(task)?)),
+        OutputFormat::Markdown => Ok(render_task_markdown(task, true)),
+        // One row, with the same columns as `tt task list`.
+        OutputFormat::Csv | OutputFormat::Tsv => Ok(render_tasks(
+            std::slice::from_ref(task),
+            format,
+            TaskListOptions::default(),
+            context,
+        )),
+        OutputFormat::Human => Ok(format_task_info_human(task, context.color)),
     }
 }
 
-fn format_task_info_human(task: &Task) -> String {
+/// The Priority and Due lines use the table's colors when `color` is on.
+fn format_task_info_human(task: &Task, color: bool) -> String {
     let mut output = String::new();
     output.push_str(&format!("Task: {}\n", task.title));
     push_optional_line(&mut output, "ID", task.id.as_deref());
     push_optional_line(&mut output, "Project ID", task.project_id.as_deref());
+    push_optional_line(&mut output, "Parent ID", task.parent_id.as_deref());
     output.push_str(&format!("Status: {}\n", task_status_label(task.status)));
+    let priority = task.priority.unwrap_or(0);
+    let priority_label = priority_name(priority);
     output.push_str(&format!(
         "Priority: {}\n",
-        task_priority_label(task.priority.unwrap_or(0))
+        match priority_ansi(priority) {
+            Some(ansi) => paint(&priority_label, ansi, color),
+            None => priority_label,
+        }
     ));
     push_optional_line(&mut output, "Start", task.start_date.as_deref());
-    push_optional_line(&mut output, "Due", task.due_date.as_deref());
+    push_optional_line(
+        &mut output,
+        "Due",
+        task.due_date.as_deref().map(|due| {
+            match due_ansi(task, Local::now().date_naive(), &Local) {
+                Some(ansi) => paint(due, ansi, color),
+                None => due.to_string(),
+            }
+        }),
+    );
     if let Some(is_all_day) = task.is_all_day {
         output.push_str(&format!("All day: {}\n", is_all_day));
     }
@@ -789,11 +3108,19 @@ fn format_task_info_human(task: &Task) -> String {
     push_optional_line(
         &mut output,
         "Reminders",
-        task.reminders
-            .as_ref()
-            .map(|reminders| reminders.join(", ")),
+        task.reminders.as_ref().map(|reminders| {
+            reminders
+                .iter()
+                .map(|reminder| describe_reminder(reminder))
+                .collect::<Vec<_>>()
+                .join(", ")
+        }),
+    );
+    push_optional_line(
+        &mut output,
+        "Repeat",
+        task.repeat_flag.as_deref().map(describe_repeat_flag),
     );
-    push_optional_line(&mut output, "Repeat", task.repeat_flag.as_deref());
     if let Some(sort_order) = task.sort_order {
         output.push_str(&format!("Sort order: {}\n", sort_order));
     }
@@ -858,31 +3185,22 @@ fn task_status_label(status: Option<TaskStatus>) -> &'static str {
     }
 }
 
-fn task_priority_label(priority: i32) -> String {
-    match priority {
-        0 => "none".to_string(),
-        1 => "low".to_string(),
-        3 => "medium".to_string(),
-        5 => "high".to_string(),
-        value => value.to_string(),
-    }
-}
-
 fn format_task_action_output(
     task_id: &str,
     project_id: &str,
     status: &str,
-    format: OutputFormat,
+    format: MessageFormat,
+    context: &OutputContext,
 ) -> Result<String> {
     match format {
-        OutputFormat::Json => Ok(format!(
+        MessageFormat::Json => Ok(format!(
             "{}\n",
-            serde_json::to_string_pretty(&serde_json::json!({
+            context.to_json_string(&serde_json::json!({
                 "status": status,
                 "taskId": task_id,
                 "projectId": project_id,
             }))?
         )),
-        OutputFormat::Human => Ok(format!("Task {}: {}\n", status, task_id)),
+        MessageFormat::Human => Ok(format!("Task {}: {}\n", status, task_id)),
     }
 }
@@ -0,0 +1,273 @@
+use super::dates::parse_ticktick_datetime;
+use super::groups::task_project_label;
+use super::offline::TaskSource;
+use super::postpone::local_task_date;
+use super::{filter_task_list, TaskListArgs};
+use crate::models::Task;
+use crate::output::{
+    paint, priority_ansi, render_task_groups, OutputContext, OutputFormat, TaskGroup,
+    TaskListOptions, ANSI_DIM,
+};
+use anyhow::Result;
+use chrono::{Duration, Local, NaiveDate, NaiveDateTime, TimeZone};
+use clap::Args;
+use std::collections::HashMap;
+use std::io;
+
+#[derive(Args)]
+pub struct AgendaArgs {
+    #[arg(long)]
+    project_id: Option<String>,
+    #[arg(long)]
+    list: Option<String>,
+    #[arg(long, help = "Only tasks with all of these tags (repeatable)")]
+    tags: Vec<String>,
+    #[arg(long, default_value = "human")]
+    output: OutputFormat,
+}
+
+#[derive(Args)]
+pub struct UpcomingArgs {
+    #[arg(
+        default_value = "7",
+        value_parser = clap::value_parser!(u32).range(1..=366),
+        help = "How many days to show, starting today"
+    )]
+    days: u32,
+    #[command(flatten)]
+    agenda: AgendaArgs,
+}
+
+/// The local date and time a task is planned for: its due date, else its start date.
+/// All-day tasks have no time, so they sort ahead of timed tasks on the same day.
+fn agenda_slot<Tz: TimeZone>(task: &Task, tz: &Tz) -> Option<(NaiveDate, Option<NaiveDateTime>)> {
+    let value = task.due_date.as_deref().or(task.start_date.as_deref())?;
+    let date = local_task_date(tz, value)?;
+    let time = match task.is_all_day {
+        Some(true) => None,
+        _ => parse_ticktick_datetime(value).map(|dt| dt.with_timezone(tz).naive_local()),
+    };
+    Some((date, time))
+}
+
+fn sort_agenda<Tz: TimeZone>(tasks: &mut [Task], tz: &Tz) {
+    tasks.sort_by_key(|task| {
+        let slot = agenda_slot(task, tz);
+        (
+            slot.map(|(date, _)| date),
+            slot.and_then(|(_, time)| time),
+            std::cmp::Reverse(task.priority.unwrap_or(0)),
+        )
+    });
+}
+
+fn push_group(groups: &mut Vec<TaskGroup>, key: String, tasks: Vec<Task>) {
+    if !tasks.is_empty() {
+        groups.push(TaskGroup { key, tasks });
+    }
+}
+
+/// `tt today`: everything overdue, then everything due today, by local date.
+pub(super) fn today_groups<Tz: TimeZone>(
+    mut tasks: Vec<Task>,
+    today: NaiveDate,
+    tz: &Tz,
+) -> Vec<TaskGroup> {
+    sort_agenda(&mut tasks, tz);
+    let (mut overdue, mut due_today) = (Vec::new(), Vec::new());
+    for task in tasks {
+        match agenda_slot(&task, tz).map(|(date, _)| date) {
+            Some(date) if date < today => overdue.push(task),
+            Some(date) if date == today => due_today.push(task),
+            _ => {}
+        }
+    }
+
+    let mut groups = Vec::new();
+    push_group(&mut groups, "Overdue".to_string(), overdue);
+    push_group(&mut groups, "Today".to_string(), due_today);
+    groups
+}
+
+fn day_heading(date: NaiveDate, today: NaiveDate) -> String {
+    let label = date.format("%a %b %-d");
+    match (date - today).num_days() {
+        0 => format!("Today · {}", label),
+        1 => format!("Tomorrow · {}", label),
+        _ => label.to_string(),
+    }
+}
+
+/// `tt upcoming`: one section per day from today through `days - 1` days ahead. Days
+/// with nothing due are left out.
+pub(super) fn upcoming_groups<Tz: TimeZone>(
+    mut tasks: Vec<Task>,
+    today: NaiveDate,
+    days: u32,
+    tz: &Tz,
+) -> Vec<TaskGroup> {
+    sort_agenda(&mut tasks, tz);
+    let last = today + Duration::days(i64::from(days) - 1);
+    let mut groups: Vec<(NaiveDate, Vec<Task>)> = Vec::new();
+    for task in tasks {
+        let Some((date, _)) = agenda_slot(&task, tz) else {
+            continue;
+        };
+        if date < today || date > last {
+            continue;
+        }
+        match groups.last_mut() {
+            Some((group_date, group)) if *group_date == date => group.push(task),
+            _ => groups.push((date, vec![task])),
+        }
+    }
+
+    groups
+        .into_iter()
+        .map(|(date, tasks)| TaskGroup {
+            key: day_heading(date, today),
+            tasks,
+        })
+        .collect()
+}
+
+fn priority_marker(priority: i32, color: bool) -> String {
+    let label = match priority {
+        5 => "!high",
+        3 => "!medium",
+        1 => "!low",
+        _ => return String::new(),
+    };
+    match priority_ansi(priority) {
+        Some(ansi) => format!("  {}", paint(label, ansi, color)),
+        None => format!("  {}", label),
+    }
+}
+
+fn agenda_line<Tz: TimeZone>(
+    task: &Task,
+    today: NaiveDate,
+    project_names: Option<&HashMap<String, String>>,
+    tz: &Tz,
+    color: bool,
+) -> String {
+    let when = match agenda_slot(task, tz) {
+        Some((date, _)) if date < today => date.format("%b %-d").to_string(),
+        Some((_, Some(time))) => time.format("%H:%M").to_string(),
+        _ => String::new(),
+    };
+    let list = project_names
+        .map(|names| {
+            paint(
+                &format!("  {}", task_project_label(task, names)),
+                ANSI_DIM,
+                color,
+            )
+        })
+        .unwrap_or_default();
+
+    format!(
+        "  {:<6}  {}{}{}\n",
+        when,
+        task.title,
+        priority_marker(task.priority.unwrap_or(0), color),
+        list
+    )
+}
+
+/// Renders agenda sections. Human output gives each task its local due time (or date,
+/// when overdue), priority, and list; JSON is the `--group-by` document.
+pub(super) fn render_agenda<Tz: TimeZone>(
+    groups: &[TaskGroup],
+    empty_message: &str,
+    format: OutputFormat,
+    today: NaiveDate,
+    project_names: Option<&HashMap<String, String>>,
+    tz: &Tz,
+    context: &OutputContext,
+) -> String {
+    if matches!(format, OutputFormat::Json) {
+        return render_task_groups(groups, format, TaskListOptions::default(), context);
+    }
+    if groups.is_empty() {
+        return format!("{}\n", empty_message);
+    }
+
+    groups
+        .iter()
+        .map(|group| {
+            let mut section = format!("{} ({})\n", group.key, group.tasks.len());
+            for task in &group.tasks {
+                section.push_str(&agenda_line(task, today, project_names, tz, context.color));
+            }
+            section
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+async fn agenda_tasks(args: &AgendaArgs) -> Result<(TaskSource, Vec<Task>)> {
+    let source = TaskSource::open()?;
+    let mut list_args = TaskListArgs {
+        project_id: args.project_id.clone(),
+        list: args.list.clone(),
+        tags: args.tags.clone(),
+        ..Default::default()
+    };
+    let (tasks, _) = filter_task_list(&source, &mut list_args).await?;
+    Ok((source, tasks))
+}
+
+/// The list column is left out when `--list` already names the list.
+async fn agenda_project_names(
+    source: &TaskSource,
+    args: &AgendaArgs,
+) -> Result<Option<HashMap<String, String>>> {
+    if args.list.is_some() || args.project_id.is_some() || matches!(args.output, OutputFormat::Json)
+    {
+        return Ok(None);
+    }
+    source.project_names().await.map(Some)
+}
+
+pub async fn today(args: AgendaArgs, context: &OutputContext) -> Result<()> {
+    let (source, tasks) = agenda_tasks(&args).await?;
+    let today = Local::now().date_naive();
+    let groups = today_groups(tasks, today, &Local);
+    let project_names = agenda_project_names(&source, &args).await?;
+
+    let rendered = render_agenda(
+        &groups,
+        "Nothing due today.",
+        args.output,
+        today,
+        project_names.as_ref(),
+        &Local,
+        context,
+    );
+    let _ = io::Write::write_all(&mut io::stdout(), rendered.as_bytes());
+    Ok(())
+}
+
+pub async fn upcoming(args: UpcomingArgs, context: &OutputContext) -> Result<()> {
+    let (source, tasks) = agenda_tasks(&args.agenda).await?;
+    let today = Local::now().date_naive();
+    let groups = upcoming_groups(tasks, today, args.days, &Local);
+    let project_names = agenda_project_names(&source, &args.agenda).await?;
+
+    let empty_message = match args.days {
+        1 => "Nothing due today.".to_string(),
+        days => format!("Nothing due in the next {} days.", days),
+    };
+    let rendered = render_agenda(
+        &groups,
+        &empty_message,
+        args.agenda.output,
+        today,
+        project_names.as_ref(),
+        &Local,
+        context,
+    );
+    let _ = io::Write::write_all(&mut io::stdout(), rendered.as_bytes());
+    Ok(())
+}
@@ -0,0 +1,209 @@
+use super::filters::{apply_tag_changes, priority_name};
+use crate::models::Task;
+use crate::output::OutputContext;
+use anyhow::Result;
+use serde::Serialize;
+
+/// Task IDs for `tt task batch-update`, one per line. Only the first field is used, so the
+/// `id|title` lines `tt ls` prints when piped work as is; repeats are dropped.
+pub(super) fn parse_task_ids(input: &str) -> Vec<String> {
+    let mut ids: Vec<String> = Vec::new();
+    for line in input.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let id = line
+            .split(|ch: char| ch == '|' || ch.is_whitespace())
+            .next()
+            .unwrap_or_default();
+        if !id.is_empty() && !ids.iter().any(|seen| seen == id) {
+            ids.push(id.to_string());
+        }
+    }
+    ids
+}
+
+/// The changes `tt task batch-update` applies to every task. `to_project` carries the
+/// resolved project ID and the list name to show for it.
+#[derive(Debug, Default)]
+pub(super) struct BatchUpdatePlan {
+    pub(super) priority: Option<i32>,
+    pub(super) tag_add: Vec<String>,
+    pub(super) tag_remove: Vec<String>,
+    pub(super) to_project: Option<(String, String)>,
+}
+
+impl BatchUpdatePlan {
+    pub(super) fn is_empty(&self) -> bool {
+        self.priority.is_none()
+            && self.tag_add.is_empty()
+            && self.tag_remove.is_empty()
+            && self.to_project.is_none()
+    }
+}
+
+fn task_tag_set(task: &Task) -> Vec<String> {
+    task.tags
+        .as_deref()
+        .unwrap_or_default()
+        .iter()
+        .map(|tag| tag.to_lowercase())
+        .collect()
+}
+
+/// Applies `plan` to `task` and describes what actually changed; an empty list means the
+/// task already matched and needs no update.
+pub(super) fn apply_batch_update(task: &mut Task, plan: &BatchUpdatePlan) -> Vec<String> {
+    let mut changes = Vec::new();
+
+    if let Some(priority) = plan.priority {
+        let previous = task.priority.unwrap_or(0);
+        if previous != priority {
+            task.priority = Some(priority);
+            changes.push(format!(
+                "priority {} -> {}",
+                priority_name(previous),
+                priority_name(priority)
+            ));
+        }
+    }
+
+    if !plan.tag_add.is_empty() || !plan.tag_remove.is_empty() {
+        let before = task_tag_set(task);
+        apply_tag_changes(task, plan.tag_add.clone(), &plan.tag_remove);
+        let after = task_tag_set(task);
+        for tag in task.tags.as_deref().unwrap_or_default() {
+            if !before.contains(&tag.to_lowercase()) {
+                changes.push(format!("+#{}", tag));
+            }
+        }
+        for tag in &plan.tag_remove {
+            if before.contains(&tag.to_lowercase()) && !after.contains(&tag.to_lowercase()) {
+                changes.push(format!("-#{}", tag));
+            }
+        }
+    }
+
+    if let Some((project_id, list)) = plan.to_project.as_ref() {
+        if task.project_id.as_deref() != Some(project_id.as_str()) {
+            task.project_id = Some(project_id.clone());
+            changes.push(format!("moved to {}", list));
+        }
+    }
+
+    changes
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(super) enum BatchUpdateStatus {
+    Updated,
+    Unchanged,
+    Failed,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct BatchUpdateResult {
+    pub(super) id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) title: Option<String>,
+    pub(super) status: BatchUpdateStatus,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub(super) changes: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) error: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BatchUpdateReport<'a> {
+    dry_run: bool,
+    results: &'a [BatchUpdateResult],
+    updated: usize,
+    unchanged: usize,
+    failed: usize,
+}
+
+fn count_status(results: &[BatchUpdateResult], status: BatchUpdateStatus) -> usize {
+    results
+        .iter()
+        .filter(|result| result.status == status)
+        .count()
+}
+
+pub(super) fn batch_update_failures(results: &[BatchUpdateResult]) -> usize {
+    count_status(results, BatchUpdateStatus::Failed)
+}
+
+/// IDs of the failed updates, in report order, so they can be retried.
+pub(super) fn batch_update_failed_ids(results: &[BatchUpdateResult]) -> Vec<&str> {
+    results
+        .iter()
+        .filter(|result| result.status == BatchUpdateStatus::Failed)
+        .map(|result| result.id.as_str())
+        .collect()
+}
+
+pub(super) fn format_batch_update_report(
+    results: &[BatchUpdateResult],
+    dry_run: bool,
+    json: bool,
+    context: &OutputContext,
+) -> Result<String> {
+    let updated = count_status(results, BatchUpdateStatus::Updated);
+    let unchanged = count_status(results, BatchUpdateStatus::Unchanged);
+    let failed = batch_update_failures(results);
+
+    if json {
+        let report = BatchUpdateReport {
+            dry_run,
+            results,
+            updated,
+            unchanged,
+            failed,
+        };
+        return Ok(format!("{}\n", context.to_json_string(&report)?));
+    }
+
+    let total = results.len();
+    let mut output = String::new();
+    for (index, result) in results.iter().enumerate() {
+        let title = result.title.as_deref().unwrap_or(&result.id);
+        let line = match result.status {
+            BatchUpdateStatus::Updated => format!(
+                "[{}/{}] {}: {} ({})",
+                index + 1,
+                total,
+                if dry_run { "would update" } else { "updated" },
+                title,
+                result.changes.join(", ")
+            ),
+            BatchUpdateStatus::Unchanged => {
+                format!("[{}/{}] unchanged: {}", index + 1, total, title)
+            }
+            BatchUpdateStatus::Failed => format!(
+                "[{}/{}] failed: {}: {}",
+                index + 1,
+                total,
+                title,
+                result.error.as_deref().unwrap_or_default()
+            ),
+        };
+        output.push_str(&line);
+        output.push('\n');
+    }
+
+    if dry_run {
+        output.push_str(&format!(
+            "Dry run: {} of {} tasks would change, {} unchanged, {} failed. Nothing was updated.\n",
+            updated, total, unchanged, failed
+        ));
+    } else {
+        output.push_str(&format!(
+            "Updated {} of {} tasks ({} unchanged, {} failed).\n",
+            updated, total, unchanged, failed
+        ));
+    }
+    Ok(output)
+}
@@ -1,6 +1,7 @@
 use crate::models::Task;
 use chrono::{
-    DateTime, Datelike, Duration, Local, NaiveDate, NaiveDateTime, TimeZone, Utc, Weekday,
+    DateTime, Datelike, Duration, FixedOffset, Local, Months, NaiveDate, NaiveDateTime, NaiveTime,
+    TimeZone, Utc, Weekday,
 };
 
 #[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
@@ -13,7 +14,14 @@ pub enum TaskWhenFilter {
     ThisWeek,
 }
 
-fn normalize_date_token(token: &str) -> String {
+/// `tt task list --sort`: newest first, tasks without the timestamp last.
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TaskSort {
+    Created,
+    Completed,
+}
+
+pub(super) fn normalize_date_token(token: &str) -> String {
     token
         .trim_matches(|ch: char| !ch.is_ascii_alphanumeric() && ch != '/' && ch != '-')
         .to_ascii_lowercase()
@@ -37,7 +45,7 @@ fn parse_year_token(token: &str) -> Option<i32> {
     }
 }
 
-fn parse_day_token(token: &str) -> Option<u32> {
+pub(super) fn parse_day_token(token: &str) -> Option<u32> {
     let day_text = token
         .strip_suffix("st")
         .or_else(|| token.strip_suffix("nd"))
@@ -71,7 +79,7 @@ fn parse_month_token(token: &str) -> Option<u32> {
     }
 }
 
-fn parse_weekday_token(token: &str) -> Option<Weekday> {
+pub(super) fn parse_weekday_token(token: &str) -> Option<Weekday> {
     match token {
         "mon" | "monday" => Some(Weekday::Mon),
         "tue" | "tues" | "tuesday" => Some(Weekday::Tue),
@@ -84,7 +92,7 @@ fn parse_weekday_token(token: &str) -> Option<Weekday> {
     }
 }
 
-fn next_or_same_weekday(today: NaiveDate, target: Weekday) -> NaiveDate {
+pub(super) fn next_or_same_weekday(today: NaiveDate, target: Weekday) -> NaiveDate {
     let today_idx = today.weekday().num_days_from_monday() as i64;
     let target_idx = target.num_days_from_monday() as i64;
     let offset = (target_idx - today_idx + 7) % 7;
@@ -96,6 +104,16 @@ fn start_of_next_week(today: NaiveDate) -> NaiveDate {
     start_of_this_week + Duration::days(7)
 }
 
+fn parse_next_phrase(token: &str, today: NaiveDate) -> Option<NaiveDate> {
+    if token == "week" {
+        return Some(start_of_next_week(today));
+    }
+
+    // "next friday" skips the nearest occurrence: it is always at least a week out.
+    parse_weekday_token(token)
+        .map(|weekday| next_or_same_weekday(today + Duration::days(7), weekday))
+}
+
 fn parse_numeric_date_token(token: &str, today: NaiveDate) -> Option<NaiveDate> {
     if let Ok(date) = NaiveDate::parse_from_str(token, "%Y-%m-%d") {
         return Some(date);
@@ -153,6 +171,125 @@ fn parse_month_day_sequence(
     Some((2, date))
 }
 
+fn parse_quantity_token(token: &str) -> Option<u32> {
+    match token {
+        "a" | "an" | "one" => Some(1),
+        _ => token.parse::<u32>().ok(),
+    }
+}
+
+enum OffsetUnit {
+    Days(u32),
+    Months(u32),
+}
+
+fn parse_offset_unit(token: &str, quantity: u32) -> Option<OffsetUnit> {
+    match token {
+        "day" | "days" => Some(OffsetUnit::Days(quantity)),
+        "week" | "weeks" => Some(OffsetUnit::Days(quantity.checked_mul(7)?)),
+        "month" | "months" => Some(OffsetUnit::Months(quantity)),
+        "year" | "years" => Some(OffsetUnit::Months(quantity.checked_mul(12)?)),
+        _ => None,
+    }
+}
+
+/// Adds calendar months, clamping to the last day of a shorter month, so Jan 31 plus
+/// one month is Feb 28 (or 29 in a leap year).
+pub(super) fn add_months(date: NaiveDate, months: u32) -> Option<NaiveDate> {
+    date.checked_add_months(Months::new(months))
+}
+
+fn apply_offset(today: NaiveDate, unit: OffsetUnit) -> Option<NaiveDate> {
+    match unit {
+        OffsetUnit::Days(days) => today.checked_add_signed(Duration::days(days.into())),
+        OffsetUnit::Months(months) => add_months(today, months),
+    }
+}
+
+fn end_of_month(date: NaiveDate) -> Option<NaiveDate> {
+    add_months(date.with_day(1)?, 1)?.pred_opt()
+}
+
+/// Recognizes `in N days|weeks|months|years` and `N days|weeks|... from now`, where N
+/// may also be `a`/`an`.
+fn parse_offset_phrase(
+    tokens: &[&str],
+    index: usize,
+    today: NaiveDate,
+) -> Option<(usize, NaiveDate)> {
+    let token_at = |offset: usize| {
+        tokens
+            .get(index + offset)
+            .map(|value| normalize_date_token(value))
+    };
+    let first = token_at(0)?;
+
+    if first == "in" {
+        let quantity = parse_quantity_token(&token_at(1)?)?;
+        let unit = parse_offset_unit(&token_at(2)?, quantity)?;
+        return apply_offset(today, unit).map(|date| (3, date));
+    }
+
+    let quantity = parse_quantity_token(&first)?;
+    let unit = parse_offset_unit(&token_at(1)?, quantity)?;
+    if token_at(2)? == "from" && token_at(3)? == "now" {
+        return apply_offset(today, unit).map(|date| (4, date));
+    }
+    None
+}
+
+fn parse_date_phrase(
+    tokens: &[&str],
+    index: usize,
+    today: NaiveDate,
+) -> Option<(usize, NaiveDate)> {
+    let token = tokens.get(index)?;
+    if token.starts_with('#') || token.starts_with('~') || token.starts_with('!') {
+        return None;
+    }
+
+    let normalized = normalize_date_token(token);
+    if normalized.is_empty() {
+        return None;
+    }
+
+    if normalized == "next" {
+        let phrase = tokens
+            .get(index + 1)
+            .map(|value| normalize_date_token(value));
+        let date = match phrase.as_deref() {
+            Some("month") => add_months(today.with_day(1)?, 1),
+            Some(value) => parse_next_phrase(value, today),
+            None => None,
+        };
+        if let Some(date) = date {
+            return Some((2, date));
+        }
+    }
+
+    if let Some(parsed) = parse_offset_phrase(tokens, index, today) {
+        return Some(parsed);
+    }
+
+    if let Some(parsed) = parse_month_day_sequence(tokens, index, today) {
+        return Some(parsed);
+    }
+
+    if let Some(date) = parse_numeric_date_token(&normalized, today) {
+        return Some((1, date));
+    }
+
+    let relative_date = match normalized.as_str() {
+        "today" | "eod" => Some(today),
+        "tomorrow" => Some(today + Duration::days(1)),
+        "yesterday" => Some(today - Duration::days(1)),
+        "eom" => end_of_month(today),
+        "eoy" => NaiveDate::from_ymd_opt(today.year(), 12, 31),
+        _ => parse_weekday_token(&normalized).map(|weekday| next_or_same_weekday(today, weekday)),
+    };
+    relative_date.map(|date| (1, date))
+}
+
 pub(super) fn extract_due_date_from_input(
     raw: &str,
     today: NaiveDate,
@@ -162,26 +299,13 @@ pub(super) fn extract_due_date_from_input(
         return (String::new(), None);
     }
 
-    for (index, token) in tokens.iter().enumerate() {
-        if token.starts_with('#') || token.starts_with('~') || token.starts_with('!') {
-            continue;
-        }
-
-        let normalized = normalize_date_token(token);
-        if normalized.is_empty() {
-            continue;
-        }
-
-        if normalized == "next"
-            && index + 1 < tokens.len()
-            && normalize_date_token(tokens[index + 1]) == "week"
-        {
-            let date = start_of_next_week(today);
+    for index in 0..tokens.len() {
+        if let Some((consumed, date)) = parse_date_phrase(&tokens, index, today) {
             let title = tokens
                 .iter()
                 .enumerate()
                 .filter_map(|(i, value)| {
-                    if i == index || i == index + 1 {
+                    if i >= index && i < index + consumed {
                         None
                     } else {
                         Some(*value)
@@ -191,66 +315,231 @@ pub(super) fn extract_due_date_from_input(
                 .join(" ");
             return (title, Some(date));
         }
+    }
 
-        if let Some((consumed, date)) = parse_month_day_sequence(&tokens, index, today) {
-            let title = tokens
-                .iter()
-                .enumerate()
-                .filter_map(|(i, value)| {
-                    if i >= index && i < index + consumed {
-                        None
-                    } else {
-                        Some(*value)
-                    }
-                })
-                .collect::<Vec<_>>()
-                .join(" ");
-            return (title, Some(date));
+    (raw.trim().to_string(), None)
+}
+
+/// Parses a whole `--due-before`/`--due-after` value: an explicit date or any phrase the
+/// title parser understands, such as `eom` or `in 2 weeks`.
+pub(super) fn parse_due_filter_phrase(
+    value: &str,
+    today: NaiveDate,
+) -> std::result::Result<NaiveDate, String> {
+    let tokens: Vec<&str> = value.split_whitespace().collect();
+    match parse_date_phrase(&tokens, 0, today) {
+        Some((consumed, date)) if consumed == tokens.len() => Ok(date),
+        _ => Err(format!(
+            "Invalid date '{}'. Use YYYY-MM-DD or a phrase like today, eom, next month, or in 3 days.",
+            value
+        )),
+    }
+}
+
+pub(super) fn parse_due_filter_value(value: &str) -> std::result::Result<NaiveDate, String> {
+    parse_due_filter_phrase(value, Local::now().date_naive())
+}
+
+pub(super) fn parse_modified_after_value(value: &str) -> std::result::Result<NaiveDate, String> {
+    parse_task_date(value.trim()).ok_or_else(|| {
+        format!(
+            "Invalid date '{}'. Use YYYY-MM-DD or a TickTick timestamp.",
+            value
+        )
+    })
+}
+
+/// Whether the task was last modified on or after local day `since` in `tz`. Tasks
+/// without a readable `modified_time` never match.
+pub(super) fn task_modified_since<Tz: TimeZone>(task: &Task, since: NaiveDate, tz: &Tz) -> bool {
+    task.modified_time
+        .as_deref()
+        .and_then(parse_ticktick_datetime)
+        .is_some_and(|modified| modified.with_timezone(tz).date_naive() >= since)
+}
+
+pub(super) fn task_matches_due_range(
+    task: &Task,
+    before: Option<NaiveDate>,
+    after: Option<NaiveDate>,
+) -> bool {
+    let Some(due) = task_due_date(task) else {
+        return false;
+    };
+    before.is_none_or(|before| due < before) && after.is_none_or(|after| due > after)
+}
+
+fn parse_hour_minute(value: &str) -> Option<(u32, u32)> {
+    let (hour, minute) = match value.split_once(':') {
+        Some((hour, minute)) if minute.len() == 2 => (hour, minute.parse::<u32>().ok()?),
+        Some(_) => return None,
+        None => (value, 0),
+    };
+    if hour.is_empty() || hour.len() > 2 {
+        return None;
+    }
+    Some((hour.parse::<u32>().ok()?, minute))
+}
+
+fn parse_meridiem_time(value: &str, meridiem: &str) -> Option<NaiveTime> {
+    let (hour, minute) = parse_hour_minute(value)?;
+    if !(1..=12).contains(&hour) {
+        return None;
+    }
+    let hour = match meridiem {
+        "am" => hour % 12,
+        "pm" => hour % 12 + 12,
+        _ => return None,
+    };
+    NaiveTime::from_hms_opt(hour, minute, 0)
+}
+
+fn parse_clock_tokens(
+    tokens: &[&str],
+    index: usize,
+    allow_24_hour: bool,
+) -> Option<(usize, NaiveTime)> {
+    let token = normalize_date_token(tokens.get(index)?);
+    match token.as_str() {
+        "noon" => return Some((1, NaiveTime::from_hms_opt(12, 0, 0)?)),
+        "midnight" => return Some((1, NaiveTime::from_hms_opt(0, 0, 0)?)),
+        _ => {}
+    }
+
+    if let Some(meridiem) = tokens
+        .get(index + 1)
+        .map(|value| normalize_date_token(value))
+    {
+        if meridiem == "am" || meridiem == "pm" {
+            if let Some(time) = parse_meridiem_time(&token, &meridiem) {
+                return Some((2, time));
+            }
         }
+    }
 
-        if let Some(date) = parse_numeric_date_token(&normalized, today) {
-            let title = tokens
-                .iter()
-                .enumerate()
-                .filter_map(|(i, value)| if i == index { None } else { Some(*value) })
-                .collect::<Vec<_>>()
-                .join(" ");
-            return (title, Some(date));
+    for meridiem in ["am", "pm"] {
+        if let Some(value) = token.strip_suffix(meridiem) {
+            return parse_meridiem_time(value, meridiem).map(|time| (1, time));
         }
+    }
 
-        let relative_date = match normalized.as_str() {
-            "today" => Some(today),
-            "tomorrow" => Some(today + Duration::days(1)),
-            _ => {
-                parse_weekday_token(&normalized).map(|weekday| next_or_same_weekday(today, weekday))
-            }
-        };
+    if allow_24_hour {
+        let (hour, minute) = parse_hour_minute(&token)?;
+        return NaiveTime::from_hms_opt(hour, minute, 0).map(|time| (1, time));
+    }
 
-        if let Some(date) = relative_date {
-            let title = tokens
-                .iter()
-                .enumerate()
-                .filter_map(|(i, value)| if i == index { None } else { Some(*value) })
-                .collect::<Vec<_>>()
-                .join(" ");
-            return (title, Some(date));
+    None
+}
+
+/// Recognizes a time of day starting at `index`: `3pm`, `3:30 pm`, `noon`, `midnight`,
+/// or `at` followed by any of those. A 24-hour clock needs context: after `at`, `at 9`
+/// is 9am and `at 15` is 3pm, and `15:30` counts right after a date phrase
+/// (`tomorrow 15:30`). Anywhere else `3:16` stays in the title, as in `Read John 3:16`.
+fn parse_time_phrase(
+    tokens: &[&str],
+    index: usize,
+    today: NaiveDate,
+) -> Option<(usize, NaiveTime)> {
+    let token = tokens.get(index)?;
+    if normalize_date_token(token) == "at" {
+        return parse_clock_tokens(tokens, index + 1, true)
+            .map(|(consumed, time)| (consumed + 1, time));
+    }
+    let after_date = token.contains(':') && follows_date_phrase(tokens, index, today);
+    parse_clock_tokens(tokens, index, after_date)
+}
+
+/// Whether a date phrase ends right before `index`.
+fn follows_date_phrase(tokens: &[&str], index: usize, today: NaiveDate) -> bool {
+    (0..index).any(|start| {
+        parse_date_phrase(tokens, start, today)
+            .is_some_and(|(consumed, _)| start + consumed == index)
+    })
+}
+
+/// Parses a whole value as a time of day: `9am`, `9:30 pm`, `21:15`, `noon`, with an
+/// optional leading `at`. A bare hour is read as a 24-hour clock.
+pub(super) fn parse_clock_time(value: &str) -> Option<NaiveTime> {
+    let mut tokens: Vec<&str> = value.split_whitespace().collect();
+    if tokens
+        .first()
+        .is_some_and(|token| normalize_date_token(token) == "at")
+    {
+        tokens.remove(0);
+    }
+    let (consumed, time) = parse_clock_tokens(&tokens, 0, true)?;
+    (consumed == tokens.len()).then_some(time)
+}
+
+/// Like [`extract_due_date_from_input`], but also pulls out a time of day. A time with
+/// no date phrase is due today.
+pub(super) fn extract_due_datetime_from_input(
+    raw: &str,
+    today: NaiveDate,
+) -> (String, Option<NaiveDate>, Option<NaiveTime>) {
+    let tokens: Vec<&str> = raw.split_whitespace().collect();
+    let mut remaining = Vec::with_capacity(tokens.len());
+    let mut time = None;
+    let mut index = 0;
+
+    while index < tokens.len() {
+        let token = tokens[index];
+        let is_shorthand =
+            token.starts_with('#') || token.starts_with('~') || token.starts_with('!');
+        if time.is_none() && !is_shorthand {
+            if let Some((consumed, parsed)) = parse_time_phrase(&tokens, index, today) {
+                time = Some(parsed);
+                index += consumed;
+                continue;
+            }
         }
+        remaining.push(token);
+        index += 1;
     }
 
-    (raw.trim().to_string(), None)
+    let (title, date) = extract_due_date_from_input(&remaining.join(" "), today);
+    let date = date.or_else(|| time.map(|_| today));
+    (title, date, time)
 }
 
-pub(super) fn format_ticktick_due_date(date: NaiveDate) -> Option<String> {
-    let local_midnight = date.and_hms_opt(0, 0, 0)?;
-    let local_dt = Local
-        .from_local_datetime(&local_midnight)
+/// Converts a wall-clock time in `tz` to UTC. Ambiguous times (the repeated hour when
+/// clocks fall back) resolve to the earlier instant; times inside a spring-forward gap
+/// move ahead by the skipped hour.
+pub(super) fn local_datetime_to_utc<Tz: TimeZone>(
+    tz: &Tz,
+    local: NaiveDateTime,
+) -> Option<DateTime<Utc>> {
+    tz.from_local_datetime(&local)
         .earliest()
-        .or_else(|| Local.from_local_datetime(&local_midnight).latest())?;
-    let utc_dt = local_dt.with_timezone(&Utc);
-    Some(utc_dt.format("%Y-%m-%dT%H:%M:%S%.3f+0000").to_string())
+        .or_else(|| {
+            tz.from_local_datetime(&(local + Duration::hours(1)))
+                .earliest()
+        })
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+/// Formats a wall-clock time in `tz` with that zone's own offset (`-0500`, `+0530`), so
+/// the date TickTick shows is the one the user meant even far from UTC.
+pub(super) fn format_ticktick_zoned_datetime<Tz: TimeZone>(
+    tz: &Tz,
+    local: NaiveDateTime,
+) -> Option<String>
+where
+    Tz::Offset: std::fmt::Display,
+{
+    let instant = local_datetime_to_utc(tz, local)?.with_timezone(tz);
+    Some(instant.format("%Y-%m-%dT%H:%M:%S%.3f%z").to_string())
+}
+
+pub(super) fn format_ticktick_local_datetime(local: NaiveDateTime) -> Option<String> {
+    format_ticktick_zoned_datetime(&Local, local)
+}
+
+pub(super) fn format_ticktick_due_date(date: NaiveDate) -> Option<String> {
+    format_ticktick_local_datetime(date.and_hms_opt(0, 0, 0)?)
 }
 
-fn format_ticktick_datetime<Tz: TimeZone>(dt: DateTime<Tz>) -> String
+pub(super) fn format_ticktick_datetime<Tz: TimeZone>(dt: DateTime<Tz>) -> String
 where
     Tz::Offset: std::fmt::Display,
 {
@@ -261,11 +550,7 @@ where
 
 fn parse_local_datetime(value: &str, format: &str) -> Option<String> {
     let naive = NaiveDateTime::parse_from_str(value, format).ok()?;
-    let local = Local
-        .from_local_datetime(&naive)
-        .earliest()
-        .or_else(|| Local.from_local_datetime(&naive).latest())?;
-    Some(format_ticktick_datetime(local))
+    format_ticktick_local_datetime(naive)
 }
 
 pub(super) fn normalize_task_datetime_input(value: &str) -> std::result::Result<String, String> {
@@ -335,6 +620,118 @@ pub(super) fn parse_task_date(value: &str) -> Option<NaiveDate> {
     NaiveDate::parse_from_str(prefix, "%Y-%m-%d").ok()
 }
 
+/// Like [`parse_task_date`], but keeps the time of day, converted to `tz`. Date-only
+/// values come back as midnight.
+pub(super) fn parse_task_datetime<Tz: TimeZone>(value: &str, tz: &Tz) -> Option<NaiveDateTime> {
+    if let Ok(epoch) = value.parse::<i64>() {
+        let dt = if value.len() > 10 {
+            DateTime::<Utc>::from_timestamp_millis(epoch)?
+        } else {
+            DateTime::<Utc>::from_timestamp(epoch, 0)?
+        };
+        return Some(dt.with_timezone(tz).naive_local());
+    }
+
+    if let Some(dt) = parse_ticktick_datetime(value)
+        .or_else(|| DateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%z").ok())
+    {
+        return Some(dt.with_timezone(tz).naive_local());
+    }
+
+    parse_task_date(value).map(|date| date.and_time(NaiveTime::MIN))
+}
+
+/// A `--due-format` strftime pattern, checked by formatting a sample date so a bad
+/// specifier fails at startup instead of while rendering.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) struct DueFormat(String);
+
+pub(super) fn parse_due_format(value: &str) -> std::result::Result<DueFormat, String> {
+    use std::fmt::Write;
+
+    let sample = NaiveDate::from_ymd_opt(2026, 3, 1)
+        .and_then(|date| date.and_hms_opt(9, 30, 0))
+        .expect("valid sample date");
+    let mut rendered = String::new();
+    if value.trim().is_empty() || write!(rendered, "{}", sample.format(value)).is_err() {
+        return Err(format!(
+            "Invalid --due-format '{}'. Use strftime fields like %Y-%m-%d, %b %d, or %a %H:%M",
+            value
+        ));
+    }
+    Ok(DueFormat(value.to_string()))
+}
+
+impl DueFormat {
+    /// The task's due (or start) date in `tz` with this pattern; blank when it has none,
+    /// and the stored text as is when it cannot be parsed.
+    pub(super) fn render<Tz: TimeZone>(&self, task: &Task, tz: &Tz) -> String {
+        let Some(value) = task.due_date.as_deref().or(task.start_date.as_deref()) else {
+            return String::new();
+        };
+        match parse_task_datetime(value, tz) {
+            Some(local) => local.format(&self.0).to_string(),
+            None => value.to_string(),
+        }
+    }
+}
+
+pub(super) fn parse_ticktick_datetime(value: &str) -> Option<DateTime<FixedOffset>> {
+    DateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f%z")
+        .or_else(|_| DateTime::parse_from_rfc3339(value))
+        .ok()
+}
+
+/// Keeps tasks whose `completed_time` falls on or after local day `since` in `tz`, oldest
+/// completion first. Tasks without a readable completion time are dropped.
+pub(super) fn tasks_completed_since<Tz: TimeZone>(
+    tasks: Vec<Task>,
+    since: NaiveDate,
+    tz: &Tz,
+) -> Vec<Task> {
+    let mut completed: Vec<(DateTime<FixedOffset>, Task)> = tasks
+        .into_iter()
+        .filter_map(|task| {
+            let completed_at = parse_ticktick_datetime(task.completed_time.as_deref()?)?;
+            (completed_at.with_timezone(tz).date_naive() >= since).then_some((completed_at, task))
+        })
+        .collect();
+    completed.sort_by_key(|(completed_at, _)| *completed_at);
+    completed.into_iter().map(|(_, task)| task).collect()
+}
+
+/// When the task was completed, in `tz`. `completed_time` is ISO 8601 from the task
+/// endpoints but may be a millisecond epoch in older exports.
+pub(super) fn task_completed_at<Tz: TimeZone>(task: &Task, tz: &Tz) -> Option<NaiveDateTime> {
+    parse_task_datetime(task.completed_time.as_deref()?, tz)
+}
+
+pub(super) fn task_created_at<Tz: TimeZone>(task: &Task, tz: &Tz) -> Option<NaiveDateTime> {
+    parse_task_datetime(task.created_time.as_deref()?, tz)
+}
+
+/// Newest first; a missing time sorts after every present one.
+pub(super) fn compare_newest_first(
+    a: Option<NaiveDateTime>,
+    b: Option<NaiveDateTime>,
+) -> std::cmp::Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => b.cmp(&a),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => std::cmp::Ordering::Equal,
+    }
+}
+
+/// Stable, so tasks with equal (or no) times keep the list order.
+pub(super) fn sort_tasks<Tz: TimeZone>(tasks: &mut [Task], sort: TaskSort, tz: &Tz) {
+    let time = |task: &Task| match sort {
+        TaskSort::Created => task_created_at(task, tz),
+        TaskSort::Completed => task_completed_at(task, tz),
+    };
+    tasks.sort_by(|a, b| compare_newest_first(time(a), time(b)));
+}
+
 pub(super) fn task_due_date(task: &Task) -> Option<NaiveDate> {
     task.due_date
         .as_deref()
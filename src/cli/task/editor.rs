@@ -0,0 +1,295 @@
+use super::dates::{normalize_task_datetime_input, parse_ticktick_datetime};
+use super::filters::{parse_priority_value, priority_name};
+use super::items::complete_checklist_item;
+use crate::models::{ChecklistItem, Task, TaskStatus};
+use anyhow::{anyhow, Context, Result};
+use chrono::TimeZone;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::process::Command;
+
+pub(super) const DOCUMENT_HEADER: &str = "\
+# Edit the task and save to update it. Delete everything to cancel.
+# Dates: YYYY-MM-DD or YYYY-MM-DDTHH:MM in local time. Priority: none, low, medium, high.
+";
+/// Marks comment lines `tt` added for a parse error so they are replaced, not stacked.
+pub(super) const ERROR_PREFIX: &str = "# error: ";
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub(super) struct DocumentItem {
+    pub(super) title: String,
+    #[serde(default)]
+    pub(super) done: bool,
+}
+
+/// The editable view of a task `--editor` opens. Fields left out of the file are cleared.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub(super) struct TaskDocument {
+    pub(super) title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(super) content: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(super) start: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(super) due: Option<String>,
+    #[serde(default)]
+    pub(super) all_day: bool,
+    #[serde(default = "default_priority")]
+    pub(super) priority: String,
+    #[serde(default)]
+    pub(super) tags: Vec<String>,
+    #[serde(default)]
+    pub(super) items: Vec<DocumentItem>,
+}
+
+fn default_priority() -> String {
+    "none".to_string()
+}
+
+/// Shows a stored TickTick timestamp as a local date (all-day) or local date and time.
+fn render_task_datetime<Tz: TimeZone>(tz: &Tz, value: &str, all_day: bool) -> String
+where
+    Tz::Offset: std::fmt::Display,
+{
+    let Some(dt) = parse_ticktick_datetime(value) else {
+        return value.to_string();
+    };
+    let local = dt.with_timezone(tz);
+    if all_day {
+        local.format("%Y-%m-%d").to_string()
+    } else {
+        local.format("%Y-%m-%dT%H:%M").to_string()
+    }
+}
+
+pub(super) fn document_from_task<Tz: TimeZone>(task: &Task, tz: &Tz) -> TaskDocument
+where
+    Tz::Offset: std::fmt::Display,
+{
+    let all_day = task.is_all_day.unwrap_or(false);
+    let render = |value: &Option<String>| {
+        value
+            .as_deref()
+            .map(|value| render_task_datetime(tz, value, all_day))
+    };
+
+    TaskDocument {
+        title: task.title.clone(),
+        content: task.content.clone().or_else(|| task.desc.clone()),
+        start: render(&task.start_date),
+        due: render(&task.due_date),
+        all_day,
+        priority: priority_name(task.priority.unwrap_or(0)),
+        tags: task.tags.clone().unwrap_or_default(),
+        items: task
+            .items
+            .iter()
+            .flatten()
+            .map(|item| DocumentItem {
+                title: item.title.clone().unwrap_or_default(),
+                done: matches!(item.status, Some(TaskStatus::Completed)),
+            })
+            .collect(),
+    }
+}
+
+pub(super) fn render_document(document: &TaskDocument) -> Result<String> {
+    let body = toml::to_string_pretty(document).context("Failed to render task document")?;
+    Ok(format!("{}\n{}", DOCUMENT_HEADER, body))
+}
+
+/// Parses an edited document. `Ok(None)` means the file was emptied to cancel.
+pub(super) fn parse_document(text: &str) -> std::result::Result<Option<TaskDocument>, String> {
+    let has_content = text.lines().any(|line| {
+        let line = line.trim();
+        !line.is_empty() && !line.starts_with('#')
+    });
+    if !has_content {
+        return Ok(None);
+    }
+
+    let document: TaskDocument = toml::from_str(text).map_err(|err| err.message().to_string())?;
+    if document.title.trim().is_empty() {
+        return Err("title cannot be empty".to_string());
+    }
+    Ok(Some(document))
+}
+
+/// Puts `error` at the top of the user's text as comments, dropping any earlier error.
+pub(super) fn document_with_error(text: &str, error: &str) -> String {
+    let mut annotated: String = error
+        .lines()
+        .map(|line| format!("{}{}\n", ERROR_PREFIX, line))
+        .collect();
+    for line in text.lines().filter(|line| !line.starts_with(ERROR_PREFIX)) {
+        annotated.push_str(line);
+        annotated.push('\n');
+    }
+    annotated
+}
+
+fn parse_document_date(field: &str, value: &Option<String>) -> Result<Option<String>> {
+    match value.as_deref().map(str::trim) {
+        None | Some("") => Ok(None),
+        Some(value) => normalize_task_datetime_input(value)
+            .map(Some)
+            .map_err(|err| anyhow!("{}: {}", field, err)),
+    }
+}
+
+fn apply_document_items(
+    current: Option<Vec<ChecklistItem>>,
+    edited: &[DocumentItem],
+    completed_time: &str,
+) -> Vec<ChecklistItem> {
+    let mut remaining = current.unwrap_or_default();
+    edited
+        .iter()
+        .enumerate()
+        .map(|(index, edited)| {
+            // Keep item IDs for titles that survived the edit so TickTick updates them in place.
+            let mut item = remaining
+                .iter()
+                .position(|item| item.title.as_deref() == Some(edited.title.as_str()))
+                .map(|position| remaining.remove(position))
+                .unwrap_or_else(|| ChecklistItem {
+                    title: Some(edited.title.clone()),
+                    status: Some(TaskStatus::Normal),
+                    ..Default::default()
+                });
+            item.sort_order = Some(index as i64);
+            let was_done = matches!(item.status, Some(TaskStatus::Completed));
+            if edited.done && !was_done {
+                complete_checklist_item(&mut item, completed_time.to_string());
+            } else if !edited.done && was_done {
+                item.status = Some(TaskStatus::Normal);
+                item.completed_time = None;
+            }
+            item
+        })
+        .collect()
+}
+
+/// Applies the fields that differ between `original` and `edited` to `task`, so values
+/// the document cannot show exactly (such as seconds in a due time) are kept unless
+/// that field was edited.
+pub(super) fn apply_document(
+    task: &mut Task,
+    original: &TaskDocument,
+    edited: &TaskDocument,
+    completed_time: &str,
+) -> Result<()> {
+    if edited.title != original.title {
+        task.title = edited.title.trim().to_string();
+    }
+    if edited.content != original.content {
+        let content = edited
+            .content
+            .clone()
+            .filter(|content| !content.trim().is_empty());
+        task.content = content.clone();
+        task.desc = content;
+    }
+    if edited.start != original.start {
+        task.start_date = parse_document_date("start", &edited.start)?;
+    }
+    if edited.due != original.due {
+        task.due_date = parse_document_date("due", &edited.due)?;
+    }
+    if edited.all_day != original.all_day {
+        task.is_all_day = Some(edited.all_day);
+    }
+    if edited.priority != original.priority {
+        task.priority = Some(
+            parse_priority_value(&edited.priority).map_err(|err| anyhow!("priority: {}", err))?,
+        );
+    }
+    if edited.tags != original.tags {
+        let tags: Vec<String> = edited
+            .tags
+            .iter()
+            .map(|tag| tag.trim().to_string())
+            .filter(|tag| !tag.is_empty())
+            .collect();
+        task.tags = (!tags.is_empty()).then_some(tags);
+    }
+    if edited.items != original.items {
+        // An empty array (not a missing field) is what clears the last item server-side.
+        task.items = Some(apply_document_items(
+            task.items.take(),
+            &edited.items,
+            completed_time,
+        ));
+    }
+    Ok(())
+}
+
+fn editor_command() -> String {
+    ["VISUAL", "EDITOR"]
+        .iter()
+        .filter_map(|key| std::env::var(key).ok())
+        .find(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| "vi".to_string())
+}
+
+fn run_editor(path: &std::path::Path) -> Result<()> {
+    let command = editor_command();
+    let mut parts = command.split_whitespace();
+    let program = parts
+        .next()
+        .ok_or_else(|| anyhow!("No editor configured; set $VISUAL or $EDITOR"))?;
+    let status = Command::new(program)
+        .args(parts)
+        .arg(path)
+        .status()
+        .with_context(|| format!("Failed to launch editor '{}'", command))?;
+    if !status.success() {
+        return Err(anyhow!("Editor '{}' exited with {}", command, status));
+    }
+    Ok(())
+}
+
+/// Opens `task` in `$VISUAL`/`$EDITOR` until the document parses and applies cleanly.
+/// Returns `None` when the user cancels by emptying the file.
+pub(super) fn edit_task_in_editor<Tz: TimeZone>(
+    task: &Task,
+    tz: &Tz,
+    completed_time: &str,
+) -> Result<Option<Task>>
+where
+    Tz::Offset: std::fmt::Display,
+{
+    let original = document_from_task(task, tz);
+    let path = std::env::temp_dir().join(format!(
+        "tt-task-{}-{}.toml",
+        task.id.as_deref().unwrap_or("new"),
+        std::process::id()
+    ));
+    let mut text = render_document(&original)?;
+
+    let result = loop {
+        fs::write(&path, &text).with_context(|| format!("Failed to write {}", path.display()))?;
+        run_editor(&path)?;
+        text = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+
+        let edited = match parse_document(&text) {
+            Ok(Some(edited)) => edited,
+            Ok(None) => break None,
+            Err(err) => {
+                text = document_with_error(&text, &err);
+                continue;
+            }
+        };
+        let mut updated = task.clone();
+        match apply_document(&mut updated, &original, &edited, completed_time) {
+            Ok(()) => break Some(updated),
+            Err(err) => text = document_with_error(&text, &err.to_string()),
+        }
+    };
+
+    let _ = fs::remove_file(&path);
+    Ok(result)
+}
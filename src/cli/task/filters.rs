@@ -1,5 +1,8 @@
 use super::dates::TaskWhenFilter;
+use crate::cli::lists::normalize_list_name;
 use crate::models::{Task, TaskStatus};
+use anyhow::{anyhow, Result};
+use regex::{Regex, RegexBuilder};
 
 #[derive(Default)]
 pub(super) struct ShorthandFilters {
@@ -10,9 +13,9 @@ pub(super) struct ShorthandFilters {
     pub(super) terms: Vec<String>,
 }
 
-pub(super) fn parse_priority_shorthand(token: &str) -> Option<i32> {
-    let value = token.strip_prefix('!')?.to_ascii_lowercase();
-    match value.as_str() {
+/// TickTick priority levels by name, shared by `!high` shorthand and `--priority high`.
+fn priority_from_name(name: &str) -> Option<i32> {
+    match name.to_ascii_lowercase().as_str() {
         "high" => Some(5),
         "medium" => Some(3),
         "low" => Some(1),
@@ -21,19 +24,90 @@ pub(super) fn parse_priority_shorthand(token: &str) -> Option<i32> {
     }
 }
 
+pub(super) fn parse_priority_shorthand(token: &str) -> Option<i32> {
+    priority_from_name(token.strip_prefix('!')?)
+}
+
+/// TickTick's priority levels from lowest to highest; other numbers are not valid.
+pub(super) const PRIORITY_LADDER: [i32; 4] = [0, 1, 3, 5];
+
+/// The name `--priority` accepts for a level; values off the ladder print as numbers.
+pub(crate) fn priority_name(priority: i32) -> String {
+    match priority {
+        0 => "none".to_string(),
+        1 => "low".to_string(),
+        3 => "medium".to_string(),
+        5 => "high".to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// `priority_name` capitalized, as tables and group headings show it.
+pub(crate) fn priority_title(priority: i32) -> String {
+    let name = priority_name(priority);
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => name,
+    }
+}
+
 pub(super) fn parse_priority_value(value: &str) -> std::result::Result<i32, String> {
-    let normalized = value.trim().to_ascii_lowercase();
-    match normalized.as_str() {
-        "none" | "normal" => Ok(0),
-        "low" => Ok(1),
-        "medium" => Ok(3),
-        "high" => Ok(5),
-        _ => value.trim().parse::<i32>().map_err(|_| {
+    let trimmed = value.trim();
+    // Accept the shorthand spelling too, so `--priority '!high'` works.
+    let name = trimmed.strip_prefix('!').unwrap_or(trimmed);
+    priority_from_name(name)
+        .or_else(|| {
+            trimmed
+                .parse::<i32>()
+                .ok()
+                .filter(|level| PRIORITY_LADDER.contains(level))
+        })
+        .ok_or_else(|| {
             format!(
-                "Invalid priority '{}'. Use an integer or one of: none, low, medium, high.",
+                "Invalid priority '{}'. Use one of: none, low, medium, high (or 0, 1, 3, 5).",
                 value
             )
-        }),
+        })
+}
+
+/// What `tt task prio` does: set a level, or step one rung along the ladder.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum PriorityChange {
+    Set(i32),
+    Up,
+    Down,
+}
+
+pub(super) fn parse_priority_change(value: &str) -> std::result::Result<PriorityChange, String> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "up" | "+" | "raise" => Ok(PriorityChange::Up),
+        "down" | "-" | "lower" => Ok(PriorityChange::Down),
+        _ => parse_priority_value(value)
+            .map(PriorityChange::Set)
+            .map_err(|_| {
+                format!(
+                    "Invalid priority change '{}'. Use up, down, or one of: none, low, medium, high.",
+                    value
+                )
+            }),
+    }
+}
+
+/// Applies `change` to `current`. Stepping stops at none and high; a level off the
+/// ladder steps to the nearest rung in that direction.
+pub(super) fn step_priority(current: i32, change: PriorityChange) -> i32 {
+    match change {
+        PriorityChange::Set(level) => level,
+        PriorityChange::Up => PRIORITY_LADDER
+            .into_iter()
+            .find(|level| *level > current)
+            .unwrap_or(current.max(PRIORITY_LADDER[3])),
+        PriorityChange::Down => PRIORITY_LADDER
+            .into_iter()
+            .rev()
+            .find(|level| *level < current)
+            .unwrap_or(current.min(PRIORITY_LADDER[0])),
     }
 }
 
@@ -130,6 +204,52 @@ pub(super) fn merge_tags(existing: &mut Vec<String>, extras: Vec<String>) {
     }
 }
 
+/// Drops every tag matching one of `removals`, ignoring case.
+pub(super) fn remove_tags(existing: &mut Vec<String>, removals: &[String]) {
+    existing.retain(|tag| {
+        !removals
+            .iter()
+            .any(|removal| removal.eq_ignore_ascii_case(tag))
+    });
+}
+
+/// One token of `tt task tag`: `+#tag` (or `#tag`) adds, `-#tag` removes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) enum TagChange {
+    Add(String),
+    Remove(String),
+}
+
+pub(super) fn parse_tag_change(value: &str) -> std::result::Result<TagChange, String> {
+    let trimmed = value.trim();
+    let (change, rest): (fn(String) -> TagChange, &str) = match trimmed.strip_prefix('-') {
+        Some(rest) => (TagChange::Remove, rest),
+        None => (TagChange::Add, trimmed.strip_prefix('+').unwrap_or(trimmed)),
+    };
+    let tag = rest.strip_prefix('#').unwrap_or(rest).trim();
+    if tag.is_empty() || tag.contains(char::is_whitespace) {
+        return Err(format!(
+            "Invalid tag change '{}'. Use +#tag to add or -#tag to remove.",
+            value
+        ));
+    }
+    Ok(change(tag.to_string()))
+}
+
+/// Adds and removes tags on `task`, keeping every tag neither list mentions. Removals
+/// win when the same tag is in both.
+pub(super) fn apply_tag_changes(task: &mut Task, additions: Vec<String>, removals: &[String]) {
+    let mut tags = task.tags.take().unwrap_or_default();
+    merge_tags(&mut tags, additions);
+    remove_tags(&mut tags, removals);
+    task.tags = Some(tags);
+}
+
+/// `--priority-min`: the task's priority (none counts as 0) is at least `min`.
+pub(super) fn task_meets_priority(task: &Task, min: i32) -> bool {
+    task.priority.unwrap_or(0) >= min
+}
+
 pub(super) fn task_has_all_tags(task: &Task, required_tags: &[String]) -> bool {
     let Some(task_tags) = task.tags.as_ref() else {
         return false;
@@ -142,15 +262,44 @@ pub(super) fn task_has_all_tags(task: &Task, required_tags: &[String]) -> bool {
     })
 }
 
-pub(super) fn normalize_list_name(value: &str) -> String {
-    value
-        .chars()
-        .filter(|ch| ch.is_alphanumeric() || ch.is_whitespace())
-        .flat_map(|ch| ch.to_lowercase())
-        .collect::<String>()
-        .split_whitespace()
-        .collect::<Vec<_>>()
-        .join(" ")
+pub(super) fn task_has_any_tag(task: &Task, wanted_tags: &[String]) -> bool {
+    let Some(task_tags) = task.tags.as_ref() else {
+        return false;
+    };
+
+    wanted_tags.iter().any(|wanted| {
+        task_tags
+            .iter()
+            .any(|actual| actual.eq_ignore_ascii_case(wanted))
+    })
+}
+
+/// The text `tt task list` search terms are matched against: title, content, and description,
+/// one per line so `--regex` anchors apply to each field.
+pub(super) fn task_search_haystack(task: &Task) -> String {
+    [task.content.as_deref(), task.desc.as_deref()]
+        .into_iter()
+        .flatten()
+        .filter(|text| !text.is_empty())
+        .fold(task.title.clone(), |haystack, text| haystack + "\n" + text)
+}
+
+/// Joins the search terms left after shorthand parsing into the single `--regex` pattern.
+pub(super) fn compile_task_list_regex(terms: &[String], case_sensitive: bool) -> Result<Regex> {
+    let pattern = terms.join(" ");
+    RegexBuilder::new(&pattern)
+        .case_insensitive(!case_sensitive)
+        .multi_line(true)
+        .build()
+        .map_err(|err| anyhow!("Invalid regex '{}': {}", pattern, err))
+}
+
+/// `--list-regex`: matched against list names, ignoring case like `--list`.
+pub(super) fn parse_list_regex(value: &str) -> std::result::Result<Regex, String> {
+    RegexBuilder::new(value)
+        .case_insensitive(true)
+        .build()
+        .map_err(|err| format!("Invalid --list-regex '{}': {}", value, err))
 }
 
 pub(super) fn is_inbox_list_name(value: &str) -> bool {
@@ -0,0 +1,124 @@
+use super::groups::task_project_label;
+use crate::models::Task;
+use crate::output::{task_date_cell, task_priority_label};
+use std::collections::HashMap;
+
+const FORMAT_FIELDS: [&str; 6] = ["id", "title", "due", "priority", "list", "tags"];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Segment {
+    Text(String),
+    Field(&'static str),
+}
+
+/// A `--format` string such as `"{due} {title} ({list})"`, parsed once and rendered per
+/// task. `{{` and `}}` stand for literal braces.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) struct TaskFormat {
+    segments: Vec<Segment>,
+}
+
+pub(super) fn parse_task_format(value: &str) -> std::result::Result<TaskFormat, String> {
+    let mut segments = Vec::new();
+    let mut text = String::new();
+    let mut chars = value.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        match ch {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                text.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                text.push('}');
+            }
+            '{' => {
+                let mut name = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(ch) => name.push(ch),
+                        None => {
+                            return Err(format!(
+                                "Unclosed '{{' in format '{}'; use {{{{ for a literal brace.",
+                                value
+                            ))
+                        }
+                    }
+                }
+                let field = FORMAT_FIELDS
+                    .iter()
+                    .find(|field| field.eq_ignore_ascii_case(name.trim()))
+                    .ok_or_else(|| {
+                        format!(
+                            "Unknown placeholder '{{{}}}'. Valid fields: {}",
+                            name,
+                            FORMAT_FIELDS
+                                .iter()
+                                .map(|field| format!("{{{}}}", field))
+                                .collect::<Vec<_>>()
+                                .join(", ")
+                        )
+                    })?;
+                if !text.is_empty() {
+                    segments.push(Segment::Text(std::mem::take(&mut text)));
+                }
+                segments.push(Segment::Field(field));
+            }
+            '}' => {
+                return Err(format!(
+                    "Unmatched '}}' in format '{}'; use }}}} for a literal brace.",
+                    value
+                ))
+            }
+            ch => text.push(ch),
+        }
+    }
+    if !text.is_empty() {
+        segments.push(Segment::Text(text));
+    }
+
+    Ok(TaskFormat { segments })
+}
+
+impl TaskFormat {
+    /// Whether `{list}` is used, so project names are only fetched when needed.
+    pub(super) fn uses_list(&self) -> bool {
+        self.segments.contains(&Segment::Field("list"))
+    }
+
+    pub(super) fn render(&self, task: &Task, project_names: &HashMap<String, String>) -> String {
+        self.segments
+            .iter()
+            .map(|segment| match segment {
+                Segment::Text(text) => text.clone(),
+                Segment::Field("id") => task.id.clone().unwrap_or_default(),
+                Segment::Field("title") => task.title.clone(),
+                Segment::Field("due") => task_date_cell(task),
+                Segment::Field("priority") => task_priority_label(task.priority.unwrap_or(0)),
+                Segment::Field("list") => task_project_label(task, project_names),
+                Segment::Field("tags") => task
+                    .tags
+                    .iter()
+                    .flatten()
+                    .map(|tag| format!("#{}", tag))
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                Segment::Field(_) => String::new(),
+            })
+            .collect()
+    }
+}
+
+/// One rendered line per task, in list order.
+pub(super) fn render_formatted_tasks(
+    tasks: &[Task],
+    format: &TaskFormat,
+    project_names: &HashMap<String, String>,
+) -> String {
+    tasks
+        .iter()
+        .map(|task| format!("{}\n", format.render(task, project_names)))
+        .collect()
+}
@@ -0,0 +1,149 @@
+use super::dates::{date_window_for, task_due_date, TaskWhenFilter};
+use super::filters::priority_title;
+use crate::models::Task;
+use crate::output::TaskGroup;
+use chrono::{Duration, NaiveDate};
+use std::collections::HashMap;
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TaskGroupBy {
+    #[value(alias = "list")]
+    Project,
+    Due,
+    Priority,
+    #[value(alias = "tags")]
+    Tag,
+}
+
+const NO_TAGS_GROUP: &str = "No tags";
+const NO_DATE_GROUP: &str = "No date";
+
+pub(super) fn group_tasks(
+    tasks: Vec<Task>,
+    group_by: TaskGroupBy,
+    project_names: &HashMap<String, String>,
+    today: NaiveDate,
+) -> Vec<TaskGroup> {
+    match group_by {
+        TaskGroupBy::Project => group_by_key(tasks, |task| task_project_label(task, project_names)),
+        TaskGroupBy::Due => group_by_due(tasks, today),
+        TaskGroupBy::Priority => group_by_priority(tasks),
+        TaskGroupBy::Tag => group_by_tag(tasks),
+    }
+}
+
+fn group_by_key<F>(tasks: Vec<Task>, key_for: F) -> Vec<TaskGroup>
+where
+    F: Fn(&Task) -> String,
+{
+    let mut groups: Vec<TaskGroup> = Vec::new();
+    for task in tasks {
+        let key = key_for(&task);
+        match groups.iter_mut().find(|group| group.key == key) {
+            Some(group) => group.tasks.push(task),
+            None => groups.push(TaskGroup {
+                key,
+                tasks: vec![task],
+            }),
+        }
+    }
+    groups
+}
+
+pub(super) fn task_project_label(task: &Task, project_names: &HashMap<String, String>) -> String {
+    let project_id = task.project_id.as_deref().unwrap_or_default().trim();
+    if let Some(name) = project_names.get(project_id) {
+        return name.clone();
+    }
+    if project_id.is_empty() || project_id.starts_with("inbox") {
+        return "Inbox".to_string();
+    }
+    project_id.to_string()
+}
+
+pub(super) fn due_bucket_label(task: &Task, today: NaiveDate) -> &'static str {
+    let Some(date) = task_due_date(task) else {
+        return NO_DATE_GROUP;
+    };
+
+    let (_, end_of_week) = date_window_for(TaskWhenFilter::ThisWeek, today);
+    if date < today {
+        "Overdue"
+    } else if date == today {
+        "Today"
+    } else if date == today + Duration::days(1) {
+        "Tomorrow"
+    } else if date <= end_of_week {
+        "This Week"
+    } else {
+        "Later"
+    }
+}
+
+fn group_by_due(tasks: Vec<Task>, today: NaiveDate) -> Vec<TaskGroup> {
+    let order = [
+        "Overdue",
+        "Today",
+        "Tomorrow",
+        "This Week",
+        "Later",
+        NO_DATE_GROUP,
+    ];
+    let mut groups = group_by_key(tasks, |task| due_bucket_label(task, today).to_string());
+    groups.sort_by_key(|group| order.iter().position(|label| *label == group.key));
+    groups
+}
+
+fn group_by_priority(mut tasks: Vec<Task>) -> Vec<TaskGroup> {
+    tasks.sort_by_key(|task| std::cmp::Reverse(task.priority.unwrap_or(0)));
+    group_by_key(tasks, |task| priority_title(task.priority.unwrap_or(0)))
+}
+
+fn group_by_tag(tasks: Vec<Task>) -> Vec<TaskGroup> {
+    let mut groups: Vec<TaskGroup> = Vec::new();
+    let mut untagged = Vec::new();
+
+    for task in tasks {
+        let tags: Vec<String> = task
+            .tags
+            .iter()
+            .flatten()
+            .filter(|tag| !tag.trim().is_empty())
+            .cloned()
+            .collect();
+        if tags.is_empty() {
+            untagged.push(task);
+            continue;
+        }
+
+        let mut seen: Vec<String> = Vec::new();
+        for tag in tags {
+            if seen
+                .iter()
+                .any(|current| current.eq_ignore_ascii_case(&tag))
+            {
+                continue;
+            }
+            seen.push(tag.clone());
+            match groups
+                .iter_mut()
+                .find(|group| group.key.eq_ignore_ascii_case(&tag))
+            {
+                Some(group) => group.tasks.push(task.clone()),
+                None => groups.push(TaskGroup {
+                    key: tag,
+                    tasks: vec![task.clone()],
+                }),
+            }
+        }
+    }
+
+    groups.sort_by_key(|group| group.key.to_lowercase());
+    if !untagged.is_empty() {
+        groups.push(TaskGroup {
+            key: NO_TAGS_GROUP.to_string(),
+            tasks: untagged,
+        });
+    }
+    groups
+}
@@ -0,0 +1,265 @@
+use super::dates::normalize_task_datetime_input;
+use super::filters::parse_priority_value;
+use super::items::new_checklist_items;
+use super::repeat::parse_repeat_value;
+use crate::models::Task;
+use crate::output::OutputContext;
+use anyhow::{anyhow, Context, Result};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// One entry of a `tt task add --from-file` document. Dates are strings in any form
+/// `--due-date` accepts, priorities are numbers or names, and lists are names.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub(super) struct TaskImport {
+    pub(super) title: String,
+    pub(super) content: Option<String>,
+    pub(super) desc: Option<String>,
+    #[serde(alias = "dueDate")]
+    pub(super) due: Option<String>,
+    #[serde(alias = "startDate")]
+    pub(super) start: Option<String>,
+    pub(super) all_day: Option<bool>,
+    pub(super) time_zone: Option<String>,
+    #[serde(default)]
+    pub(super) tags: Vec<String>,
+    pub(super) priority: Option<ImportPriority>,
+    pub(super) list: Option<String>,
+    pub(super) project_id: Option<String>,
+    #[serde(default)]
+    pub(super) items: Vec<String>,
+    pub(super) repeat: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub(super) enum ImportPriority {
+    Level(i32),
+    Name(String),
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum TaskImportDocument {
+    List(Vec<TaskImport>),
+    Tasks { tasks: Vec<TaskImport> },
+}
+
+impl TaskImportDocument {
+    fn into_tasks(self) -> Vec<TaskImport> {
+        match self {
+            Self::List(tasks) | Self::Tasks { tasks } => tasks,
+        }
+    }
+}
+
+fn parse_json_document(contents: &str) -> Result<Vec<TaskImport>> {
+    let document: TaskImportDocument = serde_json::from_str(contents)
+        .context("Expected a JSON array of tasks or an object with a \"tasks\" array")?;
+    Ok(document.into_tasks())
+}
+
+fn parse_toml_document(contents: &str) -> Result<Vec<TaskImport>> {
+    #[derive(Deserialize)]
+    struct TomlDocument {
+        tasks: Vec<TaskImport>,
+    }
+
+    let document: TomlDocument =
+        toml::from_str(contents).context("Expected a TOML document with [[tasks]] tables")?;
+    Ok(document.tasks)
+}
+
+/// Reads an import document, choosing the format from the file extension. JSON may be a
+/// bare array or `{"tasks": [...]}`; TOML uses `[[tasks]]` tables.
+pub(super) fn parse_task_import_file(path: &Path, contents: &str) -> Result<Vec<TaskImport>> {
+    let extension = path
+        .extension()
+        .and_then(|value| value.to_str())
+        .map(str::to_ascii_lowercase);
+
+    let tasks = match extension.as_deref() {
+        Some("json") => parse_json_document(contents)?,
+        Some("toml") => parse_toml_document(contents)?,
+        Some("yaml") | Some("yml") => {
+            return Err(anyhow!(
+                "YAML import is not supported yet; convert {} to JSON or TOML",
+                path.display()
+            ))
+        }
+        _ => parse_json_document(contents).or_else(|_| parse_toml_document(contents))?,
+    };
+
+    if tasks.is_empty() {
+        return Err(anyhow!("{} does not contain any tasks", path.display()));
+    }
+    Ok(tasks)
+}
+
+fn normalize_import_date(field: &str, value: Option<&str>) -> Result<Option<String>> {
+    value
+        .map(|value| {
+            normalize_task_datetime_input(value)
+                .map_err(|err| anyhow!("Invalid {}: {}", field, err))
+        })
+        .transpose()
+}
+
+fn is_date_only(value: Option<&str>) -> bool {
+    value.is_some_and(|value| NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d").is_ok())
+}
+
+/// Maps an import entry onto the task payload for `project_id`.
+pub(super) fn task_from_import(entry: &TaskImport, project_id: &str) -> Result<Task> {
+    let title = entry.title.trim();
+    if title.is_empty() {
+        return Err(anyhow!("Task title cannot be empty"));
+    }
+
+    let due_date = normalize_import_date("due", entry.due.as_deref())?;
+    let start_date = normalize_import_date("start", entry.start.as_deref())?.or(due_date.clone());
+    let all_day = entry.all_day.or_else(|| {
+        due_date
+            .as_ref()
+            .map(|_| is_date_only(entry.due.as_deref()))
+    });
+    let priority = match &entry.priority {
+        Some(ImportPriority::Level(level)) => parse_priority_value(&level.to_string()),
+        Some(ImportPriority::Name(name)) => parse_priority_value(name),
+        None => Ok(0),
+    }
+    .map_err(|err| anyhow!(err))?;
+    let repeat_flag = entry
+        .repeat
+        .as_deref()
+        .map(parse_repeat_value)
+        .transpose()
+        .map_err(|err| anyhow!(err))?;
+    let items = new_checklist_items(&entry.items)?;
+
+    Ok(Task {
+        title: title.to_string(),
+        content: entry.content.clone(),
+        desc: entry.desc.clone(),
+        project_id: Some(project_id.to_string()),
+        start_date,
+        due_date,
+        time_zone: entry.time_zone.clone(),
+        is_all_day: all_day,
+        priority: Some(priority),
+        tags: (!entry.tags.is_empty()).then(|| entry.tags.clone()),
+        repeat_flag,
+        kind: Some(if items.is_some() { "CHECKLIST" } else { "TASK" }.to_string()),
+        items,
+        ..Default::default()
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(super) enum ImportStatus {
+    Valid,
+    Created,
+    Failed,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct ImportResult {
+    pub(super) index: usize,
+    pub(super) title: String,
+    pub(super) status: ImportStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) list: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) project_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) error: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ImportReport<'a> {
+    dry_run: bool,
+    results: &'a [ImportResult],
+    succeeded: usize,
+    failed: usize,
+}
+
+pub(super) fn import_failures(results: &[ImportResult]) -> usize {
+    results
+        .iter()
+        .filter(|result| result.status == ImportStatus::Failed)
+        .count()
+}
+
+pub(super) fn format_import_report(
+    results: &[ImportResult],
+    dry_run: bool,
+    json: bool,
+    context: &OutputContext,
+) -> Result<String> {
+    let failed = import_failures(results);
+    let succeeded = results.len() - failed;
+
+    if json {
+        let report = ImportReport {
+            dry_run,
+            results,
+            succeeded,
+            failed,
+        };
+        return Ok(format!("{}\n", context.to_json_string(&report)?));
+    }
+
+    let total = results.len();
+    let mut output = String::new();
+    for result in results {
+        let line = match result.status {
+            ImportStatus::Valid => format!(
+                "[{}/{}] ok: {} (list {})",
+                result.index,
+                total,
+                result.title,
+                result
+                    .list
+                    .as_deref()
+                    .or(result.project_id.as_deref())
+                    .unwrap_or("default")
+            ),
+            ImportStatus::Created => format!(
+                "[{}/{}] created: {} ({})",
+                result.index,
+                total,
+                result.title,
+                result.id.as_deref().unwrap_or_default()
+            ),
+            ImportStatus::Failed => format!(
+                "[{}/{}] failed: {}: {}",
+                result.index,
+                total,
+                result.title,
+                result.error.as_deref().unwrap_or_default()
+            ),
+        };
+        output.push_str(&line);
+        output.push('\n');
+    }
+
+    if dry_run {
+        output.push_str(&format!(
+            "Dry run: {} of {} tasks valid, {} with errors. Nothing was created.\n",
+            succeeded, total, failed
+        ));
+    } else {
+        output.push_str(&format!(
+            "Imported {} of {} tasks ({} failed).\n",
+            succeeded, total, failed
+        ));
+    }
+    Ok(output)
+}
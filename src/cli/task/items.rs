@@ -0,0 +1,81 @@
+use crate::models::{ChecklistItem, TaskStatus};
+use anyhow::{anyhow, Result};
+
+pub(super) fn new_checklist_items(titles: &[String]) -> Result<Option<Vec<ChecklistItem>>> {
+    let mut items = Vec::new();
+    for title in titles {
+        push_checklist_item(&mut items, title)?;
+    }
+    Ok((!items.is_empty()).then_some(items))
+}
+
+pub(super) fn push_checklist_item(items: &mut Vec<ChecklistItem>, title: &str) -> Result<()> {
+    let title = title.trim();
+    if title.is_empty() {
+        return Err(anyhow!("Checklist item title cannot be empty"));
+    }
+
+    let sort_order = items
+        .iter()
+        .filter_map(|item| item.sort_order)
+        .max()
+        .map_or(0, |max| max + 1);
+    items.push(ChecklistItem {
+        title: Some(title.to_string()),
+        status: Some(TaskStatus::Normal),
+        sort_order: Some(sort_order),
+        ..Default::default()
+    });
+    Ok(())
+}
+
+/// Resolves an item by 1-based position, item ID, or case-insensitive title.
+pub(super) fn find_checklist_item(items: &[ChecklistItem], selector: &str) -> Result<usize> {
+    let selector = selector.trim();
+    if let Ok(position) = selector.parse::<usize>() {
+        if (1..=items.len()).contains(&position) {
+            return Ok(position - 1);
+        }
+    }
+
+    items
+        .iter()
+        .position(|item| item.id.as_deref() == Some(selector))
+        .or_else(|| {
+            items.iter().position(|item| {
+                item.title
+                    .as_deref()
+                    .is_some_and(|title| title.trim().eq_ignore_ascii_case(selector))
+            })
+        })
+        .ok_or_else(|| anyhow!("Checklist item not found: {}", selector))
+}
+
+pub(super) fn complete_checklist_item(item: &mut ChecklistItem, completed_time: String) {
+    item.status = Some(TaskStatus::Completed);
+    item.completed_time = Some(completed_time);
+}
+
+pub(super) fn format_checklist_lines(items: &[ChecklistItem]) -> String {
+    if items.is_empty() {
+        return "No checklist items.\n".to_string();
+    }
+
+    items
+        .iter()
+        .enumerate()
+        .map(|(index, item)| {
+            let marker = if matches!(item.status, Some(TaskStatus::Completed)) {
+                "x"
+            } else {
+                " "
+            };
+            format!(
+                "{}. [{}] {}\n",
+                index + 1,
+                marker,
+                item.title.as_deref().unwrap_or_default()
+            )
+        })
+        .collect()
+}
@@ -0,0 +1,262 @@
+use super::projects::{
+    cache_store, count_project_tasks, get_tasks_across_projects, normalize_project_id,
+    project_names_by_id, project_task_counts,
+};
+use crate::api::TickTickClient;
+use crate::cache::{
+    get_projects_cached, offline_enabled, CacheStore, ProjectTaskCounts, SyncSnapshot,
+};
+use crate::cli::bootstrap::{app_config, authenticated_client};
+use crate::cli::lists::{find_project_by_name, resolve_project_ref};
+use crate::cli::undo::describe_age;
+use crate::models::{Project, ProjectData, Task};
+use crate::output::{MessageFormat, OutputContext, Verbosity};
+use anyhow::{anyhow, Result};
+use chrono::{Local, Utc};
+use clap::Args;
+use serde_json::json;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+#[derive(Args)]
+pub struct SyncArgs {
+    #[arg(long, default_value = "human")]
+    output: MessageFormat,
+}
+
+/// Where read-only commands get their data: the API, or the `tt sync` snapshot when
+/// `--offline` is set.
+pub(crate) enum TaskSource {
+    Api {
+        client: TickTickClient,
+        cache: Option<CacheStore>,
+    },
+    Snapshot(SyncSnapshot),
+}
+
+impl TaskSource {
+    /// Opens the snapshot under `--offline`, noting its age on stderr; otherwise the API.
+    pub(crate) fn open() -> Result<Self> {
+        if !offline_enabled() {
+            return Ok(Self::Api {
+                client: authenticated_client()?,
+                cache: cache_store(),
+            });
+        }
+
+        let path = app_config()?.snapshot_path();
+        let snapshot = SyncSnapshot::load(&path)?.ok_or_else(|| {
+            anyhow!(
+                "No offline data at {}. Run 'tt sync' first.",
+                path.display()
+            )
+        })?;
+        eprintln!("{}", snapshot_age_notice(&snapshot, Utc::now().timestamp()));
+        Ok(Self::Snapshot(snapshot))
+    }
+
+    /// The API for commands that change data or need more than the snapshot holds. The
+    /// snapshot is read-only, so these refuse `--offline` rather than act on cached data.
+    pub(crate) fn online(command: &str) -> Result<(TickTickClient, Option<CacheStore>)> {
+        if offline_enabled() {
+            return Err(anyhow!(
+                "'tt {}' needs the network; drop --offline",
+                command
+            ));
+        }
+        Ok((authenticated_client()?, cache_store()))
+    }
+
+    pub(super) async fn project_names(&self) -> Result<HashMap<String, String>> {
+        match self {
+            Self::Api { client, cache } => project_names_by_id(client, cache.as_ref()).await,
+            Self::Snapshot(snapshot) => Ok(snapshot_project_names(snapshot)),
+        }
+    }
+
+    pub(crate) async fn projects(&self) -> Result<Vec<Project>> {
+        match self {
+            Self::Api { client, cache } => get_projects_cached(client, cache.as_ref(), false).await,
+            Self::Snapshot(snapshot) => Ok(snapshot.projects.clone()),
+        }
+    }
+
+    /// Resolves a project ID or list name the way [`resolve_project_ref`] does.
+    pub(crate) async fn resolve_project(&self, value: &str) -> Result<String> {
+        resolve_project_ref(&self.projects().await?, value)
+    }
+
+    /// A project with its open tasks. The snapshot has no board columns, so offline
+    /// data always comes back without them.
+    pub(crate) async fn project_data(&self, project_id: &str) -> Result<ProjectData> {
+        match self {
+            Self::Api { client, .. } => client.get_project_data(project_id).await,
+            Self::Snapshot(snapshot) => snapshot_project_data(snapshot, project_id),
+        }
+    }
+
+    /// Open and overdue counts per project; see [`project_task_counts`].
+    pub(crate) async fn project_task_counts(
+        &self,
+        project_ids: &[String],
+        on_progress: impl FnMut(usize, usize),
+    ) -> Result<HashMap<String, ProjectTaskCounts>> {
+        match self {
+            Self::Api { client, cache } => {
+                project_task_counts(client, cache.as_ref(), project_ids, on_progress).await
+            }
+            Self::Snapshot(snapshot) => {
+                let today = Local::now().date_naive();
+                Ok(project_ids
+                    .iter()
+                    .map(|project_id| {
+                        let tasks = snapshot_tasks_in_scope(snapshot, Some(project_id));
+                        (
+                            project_id.clone(),
+                            count_project_tasks(&tasks, today, &Local),
+                        )
+                    })
+                    .collect())
+            }
+        }
+    }
+}
+
+pub(super) fn snapshot_age_notice(snapshot: &SyncSnapshot, now: i64) -> String {
+    let age = describe_age(snapshot.age_secs(now));
+    if snapshot.is_stale(now) {
+        format!(
+            "Warning: offline data was synced {} ago; run 'tt sync' to refresh it.",
+            age
+        )
+    } else {
+        format!("Using offline data synced {} ago.", age)
+    }
+}
+
+pub(super) fn snapshot_project_names(snapshot: &SyncSnapshot) -> HashMap<String, String> {
+    snapshot
+        .projects
+        .iter()
+        .filter_map(|project| {
+            normalize_project_id(project.id.clone()).map(|id| (id, project.name.clone()))
+        })
+        .collect()
+}
+
+/// Resolves `--list` against the synced projects the way the API path does. An empty
+/// ID means the inbox.
+pub(super) fn snapshot_list_project_id(snapshot: &SyncSnapshot, list_name: &str) -> Result<String> {
+    let project = find_project_by_name(&snapshot.projects, list_name)?
+        .ok_or_else(|| anyhow!("List not found in offline data: {}", list_name))?;
+
+    if project.kind.as_deref() == Some("INBOX") {
+        return Ok(String::new());
+    }
+    normalize_project_id(project.id.clone())
+        .ok_or_else(|| anyhow!("List '{}' has no project ID", list_name))
+}
+
+fn is_inbox_task(task: &Task) -> bool {
+    let project_id = task.project_id.as_deref().unwrap_or_default().trim();
+    project_id.is_empty() || project_id.starts_with("inbox")
+}
+
+/// The synced tasks for one project, the inbox (an empty `project_id`), or every open
+/// project when no scope is given.
+pub(super) fn snapshot_tasks_in_scope(
+    snapshot: &SyncSnapshot,
+    project_id: Option<&str>,
+) -> Vec<Task> {
+    let closed: HashSet<&str> = snapshot
+        .projects
+        .iter()
+        .filter(|project| project.closed.unwrap_or(false))
+        .filter_map(|project| project.id.as_deref())
+        .collect();
+
+    snapshot
+        .tasks
+        .iter()
+        .filter(|task| match project_id {
+            Some("") => is_inbox_task(task),
+            Some(project_id) => task.project_id.as_deref() == Some(project_id),
+            None => !closed.contains(task.project_id.as_deref().unwrap_or_default()),
+        })
+        .cloned()
+        .collect()
+}
+
+fn snapshot_project_data(snapshot: &SyncSnapshot, project_id: &str) -> Result<ProjectData> {
+    let project = snapshot
+        .projects
+        .iter()
+        .find(|project| project.id.as_deref() == Some(project_id))
+        .cloned()
+        .ok_or_else(|| {
+            anyhow!(
+                "List {} is not in the offline data. Run 'tt sync' to refresh it.",
+                project_id
+            )
+        })?;
+    Ok(ProjectData {
+        project,
+        tasks: Some(snapshot_tasks_in_scope(snapshot, Some(project_id))),
+        columns: None,
+    })
+}
+
+pub(super) fn snapshot_task(snapshot: &SyncSnapshot, task_id: &str) -> Result<Task> {
+    snapshot
+        .tasks
+        .iter()
+        .find(|task| task.id.as_deref() == Some(task_id))
+        .cloned()
+        .ok_or_else(|| {
+            anyhow!(
+                "Task {} is not in the offline data. Run 'tt sync' to refresh it.",
+                task_id
+            )
+        })
+}
+
+fn format_sync_output(
+    snapshot: &SyncSnapshot,
+    path: &Path,
+    format: MessageFormat,
+    context: &OutputContext,
+) -> Result<String> {
+    match format {
+        MessageFormat::Json => Ok(format!(
+            "{}\n",
+            context.to_json_string(&json!({
+                "syncedAt": snapshot.synced_at,
+                "path": path.display().to_string(),
+                "projects": snapshot.projects.len(),
+                "tasks": snapshot.tasks.len(),
+            }))?
+        )),
+        MessageFormat::Human => Ok(format!(
+            "Synced {} lists and {} tasks to {}\n",
+            snapshot.projects.len(),
+            snapshot.tasks.len(),
+            path.display()
+        )),
+    }
+}
+
+/// Fetches every project and its open tasks into the snapshot `--offline` reads.
+pub async fn sync(args: SyncArgs, verbosity: Verbosity, context: &OutputContext) -> Result<()> {
+    let (client, cache) = TaskSource::online("sync")?;
+
+    let projects = get_projects_cached(&client, cache.as_ref(), true).await?;
+    let tasks = get_tasks_across_projects(&client, cache.as_ref(), true).await?;
+    let snapshot = SyncSnapshot::new(projects, tasks)?;
+
+    let path = app_config()?.snapshot_path();
+    snapshot.save(&path)?;
+
+    let rendered = format_sync_output(&snapshot, &path, args.output, context)?;
+    verbosity.confirm(&rendered, args.output);
+    Ok(())
+}
@@ -0,0 +1,172 @@
+use super::projects::{
+    get_tasks_across_projects, get_tasks_for_project, project_names_by_id, resolve_project_id,
+};
+use crate::api::TickTickClient;
+use crate::cache::CacheStore;
+use crate::models::{Task, TaskStatus};
+use anyhow::{anyhow, Result};
+use atty::Stream;
+use std::io::{self, BufRead, Write};
+
+/// How many matches the picker lists at once; typing more narrows the rest.
+const PICKER_PAGE_SIZE: usize = 15;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct PickerCandidate {
+    pub(super) task_id: String,
+    pub(super) project_id: String,
+    pub(super) label: String,
+}
+
+pub(super) fn picker_candidates(
+    tasks: &[Task],
+    project_names: &std::collections::HashMap<String, String>,
+) -> Vec<PickerCandidate> {
+    tasks
+        .iter()
+        .filter(|task| !matches!(task.status, Some(TaskStatus::Completed)))
+        .filter_map(|task| {
+            let task_id = task.id.clone()?;
+            let project_id = task.project_id.clone().unwrap_or_default();
+            let list = project_names
+                .get(&project_id)
+                .map(String::as_str)
+                .unwrap_or("Inbox");
+            Some(PickerCandidate {
+                task_id,
+                project_id,
+                label: format!("{} ({})", task.title, list),
+            })
+        })
+        .collect()
+}
+
+/// Scores `text` against `query` as a case-insensitive subsequence. Runs of consecutive
+/// characters and matches at word starts score higher; `None` means no match.
+pub(super) fn fuzzy_score(query: &str, text: &str) -> Option<i64> {
+    let query: Vec<char> = query
+        .chars()
+        .filter(|ch| !ch.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect();
+    if query.is_empty() {
+        return Some(0);
+    }
+
+    let text: Vec<char> = text.chars().flat_map(char::to_lowercase).collect();
+    let mut score = 0;
+    let mut next = 0;
+    let mut previous_match: Option<usize> = None;
+    for (index, ch) in text.iter().enumerate() {
+        if next == query.len() {
+            break;
+        }
+        if *ch != query[next] {
+            continue;
+        }
+        score += 1;
+        if previous_match.is_some_and(|previous| previous + 1 == index) {
+            score += 5;
+        }
+        if index == 0 || !text[index - 1].is_alphanumeric() {
+            score += 3;
+        }
+        previous_match = Some(index);
+        next += 1;
+    }
+
+    (next == query.len()).then_some(score - text.len() as i64 / 10)
+}
+
+pub(super) fn filter_candidates<'a>(
+    candidates: &'a [PickerCandidate],
+    query: &str,
+) -> Vec<&'a PickerCandidate> {
+    let mut matches: Vec<(i64, &PickerCandidate)> = candidates
+        .iter()
+        .filter_map(|candidate| {
+            fuzzy_score(query, &candidate.label).map(|score| (score, candidate))
+        })
+        .collect();
+    // Stable sort keeps the list order among equal scores.
+    matches.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+    matches
+        .into_iter()
+        .map(|(_, candidate)| candidate)
+        .collect()
+}
+
+/// Prompts until the user picks a number from the current matches; anything else
+/// becomes the new filter. An empty line cancels.
+fn prompt_for_candidate(candidates: &[PickerCandidate]) -> Result<Option<PickerCandidate>> {
+    let stdin = io::stdin();
+    let mut query = String::new();
+
+    loop {
+        let matches = filter_candidates(candidates, &query);
+        if matches.is_empty() {
+            println!("No open tasks match '{}'.", query);
+        }
+        for (index, candidate) in matches.iter().take(PICKER_PAGE_SIZE).enumerate() {
+            println!("{:>3}. {}", index + 1, candidate.label);
+        }
+        if matches.len() > PICKER_PAGE_SIZE {
+            println!(
+                "     ... {} more; type to narrow",
+                matches.len() - PICKER_PAGE_SIZE
+            );
+        }
+        print!("Pick a number, type to filter, or press Enter to cancel: ");
+        io::stdout().flush()?;
+
+        let mut input = String::new();
+        if stdin.lock().read_line(&mut input)? == 0 {
+            return Ok(None);
+        }
+        let input = input.trim();
+        if input.is_empty() {
+            return Ok(None);
+        }
+        if let Ok(choice) = input.parse::<usize>() {
+            if let Some(candidate) = choice
+                .checked_sub(1)
+                .and_then(|index| matches.get(index))
+                .filter(|_| choice <= PICKER_PAGE_SIZE)
+            {
+                return Ok(Some((*candidate).clone()));
+            }
+        }
+        query = input.to_string();
+    }
+}
+
+pub(super) fn missing_task_id() -> anyhow::Error {
+    anyhow!("the following required arguments were not provided: <TASK_ID>")
+}
+
+/// Lets the user choose an open task, scoped by `--project-id`/`--list` when given, and
+/// returns its ID and project. Only runs when stdin and stdout are terminals.
+pub(super) async fn pick_task(
+    client: &TickTickClient,
+    cache: Option<&CacheStore>,
+    project_id: Option<String>,
+    list: Option<String>,
+) -> Result<(String, String)> {
+    if !atty::is(Stream::Stdin) || !atty::is(Stream::Stdout) {
+        return Err(missing_task_id());
+    }
+
+    let tasks = match resolve_project_id(client, cache, project_id, list).await? {
+        Some(project_id) => get_tasks_for_project(client, &project_id).await?,
+        None => get_tasks_across_projects(client, cache, false).await?,
+    };
+    let project_names = project_names_by_id(client, cache).await?;
+    let candidates = picker_candidates(&tasks, &project_names);
+    if candidates.is_empty() {
+        return Err(anyhow!("No open tasks to pick from"));
+    }
+
+    prompt_for_candidate(&candidates)?
+        .map(|candidate| (candidate.task_id, candidate.project_id))
+        .ok_or_else(|| anyhow!("No task selected"))
+}
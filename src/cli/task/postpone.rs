@@ -0,0 +1,204 @@
+use super::dates::{
+    format_ticktick_datetime, local_datetime_to_utc, next_or_same_weekday, parse_due_filter_phrase,
+    parse_task_date, parse_ticktick_datetime, parse_weekday_token,
+};
+use crate::models::Task;
+use anyhow::{anyhow, Result};
+use chrono::{Duration, NaiveDate, TimeZone, Weekday};
+
+/// Where `tt task postpone` moves a task: a relative shift of its current dates, or an
+/// absolute day that the due date lands on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum PostponeTarget {
+    Days(i64),
+    Hours(i64),
+    Date(NaiveDate),
+}
+
+fn parse_postpone_offset(value: &str) -> Option<PostponeTarget> {
+    let value = value.trim().trim_start_matches('+').to_ascii_lowercase();
+    let split = value.find(|ch: char| !ch.is_ascii_digit())?;
+    let (amount, unit) = value.split_at(split);
+    let amount = amount.parse::<i64>().ok().filter(|amount| *amount > 0)?;
+
+    match unit.trim() {
+        "h" | "hr" | "hrs" | "hour" | "hours" => Some(PostponeTarget::Hours(amount)),
+        "d" | "day" | "days" => Some(PostponeTarget::Days(amount)),
+        "w" | "wk" | "week" | "weeks" => Some(PostponeTarget::Days(amount.checked_mul(7)?)),
+        _ => None,
+    }
+}
+
+/// Accepts an offset (`3h`, `1d`, `2w`) or any date phrase `--due-before` understands
+/// (`tomorrow`, `next monday`, `2026-03-01`).
+pub(super) fn parse_postpone_target(
+    value: &str,
+    today: NaiveDate,
+) -> std::result::Result<PostponeTarget, String> {
+    if let Some(offset) = parse_postpone_offset(value) {
+        return Ok(offset);
+    }
+
+    parse_due_filter_phrase(value, today)
+        .map(PostponeTarget::Date)
+        .map_err(|_| {
+            format!(
+                "Invalid postpone target '{}'. Use an offset like 3h, 1d, 2w or a date like tomorrow, next monday, 2026-03-01.",
+                value
+            )
+        })
+}
+
+/// Shifts a TickTick timestamp by whole calendar days in `tz` (keeping the wall-clock
+/// time across DST changes) and then by elapsed hours.
+pub(super) fn shift_ticktick_datetime<Tz: TimeZone>(
+    tz: &Tz,
+    value: &str,
+    days: i64,
+    hours: i64,
+) -> Option<String> {
+    let local = parse_ticktick_datetime(value)?
+        .with_timezone(tz)
+        .naive_local();
+    let shifted = local_datetime_to_utc(tz, local.checked_add_signed(Duration::days(days))?)?;
+    Some(format_ticktick_datetime(
+        shifted.checked_add_signed(Duration::hours(hours))?,
+    ))
+}
+
+pub(super) fn local_task_date<Tz: TimeZone>(tz: &Tz, value: &str) -> Option<NaiveDate> {
+    match parse_ticktick_datetime(value) {
+        Some(dt) => Some(dt.with_timezone(tz).date_naive()),
+        None => parse_task_date(value),
+    }
+}
+
+/// Moves a task's due and start dates by the same amount. Tasks without a due date can
+/// only be given an absolute target, which becomes an all-day due date unless the task
+/// says otherwise.
+pub(super) fn postpone_task<Tz: TimeZone>(
+    task: &mut Task,
+    target: PostponeTarget,
+    tz: &Tz,
+) -> Result<()> {
+    let Some(due_date) = task.due_date.clone() else {
+        let PostponeTarget::Date(date) = target else {
+            return Err(anyhow!(
+                "Task '{}' has no due date to shift; give a date like tomorrow or 2026-03-01",
+                task.title
+            ));
+        };
+        let midnight = local_datetime_to_utc(tz, date.and_hms_opt(0, 0, 0).unwrap_or_default())
+            .map(format_ticktick_datetime)
+            .ok_or_else(|| anyhow!("Failed to format date '{}'", date))?;
+        task.due_date = Some(midnight.clone());
+        task.start_date = Some(midnight);
+        if task.is_all_day.is_none() {
+            task.is_all_day = Some(true);
+        }
+        return Ok(());
+    };
+
+    let (days, hours) = match target {
+        PostponeTarget::Days(days) => (days, 0),
+        PostponeTarget::Hours(hours) => (0, hours),
+        PostponeTarget::Date(date) => {
+            let current = local_task_date(tz, &due_date)
+                .ok_or_else(|| anyhow!("Unrecognized due date '{}'", due_date))?;
+            ((date - current).num_days(), 0)
+        }
+    };
+
+    for field in [&mut task.due_date, &mut task.start_date] {
+        if let Some(value) = field.as_deref() {
+            let shifted = shift_ticktick_datetime(tz, value, days, hours)
+                .ok_or_else(|| anyhow!("Unrecognized task date '{}'", value))?;
+            *field = Some(shifted);
+        }
+    }
+
+    Ok(())
+}
+
+/// How far `tt task defer` pushes a task. Unlike postpone targets these count from the
+/// task's own due date (or today when it has none), so `friday` means the Friday after it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum DeferSpec {
+    Days(i64),
+    Weekday(Weekday),
+}
+
+/// Accepts `1d`, `2w`, `tomorrow`, `week`, or a weekday such as `fri` or `next friday`.
+pub(super) fn parse_defer_spec(value: &str) -> std::result::Result<DeferSpec, String> {
+    match parse_postpone_offset(value) {
+        Some(PostponeTarget::Days(days)) => return Ok(DeferSpec::Days(days)),
+        Some(_) => {
+            return Err(format!(
+                "Defer moves whole days; use 'tt task postpone' for '{}'.",
+                value.trim()
+            ))
+        }
+        None => {}
+    }
+
+    let normalized = value.trim().to_ascii_lowercase();
+    let phrase = normalized
+        .strip_prefix("next ")
+        .unwrap_or(&normalized)
+        .trim();
+    match phrase {
+        "tomorrow" => Ok(DeferSpec::Days(1)),
+        "week" => Ok(DeferSpec::Days(7)),
+        _ => parse_weekday_token(phrase)
+            .map(DeferSpec::Weekday)
+            .ok_or_else(|| {
+                format!(
+                    "Invalid defer spec '{}'. Use 1d, 2w, tomorrow, or a weekday like friday.",
+                    value
+                )
+            }),
+    }
+}
+
+/// The day a deferred task lands on; a weekday is always strictly after `from`.
+pub(super) fn defer_target(from: NaiveDate, spec: DeferSpec) -> NaiveDate {
+    match spec {
+        DeferSpec::Days(days) => from + Duration::days(days),
+        DeferSpec::Weekday(weekday) => next_or_same_weekday(from + Duration::days(1), weekday),
+    }
+}
+
+/// Moves a task's due date by `spec`, counted from its current due date or `today`.
+/// Start moves with due and timed tasks keep their time of day.
+pub(super) fn defer_task<Tz: TimeZone>(
+    task: &mut Task,
+    spec: DeferSpec,
+    today: NaiveDate,
+    tz: &Tz,
+) -> Result<()> {
+    let Some(due_date) = task.due_date.as_deref() else {
+        return postpone_task(task, PostponeTarget::Date(defer_target(today, spec)), tz);
+    };
+    let current = local_task_date(tz, due_date)
+        .ok_or_else(|| anyhow!("Unrecognized due date '{}'", due_date))?;
+    let days = (defer_target(current, spec) - current).num_days();
+    postpone_task(task, PostponeTarget::Days(days), tz)
+}
+
+/// A due date as the user thinks of it: the local day, plus the time for timed tasks.
+pub(super) fn describe_task_due<Tz: TimeZone>(task: &Task, tz: &Tz) -> String
+where
+    Tz::Offset: std::fmt::Display,
+{
+    let Some(due) = task.due_date.as_deref() else {
+        return "no due date".to_string();
+    };
+    match parse_ticktick_datetime(due) {
+        Some(dt) if !task.is_all_day.unwrap_or(false) => {
+            dt.with_timezone(tz).format("%Y-%m-%d %H:%M").to_string()
+        }
+        _ => local_task_date(tz, due)
+            .map(|date| date.to_string())
+            .unwrap_or_else(|| due.to_string()),
+    }
+}
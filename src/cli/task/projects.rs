@@ -1,13 +1,20 @@
-use super::filters::{is_inbox_list_name, normalize_list_name};
+use super::filters::is_inbox_list_name;
+use super::postpone::local_task_date;
 use crate::api::TickTickClient;
-use crate::cache::{get_projects_cached, CacheStore};
-use crate::models::Task;
+use crate::cache::{get_projects_cached, CacheStore, JournalOperation, ProjectTaskCounts};
+use crate::cli::lists::find_project_by_name;
+use crate::config::{AppConfig, DefaultProjectIds};
+use crate::models::{Project, Task};
 use anyhow::{anyhow, Result};
+use chrono::{Local, NaiveDate, TimeZone};
+use regex::Regex;
 use serde_json::Value;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use tokio::task::JoinSet;
 
 const MAX_CONCURRENT_PROJECT_FETCHES: usize = 8;
+/// How long the default list and Inbox IDs cached in `config.toml` are trusted.
+const DEFAULT_PROJECT_IDS_TTL_SECS: i64 = 24 * 60 * 60;
 
 #[derive(Debug, Clone)]
 pub(super) struct ResolvedTaskProjectId {
@@ -19,6 +26,11 @@ pub(super) fn cache_store() -> Option<CacheStore> {
     CacheStore::new().ok()
 }
 
+/// Where the default list and Inbox IDs are cached; `None` skips that cache.
+pub(super) fn default_project_config() -> Option<AppConfig> {
+    AppConfig::new().ok()
+}
+
 pub(super) fn remember_tasks(
     cache: Option<&CacheStore>,
     tasks: &[Task],
@@ -37,6 +49,13 @@ pub(super) fn remember_task(
     remember_tasks(cache, std::slice::from_ref(task), fallback_project_id);
 }
 
+/// Journals a mutation for `tt undo`. Failing to record never fails the command itself.
+pub(super) fn record_operation(cache: Option<&CacheStore>, operation: JournalOperation) {
+    if let Some(cache) = cache {
+        let _ = cache.record_operation(operation);
+    }
+}
+
 fn store_task_project_id(cache: Option<&CacheStore>, task_id: &str, project_id: &str) {
     if let Some(cache) = cache {
         let _ = cache.set_task_project_id(task_id, project_id);
@@ -67,14 +86,8 @@ async fn resolve_project_from_list(
     list_name: &str,
 ) -> Result<String> {
     let projects = get_projects_cached(client, cache, false).await?;
-    let needle = normalize_list_name(list_name);
-
-    let project = projects.iter().find(|project| {
-        project.name.eq_ignore_ascii_case(list_name)
-            || (!needle.is_empty() && normalize_list_name(&project.name) == needle)
-    });
 
-    let Some(project) = project else {
+    let Some(project) = find_project_by_name(&projects, list_name)? else {
         if is_inbox_list_name(list_name) {
             return Ok(String::new());
         }
@@ -111,12 +124,65 @@ pub(super) async fn resolve_project_id(
     Ok(None)
 }
 
-pub(super) async fn infer_default_project_id(
+/// TickTick's inbox id (`inbox<user id>`): the `INBOX` entry of the project list when the
+/// account has one, otherwise the id reported by the inbox data endpoint.
+pub(super) async fn resolve_inbox_project_id(
     client: &TickTickClient,
     cache: Option<&CacheStore>,
+    defaults: Option<&AppConfig>,
 ) -> Result<String> {
+    if let Some(inbox_id) = fresh_default_project_ids(defaults).and_then(|ids| ids.inbox_id) {
+        return Ok(inbox_id);
+    }
+
     let projects = get_projects_cached(client, cache, false).await?;
+    remember_default_project_ids(defaults, &projects);
+    if let Some(project_id) = inbox_project_id(&projects) {
+        return Ok(project_id);
+    }
+
+    client
+        .get_inbox_project_id()
+        .await?
+        .ok_or_else(|| anyhow!("Unable to determine the Inbox list ID. Pass --project-id instead."))
+}
 
+/// Resolves the list a new task is created in. `--inbox`, and an inbox-named list that
+/// has no ID of its own, resolve to the real inbox ID; the API rejects an empty one.
+pub(super) async fn resolve_create_project_id(
+    client: &TickTickClient,
+    cache: Option<&CacheStore>,
+    defaults: Option<&AppConfig>,
+    inbox: bool,
+    project_id: Option<String>,
+    list_name: Option<String>,
+) -> Result<Option<String>> {
+    if inbox {
+        return resolve_inbox_project_id(client, cache, defaults)
+            .await
+            .map(Some);
+    }
+
+    match resolve_project_id(client, cache, project_id, list_name).await? {
+        Some(project_id) if project_id.trim().is_empty() => {
+            resolve_inbox_project_id(client, cache, defaults)
+                .await
+                .map(Some)
+        }
+        resolved => Ok(resolved),
+    }
+}
+
+fn inbox_project_id(projects: &[Project]) -> Option<String> {
+    projects
+        .iter()
+        .find(|project| project.kind.as_deref() == Some("INBOX"))
+        .and_then(|project| normalize_project_id(project.id.clone()))
+}
+
+/// The list new tasks go to when none is given: the Inbox, a list named "Inbox", the
+/// first open list, then any list.
+pub(super) fn pick_default_project_id(projects: &[Project]) -> Result<String> {
     if projects.is_empty() {
         return Err(anyhow!(
             "No lists found. Create one with 'tt project add <name>' first."
@@ -143,6 +209,70 @@ pub(super) async fn infer_default_project_id(
         .ok_or_else(|| anyhow!("Unable to infer a default list. Pass --project-id or --list."))
 }
 
+fn fresh_default_project_ids(defaults: Option<&AppConfig>) -> Option<DefaultProjectIds> {
+    defaults?
+        .load_default_project_ids()
+        .ok()
+        .flatten()
+        .filter(|ids| ids.is_fresh(chrono::Utc::now().timestamp(), DEFAULT_PROJECT_IDS_TTL_SECS))
+}
+
+/// Resolves the default list from the IDs cached in `config.toml` while they are fresh,
+/// otherwise from the project list, caching what it finds for next time.
+pub(super) async fn infer_default_project_id(
+    client: &TickTickClient,
+    cache: Option<&CacheStore>,
+    defaults: Option<&AppConfig>,
+) -> Result<String> {
+    if let Some(ids) = fresh_default_project_ids(defaults) {
+        return Ok(ids.default_project_id);
+    }
+
+    let projects = get_projects_cached(client, cache, false).await?;
+    remember_default_project_ids(defaults, &projects);
+    pick_default_project_id(&projects)
+}
+
+/// Caches the default list and Inbox IDs from a fresh project list; best effort.
+fn remember_default_project_ids(defaults: Option<&AppConfig>, projects: &[Project]) {
+    let (Some(defaults), Ok(default_project_id)) = (defaults, pick_default_project_id(projects))
+    else {
+        return;
+    };
+    let ids = DefaultProjectIds {
+        default_project_id,
+        inbox_id: inbox_project_id(projects),
+        updated_at: chrono::Utc::now().timestamp(),
+    };
+    let _ = defaults.save_default_project_ids(Some(&ids));
+}
+
+/// Drops the cached default list and Inbox IDs when `project_id` is one of them, after the
+/// API said it no longer exists. Returns whether it was, i.e. whether a retry may help.
+pub(super) fn forget_default_project_id(defaults: Option<&AppConfig>, project_id: &str) -> bool {
+    let Some(defaults) = defaults else {
+        return false;
+    };
+    match defaults.load_default_project_ids() {
+        Ok(Some(ids)) if ids.contains(project_id) => {
+            let _ = defaults.save_default_project_ids(None);
+            true
+        }
+        _ => false,
+    }
+}
+
+pub(super) async fn project_names_by_id(
+    client: &TickTickClient,
+    cache: Option<&CacheStore>,
+) -> Result<HashMap<String, String>> {
+    Ok(get_projects_cached(client, cache, false)
+        .await?
+        .into_iter()
+        .filter_map(|project| normalize_project_id(project.id).map(|id| (id, project.name)))
+        .collect())
+}
+
 pub(super) async fn get_tasks_for_project(
     client: &TickTickClient,
     project_id: &str,
@@ -183,24 +313,29 @@ pub(super) async fn get_tasks_for_project(
     Ok(data.tasks.unwrap_or_default())
 }
 
-async fn fetch_tasks_for_project_batch(
+/// Fetches one batch of projects concurrently, keeping each project's result (in input
+/// order) rather than stopping at the first failure.
+async fn fetch_project_batch_results(
     client: &TickTickClient,
     project_ids: &[String],
-) -> Result<Vec<(String, Vec<Task>)>> {
+) -> Result<Vec<(String, Result<Vec<Task>>)>> {
     let mut results = Vec::with_capacity(project_ids.len());
     let mut tasks = JoinSet::new();
 
     for (index, project_id) in project_ids.iter().cloned().enumerate() {
         let client = client.clone();
         tasks.spawn(async move {
-            let data = client.get_project_data(&project_id).await?;
-            Ok::<_, anyhow::Error>((index, project_id, data.tasks.unwrap_or_default()))
+            let tasks_for_project = client
+                .get_project_data(&project_id)
+                .await
+                .map(|data| data.tasks.unwrap_or_default());
+            (index, project_id, tasks_for_project)
         });
     }
 
     while let Some(result) = tasks.join_next().await {
         let (index, project_id, tasks_for_project) =
-            result.map_err(|err| anyhow!("Task fetch worker failed: {}", err))??;
+            result.map_err(|err| anyhow!("Task fetch worker failed: {}", err))?;
         results.push((index, project_id, tasks_for_project));
     }
 
@@ -211,18 +346,149 @@ async fn fetch_tasks_for_project_batch(
         .collect())
 }
 
+async fn fetch_tasks_for_project_batch(
+    client: &TickTickClient,
+    project_ids: &[String],
+) -> Result<Vec<(String, Vec<Task>)>> {
+    fetch_project_batch_results(client, project_ids)
+        .await?
+        .into_iter()
+        .map(|(project_id, tasks_for_project)| Ok((project_id, tasks_for_project?)))
+        .collect()
+}
+
+pub(super) fn count_project_tasks<Tz: TimeZone>(
+    tasks: &[Task],
+    today: NaiveDate,
+    tz: &Tz,
+) -> ProjectTaskCounts {
+    ProjectTaskCounts {
+        open: tasks.len(),
+        overdue: tasks
+            .iter()
+            .filter(|task| {
+                task.due_date
+                    .as_deref()
+                    .and_then(|due| local_task_date(tz, due))
+                    .is_some_and(|due| due < today)
+            })
+            .count(),
+    }
+}
+
+/// Open and overdue counts for `project_ids`, from the counts cache when fresh and
+/// otherwise fetched in batches. Projects whose fetch fails are left out of the map.
+/// `on_progress` gets the number of projects done after each batch.
+pub(super) async fn project_task_counts(
+    client: &TickTickClient,
+    cache: Option<&CacheStore>,
+    project_ids: &[String],
+    mut on_progress: impl FnMut(usize, usize),
+) -> Result<HashMap<String, ProjectTaskCounts>> {
+    let mut counts = cache
+        .and_then(|cache| cache.load_project_counts().ok())
+        .unwrap_or_default();
+    let missing: Vec<String> = project_ids
+        .iter()
+        .filter(|id| !counts.contains_key(*id))
+        .cloned()
+        .collect();
+    if missing.is_empty() {
+        return Ok(counts);
+    }
+
+    let today = Local::now().date_naive();
+    let mut done = 0;
+    for batch in missing.chunks(MAX_CONCURRENT_PROJECT_FETCHES) {
+        for (project_id, tasks_for_project) in fetch_project_batch_results(client, batch).await? {
+            if let Ok(tasks_for_project) = tasks_for_project {
+                remember_tasks(cache, &tasks_for_project, Some(&project_id));
+                counts.insert(
+                    project_id,
+                    count_project_tasks(&tasks_for_project, today, &Local),
+                );
+            }
+        }
+        done += batch.len();
+        on_progress(done, missing.len());
+    }
+
+    if let Some(cache) = cache {
+        let _ = cache.save_project_counts(&counts);
+    }
+    Ok(counts)
+}
+
 pub(super) async fn get_tasks_across_projects(
     client: &TickTickClient,
     cache: Option<&CacheStore>,
+    include_closed: bool,
 ) -> Result<Vec<Task>> {
     let projects = get_projects_cached(client, cache, false).await?;
-    let mut tasks = Vec::new();
     let project_ids: Vec<String> = projects
         .into_iter()
+        .filter(|project| include_closed || !project.closed.unwrap_or(false))
         .filter_map(|project| normalize_project_id(project.id))
         .collect();
 
-    for batch in project_ids.chunks(MAX_CONCURRENT_PROJECT_FETCHES) {
+    get_tasks_in_scope(
+        client,
+        cache,
+        &ListScope {
+            project_ids,
+            inbox: true,
+        },
+    )
+    .await
+}
+
+/// The lists picked by `--list-regex`: project IDs, plus the Inbox when its name matches.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(super) struct ListScope {
+    pub(super) project_ids: Vec<String>,
+    pub(super) inbox: bool,
+}
+
+impl ListScope {
+    /// Open lists (closed ones too with `include_closed`) whose name matches `pattern`.
+    /// The Inbox is not in the project list, so it is matched by its name alone.
+    pub(super) fn matching(projects: &[Project], pattern: &Regex, include_closed: bool) -> Self {
+        let mut scope = ListScope {
+            inbox: pattern.is_match("Inbox"),
+            ..Default::default()
+        };
+        for project in projects {
+            if !pattern.is_match(&project.name)
+                || (!include_closed && project.closed.unwrap_or(false))
+            {
+                continue;
+            }
+            if project.kind.as_deref() == Some("INBOX") {
+                scope.inbox = true;
+            } else if let Some(id) = normalize_project_id(project.id.clone()) {
+                scope.project_ids.push(id);
+            }
+        }
+        scope
+    }
+
+    pub(super) fn contains(&self, task: &Task) -> bool {
+        match normalize_project_id(task.project_id.clone()) {
+            Some(id) if id.starts_with("inbox") => self.inbox,
+            Some(id) => self.project_ids.contains(&id),
+            None => self.inbox,
+        }
+    }
+}
+
+/// Tasks in each list of `scope`, fetched a few lists at a time.
+pub(super) async fn get_tasks_in_scope(
+    client: &TickTickClient,
+    cache: Option<&CacheStore>,
+    scope: &ListScope,
+) -> Result<Vec<Task>> {
+    let mut tasks = Vec::new();
+    for batch in scope.project_ids.chunks(MAX_CONCURRENT_PROJECT_FETCHES) {
         let batch_tasks = fetch_tasks_for_project_batch(client, batch).await?;
         for (project_id, project_tasks) in batch_tasks {
             remember_tasks(cache, &project_tasks, Some(&project_id));
@@ -230,16 +496,62 @@ pub(super) async fn get_tasks_across_projects(
         }
     }
 
-    if let Ok(inbox_tasks) = get_tasks_for_project(client, "").await {
-        remember_tasks(cache, &inbox_tasks, None);
-        tasks.extend(inbox_tasks);
+    if scope.inbox {
+        if let Ok(inbox_tasks) = get_tasks_for_project(client, "").await {
+            remember_tasks(cache, &inbox_tasks, None);
+            tasks.extend(inbox_tasks);
+        }
     }
 
     dedupe_tasks_by_id(&mut tasks);
     Ok(tasks)
 }
 
-fn dedupe_tasks_by_id(tasks: &mut Vec<Task>) {
+/// Crawls completed tasks for the lists in scope: the resolved project, the projects
+/// seen on `open_tasks` (which covers the inbox), or every list when listing across projects.
+/// `completed_from` limits the crawl to tasks completed at or after that timestamp.
+pub(super) async fn get_completed_tasks_in_scope(
+    client: &TickTickClient,
+    cache: Option<&CacheStore>,
+    project_id: Option<&str>,
+    across_projects: bool,
+    open_tasks: &[Task],
+    completed_from: Option<&str>,
+) -> Result<Vec<Task>> {
+    let mut project_ids: Vec<String> = project_id
+        .and_then(|id| normalize_project_id(Some(id.to_string())))
+        .into_iter()
+        .collect();
+    if across_projects {
+        project_ids.extend(
+            get_projects_cached(client, cache, false)
+                .await?
+                .into_iter()
+                .filter_map(|project| normalize_project_id(project.id)),
+        );
+    }
+    if project_ids.is_empty() || across_projects {
+        project_ids.extend(
+            open_tasks
+                .iter()
+                .filter_map(|task| normalize_project_id(task.project_id.clone())),
+        );
+    }
+
+    let mut seen = HashSet::new();
+    project_ids.retain(|id| seen.insert(id.clone()));
+    if project_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let tasks = client
+        .get_completed_tasks(&project_ids, completed_from, None)
+        .await?;
+    remember_tasks(cache, &tasks, None);
+    Ok(tasks)
+}
+
+pub(super) fn dedupe_tasks_by_id(tasks: &mut Vec<Task>) {
     let mut seen = HashSet::new();
     tasks.retain(|task| match task.id.as_deref() {
         Some(id) => seen.insert(id.to_string()),
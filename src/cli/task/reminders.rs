@@ -0,0 +1,309 @@
+use super::dates::{
+    local_datetime_to_utc, normalize_date_token, parse_clock_time, parse_ticktick_datetime,
+};
+use anyhow::{anyhow, Result};
+use chrono::{NaiveDate, NaiveTime, TimeZone};
+
+const TRIGGER_PREFIX: &str = "TRIGGER:";
+const AT_TIME_TRIGGER: &str = "TRIGGER:PT0S";
+const ACCEPTED_FORMS: &str =
+    "0, at-time, 30m, 1h, 1d, 2w, combinations like 1h30m, or a raw TRIGGER:-PT30M";
+
+/// How long before the task's time a reminder fires.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct ReminderOffset {
+    days: u32,
+    hours: u32,
+    minutes: u32,
+}
+
+impl ReminderOffset {
+    fn is_zero(self) -> bool {
+        self.days == 0 && self.hours == 0 && self.minutes == 0
+    }
+
+    fn add(&mut self, amount: u32, unit: &str) -> Option<()> {
+        match unit {
+            "m" | "min" | "mins" | "minute" | "minutes" => self.minutes += amount,
+            "h" | "hr" | "hrs" | "hour" | "hours" => self.hours += amount,
+            "d" | "day" | "days" => self.days += amount,
+            "w" | "week" | "weeks" => self.days += amount.checked_mul(7)?,
+            _ => return None,
+        }
+        Some(())
+    }
+
+    fn to_trigger(self) -> String {
+        if self.is_zero() {
+            return AT_TIME_TRIGGER.to_string();
+        }
+
+        let mut trigger = format!("{}-P", TRIGGER_PREFIX);
+        if self.days > 0 {
+            trigger.push_str(&format!("{}D", self.days));
+        }
+        if self.hours > 0 || self.minutes > 0 {
+            trigger.push('T');
+            if self.hours > 0 {
+                trigger.push_str(&format!("{}H", self.hours));
+            }
+            if self.minutes > 0 {
+                trigger.push_str(&format!("{}M", self.minutes));
+            }
+        }
+        trigger
+    }
+
+    fn to_friendly(self) -> String {
+        if self.is_zero() {
+            return "at-time".to_string();
+        }
+
+        let mut friendly = String::new();
+        for (amount, unit) in [(self.days, "d"), (self.hours, "h"), (self.minutes, "m")] {
+            if amount > 0 {
+                friendly.push_str(&format!("{}{}", amount, unit));
+            }
+        }
+        friendly
+    }
+}
+
+/// Parses `30m`, `1h30m`, or `2 days` style offsets; `0` and `at-time` mean no offset.
+fn parse_friendly_offset(value: &str) -> Option<ReminderOffset> {
+    let value = value.trim().to_ascii_lowercase();
+    if matches!(value.as_str(), "0" | "at-time" | "attime" | "on-time") {
+        return Some(ReminderOffset::default());
+    }
+
+    let compact: String = value.chars().filter(|ch| !ch.is_whitespace()).collect();
+    let mut offset = ReminderOffset::default();
+    let mut chars = compact.chars().peekable();
+    let mut saw_part = false;
+
+    while chars.peek().is_some() {
+        let mut digits = String::new();
+        while let Some(ch) = chars.peek().copied().filter(char::is_ascii_digit) {
+            digits.push(ch);
+            chars.next();
+        }
+        let mut unit = String::new();
+        while let Some(ch) = chars.peek().copied().filter(char::is_ascii_alphabetic) {
+            unit.push(ch);
+            chars.next();
+        }
+        if digits.is_empty() || unit.is_empty() {
+            return None;
+        }
+        offset.add(digits.parse().ok()?, &unit)?;
+        saw_part = true;
+    }
+
+    saw_part.then_some(offset)
+}
+
+fn parse_iso_duration(duration: &str) -> Option<ReminderOffset> {
+    let (date_part, time_part) = match duration.split_once('T') {
+        Some((date_part, time_part)) => (date_part, time_part),
+        None => (duration, ""),
+    };
+
+    let mut offset = ReminderOffset::default();
+    for (part, units) in [(date_part, "DW"), (time_part, "HMS")] {
+        let mut digits = String::new();
+        for ch in part.chars() {
+            if ch.is_ascii_digit() {
+                digits.push(ch);
+                continue;
+            }
+            if digits.is_empty() || !units.contains(ch) {
+                return None;
+            }
+            let amount: u32 = digits.parse().ok()?;
+            match ch {
+                'W' => offset.days += amount.checked_mul(7)?,
+                'D' => offset.days += amount,
+                'H' => offset.hours += amount,
+                'M' => offset.minutes += amount,
+                'S' if amount == 0 => {}
+                _ => return None,
+            }
+            digits.clear();
+        }
+        if !digits.is_empty() {
+            return None;
+        }
+    }
+    Some(offset)
+}
+
+/// Parses TickTick's `TRIGGER:-P1DT2H30M` form into an offset before the task time.
+/// Positive triggers (such as all-day `TRIGGER:P0DT9H0M0S`) have no friendly form
+/// unless they are zero, so they return `None`.
+fn parse_trigger(trigger: &str) -> Option<ReminderOffset> {
+    let duration = trigger.strip_prefix(TRIGGER_PREFIX)?;
+    match duration.strip_prefix('-') {
+        Some(before) => parse_iso_duration(before.strip_prefix('P')?),
+        None => parse_iso_duration(duration.strip_prefix('P')?).filter(|offset| offset.is_zero()),
+    }
+}
+
+fn is_valid_trigger(trigger: &str) -> bool {
+    trigger
+        .strip_prefix(TRIGGER_PREFIX)
+        .map(|duration| duration.strip_prefix('-').unwrap_or(duration))
+        .and_then(|duration| duration.strip_prefix('P'))
+        .and_then(parse_iso_duration)
+        .is_some()
+}
+
+/// Value parser for `--reminders`: turns a friendly offset into a TickTick trigger, and
+/// passes valid raw triggers through unchanged.
+pub(super) fn parse_reminder_value(value: &str) -> std::result::Result<String, String> {
+    let trimmed = value.trim();
+    if trimmed.to_ascii_uppercase().starts_with(TRIGGER_PREFIX) {
+        let trigger = trimmed.to_ascii_uppercase();
+        if is_valid_trigger(&trigger) {
+            return Ok(trigger);
+        }
+        return Err(format!(
+            "Invalid reminder trigger '{}'. Use {}.",
+            value, ACCEPTED_FORMS
+        ));
+    }
+
+    parse_friendly_offset(trimmed)
+        .map(ReminderOffset::to_trigger)
+        .ok_or_else(|| format!("Invalid reminder '{}'. Use {}.", value, ACCEPTED_FORMS))
+}
+
+/// A `--reminder-at` value: a time of day on the due date, or a full date and time.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) struct ReminderAt {
+    date: Option<NaiveDate>,
+    time: NaiveTime,
+}
+
+/// Value parser for `--reminder-at`: `9am`, `21:15`, `2026-10-14T08:00`, or
+/// `2026-10-14 9am`.
+pub(super) fn parse_reminder_at(value: &str) -> std::result::Result<ReminderAt, String> {
+    let trimmed = value.trim();
+    let (date, time) = match trimmed.split_once(['T', ' ']) {
+        Some((date, time)) => match NaiveDate::parse_from_str(date, "%Y-%m-%d") {
+            Ok(date) => (Some(date), time),
+            Err(_) => (None, trimmed),
+        },
+        None => (None, trimmed),
+    };
+
+    parse_clock_time(time)
+        .map(|time| ReminderAt { date, time })
+        .ok_or_else(|| {
+            format!(
+                "Invalid reminder time '{}'. Use a time like 9am or 21:15, or a date and time like 2026-10-14T09:00.",
+                value
+            )
+        })
+}
+
+/// Turns an absolute reminder into the trigger TickTick stores, which is relative to the
+/// due time: `-P...` fires before it and `P...` after it (TickTick's form for all-day
+/// tasks, such as `TRIGGER:P0DT9H0M0S` for 9am on the day).
+pub(super) fn reminder_at_trigger<Tz: TimeZone>(
+    at: ReminderAt,
+    due_date: Option<&str>,
+    tz: &Tz,
+) -> Result<String> {
+    let due = due_date.and_then(parse_ticktick_datetime).ok_or_else(|| match at.date {
+        None => anyhow!(
+            "--reminder-at {} needs a due date to anchor to; set one, or give a full date and time with --due-date",
+            at.time.format("%H:%M")
+        ),
+        Some(_) => anyhow!(
+            "TickTick reminders are relative to the due date; set --due-date to use --reminder-at"
+        ),
+    })?;
+    let date = at
+        .date
+        .unwrap_or_else(|| due.with_timezone(tz).date_naive());
+    let fires_at = local_datetime_to_utc(tz, date.and_time(at.time))
+        .ok_or_else(|| anyhow!("Reminder time {} {} does not exist", date, at.time))?;
+
+    let minutes = (fires_at - due.to_utc()).num_minutes();
+    let magnitude = u32::try_from(minutes.unsigned_abs())
+        .map_err(|_| anyhow!("Reminder is too far from the due date"))?;
+    let offset = ReminderOffset {
+        days: magnitude / (24 * 60),
+        hours: magnitude % (24 * 60) / 60,
+        minutes: magnitude % 60,
+    };
+    if minutes <= 0 {
+        return Ok(offset.to_trigger());
+    }
+    Ok(format!(
+        "{}P{}DT{}H{}M0S",
+        TRIGGER_PREFIX, offset.days, offset.hours, offset.minutes
+    ))
+}
+
+/// Renders a stored trigger in the friendly form `--reminders` accepts; unknown triggers
+/// are shown as-is.
+pub(super) fn describe_reminder(trigger: &str) -> String {
+    parse_trigger(trigger)
+        .map(ReminderOffset::to_friendly)
+        .unwrap_or_else(|| trigger.to_string())
+}
+
+/// Matches `remind me 30 min before`, `remind me 1h before`, or `remind me at time`.
+fn parse_reminder_phrase(tokens: &[&str], index: usize) -> Option<(usize, String)> {
+    let token_at = |offset: usize| {
+        tokens
+            .get(index + offset)
+            .map(|value| normalize_date_token(value))
+    };
+    if token_at(0)? != "remind" || token_at(1)? != "me" {
+        return None;
+    }
+
+    if token_at(2)? == "at" && token_at(3)? == "time" {
+        return Some((4, AT_TIME_TRIGGER.to_string()));
+    }
+
+    for length in [1, 2] {
+        let offset_text = (2..2 + length)
+            .map(token_at)
+            .collect::<Option<Vec<_>>>()?
+            .join("");
+        if token_at(2 + length).as_deref() != Some("before") {
+            continue;
+        }
+        if let Some(offset) = parse_friendly_offset(&offset_text) {
+            return Some((3 + length, offset.to_trigger()));
+        }
+    }
+    None
+}
+
+pub(super) fn extract_reminder_from_input(raw: &str) -> (String, Option<String>) {
+    let tokens: Vec<&str> = raw.split_whitespace().collect();
+
+    for index in 0..tokens.len() {
+        if let Some((consumed, trigger)) = parse_reminder_phrase(&tokens, index) {
+            let title = tokens
+                .iter()
+                .enumerate()
+                .filter_map(|(i, value)| {
+                    if i >= index && i < index + consumed {
+                        None
+                    } else {
+                        Some(*value)
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(" ");
+            return (title, Some(trigger));
+        }
+    }
+
+    (raw.trim().to_string(), None)
+}
@@ -0,0 +1,571 @@
+use super::dates::{normalize_date_token, parse_day_token, parse_weekday_token};
+use chrono::Weekday;
+
+const RRULE_PREFIX: &str = "RRULE:";
+const WEEKDAYS: [Weekday; 5] = [
+    Weekday::Mon,
+    Weekday::Tue,
+    Weekday::Wed,
+    Weekday::Thu,
+    Weekday::Fri,
+];
+const WEEKEND: [Weekday; 2] = [Weekday::Sat, Weekday::Sun];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum RepeatFrequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+impl RepeatFrequency {
+    fn rrule_name(self) -> &'static str {
+        match self {
+            Self::Daily => "DAILY",
+            Self::Weekly => "WEEKLY",
+            Self::Monthly => "MONTHLY",
+            Self::Yearly => "YEARLY",
+        }
+    }
+
+    fn from_rrule_name(value: &str) -> Option<Self> {
+        match value {
+            "DAILY" => Some(Self::Daily),
+            "WEEKLY" => Some(Self::Weekly),
+            "MONTHLY" => Some(Self::Monthly),
+            "YEARLY" => Some(Self::Yearly),
+            _ => None,
+        }
+    }
+
+    fn unit(self) -> &'static str {
+        match self {
+            Self::Daily => "day",
+            Self::Weekly => "week",
+            Self::Monthly => "month",
+            Self::Yearly => "year",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) struct RepeatRule {
+    pub(super) frequency: RepeatFrequency,
+    pub(super) interval: u32,
+    pub(super) by_day: Vec<Weekday>,
+    pub(super) by_month_day: Option<i32>,
+    pub(super) count: Option<u32>,
+    pub(super) until: Option<String>,
+}
+
+impl RepeatRule {
+    fn new(frequency: RepeatFrequency, interval: u32) -> Self {
+        Self {
+            frequency,
+            interval,
+            by_day: Vec::new(),
+            by_month_day: None,
+            count: None,
+            until: None,
+        }
+    }
+
+    fn on_days(days: &[Weekday]) -> Self {
+        let mut rule = Self::new(RepeatFrequency::Weekly, 1);
+        rule.by_day = days.to_vec();
+        rule
+    }
+
+    /// Renders the rule in the `RRULE:` form TickTick stores in `repeatFlag`.
+    pub(super) fn to_rrule(&self) -> String {
+        let mut parts = vec![format!("FREQ={}", self.frequency.rrule_name())];
+        if self.interval > 1 {
+            parts.push(format!("INTERVAL={}", self.interval));
+        }
+        if !self.by_day.is_empty() {
+            let days = self
+                .by_day
+                .iter()
+                .map(|day| rrule_weekday(*day))
+                .collect::<Vec<_>>()
+                .join(",");
+            parts.push(format!("BYDAY={}", days));
+        }
+        if let Some(day) = self.by_month_day {
+            parts.push(format!("BYMONTHDAY={}", day));
+        }
+        if let Some(count) = self.count {
+            parts.push(format!("COUNT={}", count));
+        }
+        if let Some(until) = &self.until {
+            parts.push(format!("UNTIL={}", until));
+        }
+        format!("{}{}", RRULE_PREFIX, parts.join(";"))
+    }
+
+    /// A phrase like `every 2 weeks on Monday` that reads back as shorthand.
+    pub(super) fn describe(&self) -> String {
+        let mut phrase = if self.frequency == RepeatFrequency::Weekly
+            && self.interval == 1
+            && self.by_day == WEEKDAYS
+        {
+            "every weekday".to_string()
+        } else if self.frequency == RepeatFrequency::Weekly
+            && self.interval == 1
+            && self.by_day == WEEKEND
+        {
+            "every weekend".to_string()
+        } else {
+            let unit = self.frequency.unit();
+            let mut phrase = if self.interval > 1 {
+                format!("every {} {}s", self.interval, unit)
+            } else {
+                format!("every {}", unit)
+            };
+            if !self.by_day.is_empty() {
+                let days = self
+                    .by_day
+                    .iter()
+                    .map(|day| weekday_name(*day))
+                    .collect::<Vec<_>>();
+                phrase.push_str(&format!(" on {}", join_with_and(&days)));
+            }
+            phrase
+        };
+
+        match self.by_month_day {
+            Some(-1) => phrase.push_str(" on the last day"),
+            Some(day) => phrase.push_str(&format!(" on the {}", ordinal(day))),
+            None => {}
+        }
+        if let Some(count) = self.count {
+            phrase.push_str(&format!(
+                ", {} time{}",
+                count,
+                if count == 1 { "" } else { "s" }
+            ));
+        }
+        if let Some(until) = &self.until {
+            phrase.push_str(&format!(", until {}", format_until(until)));
+        }
+        phrase
+    }
+}
+
+fn rrule_weekday(day: Weekday) -> &'static str {
+    match day {
+        Weekday::Mon => "MO",
+        Weekday::Tue => "TU",
+        Weekday::Wed => "WE",
+        Weekday::Thu => "TH",
+        Weekday::Fri => "FR",
+        Weekday::Sat => "SA",
+        Weekday::Sun => "SU",
+    }
+}
+
+fn parse_rrule_weekday(value: &str) -> Option<Weekday> {
+    match value {
+        "MO" => Some(Weekday::Mon),
+        "TU" => Some(Weekday::Tue),
+        "WE" => Some(Weekday::Wed),
+        "TH" => Some(Weekday::Thu),
+        "FR" => Some(Weekday::Fri),
+        "SA" => Some(Weekday::Sat),
+        "SU" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+fn weekday_name(day: Weekday) -> &'static str {
+    match day {
+        Weekday::Mon => "Monday",
+        Weekday::Tue => "Tuesday",
+        Weekday::Wed => "Wednesday",
+        Weekday::Thu => "Thursday",
+        Weekday::Fri => "Friday",
+        Weekday::Sat => "Saturday",
+        Weekday::Sun => "Sunday",
+    }
+}
+
+fn join_with_and(values: &[&str]) -> String {
+    match values {
+        [] => String::new(),
+        [only] => only.to_string(),
+        [rest @ .., last] => format!("{} and {}", rest.join(", "), last),
+    }
+}
+
+fn ordinal(day: i32) -> String {
+    let suffix = match (day % 10, day % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{}{}", day, suffix)
+}
+
+fn format_until(value: &str) -> String {
+    match (value.get(0..4), value.get(4..6), value.get(6..8)) {
+        (Some(year), Some(month), Some(day)) if value.len() >= 8 => {
+            format!("{}-{}-{}", year, month, day)
+        }
+        _ => value.to_string(),
+    }
+}
+
+fn parse_frequency_unit(token: &str) -> Option<RepeatFrequency> {
+    match token {
+        "day" | "days" => Some(RepeatFrequency::Daily),
+        "week" | "weeks" => Some(RepeatFrequency::Weekly),
+        "month" | "months" => Some(RepeatFrequency::Monthly),
+        "year" | "years" => Some(RepeatFrequency::Yearly),
+        _ => None,
+    }
+}
+
+fn parse_frequency_adverb(token: &str) -> Option<RepeatFrequency> {
+    match token {
+        "daily" => Some(RepeatFrequency::Daily),
+        "weekly" => Some(RepeatFrequency::Weekly),
+        "monthly" => Some(RepeatFrequency::Monthly),
+        "yearly" | "annually" => Some(RepeatFrequency::Yearly),
+        _ => None,
+    }
+}
+
+fn parse_repeat_weekday(token: &str) -> Option<Weekday> {
+    parse_weekday_token(token).or_else(|| parse_weekday_token(token.strip_suffix('s')?))
+}
+
+fn token_at(tokens: &[&str], index: usize) -> Option<String> {
+    tokens.get(index).map(|token| normalize_date_token(token))
+}
+
+fn parse_weekday_group(token: &str) -> Option<Vec<Weekday>> {
+    let days: Option<Vec<Weekday>> = token
+        .split(',')
+        .filter(|part| !part.is_empty())
+        .map(parse_repeat_weekday)
+        .collect();
+    days.filter(|days| !days.is_empty())
+}
+
+/// Parses `monday`, `mon and thu`, `mon, wed`, or `mon,wed,fri` starting at `index`.
+fn parse_weekday_list(tokens: &[&str], index: usize) -> Option<(usize, Vec<Weekday>)> {
+    let mut days: Vec<Weekday> = Vec::new();
+    let mut consumed = 0;
+
+    while let Some(group) = token_at(tokens, index + consumed)
+        .as_deref()
+        .and_then(parse_weekday_group)
+    {
+        for day in group {
+            if !days.contains(&day) {
+                days.push(day);
+            }
+        }
+        consumed += 1;
+
+        let continues_with_comma = tokens[index + consumed - 1].ends_with(',');
+        let continues_with_and = token_at(tokens, index + consumed).as_deref() == Some("and")
+            && token_at(tokens, index + consumed + 1)
+                .as_deref()
+                .and_then(parse_weekday_group)
+                .is_some();
+        if continues_with_and {
+            consumed += 1;
+        } else if !continues_with_comma {
+            break;
+        }
+    }
+
+    if days.is_empty() {
+        return None;
+    }
+    days.sort_by_key(|day| day.num_days_from_monday());
+    Some((consumed, days))
+}
+
+/// Parses `on the 15th`, `on the last day`, or `on 3` after a monthly phrase.
+fn parse_month_day_suffix(tokens: &[&str], index: usize) -> Option<(usize, i32)> {
+    if token_at(tokens, index)? != "on" {
+        return None;
+    }
+    let mut offset = 1;
+    if token_at(tokens, index + offset)? == "the" {
+        offset += 1;
+    }
+
+    let token = token_at(tokens, index + offset)?;
+    if token == "last" {
+        let consumed = if token_at(tokens, index + offset + 1).as_deref() == Some("day") {
+            offset + 2
+        } else {
+            offset + 1
+        };
+        return Some((consumed, -1));
+    }
+
+    let day = parse_day_token(&token)?;
+    Some((offset + 1, day as i32))
+}
+
+fn parse_repeat_phrase(tokens: &[&str], index: usize) -> Option<(usize, RepeatRule)> {
+    let first = token_at(tokens, index)?;
+    let (consumed, mut rule) = if let Some(frequency) = parse_frequency_adverb(&first) {
+        (1, RepeatRule::new(frequency, 1))
+    } else if first == "every" {
+        parse_every_phrase(tokens, index + 1).map(|(consumed, rule)| (consumed + 1, rule))?
+    } else {
+        return None;
+    };
+
+    if rule.frequency == RepeatFrequency::Monthly {
+        if let Some((extra, day)) = parse_month_day_suffix(tokens, index + consumed) {
+            rule.by_month_day = Some(day);
+            return Some((consumed + extra, rule));
+        }
+    }
+    Some((consumed, rule))
+}
+
+/// Parses whatever follows `every`.
+fn parse_every_phrase(tokens: &[&str], index: usize) -> Option<(usize, RepeatRule)> {
+    let first = token_at(tokens, index)?;
+
+    if let Some(frequency) = parse_frequency_unit(&first) {
+        return Some((1, RepeatRule::new(frequency, 1)));
+    }
+    match first.as_str() {
+        "weekday" | "weekdays" => return Some((1, RepeatRule::on_days(&WEEKDAYS))),
+        "weekend" | "weekends" => return Some((1, RepeatRule::on_days(&WEEKEND))),
+        _ => {}
+    }
+    if let Some((consumed, days)) = parse_weekday_list(tokens, index) {
+        return Some((consumed, RepeatRule::on_days(&days)));
+    }
+
+    let interval = match first.as_str() {
+        "other" => 2,
+        value => value.parse::<u32>().ok().filter(|interval| *interval > 0)?,
+    };
+    let frequency = parse_frequency_unit(&token_at(tokens, index + 1)?)?;
+    Some((2, RepeatRule::new(frequency, interval)))
+}
+
+/// Strips the first recurrence phrase (`every day`, `every monday`, `every 2 weeks`,
+/// `monthly on the 15th`, ...) from `raw` and returns it as an RRULE.
+pub(super) fn extract_repeat_from_input(raw: &str) -> (String, Option<String>) {
+    let tokens: Vec<&str> = raw.split_whitespace().collect();
+
+    for index in 0..tokens.len() {
+        if tokens[index].starts_with('#') || tokens[index].starts_with('~') {
+            continue;
+        }
+        if let Some((consumed, rule)) = parse_repeat_phrase(&tokens, index) {
+            let title = tokens
+                .iter()
+                .enumerate()
+                .filter_map(|(i, value)| {
+                    if i >= index && i < index + consumed {
+                        None
+                    } else {
+                        Some(*value)
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(" ");
+            return (title, Some(rule.to_rrule()));
+        }
+    }
+
+    (raw.trim().to_string(), None)
+}
+
+fn parse_positive(key: &str, value: &str) -> std::result::Result<u32, String> {
+    value
+        .parse::<u32>()
+        .ok()
+        .filter(|parsed| *parsed > 0)
+        .ok_or_else(|| format!("{} must be a positive integer, got '{}'", key, value))
+}
+
+fn parse_month_day_value(value: &str) -> std::result::Result<i32, String> {
+    value
+        .parse::<i32>()
+        .ok()
+        .filter(|day| (1..=31).contains(day) || (-31..=-1).contains(day))
+        .ok_or_else(|| format!("BYMONTHDAY must be 1-31 or -31 to -1, got '{}'", value))
+}
+
+/// Splits a `BYDAY` entry like `-1FR` into its ordinal and weekday.
+fn parse_byday_entry(value: &str) -> Option<(Option<i32>, Weekday)> {
+    let split = value.len().checked_sub(2)?;
+    let day = parse_rrule_weekday(value.get(split..)?)?;
+    let ordinal = value.get(..split)?;
+    if ordinal.is_empty() {
+        return Some((None, day));
+    }
+    let ordinal = ordinal.parse::<i32>().ok()?;
+    ((1..=5).contains(&ordinal) || (-5..=-1).contains(&ordinal)).then_some((Some(ordinal), day))
+}
+
+fn is_rrule_until(value: &str) -> bool {
+    let digits = value.strip_suffix('Z').unwrap_or(value);
+    match digits.split_once('T') {
+        Some((date, time)) => {
+            date.len() == 8
+                && time.len() == 6
+                && date
+                    .chars()
+                    .chain(time.chars())
+                    .all(|ch| ch.is_ascii_digit())
+        }
+        None => digits.len() == 8 && digits.chars().all(|ch| ch.is_ascii_digit()),
+    }
+}
+
+/// Checks an RRULE against the subset TickTick accepts and returns its canonical form
+/// (upper-cased, with the `RRULE:` prefix).
+pub(super) fn validate_rrule(value: &str) -> std::result::Result<String, String> {
+    let normalized = value.trim().to_ascii_uppercase();
+    let body = normalized
+        .strip_prefix(RRULE_PREFIX)
+        .ok_or_else(|| format!("Repeat rule must start with RRULE:, got '{}'", value))?;
+
+    let mut frequency = None;
+    let mut has_count = false;
+    let mut has_until = false;
+    for part in body.split(';').filter(|part| !part.is_empty()) {
+        let (key, part_value) = part
+            .split_once('=')
+            .ok_or_else(|| format!("Malformed RRULE part '{}'", part))?;
+        match key {
+            "FREQ" => {
+                frequency = Some(RepeatFrequency::from_rrule_name(part_value).ok_or_else(|| {
+                    format!(
+                        "Unsupported FREQ '{}'. TickTick accepts DAILY, WEEKLY, MONTHLY, or YEARLY",
+                        part_value
+                    )
+                })?)
+            }
+            "INTERVAL" => {
+                parse_positive(key, part_value)?;
+            }
+            "COUNT" => {
+                parse_positive(key, part_value)?;
+                has_count = true;
+            }
+            "UNTIL" => {
+                if !is_rrule_until(part_value) {
+                    return Err(format!(
+                        "UNTIL must look like 20261231 or 20261231T000000Z, got '{}'",
+                        part_value
+                    ));
+                }
+                has_until = true;
+            }
+            "BYDAY" => {
+                for day in part_value.split(',') {
+                    parse_byday_entry(day)
+                        .ok_or_else(|| format!("Invalid BYDAY value '{}'", day))?;
+                }
+            }
+            "BYMONTHDAY" => {
+                for day in part_value.split(',') {
+                    parse_month_day_value(day)?;
+                }
+            }
+            "BYMONTH" => {
+                for month in part_value.split(',') {
+                    month
+                        .parse::<u32>()
+                        .ok()
+                        .filter(|month| (1..=12).contains(month))
+                        .ok_or_else(|| format!("BYMONTH must be 1-12, got '{}'", month))?;
+                }
+            }
+            "BYSETPOS" | "WKST" => {}
+            _ => return Err(format!("Unsupported RRULE part '{}'", key)),
+        }
+    }
+
+    if frequency.is_none() {
+        return Err(format!("Repeat rule '{}' is missing FREQ", value));
+    }
+    if has_count && has_until {
+        return Err("Repeat rule cannot set both COUNT and UNTIL".to_string());
+    }
+    Ok(normalized)
+}
+
+/// Models an RRULE as a [`RepeatRule`] when every part can be read back as a phrase.
+fn rule_from_rrule(value: &str) -> Option<RepeatRule> {
+    let canonical = validate_rrule(value).ok()?;
+    let mut rule = RepeatRule::new(RepeatFrequency::Daily, 1);
+    let mut has_frequency = false;
+
+    for part in canonical.strip_prefix(RRULE_PREFIX)?.split(';') {
+        let Some((key, value)) = part.split_once('=') else {
+            continue;
+        };
+        match key {
+            "FREQ" => {
+                rule.frequency = RepeatFrequency::from_rrule_name(value)?;
+                has_frequency = true;
+            }
+            "INTERVAL" => rule.interval = value.parse().ok()?,
+            "COUNT" => rule.count = value.parse().ok(),
+            "UNTIL" => rule.until = Some(value.to_string()),
+            "BYDAY" => {
+                for entry in value.split(',') {
+                    match parse_byday_entry(entry)? {
+                        (None, day) => rule.by_day.push(day),
+                        (Some(_), _) => return None,
+                    }
+                }
+            }
+            "BYMONTHDAY" if !value.contains(',') => rule.by_month_day = value.parse().ok(),
+            "WKST" => {}
+            _ => return None,
+        }
+    }
+
+    has_frequency.then_some(rule)
+}
+
+/// Human phrase for a stored repeat flag, falling back to the raw value for rules the
+/// phrase grammar cannot express.
+pub(super) fn describe_repeat_flag(value: &str) -> String {
+    rule_from_rrule(value)
+        .map(|rule| rule.describe())
+        .unwrap_or_else(|| value.to_string())
+}
+
+/// Value parser for `--repeat`: a phrase (`daily`, `every 2 weeks`, `weekday`, ...) or a
+/// raw RRULE.
+pub(super) fn parse_repeat_value(value: &str) -> std::result::Result<String, String> {
+    let trimmed = value.trim();
+    if trimmed.to_ascii_uppercase().starts_with(RRULE_PREFIX) {
+        return validate_rrule(trimmed);
+    }
+
+    for candidate in [trimmed.to_string(), format!("every {}", trimmed)] {
+        let tokens: Vec<&str> = candidate.split_whitespace().collect();
+        if let Some((consumed, rule)) = parse_repeat_phrase(&tokens, 0) {
+            if consumed == tokens.len() {
+                return Ok(rule.to_rrule());
+            }
+        }
+    }
+
+    Err(format!(
+        "Unrecognized repeat '{}'. Try daily, weekly, every monday, every 2 weeks, every weekday, monthly on the 15th, or an RRULE",
+        value
+    ))
+}
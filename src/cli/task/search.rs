@@ -0,0 +1,167 @@
+use crate::models::Task;
+use anyhow::{anyhow, Result};
+use regex::{Regex, RegexBuilder};
+
+#[derive(Debug)]
+pub(super) enum SearchMatcher {
+    Substring(String),
+    Pattern(Regex),
+}
+
+impl SearchMatcher {
+    fn new(text: &str, regex: bool) -> Result<Self> {
+        if regex {
+            let pattern = RegexBuilder::new(text)
+                .case_insensitive(true)
+                .build()
+                .map_err(|err| anyhow!("Invalid regex '{}': {}", text, err))?;
+            return Ok(Self::Pattern(pattern));
+        }
+
+        Ok(Self::Substring(text.to_lowercase()))
+    }
+
+    fn is_match(&self, haystack: &str, lowered_haystack: &str) -> bool {
+        match self {
+            Self::Substring(needle) => lowered_haystack.contains(needle.as_str()),
+            Self::Pattern(pattern) => pattern.is_match(haystack),
+        }
+    }
+}
+
+/// A parsed `tt search` query: every clause must match (AND), a clause matches when any
+/// of its `|`-separated alternatives match (OR), and no excluded term may match.
+#[derive(Debug, Default)]
+pub(super) struct SearchQuery {
+    pub(super) clauses: Vec<Vec<SearchMatcher>>,
+    pub(super) excluded: Vec<SearchMatcher>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum SearchToken {
+    Or,
+    Term { text: String, negated: bool },
+}
+
+fn tokenize_search_query(raw: &str) -> Result<Vec<SearchToken>> {
+    let mut tokens = Vec::new();
+    let mut chars = raw.chars().peekable();
+
+    while let Some(&ch) = chars.peek() {
+        if ch.is_whitespace() {
+            chars.next();
+            continue;
+        }
+        if ch == '|' {
+            chars.next();
+            tokens.push(SearchToken::Or);
+            continue;
+        }
+
+        let mut negated = false;
+        if ch == '-' {
+            chars.next();
+            match chars.peek() {
+                Some(next) if !next.is_whitespace() && *next != '|' => negated = true,
+                _ => {
+                    tokens.push(SearchToken::Term {
+                        text: "-".to_string(),
+                        negated: false,
+                    });
+                    continue;
+                }
+            }
+        }
+
+        let mut text = String::new();
+        if chars.peek() == Some(&'"') {
+            chars.next();
+            let mut closed = false;
+            for next in chars.by_ref() {
+                if next == '"' {
+                    closed = true;
+                    break;
+                }
+                text.push(next);
+            }
+            if !closed {
+                return Err(anyhow!("Unterminated quote in search query: {}", raw));
+            }
+        } else {
+            while let Some(&next) = chars.peek() {
+                if next.is_whitespace() || next == '|' {
+                    break;
+                }
+                text.push(next);
+                chars.next();
+            }
+        }
+
+        if !text.is_empty() {
+            tokens.push(SearchToken::Term { text, negated });
+        }
+    }
+
+    Ok(tokens)
+}
+
+pub(super) fn parse_search_query(raw: &str, regex: bool) -> Result<SearchQuery> {
+    let mut query = SearchQuery::default();
+    let mut join_with_previous = false;
+
+    for token in tokenize_search_query(raw)? {
+        match token {
+            SearchToken::Or => join_with_previous = !query.clauses.is_empty(),
+            SearchToken::Term { text, negated } => {
+                let matcher = SearchMatcher::new(&text, regex)?;
+                if negated {
+                    query.excluded.push(matcher);
+                } else if join_with_previous {
+                    if let Some(clause) = query.clauses.last_mut() {
+                        clause.push(matcher);
+                    }
+                } else {
+                    query.clauses.push(vec![matcher]);
+                }
+                join_with_previous = false;
+            }
+        }
+    }
+
+    if query.clauses.is_empty() && query.excluded.is_empty() {
+        return Err(anyhow!("Search query cannot be empty"));
+    }
+
+    Ok(query)
+}
+
+pub(super) fn task_search_haystack(task: &Task) -> String {
+    let mut parts = vec![
+        task.title.as_str(),
+        task.content.as_deref().unwrap_or_default(),
+        task.desc.as_deref().unwrap_or_default(),
+    ];
+    parts.extend(
+        task.items
+            .iter()
+            .flatten()
+            .filter_map(|item| item.title.as_deref()),
+    );
+    parts.join("\n")
+}
+
+impl SearchQuery {
+    pub(super) fn matches_task(&self, task: &Task) -> bool {
+        let haystack = task_search_haystack(task);
+        let lowered = haystack.to_lowercase();
+
+        self.clauses.iter().all(|alternatives| {
+            alternatives
+                .iter()
+                .any(|matcher| matcher.is_match(&haystack, &lowered))
+        }) && !self
+            .excluded
+            .iter()
+            .any(|matcher| matcher.is_match(&haystack, &lowered))
+    }
+}
@@ -0,0 +1,308 @@
+use super::dates::parse_ticktick_datetime;
+use super::groups::{due_bucket_label, task_project_label};
+use super::postpone::local_task_date;
+use crate::models::{Task, TaskStatus};
+use crate::output::{MessageFormat, OutputContext};
+use anyhow::Result;
+use chrono::{NaiveDate, TimeZone};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+
+#[derive(Debug, Default, PartialEq, Eq, Serialize)]
+pub(super) struct PriorityCounts {
+    pub(super) none: usize,
+    pub(super) low: usize,
+    pub(super) medium: usize,
+    pub(super) high: usize,
+}
+
+impl PriorityCounts {
+    /// Levels other than 0/1/3/5 count toward the level below them.
+    fn add(&mut self, priority: i32) {
+        match priority {
+            level if level >= 5 => self.high += 1,
+            level if level >= 3 => self.medium += 1,
+            level if level >= 1 => self.low += 1,
+            _ => self.none += 1,
+        }
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct DueCounts {
+    pub(super) overdue: usize,
+    pub(super) today: usize,
+    pub(super) this_week: usize,
+    pub(super) later: usize,
+    pub(super) none: usize,
+}
+
+/// Totals for `tt task count`, over the same tasks `tt task list` would show.
+#[derive(Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct TaskStats {
+    pub(super) total: usize,
+    pub(super) by_list: BTreeMap<String, usize>,
+    pub(super) by_priority: PriorityCounts,
+    pub(super) by_due: DueCounts,
+}
+
+pub(super) fn task_stats(
+    tasks: &[Task],
+    project_names: &HashMap<String, String>,
+    today: NaiveDate,
+) -> TaskStats {
+    let mut stats = TaskStats {
+        total: tasks.len(),
+        ..Default::default()
+    };
+
+    for task in tasks {
+        *stats
+            .by_list
+            .entry(task_project_label(task, project_names))
+            .or_default() += 1;
+
+        stats.by_priority.add(task.priority.unwrap_or(0));
+
+        // Tomorrow is its own section in --group-by due; here it is part of this week.
+        let due = &mut stats.by_due;
+        match due_bucket_label(task, today) {
+            "Overdue" => due.overdue += 1,
+            "Today" => due.today += 1,
+            "Tomorrow" | "This Week" => due.this_week += 1,
+            "Later" => due.later += 1,
+            _ => due.none += 1,
+        }
+    }
+
+    stats
+}
+
+fn push_section(output: &mut String, heading: &str, rows: &[(&str, usize)]) {
+    let width = rows.iter().map(|(label, _)| label.len()).max().unwrap_or(0);
+    output.push_str(&format!("\n{}:\n", heading));
+    if rows.is_empty() {
+        output.push_str("  (none)\n");
+    }
+    for (label, count) in rows {
+        output.push_str(&format!("  {:width$}  {}\n", label, count, width = width));
+    }
+}
+
+pub(super) fn format_task_stats(
+    stats: &TaskStats,
+    format: MessageFormat,
+    context: &OutputContext,
+) -> Result<String> {
+    match format {
+        MessageFormat::Json => Ok(format!("{}\n", context.to_json_string(stats)?)),
+        MessageFormat::Human => {
+            let mut output = format!("Tasks: {}\n", stats.total);
+
+            let lists: Vec<(&str, usize)> = stats
+                .by_list
+                .iter()
+                .map(|(list, count)| (list.as_str(), *count))
+                .collect();
+            push_section(&mut output, "By list", &lists);
+
+            let priority = &stats.by_priority;
+            push_section(
+                &mut output,
+                "By priority",
+                &[
+                    ("High", priority.high),
+                    ("Medium", priority.medium),
+                    ("Low", priority.low),
+                    ("None", priority.none),
+                ],
+            );
+
+            let due = &stats.by_due;
+            push_section(
+                &mut output,
+                "By due date",
+                &[
+                    ("Overdue", due.overdue),
+                    ("Today", due.today),
+                    ("This week", due.this_week),
+                    ("Later", due.later),
+                    ("No date", due.none),
+                ],
+            );
+            Ok(output)
+        }
+    }
+}
+
+/// A task named in `tt project stats`, with the date it was picked for.
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub(crate) struct TaskMention {
+    pub(super) id: Option<String>,
+    pub(super) title: String,
+    pub(super) date: NaiveDate,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ChecklistProgress {
+    pub(super) done: usize,
+    pub(super) total: usize,
+    pub(super) percent: u32,
+}
+
+/// The weekly-review summary of one list for `tt project stats`. `completed` is `None`
+/// when the completed-task endpoint could not be read.
+#[derive(Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ProjectStats {
+    pub(super) project: String,
+    pub(super) open: usize,
+    pub(super) completed: Option<usize>,
+    pub(super) overdue: usize,
+    pub(super) by_priority: PriorityCounts,
+    pub(super) by_tag: BTreeMap<String, usize>,
+    pub(super) oldest_open: Option<TaskMention>,
+    pub(super) next_due: Option<TaskMention>,
+    pub(super) checklist: Option<ChecklistProgress>,
+}
+
+fn mention(task: &Task, date: NaiveDate) -> TaskMention {
+    TaskMention {
+        id: task.id.clone(),
+        title: task.title.clone(),
+        date,
+    }
+}
+
+/// Totals over a list's open tasks (`completed` only counts). Overdue and next-due use
+/// the local due date in `tz`; the oldest task is the one created first.
+pub(crate) fn summarize_project<Tz: TimeZone>(
+    project: &str,
+    open: &[Task],
+    completed: Option<&[Task]>,
+    today: NaiveDate,
+    tz: &Tz,
+) -> ProjectStats {
+    let mut stats = ProjectStats {
+        project: project.to_string(),
+        open: open.len(),
+        completed: completed.map(<[Task]>::len),
+        overdue: 0,
+        by_priority: PriorityCounts::default(),
+        by_tag: BTreeMap::new(),
+        oldest_open: None,
+        next_due: None,
+        checklist: None,
+    };
+    let (mut items_done, mut items_total) = (0, 0);
+
+    for task in open {
+        stats.by_priority.add(task.priority.unwrap_or(0));
+        for tag in task.tags.iter().flatten() {
+            *stats.by_tag.entry(tag.to_lowercase()).or_default() += 1;
+        }
+
+        if let Some(due) = task
+            .due_date
+            .as_deref()
+            .and_then(|due| local_task_date(tz, due))
+        {
+            if due < today {
+                stats.overdue += 1;
+            } else if stats.next_due.as_ref().is_none_or(|next| due < next.date) {
+                stats.next_due = Some(mention(task, due));
+            }
+        }
+
+        let created = task
+            .created_time
+            .as_deref()
+            .and_then(parse_ticktick_datetime)
+            .map(|created| created.with_timezone(tz).date_naive());
+        if let Some(created) = created {
+            if stats
+                .oldest_open
+                .as_ref()
+                .is_none_or(|oldest| created < oldest.date)
+            {
+                stats.oldest_open = Some(mention(task, created));
+            }
+        }
+
+        for item in task.items.iter().flatten() {
+            items_total += 1;
+            if matches!(item.status, Some(TaskStatus::Completed)) {
+                items_done += 1;
+            }
+        }
+    }
+
+    if items_total > 0 {
+        stats.checklist = Some(ChecklistProgress {
+            done: items_done,
+            total: items_total,
+            percent: (items_done * 100 / items_total) as u32,
+        });
+    }
+    stats
+}
+
+fn mention_line(mention: Option<&TaskMention>) -> String {
+    match mention {
+        Some(mention) => format!("{} ({})", mention.title, mention.date.format("%Y-%m-%d")),
+        None => "-".to_string(),
+    }
+}
+
+pub(crate) fn format_project_stats(
+    stats: &ProjectStats,
+    format: MessageFormat,
+    context: &OutputContext,
+) -> Result<String> {
+    match format {
+        MessageFormat::Json => Ok(format!("{}\n", context.to_json_string(stats)?)),
+        MessageFormat::Human => {
+            let completed = stats
+                .completed
+                .map(|count| count.to_string())
+                .unwrap_or_else(|| "unavailable".to_string());
+            let mut output = format!(
+                "{}\n  Open       {}\n  Completed  {}\n  Overdue    {}\n",
+                stats.project, stats.open, completed, stats.overdue
+            );
+            output.push_str(&format!(
+                "  Next due   {}\n  Oldest     {}\n",
+                mention_line(stats.next_due.as_ref()),
+                mention_line(stats.oldest_open.as_ref())
+            ));
+            if let Some(checklist) = &stats.checklist {
+                output.push_str(&format!(
+                    "  Checklist  {}/{} items done ({}%)\n",
+                    checklist.done, checklist.total, checklist.percent
+                ));
+            }
+
+            let priority = &stats.by_priority;
+            push_section(
+                &mut output,
+                "By priority",
+                &[
+                    ("High", priority.high),
+                    ("Medium", priority.medium),
+                    ("Low", priority.low),
+                    ("None", priority.none),
+                ],
+            );
+            let tags: Vec<(&str, usize)> = stats
+                .by_tag
+                .iter()
+                .map(|(tag, count)| (tag.as_str(), *count))
+                .collect();
+            push_section(&mut output, "By tag", &tags);
+            Ok(output)
+        }
+    }
+}
@@ -0,0 +1,169 @@
+use super::batch::{BatchUpdateResult, BatchUpdateStatus};
+use super::filters::merge_tags;
+use super::projects::remember_task;
+use super::{build_task_update_payload, TaskUpdateClearFlags};
+use crate::api::TickTickClient;
+use crate::cache::CacheStore;
+use crate::models::{Task, TaskStatus};
+use crate::output::TagCount;
+use anyhow::{anyhow, Result};
+use atty::Stream;
+use std::collections::HashMap;
+use tokio::task::JoinSet;
+
+/// Tag rewrites are one request per task; keep only a few in flight at once.
+const MAX_CONCURRENT_TAG_UPDATES: usize = 4;
+
+/// Open and total task counts per tag, matched case-insensitively like the `--tags`
+/// filter. A tag is shown with the spelling it first appeared with; sorted by name.
+pub(crate) fn count_tags(tasks: &[Task]) -> Vec<TagCount> {
+    let mut counts: Vec<TagCount> = Vec::new();
+    let mut index_by_key: HashMap<String, usize> = HashMap::new();
+
+    for task in tasks {
+        let open = !matches!(task.status, Some(TaskStatus::Completed));
+        let mut seen_on_task: Vec<String> = Vec::new();
+        for tag in task.tags.as_deref().unwrap_or_default() {
+            let key = tag.to_lowercase();
+            if key.is_empty() || seen_on_task.contains(&key) {
+                continue;
+            }
+            seen_on_task.push(key.clone());
+
+            let index = *index_by_key.entry(key).or_insert_with(|| {
+                counts.push(TagCount {
+                    tag: tag.clone(),
+                    open: 0,
+                    total: 0,
+                });
+                counts.len() - 1
+            });
+            counts[index].total += 1;
+            if open {
+                counts[index].open += 1;
+            }
+        }
+    }
+
+    counts.sort_by(|a, b| {
+        a.tag
+            .to_lowercase()
+            .cmp(&b.tag.to_lowercase())
+            .then_with(|| a.tag.cmp(&b.tag))
+    });
+    counts
+}
+
+/// `tt tag list --contains`: a case-insensitive substring of the tag name.
+pub(super) fn filter_tag_counts(counts: &mut Vec<TagCount>, contains: Option<&str>) {
+    let Some(needle) = contains.map(|value| value.trim_start_matches('#').to_lowercase()) else {
+        return;
+    };
+    counts.retain(|count| count.tag.to_lowercase().contains(&needle));
+}
+
+/// Swaps any of the `from` tags on `task` (ignoring case) for `to` at the first one's
+/// position, or drops them when `to` is `None`. Returns a description of the change, or
+/// `None` when the task carried none of them.
+pub(super) fn replace_task_tags(
+    task: &mut Task,
+    from: &[String],
+    to: Option<&str>,
+) -> Option<String> {
+    let tags = task.tags.as_deref().unwrap_or_default();
+    let matches = |tag: &String| from.iter().any(|old| old.eq_ignore_ascii_case(tag));
+    let position = tags.iter().position(matches)?;
+
+    let removed: Vec<String> = tags
+        .iter()
+        .filter(|tag| matches(tag))
+        .map(|tag| format!("#{}", tag))
+        .collect();
+    let mut kept: Vec<String> = tags.iter().filter(|tag| !matches(tag)).cloned().collect();
+    if let Some(to) = to {
+        let tail = kept.split_off(position);
+        merge_tags(&mut kept, vec![to.to_string()]);
+        merge_tags(&mut kept, tail);
+    }
+    task.tags = Some(kept);
+
+    Some(match to {
+        Some(to) => format!("{} -> #{}", removed.join(", "), to),
+        None => format!("removed {}", removed.join(", ")),
+    })
+}
+
+fn show_update_progress(done: usize, total: usize) {
+    if !atty::is(Stream::Stderr) {
+        return;
+    }
+    if done < total {
+        eprint!("\rUpdating tasks {}/{}", done, total);
+    } else {
+        eprint!("\r\x1b[K");
+    }
+}
+
+/// Saves retagged tasks a few at a time, keeping each task's outcome (in input order)
+/// instead of stopping at the first failure.
+pub(super) async fn save_retagged_tasks(
+    client: &TickTickClient,
+    cache: Option<&CacheStore>,
+    planned: Vec<(Task, String)>,
+) -> Result<Vec<BatchUpdateResult>> {
+    let total = planned.len();
+    let mut results = Vec::with_capacity(total);
+    let mut pending = planned.into_iter().enumerate();
+
+    loop {
+        let mut updates = JoinSet::new();
+        for (index, (task, change)) in pending.by_ref().take(MAX_CONCURRENT_TAG_UPDATES) {
+            let client = client.clone();
+            // A task left without tags must send `tags: []`; TickTick keeps the old
+            // tags when the field is missing or null.
+            let clear_flags = TaskUpdateClearFlags {
+                tags: task.tags.as_ref().is_some_and(Vec::is_empty),
+                ..Default::default()
+            };
+            let payload = build_task_update_payload(&task, clear_flags);
+            updates.spawn(async move {
+                let id = task.id.clone().unwrap_or_default();
+                let updated = match payload {
+                    Ok(payload) => client.update_task(&id, &payload).await,
+                    Err(err) => Err(err),
+                };
+                (index, task, change, updated)
+            });
+        }
+        if updates.is_empty() {
+            break;
+        }
+
+        while let Some(joined) = updates.join_next().await {
+            let (index, task, change, updated) =
+                joined.map_err(|err| anyhow!("Tag update worker failed: {}", err))?;
+            let id = task.id.clone().unwrap_or_default();
+            let (status, error) = match updated {
+                Ok(updated) => {
+                    remember_task(cache, &updated, task.project_id.as_deref());
+                    (BatchUpdateStatus::Updated, None)
+                }
+                Err(err) => (BatchUpdateStatus::Failed, Some(err.to_string())),
+            };
+            results.push((
+                index,
+                BatchUpdateResult {
+                    id,
+                    title: Some(task.title),
+                    status,
+                    changes: vec![change],
+                    error,
+                },
+            ));
+        }
+        show_update_progress(results.len(), total);
+    }
+
+    results.sort_by_key(|(index, _)| *index);
+    Ok(results.into_iter().map(|(_, result)| result).collect())
+}
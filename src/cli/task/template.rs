@@ -0,0 +1,483 @@
+use super::dates::{
+    extract_due_datetime_from_input, format_ticktick_due_date, format_ticktick_local_datetime,
+};
+use super::filters::{merge_tags, parse_priority_value};
+use super::items::new_checklist_items;
+use super::offline::{snapshot_task, TaskSource};
+use super::projects::resolve_task_project_id;
+use super::{resolve_task_note_fields, TaskAddArgs};
+use crate::models::{Task, TaskStatus};
+use crate::output::{MessageFormat, OutputContext, Verbosity};
+use anyhow::{anyhow, Context, Result};
+use chrono::NaiveDate;
+use clap::{Args, Subcommand};
+use serde_json::{json, Map, Value};
+use std::fs;
+use std::path::PathBuf;
+
+/// Template keys that are not task fields: relative date expressions and a list name,
+/// all resolved when the template is used.
+const TEMPLATE_DUE_KEY: &str = "due";
+const TEMPLATE_START_KEY: &str = "start";
+const TEMPLATE_LIST_KEY: &str = "list";
+
+/// Task fields that describe one particular task rather than the kind of task a
+/// template stands for.
+const INSTANCE_ONLY_KEYS: &[&str] = &[
+    "id",
+    "status",
+    "completedTime",
+    "dueDate",
+    "startDate",
+    "sortOrder",
+    "createdTime",
+    "modifiedTime",
+    "etag",
+];
+
+#[derive(Subcommand)]
+pub enum TemplateCommands {
+    Save(TemplateSaveArgs),
+    #[command(alias = "ls")]
+    List(TemplateListArgs),
+    #[command(aliases = ["rm", "del"])]
+    Delete(TemplateDeleteArgs),
+}
+
+#[derive(Args)]
+pub struct TemplateSaveArgs {
+    name: String,
+    #[arg(long, value_name = "TASK_ID", help = "Start from an existing task")]
+    from_task: Option<String>,
+    #[arg(long)]
+    title: Option<String>,
+    #[arg(long)]
+    content: Option<String>,
+    #[arg(long, help = "List to create tasks in, by name")]
+    list: Option<String>,
+    #[arg(long, value_parser = parse_priority_value)]
+    priority: Option<i32>,
+    #[arg(long)]
+    tags: Vec<String>,
+    #[arg(long = "item", help = "Checklist item (repeatable)")]
+    items: Vec<String>,
+    #[arg(
+        long,
+        value_parser = validate_template_date,
+        help = "Due date worked out each time the template is used: 'next friday', 'in 3 days at 5pm'"
+    )]
+    due: Option<String>,
+    #[arg(long, value_parser = validate_template_date)]
+    start: Option<String>,
+    #[arg(long, help = "Replace an existing template with the same name")]
+    force: bool,
+    #[arg(long, default_value = "human")]
+    output: MessageFormat,
+}
+
+#[derive(Args)]
+pub struct TemplateListArgs {
+    #[arg(long, default_value = "human")]
+    output: MessageFormat,
+}
+
+#[derive(Args)]
+pub struct TemplateDeleteArgs {
+    name: String,
+    #[arg(long, default_value = "human")]
+    output: MessageFormat,
+}
+
+fn templates_dir() -> Result<PathBuf> {
+    Ok(crate::config::config_dir()?.join("templates"))
+}
+
+fn validate_template_name(name: &str) -> Result<()> {
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_')
+    {
+        return Err(anyhow!(
+            "Invalid template name '{}'. Use letters, digits, '-' and '_'.",
+            name
+        ));
+    }
+    Ok(())
+}
+
+fn template_path(name: &str) -> Result<PathBuf> {
+    validate_template_name(name)?;
+    Ok(templates_dir()?.join(format!("{}.json", name)))
+}
+
+fn template_names() -> Result<Vec<String>> {
+    let dir = templates_dir()?;
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut names: Vec<String> = fs::read_dir(&dir)
+        .with_context(|| format!("Failed to read {}", dir.display()))?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let path = entry.path();
+            (path.extension()? == "json")
+                .then(|| path.file_stem()?.to_str().map(str::to_string))
+                .flatten()
+        })
+        .collect();
+    names.sort();
+    Ok(names)
+}
+
+pub(super) fn load_template(name: &str) -> Result<Value> {
+    let path = template_path(name)?;
+    if !path.exists() {
+        let names = template_names()?;
+        return Err(anyhow!(
+            "Template '{}' not found. {}",
+            name,
+            if names.is_empty() {
+                "Save one with `tt template save`.".to_string()
+            } else {
+                format!("Saved templates: {}", names.join(", "))
+            }
+        ));
+    }
+
+    let contents =
+        fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let template: Value = serde_json::from_str(&contents)
+        .with_context(|| format!("Failed to parse template {}", path.display()))?;
+    if !template.is_object() {
+        return Err(anyhow!("Template {} is not a JSON object", path.display()));
+    }
+    Ok(template)
+}
+
+/// Resolves a relative date expression such as `next friday` or `tomorrow at 9` against
+/// `today`, returning the TickTick timestamp and whether it is all-day.
+pub(super) fn resolve_template_date(expression: &str, today: NaiveDate) -> Result<(String, bool)> {
+    let (rest, date, time) = extract_due_datetime_from_input(expression, today);
+    let date = date
+        .filter(|_| rest.trim().is_empty())
+        .ok_or_else(|| anyhow!("'{}' is not a date expression", expression.trim()))?;
+    let formatted = match time {
+        Some(time) => format_ticktick_local_datetime(date.and_time(time)),
+        None => format_ticktick_due_date(date),
+    }
+    .ok_or_else(|| anyhow!("Failed to format date '{}'", date))?;
+    Ok((formatted, time.is_none()))
+}
+
+fn validate_template_date(value: &str) -> std::result::Result<String, String> {
+    let today = chrono::Local::now().date_naive();
+    resolve_template_date(value, today)
+        .map(|_| value.trim().to_string())
+        .map_err(|err| err.to_string())
+}
+
+/// Turns a task into a template: drops its identity, completion state, and absolute
+/// dates, and resets checklist items to open.
+pub(super) fn template_from_task(task: &Task) -> Result<Map<String, Value>> {
+    let Value::Object(mut template) = serde_json::to_value(task)? else {
+        return Err(anyhow!("Task did not serialize to an object"));
+    };
+    for key in INSTANCE_ONLY_KEYS {
+        template.remove(*key);
+    }
+    if let Some(Value::Array(items)) = template.get_mut("items") {
+        for item in items.iter_mut().filter_map(Value::as_object_mut) {
+            item.remove("id");
+            item.remove("completedTime");
+            item.insert(
+                "status".to_string(),
+                serde_json::to_value(TaskStatus::Normal)?,
+            );
+        }
+    }
+    Ok(template)
+}
+
+fn apply_template_save_args(
+    template: &mut Map<String, Value>,
+    args: &TemplateSaveArgs,
+) -> Result<()> {
+    if let Some(title) = args.title.as_ref() {
+        template.insert("title".to_string(), json!(title));
+    }
+    if args.content.is_some() {
+        let (content, desc) = resolve_task_note_fields(args.content.clone(), None);
+        template.insert("content".to_string(), json!(content));
+        template.insert("desc".to_string(), json!(desc));
+    }
+    if let Some(list) = args.list.as_ref() {
+        template.remove("projectId");
+        template.insert(TEMPLATE_LIST_KEY.to_string(), json!(list));
+    }
+    if let Some(priority) = args.priority {
+        template.insert("priority".to_string(), json!(priority));
+    }
+    if !args.tags.is_empty() {
+        let mut tags = string_array(template.get("tags"));
+        merge_tags(&mut tags, args.tags.clone());
+        template.insert("tags".to_string(), json!(tags));
+    }
+    if !args.items.is_empty() {
+        append_items(template, &args.items)?;
+    }
+    if let Some(due) = args.due.as_ref() {
+        template.insert(TEMPLATE_DUE_KEY.to_string(), json!(due));
+    }
+    if let Some(start) = args.start.as_ref() {
+        template.insert(TEMPLATE_START_KEY.to_string(), json!(start));
+    }
+
+    let has_title = template
+        .get("title")
+        .and_then(Value::as_str)
+        .is_some_and(|title| !title.trim().is_empty());
+    if !has_title {
+        return Err(anyhow!(
+            "A template needs a title: pass --title or --from-task"
+        ));
+    }
+    Ok(())
+}
+
+fn string_array(value: Option<&Value>) -> Vec<String> {
+    value
+        .and_then(Value::as_array)
+        .map(|values| {
+            values
+                .iter()
+                .filter_map(|value| value.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn append_items(task: &mut Map<String, Value>, titles: &[String]) -> Result<()> {
+    let Some(new_items) = new_checklist_items(titles)? else {
+        return Ok(());
+    };
+    let items = task
+        .entry("items".to_string())
+        .or_insert_with(|| Value::Array(Vec::new()));
+    if let Value::Array(items) = items {
+        for item in new_items {
+            items.push(serde_json::to_value(item)?);
+        }
+    }
+    task.insert("kind".to_string(), json!("CHECKLIST"));
+    Ok(())
+}
+
+/// Builds the task body for `tt task add --template`. Flags and shorthand in `args` win
+/// over the template; tags and checklist items are added to the template's. Unknown
+/// template fields pass through untouched. Returns the body and the template's list name.
+pub(super) fn task_value_from_template(
+    template: &Value,
+    args: &TaskAddArgs,
+    title: &str,
+    today: NaiveDate,
+) -> Result<(Map<String, Value>, Option<String>)> {
+    let mut task = template.as_object().cloned().unwrap_or_default();
+    let list = task
+        .remove(TEMPLATE_LIST_KEY)
+        .and_then(|value| value.as_str().map(str::to_string));
+    let due = task.remove(TEMPLATE_DUE_KEY);
+    let start = task.remove(TEMPLATE_START_KEY);
+
+    if !title.is_empty() {
+        task.insert("title".to_string(), json!(title));
+    }
+    let has_title = task
+        .get("title")
+        .and_then(Value::as_str)
+        .is_some_and(|title| !title.trim().is_empty());
+    if !has_title {
+        return Err(anyhow!("Template has no title; pass one after --template"));
+    }
+
+    if args.content.is_some() || args.desc.is_some() {
+        let (content, desc) = resolve_task_note_fields(args.content.clone(), args.desc.clone());
+        task.insert("content".to_string(), json!(content));
+        task.insert("desc".to_string(), json!(desc));
+    }
+    if let Some(priority) = args.priority {
+        task.insert("priority".to_string(), json!(priority));
+    }
+    if !args.tags.is_empty() {
+        let mut tags = string_array(task.get("tags"));
+        merge_tags(&mut tags, args.tags.clone());
+        task.insert("tags".to_string(), json!(tags));
+    }
+    if !args.reminders.is_empty() {
+        task.insert("reminders".to_string(), json!(args.reminders));
+    }
+    if let Some(repeat_flag) = args.repeat_flag.as_ref() {
+        task.insert("repeatFlag".to_string(), json!(repeat_flag));
+    }
+    if let Some(sort_order) = args.sort_order {
+        task.insert("sortOrder".to_string(), json!(sort_order));
+    }
+    if let Some(time_zone) = args.time_zone.as_ref() {
+        task.insert("timeZone".to_string(), json!(time_zone));
+    }
+    append_items(&mut task, &args.items)?;
+
+    if let Some(due_date) = args.due_date.as_ref() {
+        task.insert("dueDate".to_string(), json!(due_date));
+    } else if let Some(expression) = due.as_ref().and_then(Value::as_str) {
+        let (due_date, all_day) =
+            resolve_template_date(expression, today).context("Template due date")?;
+        // Like dates inferred from a title, a template due date also starts the task.
+        if args.start_date.is_none() && start.is_none() {
+            task.insert("startDate".to_string(), json!(due_date));
+        }
+        task.insert("dueDate".to_string(), json!(due_date));
+        task.insert("isAllDay".to_string(), json!(all_day));
+    }
+    if let Some(start_date) = args.start_date.as_ref() {
+        task.insert("startDate".to_string(), json!(start_date));
+    } else if let Some(expression) = start.as_ref().and_then(Value::as_str) {
+        let (start_date, all_day) =
+            resolve_template_date(expression, today).context("Template start date")?;
+        task.insert("startDate".to_string(), json!(start_date));
+        task.entry("isAllDay".to_string())
+            .or_insert_with(|| json!(all_day));
+    }
+    if let Some(all_day) = args.all_day {
+        task.insert("isAllDay".to_string(), json!(all_day));
+    }
+
+    Ok((task, list))
+}
+
+pub async fn template_save(
+    args: TemplateSaveArgs,
+    verbosity: Verbosity,
+    context: &OutputContext,
+) -> Result<()> {
+    let path = template_path(&args.name)?;
+    if path.exists() && !args.force {
+        return Err(anyhow!(
+            "Template '{}' already exists; pass --force to replace it",
+            args.name
+        ));
+    }
+
+    let mut template = match args.from_task.as_deref() {
+        Some(task_id) => {
+            let task = match TaskSource::open()? {
+                TaskSource::Api { client, cache } => {
+                    let resolved =
+                        resolve_task_project_id(&client, cache.as_ref(), task_id, None, None)
+                            .await?;
+                    client.get_task(&resolved.project_id, task_id).await?
+                }
+                TaskSource::Snapshot(snapshot) => snapshot_task(&snapshot, task_id)?,
+            };
+            template_from_task(&task)?
+        }
+        None => Map::new(),
+    };
+    apply_template_save_args(&mut template, &args)?;
+
+    let dir = templates_dir()?;
+    fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let template = Value::Object(template);
+    fs::write(
+        &path,
+        format!("{}\n", serde_json::to_string_pretty(&template)?),
+    )
+    .with_context(|| format!("Failed to write {}", path.display()))?;
+
+    let rendered = match args.output {
+        MessageFormat::Json => format!(
+            "{}\n",
+            context.to_json_string(&json!({
+                "name": args.name,
+                "path": path,
+                "template": template,
+            }))?
+        ),
+        MessageFormat::Human => {
+            format!("Template saved: {} ({})\n", args.name, path.display())
+        }
+    };
+    verbosity.confirm(&rendered, args.output);
+    Ok(())
+}
+
+fn format_template_list(
+    templates: &[(String, Value)],
+    format: MessageFormat,
+    context: &OutputContext,
+) -> Result<String> {
+    match format {
+        MessageFormat::Json => {
+            let entries: Vec<Value> = templates
+                .iter()
+                .map(|(name, template)| json!({ "name": name, "template": template }))
+                .collect();
+            Ok(format!("{}\n", context.to_json_string(&entries)?))
+        }
+        MessageFormat::Human => {
+            if templates.is_empty() {
+                return Ok("No templates saved.\n".to_string());
+            }
+            Ok(templates
+                .iter()
+                .map(|(name, template)| {
+                    let title = template
+                        .get("title")
+                        .and_then(Value::as_str)
+                        .unwrap_or_default();
+                    match template.get(TEMPLATE_DUE_KEY).and_then(Value::as_str) {
+                        Some(due) => format!("{}: {} (due {})\n", name, title, due),
+                        None => format!("{}: {}\n", name, title),
+                    }
+                })
+                .collect())
+        }
+    }
+}
+
+pub async fn template_list(args: TemplateListArgs, context: &OutputContext) -> Result<()> {
+    let templates = template_names()?
+        .into_iter()
+        .map(|name| load_template(&name).map(|template| (name, template)))
+        .collect::<Result<Vec<_>>>()?;
+    print!(
+        "{}",
+        format_template_list(&templates, args.output, context)?
+    );
+    Ok(())
+}
+
+pub async fn template_delete(
+    args: TemplateDeleteArgs,
+    verbosity: Verbosity,
+    context: &OutputContext,
+) -> Result<()> {
+    let path = template_path(&args.name)?;
+    if !path.exists() {
+        return Err(anyhow!("Template '{}' not found", args.name));
+    }
+    fs::remove_file(&path).with_context(|| format!("Failed to remove {}", path.display()))?;
+
+    let rendered = match args.output {
+        MessageFormat::Json => format!(
+            "{}\n",
+            context.to_json_string(&json!({ "status": "deleted", "name": args.name }))?
+        ),
+        MessageFormat::Human => {
+            format!("Template deleted: {}\n", args.name)
+        }
+    };
+    verbosity.confirm(&rendered, args.output);
+    Ok(())
+}
@@ -1,15 +1,72 @@
-use super::dates::{date_window_for, parse_task_date, task_due_date};
+use super::agenda::{render_agenda, today_groups, upcoming_groups};
+use super::batch::{
+    apply_batch_update, batch_update_failed_ids, format_batch_update_report, parse_task_ids,
+    BatchUpdatePlan, BatchUpdateResult, BatchUpdateStatus,
+};
+use super::dates::{
+    add_months, compare_newest_first, date_window_for, extract_due_date_from_input,
+    format_ticktick_zoned_datetime, local_datetime_to_utc, parse_due_filter_phrase,
+    parse_due_format, parse_modified_after_value, parse_task_date, parse_task_datetime, sort_tasks,
+    task_completed_at, task_due_date, task_matches_due_range, task_modified_since,
+    tasks_completed_since, TaskSort,
+};
+use super::editor::{
+    apply_document, document_from_task, document_with_error, parse_document, render_document,
+    DocumentItem, DOCUMENT_HEADER, ERROR_PREFIX,
+};
 use super::filters::{
-    normalize_list_name, parse_priority_shorthand, parse_task_status_value, parse_when_token,
+    apply_tag_changes, compile_task_list_regex, parse_list_regex, parse_priority_change,
+    parse_priority_shorthand, parse_tag_change, parse_task_status_value, parse_when_token,
+    priority_name, priority_title, step_priority, task_meets_priority, task_search_haystack,
+    PriorityChange, TagChange,
+};
+use super::format::{parse_task_format, render_formatted_tasks};
+use super::groups::due_bucket_label;
+use super::import::{
+    format_import_report, parse_task_import_file, task_from_import, ImportResult, ImportStatus,
+};
+use super::items::{
+    complete_checklist_item, find_checklist_item, format_checklist_lines, new_checklist_items,
+    push_checklist_item,
+};
+use super::offline::{
+    snapshot_age_notice, snapshot_list_project_id, snapshot_task, snapshot_tasks_in_scope,
+};
+use super::picker::{filter_candidates, fuzzy_score, picker_candidates, PickerCandidate};
+use super::postpone::{
+    defer_target, defer_task, describe_task_due, parse_defer_spec, parse_postpone_target,
+    postpone_task, shift_ticktick_datetime, DeferSpec, PostponeTarget,
 };
 use super::projects::{
-    extract_inbox_tasks_from_value, normalize_project_id, task_project_id_or_fallback,
+    count_project_tasks, extract_inbox_tasks_from_value, forget_default_project_id,
+    infer_default_project_id, normalize_project_id, pick_default_project_id,
+    resolve_create_project_id, task_project_id_or_fallback, ListScope,
+};
+use super::reminders::{
+    describe_reminder, extract_reminder_from_input, parse_reminder_at, parse_reminder_value,
+    reminder_at_trigger,
 };
+use super::repeat::{
+    describe_repeat_flag, extract_repeat_from_input, parse_repeat_value, validate_rrule,
+};
+use super::search::parse_search_query;
+use super::stats::{
+    format_project_stats, format_task_stats, summarize_project, task_stats, DueCounts,
+    PriorityCounts,
+};
+use super::tags::{count_tags, filter_tag_counts, replace_task_tags, save_retagged_tasks};
+use super::template::{resolve_template_date, task_value_from_template, template_from_task};
+use super::watch::{diff_task_lists, parse_watch_interval, render_watch_footer, WatchDiff};
 use super::*;
-use chrono::{DateTime, NaiveDate};
+use crate::cache::{SyncSnapshot, SNAPSHOT_STALE_AFTER_SECS};
+use crate::cli::lists::normalize_list_name;
+use crate::models::{ChecklistItem, Project};
+use chrono::{DateTime, FixedOffset, LocalResult, NaiveDate, NaiveDateTime, NaiveTime, TimeZone};
 use clap::Parser;
 use iana_time_zone::get_timezone;
 use serde_json::Value;
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
 
 #[derive(Debug, Parser)]
 struct TaskUpdateArgsCli {
@@ -17,6 +74,26 @@ struct TaskUpdateArgsCli {
     args: TaskUpdateArgs,
 }
 
+#[derive(Parser)]
+struct TaskListArgsCli {
+    #[command(flatten)]
+    args: TaskListArgs,
+}
+
+#[derive(Parser)]
+struct TaskAddArgsCli {
+    #[command(flatten)]
+    args: TaskAddArgs,
+}
+
+fn terminal() -> OutputContext {
+    OutputContext {
+        is_tty: true,
+        color: true,
+        ..Default::default()
+    }
+}
+
 fn make_task(
     due_date: Option<&str>,
     start_date: Option<&str>,
@@ -43,12 +120,90 @@ fn parses_priority_shorthand_case_insensitive() {
     assert_eq!(parse_priority_shorthand("!urgent"), None);
 }
 
+#[test]
+fn priority_min_keeps_tasks_at_or_above_the_threshold() {
+    let tasks: Vec<Task> = [None, Some(0), Some(1), Some(3), Some(5)]
+        .into_iter()
+        .map(|priority| make_task(None, None, None, priority))
+        .collect();
+    let kept = |min: i32| -> Vec<i32> {
+        tasks
+            .iter()
+            .filter(|task| task_meets_priority(task, min))
+            .map(|task| task.priority.unwrap_or(0))
+            .collect()
+    };
+
+    assert_eq!(kept(0), vec![0, 0, 1, 3, 5]);
+    assert_eq!(kept(1), vec![1, 3, 5]);
+    assert_eq!(kept(3), vec![3, 5]);
+    assert_eq!(kept(5), vec![5]);
+}
+
+#[tokio::test]
+async fn priority_min_parses_names_and_conflicts_with_exact_priority() {
+    let parsed = TaskListArgsCli::try_parse_from(["tt", "--priority-min", "medium"]).unwrap();
+    assert_eq!(parsed.args.priority_min, Some(3));
+    assert_eq!(
+        TaskListArgsCli::try_parse_from(["tt", "--priority-min", "3"])
+            .unwrap()
+            .args
+            .priority_min,
+        Some(3)
+    );
+    assert!(TaskListArgsCli::try_parse_from(["tt", "--priority-min", "2"]).is_err());
+    assert_eq!(
+        TaskListArgsCli::try_parse_from(["tt", "--priority", "high", "--priority-min", "low"])
+            .err()
+            .unwrap()
+            .kind(),
+        clap::error::ErrorKind::ArgumentConflict
+    );
+
+    let mut args = TaskListArgsCli::try_parse_from(["tt", "!high", "--priority-min", "low"])
+        .unwrap()
+        .args;
+    let err = filter_task_list(&TaskSource::Snapshot(sample_snapshot()), &mut args)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("--priority-min"));
+}
+
 #[test]
 fn parses_priority_values_from_aliases_and_numbers() {
     assert_eq!(parse_priority_value("high"), Ok(5));
     assert_eq!(parse_priority_value("Medium"), Ok(3));
     assert_eq!(parse_priority_value("0"), Ok(0));
-    assert_eq!(parse_priority_value("4"), Ok(4));
+    assert_eq!(parse_priority_value("5"), Ok(5));
+    assert_eq!(parse_priority_value("!low"), Ok(1));
+    assert_eq!(parse_priority_value(" none "), Ok(0));
+}
+
+#[test]
+fn priority_names_round_trip_and_capitalize_for_tables() {
+    for priority in [0, 1, 3, 5] {
+        assert_eq!(parse_priority_value(&priority_name(priority)), Ok(priority));
+    }
+    assert_eq!(priority_title(3), "Medium");
+    assert_eq!(priority_title(0), "None");
+    assert_eq!(priority_title(2), "2");
+    assert_eq!(crate::output::task_priority_label(0), "");
+    assert_eq!(crate::output::task_priority_label(5), "High");
+}
+
+#[test]
+fn priority_flag_matches_shorthand_names() {
+    for name in ["none", "normal", "low", "medium", "high"] {
+        assert_eq!(
+            parse_priority_value(name).ok(),
+            parse_priority_shorthand(&format!("!{}", name))
+        );
+    }
+    let parsed = TaskUpdateArgsCli::try_parse_from(["tt", "task-1", "--priority", "medium"])
+        .unwrap()
+        .args;
+    assert_eq!(parsed.priority, Some(3));
+    assert!(TaskUpdateArgsCli::try_parse_from(["tt", "task-1", "--priority", "urgent"]).is_err());
 }
 
 #[test]
@@ -56,6 +211,40 @@ fn rejects_invalid_priority_values_with_actionable_message() {
     let err = parse_priority_value("urgent").unwrap_err();
     assert!(err.contains("Invalid priority"));
     assert!(err.contains("none, low, medium, high"));
+
+    for off_ladder in ["2", "4", "7", "-1"] {
+        let err = parse_priority_value(off_ladder).unwrap_err();
+        assert!(err.contains("(or 0, 1, 3, 5)"), "{}", off_ladder);
+    }
+}
+
+#[test]
+fn priority_steps_along_the_ladder_and_stops_at_both_ends() {
+    assert_eq!(step_priority(0, PriorityChange::Up), 1);
+    assert_eq!(step_priority(1, PriorityChange::Up), 3);
+    assert_eq!(step_priority(3, PriorityChange::Up), 5);
+    assert_eq!(step_priority(5, PriorityChange::Up), 5);
+
+    assert_eq!(step_priority(5, PriorityChange::Down), 3);
+    assert_eq!(step_priority(3, PriorityChange::Down), 1);
+    assert_eq!(step_priority(1, PriorityChange::Down), 0);
+    assert_eq!(step_priority(0, PriorityChange::Down), 0);
+
+    // Levels TickTick never assigns still step to the neighbouring rung.
+    assert_eq!(step_priority(2, PriorityChange::Up), 3);
+    assert_eq!(step_priority(2, PriorityChange::Down), 1);
+    assert_eq!(step_priority(1, PriorityChange::Set(5)), 5);
+}
+
+#[test]
+fn parses_priority_changes() {
+    assert_eq!(parse_priority_change("up"), Ok(PriorityChange::Up));
+    assert_eq!(parse_priority_change("Down"), Ok(PriorityChange::Down));
+    assert_eq!(parse_priority_change("high"), Ok(PriorityChange::Set(5)));
+    assert_eq!(parse_priority_change("none"), Ok(PriorityChange::Set(0)));
+    assert!(parse_priority_change("2")
+        .unwrap_err()
+        .contains("Use up, down"));
 }
 
 #[test]
@@ -135,7 +324,7 @@ fn task_update_args_parse_extended_fields_and_clear_flags() {
     .unwrap()
     .args;
 
-    assert_eq!(parsed.task_id, "task-123");
+    assert_eq!(parsed.task_id.as_deref(), Some("task-123"));
     assert_eq!(parsed.all_day, Some(false));
     assert_eq!(parsed.status, Some(TaskStatus::Completed));
     assert_eq!(parsed.tags, vec!["work".to_string(), "ops".to_string()]);
@@ -173,6 +362,8 @@ fn build_task_update_payload_includes_explicit_clears() {
     let payload = build_task_update_payload(
         &task,
         TaskUpdateClearFlags {
+            content: true,
+            desc: true,
             start_date: true,
             due_date: true,
             time_zone: true,
@@ -184,6 +375,8 @@ fn build_task_update_payload_includes_explicit_clears() {
     )
     .unwrap();
 
+    assert_eq!(payload["content"], "");
+    assert_eq!(payload["desc"], "");
     assert_eq!(payload["startDate"], Value::Null);
     assert_eq!(payload["dueDate"], Value::Null);
     assert_eq!(payload["timeZone"], Value::Null);
@@ -193,6 +386,82 @@ fn build_task_update_payload_includes_explicit_clears() {
     assert_eq!(payload["sortOrder"], Value::Null);
 }
 
+#[test]
+fn build_task_update_payload_only_nulls_fields_that_were_cleared() {
+    let task = Task {
+        title: "sample".to_string(),
+        content: Some("keep me".to_string()),
+        ..Default::default()
+    };
+
+    let untouched = build_task_update_payload(&task, TaskUpdateClearFlags::default()).unwrap();
+    let untouched = serde_json::to_string(&untouched).unwrap();
+    assert!(!untouched.contains("dueDate"), "{}", untouched);
+    assert!(!untouched.contains("null"), "{}", untouched);
+
+    let cleared = build_task_update_payload(
+        &task,
+        TaskUpdateClearFlags {
+            due_date: true,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    let cleared = serde_json::to_string(&cleared).unwrap();
+    assert!(cleared.contains("\"dueDate\":null"), "{}", cleared);
+    assert!(!cleared.contains("\"startDate\""), "{}", cleared);
+    assert!(cleared.contains("\"content\":\"keep me\""), "{}", cleared);
+}
+
+#[test]
+fn short_clear_flags_parse_and_conflict_with_their_setters() {
+    let parsed = TaskUpdateArgsCli::try_parse_from([
+        "tt",
+        "task-1",
+        "--clear-due",
+        "--clear-start",
+        "--clear-repeat",
+        "--clear-content",
+    ])
+    .unwrap()
+    .args;
+    assert!(parsed.clear_due_date && parsed.clear_start_date && parsed.clear_repeat_flag);
+    assert!(parsed.clear_content && !parsed.clear_desc);
+
+    for conflicting in [
+        ["--content", "x", "--clear-content"],
+        ["--desc", "x", "--clear-content"],
+        ["--desc", "x", "--clear-desc"],
+    ] {
+        let mut argv = vec!["tt", "task-1"];
+        argv.extend(conflicting);
+        assert_eq!(
+            TaskUpdateArgsCli::try_parse_from(argv).unwrap_err().kind(),
+            clap::error::ErrorKind::ArgumentConflict
+        );
+    }
+}
+
+#[test]
+fn restoring_a_previous_task_clears_fields_it_did_not_have() {
+    let previous = Task {
+        id: Some("task-1".to_string()),
+        title: "sample".to_string(),
+        due_date: Some("2026-03-02T00:00:00.000+0000".to_string()),
+        tags: Some(vec!["work".to_string()]),
+        ..Default::default()
+    };
+
+    let payload =
+        build_task_update_payload(&previous, TaskUpdateClearFlags::restoring(&previous)).unwrap();
+
+    assert_eq!(payload["dueDate"], "2026-03-02T00:00:00.000+0000");
+    assert_eq!(payload["tags"], serde_json::json!(["work"]));
+    assert_eq!(payload["startDate"], Value::Null);
+    assert_eq!(payload["reminders"], serde_json::json!([]));
+    assert_eq!(payload["repeatFlag"], Value::Null);
+}
+
 #[test]
 fn applies_system_time_zone_default_when_task_has_dates() {
     let mut task = Task {
@@ -240,15 +509,23 @@ fn format_task_mutation_outputs_match_selected_mode() {
         ..Default::default()
     };
 
-    let created = format_task_create_output(&task, OutputFormat::Human).unwrap();
+    let created =
+        format_task_create_output(&task, MessageFormat::Human, &OutputContext::default()).unwrap();
     assert!(created.contains("Task created: Inbox zero"));
     assert!(created.contains("ID: task-1"));
 
-    let updated = format_task_update_output(&task, OutputFormat::Json).unwrap();
+    let updated =
+        format_task_update_output(&task, MessageFormat::Json, &OutputContext::default()).unwrap();
     assert!(updated.contains("\"title\": \"Inbox zero\""));
 
-    let action =
-        format_task_action_output("task-1", "project-1", "completed", OutputFormat::Json).unwrap();
+    let action = format_task_action_output(
+        "task-1",
+        "project-1",
+        "completed",
+        MessageFormat::Json,
+        &OutputContext::default(),
+    )
+    .unwrap();
     assert!(action.contains("\"status\": \"completed\""));
     assert!(action.contains("\"taskId\": \"task-1\""));
     assert!(action.contains("\"projectId\": \"project-1\""));
@@ -272,15 +549,35 @@ fn format_task_info_output_includes_detail_fields() {
         ..Default::default()
     };
 
-    let human = format_task_info_output(&task, OutputFormat::Human).unwrap();
+    let human =
+        format_task_info_output(&task, OutputFormat::Human, &OutputContext::default()).unwrap();
     assert!(human.contains("Task: Write release notes"));
     assert!(human.contains("ID: task-1"));
     assert!(human.contains("Priority: high"));
     assert!(human.contains("Tags: release, docs"));
     assert!(human.contains("Content:\nExplain the user-facing changes."));
     assert!(human.contains("- [x] Draft changelog"));
+    assert!(!human.contains('\x1b'));
+
+    // The same colors as the table: high priority, and a due date in the past.
+    let color = OutputContext {
+        color: true,
+        ..Default::default()
+    };
+    let colored = format_task_info_output(&task, OutputFormat::Human, &color).unwrap();
+    assert!(colored.contains("Priority: \x1b[1;31mhigh\x1b[0m"));
+    assert!(colored.contains("Due: \x1b[31m2026-03-08T09:00:00Z\x1b[0m"));
+
+    let repeating = Task {
+        repeat_flag: Some("RRULE:FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,TH".to_string()),
+        ..task.clone()
+    };
+    let human = format_task_info_output(&repeating, OutputFormat::Human, &OutputContext::default())
+        .unwrap();
+    assert!(human.contains("Repeat: every 2 weeks on Monday and Thursday\n"));
 
-    let json = format_task_info_output(&task, OutputFormat::Json).unwrap();
+    let json =
+        format_task_info_output(&task, OutputFormat::Json, &OutputContext::default()).unwrap();
     assert!(json.contains("\"title\": \"Write release notes\""));
     assert!(json.contains("\"projectId\": \"project-1\""));
 }
@@ -301,6 +598,172 @@ fn extracts_due_date_next_week_phrase() {
     assert_eq!(date, Some(NaiveDate::from_ymd_opt(2026, 2, 23).unwrap()));
 }
 
+#[test]
+fn extracts_due_date_next_weekday_phrase() {
+    let today = NaiveDate::from_ymd_opt(2026, 2, 18).unwrap();
+    let (title, date) = extract_due_date_from_input("ship draft next friday", today);
+    assert_eq!(title, "ship draft");
+    assert_eq!(date, Some(NaiveDate::from_ymd_opt(2026, 2, 27).unwrap()));
+
+    let (title, date) = extract_due_date_from_input("standup next Wed notes", today);
+    assert_eq!(title, "standup notes");
+    assert_eq!(date, Some(NaiveDate::from_ymd_opt(2026, 2, 25).unwrap()));
+
+    let (title, date) = extract_due_date_from_input("plan next monday", today);
+    assert_eq!(title, "plan");
+    assert_eq!(date, Some(NaiveDate::from_ymd_opt(2026, 3, 2).unwrap()));
+}
+
+fn ymd(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+#[test]
+fn extracts_relative_offsets_in_days_weeks_and_months() {
+    let today = ymd(2026, 2, 20);
+
+    let (title, date) = extract_due_date_from_input("follow up in 3 days", today);
+    assert_eq!(title, "follow up");
+    assert_eq!(date, Some(ymd(2026, 2, 23)));
+
+    let (title, date) = extract_due_date_from_input("review in 2 weeks with team", today);
+    assert_eq!(title, "review with team");
+    assert_eq!(date, Some(ymd(2026, 3, 6)));
+
+    let (title, date) = extract_due_date_from_input("renew in a month", today);
+    assert_eq!(title, "renew");
+    assert_eq!(date, Some(ymd(2026, 3, 20)));
+
+    let (title, date) = extract_due_date_from_input("call back 10 days from now", today);
+    assert_eq!(title, "call back");
+    assert_eq!(date, Some(ymd(2026, 3, 2)));
+}
+
+#[test]
+fn relative_offsets_roll_over_month_and_year_boundaries() {
+    let (_, date) = extract_due_date_from_input("pay in 5 days", ymd(2026, 12, 29));
+    assert_eq!(date, Some(ymd(2027, 1, 3)));
+
+    let (_, date) = extract_due_date_from_input("pay in 2 months", ymd(2026, 11, 15));
+    assert_eq!(date, Some(ymd(2027, 1, 15)));
+
+    let (_, date) = extract_due_date_from_input("audit in 1 year", ymd(2028, 2, 29));
+    assert_eq!(date, Some(ymd(2029, 2, 28)));
+}
+
+#[test]
+fn add_months_clamps_to_the_last_valid_day() {
+    assert_eq!(add_months(ymd(2026, 1, 31), 1), Some(ymd(2026, 2, 28)));
+    assert_eq!(add_months(ymd(2028, 1, 31), 1), Some(ymd(2028, 2, 29)));
+    assert_eq!(add_months(ymd(2026, 3, 31), 1), Some(ymd(2026, 4, 30)));
+    assert_eq!(add_months(ymd(2026, 8, 31), 6), Some(ymd(2027, 2, 28)));
+    assert_eq!(add_months(ymd(2026, 1, 15), 0), Some(ymd(2026, 1, 15)));
+}
+
+#[test]
+fn extracts_end_of_day_month_and_year_shorthands() {
+    let (title, date) = extract_due_date_from_input("send invoice eod", ymd(2026, 2, 20));
+    assert_eq!(title, "send invoice");
+    assert_eq!(date, Some(ymd(2026, 2, 20)));
+
+    let (_, date) = extract_due_date_from_input("close books eom", ymd(2026, 2, 3));
+    assert_eq!(date, Some(ymd(2026, 2, 28)));
+    let (_, date) = extract_due_date_from_input("close books eom", ymd(2028, 2, 3));
+    assert_eq!(date, Some(ymd(2028, 2, 29)));
+    let (_, date) = extract_due_date_from_input("close books EOM", ymd(2026, 12, 31));
+    assert_eq!(date, Some(ymd(2026, 12, 31)));
+
+    let (title, date) = extract_due_date_from_input("file taxes eoy", ymd(2026, 6, 1));
+    assert_eq!(title, "file taxes");
+    assert_eq!(date, Some(ymd(2026, 12, 31)));
+}
+
+#[test]
+fn extracts_next_month_as_its_first_day() {
+    let (title, date) = extract_due_date_from_input("plan budget next month", ymd(2026, 1, 31));
+    assert_eq!(title, "plan budget");
+    assert_eq!(date, Some(ymd(2026, 2, 1)));
+
+    let (_, date) = extract_due_date_from_input("plan budget next month", ymd(2026, 12, 10));
+    assert_eq!(date, Some(ymd(2027, 1, 1)));
+}
+
+#[test]
+fn relative_phrases_need_a_quantity_and_unit() {
+    let today = ymd(2026, 2, 20);
+    let (title, date) = extract_due_date_from_input("read chapter 3 days", today);
+    assert_eq!(title, "read chapter 3 days");
+    assert_eq!(date, None);
+
+    let (title, date) = extract_due_date_from_input("stay in touch", today);
+    assert_eq!(title, "stay in touch");
+    assert_eq!(date, None);
+}
+
+#[test]
+fn due_filter_phrases_accept_dates_and_relative_tokens() {
+    let today = ymd(2026, 1, 31);
+    assert_eq!(
+        parse_due_filter_phrase("2026-03-05", today),
+        Ok(ymd(2026, 3, 5))
+    );
+    assert_eq!(parse_due_filter_phrase("eom", today), Ok(ymd(2026, 1, 31)));
+    assert_eq!(
+        parse_due_filter_phrase("in 1 month", today),
+        Ok(ymd(2026, 2, 28))
+    );
+    assert_eq!(
+        parse_due_filter_phrase("next month", today),
+        Ok(ymd(2026, 2, 1))
+    );
+    assert_eq!(
+        parse_due_filter_phrase("tomorrow", today),
+        Ok(ymd(2026, 2, 1))
+    );
+
+    let err = parse_due_filter_phrase("eom please", today).unwrap_err();
+    assert!(err.contains("Invalid date 'eom please'"));
+    assert!(parse_due_filter_phrase("", today).is_err());
+}
+
+#[test]
+fn due_range_filter_is_exclusive_and_skips_undated_tasks() {
+    let task = make_task(Some("2026-03-10T00:00:00.000+0000"), None, None, None);
+
+    assert!(task_matches_due_range(&task, Some(ymd(2026, 3, 11)), None));
+    assert!(!task_matches_due_range(&task, Some(ymd(2026, 3, 10)), None));
+    assert!(task_matches_due_range(&task, None, Some(ymd(2026, 3, 9))));
+    assert!(!task_matches_due_range(&task, None, Some(ymd(2026, 3, 10))));
+    assert!(task_matches_due_range(
+        &task,
+        Some(ymd(2026, 4, 1)),
+        Some(ymd(2026, 3, 1))
+    ));
+
+    let undated = make_task(None, None, None, None);
+    assert!(!task_matches_due_range(
+        &undated,
+        Some(ymd(2026, 4, 1)),
+        None
+    ));
+}
+
+#[test]
+fn extracts_due_date_yesterday() {
+    let today = NaiveDate::from_ymd_opt(2026, 3, 1).unwrap();
+    let (title, date) = extract_due_date_from_input("log run yesterday", today);
+    assert_eq!(title, "log run");
+    assert_eq!(date, Some(NaiveDate::from_ymd_opt(2026, 2, 28).unwrap()));
+}
+
+#[test]
+fn keeps_next_when_not_followed_by_date_word() {
+    let today = NaiveDate::from_ymd_opt(2026, 2, 18).unwrap();
+    let (title, date) = extract_due_date_from_input("pick next steps", today);
+    assert_eq!(title, "pick next steps");
+    assert_eq!(date, None);
+}
+
 #[test]
 fn extracts_due_date_weekday() {
     let today = NaiveDate::from_ymd_opt(2026, 2, 18).unwrap();
@@ -361,8 +824,100 @@ fn extracts_due_date_text_month_day_year_capitalized() {
 fn formats_inferred_due_date_for_ticktick_api() {
     let date = NaiveDate::from_ymd_opt(2026, 2, 20).unwrap();
     let value = format_ticktick_due_date(date).unwrap();
-    assert!(DateTime::parse_from_str(&value, "%Y-%m-%dT%H:%M:%S%.f%z").is_ok());
-    assert!(value.ends_with("+0000"));
+    let parsed = DateTime::parse_from_str(&value, "%Y-%m-%dT%H:%M:%S%.f%z").unwrap();
+    assert_eq!(parsed.date_naive(), date);
+    assert_eq!(parse_task_date(&value), Some(date));
+}
+
+#[test]
+fn parse_task_datetime_keeps_the_time_in_the_given_zone() {
+    let utc = FixedOffset::east_opt(0).unwrap();
+    let nine_thirty = NaiveDate::from_ymd_opt(2026, 3, 1)
+        .unwrap()
+        .and_hms_opt(9, 30, 0)
+        .unwrap();
+    assert_eq!(
+        parse_task_datetime("2026-03-01T09:30:00.000+0000", &utc),
+        Some(nine_thirty)
+    );
+
+    let kolkata = FixedOffset::east_opt(5 * 3600 + 30 * 60).unwrap();
+    assert_eq!(
+        parse_task_datetime("2026-03-01T04:00:00.000+0000", &kolkata),
+        Some(nine_thirty)
+    );
+    assert_eq!(
+        parse_task_datetime("2026-03-01", &kolkata),
+        NaiveDate::from_ymd_opt(2026, 3, 1).map(|date| date.and_time(NaiveTime::MIN))
+    );
+    assert_eq!(parse_task_datetime("someday", &utc), None);
+}
+
+#[test]
+fn due_format_renders_due_dates_with_the_pattern() {
+    let utc = FixedOffset::east_opt(0).unwrap();
+    let format = parse_due_format("%a %b %d %H:%M").unwrap();
+
+    let timed = make_task(Some("2026-03-02T14:05:00.000+0000"), None, None, None);
+    assert_eq!(format.render(&timed, &utc), "Mon Mar 02 14:05");
+
+    let start_only = make_task(None, Some("2026-03-03T00:00:00.000+0000"), None, None);
+    assert_eq!(format.render(&start_only, &utc), "Tue Mar 03 00:00");
+
+    assert_eq!(format.render(&make_task(None, None, None, None), &utc), "");
+    let odd = make_task(Some("soon"), None, None, None);
+    assert_eq!(format.render(&odd, &utc), "soon");
+}
+
+#[test]
+fn due_format_rejects_invalid_patterns_up_front() {
+    assert!(parse_due_format("%Y-%m-%d").is_ok());
+    let err = parse_due_format("%Q").unwrap_err();
+    assert!(err.contains("Invalid --due-format '%Q'"));
+    assert!(parse_due_format("  ").is_err());
+
+    assert!(TaskListArgsCli::try_parse_from(["tt", "--due-format", "%Q"]).is_err());
+    let parsed = TaskListArgsCli::try_parse_from(["tt", "--due-format", "%b %d"]).unwrap();
+    assert_eq!(parsed.args.due_format, parse_due_format("%b %d").ok());
+}
+
+#[test]
+fn zoned_due_dates_keep_the_local_day_on_both_sides_of_utc() {
+    let midnight = NaiveDate::from_ymd_opt(2026, 2, 20)
+        .unwrap()
+        .and_hms_opt(0, 0, 0)
+        .unwrap();
+
+    let kolkata = FixedOffset::east_opt(5 * 3600 + 30 * 60).unwrap();
+    let ahead = format_ticktick_zoned_datetime(&kolkata, midnight).unwrap();
+    assert_eq!(ahead, "2026-02-20T00:00:00.000+0530");
+    assert_eq!(
+        parse_task_date(&ahead),
+        NaiveDate::from_ymd_opt(2026, 2, 20)
+    );
+
+    let los_angeles = FixedOffset::west_opt(8 * 3600).unwrap();
+    let behind = format_ticktick_zoned_datetime(&los_angeles, midnight).unwrap();
+    assert_eq!(behind, "2026-02-20T00:00:00.000-0800");
+    assert_eq!(
+        parse_task_date(&behind),
+        NaiveDate::from_ymd_opt(2026, 2, 20)
+    );
+    // Same instant as before, only now the offset is kept.
+    assert_eq!(
+        DateTime::parse_from_str(&behind, "%Y-%m-%dT%H:%M:%S%.f%z")
+            .unwrap()
+            .naive_utc(),
+        midnight + chrono::Duration::hours(8)
+    );
+}
+
+#[test]
+fn inferred_due_dates_set_the_system_time_zone() {
+    let mut args = template_add_args(&[]);
+    args.due_date = format_ticktick_due_date(NaiveDate::from_ymd_opt(2026, 2, 20).unwrap());
+    let task = task_from_add_args(args, "Pay rent".to_string(), "p1").unwrap();
+    assert_eq!(task.time_zone, Some(get_timezone().unwrap()));
 }
 
 #[test]
@@ -409,40 +964,411 @@ fn matches_tags_case_insensitively() {
 }
 
 #[test]
-fn normalizes_list_names_without_emoji() {
-    assert_eq!(normalize_list_name("🚀Personal"), "personal");
-    assert_eq!(normalize_list_name("👨🏻‍💻 Projects"), "projects");
-    assert_eq!(normalize_list_name("Personal Team"), "personal team");
+fn any_tag_matching_needs_one_shared_tag() {
+    let task = make_task(None, None, Some(vec!["Work", "ops"]), None);
+    let wanted = ["OPS".to_string(), "missing".to_string()];
+    assert!(!task_has_all_tags(&task, &wanted));
+    assert!(task_has_any_tag(&task, &wanted));
+    assert!(!task_has_any_tag(
+        &task,
+        &["home".to_string(), "errands".to_string()]
+    ));
+
+    let untagged = make_task(None, None, None, None);
+    assert!(!task_has_any_tag(&untagged, &wanted));
 }
 
 #[test]
-fn detects_inbox_list_name_variants() {
-    assert!(is_inbox_list_name("inbox"));
-    assert!(is_inbox_list_name("Inbox"));
-    assert!(is_inbox_list_name("  Inbox  "));
-    assert!(is_inbox_list_name("📥 Inbox"));
-    assert!(!is_inbox_list_name("work"));
+fn regex_search_matches_title_or_content() {
+    let mut title_match = make_task(None, None, None, None);
+    title_match.title = "Deploy v2 to staging".to_string();
+    let mut content_match = make_task(None, None, None, None);
+    content_match.content = Some("Ship build v3".to_string());
+
+    let version = compile_task_list_regex(&[r"v\d".to_string()], false).unwrap();
+    assert!(version.is_match(&task_search_haystack(&title_match)));
+    assert!(version.is_match(&task_search_haystack(&content_match)));
+
+    let leading = compile_task_list_regex(&["^deploy".to_string()], false).unwrap();
+    assert!(leading.is_match(&task_search_haystack(&title_match)));
+    assert!(!leading.is_match(&task_search_haystack(&content_match)));
+
+    let joined = compile_task_list_regex(&["build".to_string(), "v3$".to_string()], false).unwrap();
+    assert!(joined.is_match(&task_search_haystack(&content_match)));
+
+    let exact = compile_task_list_regex(&["^deploy".to_string()], true).unwrap();
+    assert!(!exact.is_match(&task_search_haystack(&title_match)));
+}
+
+#[tokio::test]
+async fn regex_search_rejects_invalid_patterns_before_listing() {
+    let err = compile_task_list_regex(&["(unclosed".to_string()], false).unwrap_err();
+    assert!(err.to_string().starts_with("Invalid regex '(unclosed'"));
+
+    let mut args = TaskListArgsCli::try_parse_from(["tt", "--regex", "task [t"])
+        .unwrap()
+        .args;
+    let err = filter_task_list(&TaskSource::Snapshot(sample_snapshot()), &mut args)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("Invalid regex 'task [t'"));
+
+    let mut args = TaskListArgsCli::try_parse_from(["tt", "--regex", "task t[13]$"])
+        .unwrap()
+        .args;
+    let (tasks, _) = filter_task_list(&TaskSource::Snapshot(sample_snapshot()), &mut args)
+        .await
+        .unwrap();
+    assert_eq!(task_ids(&tasks), ["t1", "t3"]);
+
+    assert!(TaskListArgsCli::try_parse_from(["tt", "--case-sensitive", "deploy"]).is_err());
+}
+
+#[tokio::test]
+async fn ids_only_prints_bare_ids_for_any_terminal_and_output() {
+    let source = TaskSource::Snapshot(sample_snapshot());
+    for (extra, context) in [
+        (None, &terminal()),
+        (Some("--output=json"), &OutputContext::default()),
+    ] {
+        let mut argv = vec!["tt", "--ids-only", "--list", "Work"];
+        argv.extend(extra);
+        let mut args = TaskListArgsCli::try_parse_from(argv).unwrap().args;
+        let (_, rendered) = render_task_list(&source, &mut args, context).await.unwrap();
+        assert_eq!(rendered, "t1\n");
+    }
+
+    let mut args = TaskListArgsCli::try_parse_from(["tt", "--ids-only", "no such task"])
+        .unwrap()
+        .args;
+    let (tasks, rendered) = render_task_list(&source, &mut args, &terminal())
+        .await
+        .unwrap();
+    assert!(tasks.is_empty());
+    assert_eq!(rendered, "");
+
+    for conflict in ["--group-by=project", "--format={id}", "--watch"] {
+        assert!(TaskListArgsCli::try_parse_from(["tt", "--ids-only", conflict]).is_err());
+    }
 }
 
 #[test]
-fn extracts_implicit_inbox_list_from_single_term() {
-    let mut terms = vec!["inbox".to_string()];
+fn tag_counts_are_case_insensitive_and_split_open_from_total() {
+    let mut done = make_task(None, None, Some(vec!["Work"]), None);
+    done.status = Some(TaskStatus::Completed);
+    let tasks = vec![
+        make_task(None, None, Some(vec!["work", "errands"]), None),
+        make_task(None, None, Some(vec!["WORK", "work"]), None),
+        done,
+        make_task(None, None, None, None),
+    ];
+
+    let counts = count_tags(&tasks);
+    let summary: Vec<(&str, usize, usize)> = counts
+        .iter()
+        .map(|count| (count.tag.as_str(), count.open, count.total))
+        .collect();
+    assert_eq!(summary, vec![("errands", 1, 1), ("work", 2, 3)]);
+
+    let mut filtered = counts.clone();
+    filter_tag_counts(&mut filtered, Some("#ERR"));
+    assert_eq!(filtered.len(), 1);
+    assert_eq!(filtered[0].tag, "errands");
+}
+
+#[test]
+fn replace_task_tags_renames_in_place_and_merges_duplicates() {
+    let from = vec!["work".to_string()];
+
+    let mut task = make_task(None, None, Some(vec!["urgent", "Work", "home"]), None);
     assert_eq!(
-        extract_implicit_list_from_terms(&mut terms),
-        Some("inbox".to_string())
+        replace_task_tags(&mut task, &from, Some("job")).as_deref(),
+        Some("#Work -> #job")
+    );
+    assert_eq!(
+        task.tags,
+        Some(vec!["urgent".into(), "job".into(), "home".into()])
     );
-    assert!(terms.is_empty());
 
-    let mut terms = vec!["inbox".to_string(), "urgent".to_string()];
-    assert_eq!(extract_implicit_list_from_terms(&mut terms), None);
-    assert_eq!(terms, vec!["inbox".to_string(), "urgent".to_string()]);
+    let mut task = make_task(None, None, Some(vec!["work", "JOB", "WORK"]), None);
+    assert_eq!(
+        replace_task_tags(&mut task, &from, Some("job")).as_deref(),
+        Some("#work, #WORK -> #job")
+    );
+    assert_eq!(task.tags, Some(vec!["job".into()]));
+
+    let mut task = make_task(None, None, Some(vec!["Work"]), None);
+    assert_eq!(
+        replace_task_tags(&mut task, &from, None).as_deref(),
+        Some("removed #Work")
+    );
+    assert_eq!(task.tags, Some(Vec::new()));
+
+    let mut untouched = make_task(None, None, Some(vec!["home"]), None);
+    assert_eq!(replace_task_tags(&mut untouched, &from, Some("job")), None);
+    assert_eq!(untouched.tags, Some(vec!["home".into()]));
 }
 
-#[test]
-fn extracts_inbox_tasks_from_multiple_payload_shapes() {
-    let direct = serde_json::json!({
-        "tasks": [
-            {"id": "a", "title": "one", "projectId": "p"}
+#[tokio::test]
+async fn retagged_tasks_are_saved_and_failures_kept_in_order() {
+    let (base_url, requests) = mock_api(vec![
+        (
+            "POST /task/t1",
+            serde_json::json!({ "id": "t1", "title": "One", "projectId": "p1", "tags": ["job"] }),
+        ),
+        (
+            "POST /task/t3",
+            serde_json::json!({ "id": "t3", "title": "Three", "projectId": "p1", "tags": [] }),
+        ),
+    ]);
+    let client = mock_client(&base_url);
+    let task = |id: &str, tags: Vec<&str>| Task {
+        id: Some(id.to_string()),
+        title: id.to_uppercase(),
+        project_id: Some("p1".to_string()),
+        tags: Some(tags.into_iter().map(str::to_string).collect()),
+        ..Default::default()
+    };
+    let planned = vec![
+        (task("t1", vec!["job"]), "#work -> #job".to_string()),
+        (task("t2", vec!["job"]), "#work -> #job".to_string()),
+        (task("t3", vec![]), "removed #work".to_string()),
+    ];
+
+    let results = save_retagged_tasks(&client, None, planned).await.unwrap();
+    let statuses: Vec<(&str, BatchUpdateStatus)> = results
+        .iter()
+        .map(|result| (result.id.as_str(), result.status))
+        .collect();
+    assert_eq!(
+        statuses,
+        vec![
+            ("t1", BatchUpdateStatus::Updated),
+            ("t2", BatchUpdateStatus::Failed),
+            ("t3", BatchUpdateStatus::Updated),
+        ]
+    );
+
+    let bodies: HashMap<String, Value> = requests
+        .try_iter()
+        .map(|(route, body)| (route, serde_json::from_str(&body).unwrap()))
+        .collect();
+    assert_eq!(bodies["POST /task/t1"]["tags"], serde_json::json!(["job"]));
+    assert_eq!(bodies["POST /task/t3"]["tags"], serde_json::json!([]));
+}
+
+#[test]
+fn confirmation_prompts_need_a_terminal_and_non_json_output() {
+    assert!(ensure_can_prompt(true, MessageFormat::Human).is_ok());
+
+    let err = ensure_can_prompt(false, MessageFormat::Human).unwrap_err();
+    assert!(err.to_string().contains("pass --yes"), "{}", err);
+    let err = ensure_can_prompt(true, MessageFormat::Json).unwrap_err();
+    assert!(err.to_string().contains("--output json"), "{}", err);
+}
+
+#[tokio::test]
+async fn tag_tasks_adds_the_tag_to_the_list_filter() {
+    let mut snapshot = sample_snapshot();
+    snapshot.tasks[0].tags = Some(vec!["Ops".to_string()]);
+    let source = TaskSource::Snapshot(snapshot);
+
+    let mut args = TaskListArgsCli::try_parse_from(["tt"])
+        .unwrap()
+        .args
+        .with_tag("#ops");
+    let (tasks, _) = filter_task_list(&source, &mut args).await.unwrap();
+    assert_eq!(task_ids(&tasks), ["t1"]);
+}
+
+#[tokio::test]
+async fn task_list_hides_completed_tasks_unless_asked_for() {
+    let (base_url, _requests) = mock_api(vec![(
+        "GET /project/p1/data",
+        serde_json::json!({
+            "project": { "id": "p1", "name": "Work" },
+            "tasks": [
+                { "id": "open", "title": "Open", "projectId": "p1", "status": 0 },
+                { "id": "done", "title": "Done", "projectId": "p1", "status": 2 },
+                { "id": "unset", "title": "No status", "projectId": "p1" }
+            ]
+        }),
+    )]);
+    let source = TaskSource::Api {
+        client: mock_client(&base_url),
+        cache: None,
+    };
+    let list = |flags: &[&str]| {
+        let mut argv = vec!["tt", "--project-id", "p1"];
+        argv.extend_from_slice(flags);
+        TaskListArgsCli::try_parse_from(argv).map(|parsed| parsed.args)
+    };
+
+    let mut args = list(&[]).unwrap();
+    let (tasks, show_status) = filter_task_list(&source, &mut args).await.unwrap();
+    assert_eq!(task_ids(&tasks), ["open", "unset"]);
+    assert!(!show_status);
+
+    let mut args = list(&["--all-status"]).unwrap();
+    let (tasks, show_status) = filter_task_list(&source, &mut args).await.unwrap();
+    assert_eq!(task_ids(&tasks), ["open", "done", "unset"]);
+    assert!(show_status);
+
+    let mut args = list(&["--status", "done"]).unwrap();
+    let (tasks, _) = filter_task_list(&source, &mut args).await.unwrap();
+    assert_eq!(task_ids(&tasks), ["done"]);
+
+    let mut args = list(&["--status", "open"]).unwrap();
+    let (tasks, _) = filter_task_list(&source, &mut args).await.unwrap();
+    assert_eq!(task_ids(&tasks), ["open", "unset"]);
+
+    assert!(list(&["--all-status", "--status", "done"]).is_err());
+}
+
+#[test]
+fn list_scope_keeps_lists_whose_name_matches() {
+    let project = |id: &str, name: &str, closed: bool| Project {
+        id: Some(id.to_string()),
+        name: name.to_string(),
+        closed: Some(closed),
+        ..Default::default()
+    };
+    let projects = vec![
+        project("fe", "Work - Frontend", false),
+        project("be", "Work - Backend", false),
+        project("old", "Work - Legacy", true),
+        project("home", "Home", false),
+    ];
+    let pattern = parse_list_regex("^work - ").unwrap();
+
+    let scope = ListScope::matching(&projects, &pattern, false);
+    assert_eq!(scope.project_ids, ["fe", "be"]);
+    assert!(!scope.inbox);
+    assert_eq!(
+        ListScope::matching(&projects, &pattern, true).project_ids,
+        ["fe", "be", "old"]
+    );
+
+    let scope = ListScope::matching(&projects, &parse_list_regex("inbox|^home$").unwrap(), false);
+    assert_eq!(scope.project_ids, ["home"]);
+    assert!(scope.inbox);
+    let in_list = |project_id: Option<&str>| Task {
+        project_id: project_id.map(str::to_string),
+        ..Default::default()
+    };
+    assert!(scope.contains(&in_list(Some("home"))));
+    assert!(scope.contains(&in_list(Some("inbox123"))));
+    assert!(scope.contains(&in_list(None)));
+    assert!(!scope.contains(&in_list(Some("fe"))));
+
+    let err = parse_list_regex("work (").unwrap_err();
+    assert!(err.starts_with("Invalid --list-regex 'work ('"), "{}", err);
+}
+
+#[tokio::test]
+async fn list_regex_scopes_the_task_list_to_matching_lists() {
+    let source = TaskSource::Snapshot(sample_snapshot());
+    let list = |argv: &[&str]| {
+        let mut argv = argv.to_vec();
+        argv.insert(0, "tt");
+        TaskListArgsCli::try_parse_from(argv).map(|parsed| parsed.args)
+    };
+
+    let mut args = list(&["--list-regex", "^w"]).unwrap();
+    let (tasks, _) = filter_task_list(&source, &mut args).await.unwrap();
+    assert_eq!(task_ids(&tasks), ["t1"]);
+
+    let mut args = list(&["--list-pattern", "inbox|work|archive"]).unwrap();
+    let (tasks, _) = filter_task_list(&source, &mut args).await.unwrap();
+    assert_eq!(task_ids(&tasks), ["t1", "t3"]);
+
+    assert!(list(&["--list-regex", "w", "--list", "Work"]).is_err());
+    assert!(list(&["--list-regex", "(w"]).is_err());
+    let mut args = list(&["--list-regex", "w", "~Work"]).unwrap();
+    let err = filter_task_list(&source, &mut args).await.unwrap_err();
+    assert!(err.to_string().contains("--list-regex"));
+}
+
+#[tokio::test]
+async fn list_regex_fetches_only_the_matching_lists() {
+    let (base_url, requests) = mock_api(vec![
+        (
+            "GET /project",
+            serde_json::json!([
+                { "id": "fe", "name": "Work - Frontend" },
+                { "id": "be", "name": "Work - Backend" },
+                { "id": "home", "name": "Home" }
+            ]),
+        ),
+        (
+            "GET /project/fe/data",
+            serde_json::json!({
+                "project": { "id": "fe", "name": "Work - Frontend" },
+                "tasks": [{ "id": "t1", "title": "Ship", "projectId": "fe" }]
+            }),
+        ),
+        (
+            "GET /project/be/data",
+            serde_json::json!({
+                "project": { "id": "be", "name": "Work - Backend" },
+                "tasks": [{ "id": "t2", "title": "Migrate", "projectId": "be" }]
+            }),
+        ),
+    ]);
+    let source = TaskSource::Api {
+        client: mock_client(&base_url),
+        cache: None,
+    };
+    let mut args = TaskListArgsCli::try_parse_from(["tt", "--list-regex", "^work"])
+        .unwrap()
+        .args;
+
+    let (tasks, _) = filter_task_list(&source, &mut args).await.unwrap();
+    let mut ids = task_ids(&tasks);
+    ids.sort();
+    assert_eq!(ids, ["t1", "t2"]);
+
+    let routes: Vec<String> = requests.try_iter().map(|(route, _)| route).collect();
+    assert!(!routes
+        .iter()
+        .any(|route| route.contains("home") || route.contains("inbox")));
+}
+
+#[test]
+fn normalizes_list_names_without_emoji() {
+    assert_eq!(normalize_list_name("🚀Personal"), "personal");
+    assert_eq!(normalize_list_name("👨🏻‍💻 Projects"), "projects");
+    assert_eq!(normalize_list_name("Personal Team"), "personal team");
+}
+
+#[test]
+fn detects_inbox_list_name_variants() {
+    assert!(is_inbox_list_name("inbox"));
+    assert!(is_inbox_list_name("Inbox"));
+    assert!(is_inbox_list_name("  Inbox  "));
+    assert!(is_inbox_list_name("📥 Inbox"));
+    assert!(!is_inbox_list_name("work"));
+}
+
+#[test]
+fn extracts_implicit_inbox_list_from_single_term() {
+    let mut terms = vec!["inbox".to_string()];
+    assert_eq!(
+        extract_implicit_list_from_terms(&mut terms),
+        Some("inbox".to_string())
+    );
+    assert!(terms.is_empty());
+
+    let mut terms = vec!["inbox".to_string(), "urgent".to_string()];
+    assert_eq!(extract_implicit_list_from_terms(&mut terms), None);
+    assert_eq!(terms, vec!["inbox".to_string(), "urgent".to_string()]);
+}
+
+#[test]
+fn extracts_inbox_tasks_from_multiple_payload_shapes() {
+    let direct = serde_json::json!({
+        "tasks": [
+            {"id": "a", "title": "one", "projectId": "p"}
         ]
     });
     let wrapped = serde_json::json!({
@@ -728,3 +1654,2670 @@ fn resolve_task_note_fields_preserves_distinct_explicit_values() {
     assert_eq!(content.as_deref(), Some("content"));
     assert_eq!(desc.as_deref(), Some("desc"));
 }
+
+fn group_keys(groups: &[crate::output::TaskGroup]) -> Vec<&str> {
+    groups.iter().map(|group| group.key.as_str()).collect()
+}
+
+#[test]
+fn groups_tasks_by_due_bucket_in_fixed_order() {
+    let today = NaiveDate::from_ymd_opt(2026, 2, 18).unwrap();
+    let tasks = vec![
+        make_task(None, None, None, None),
+        make_task(Some("2026-03-10"), None, None, None),
+        make_task(Some("2026-02-20"), None, None, None),
+        make_task(Some("2026-02-19"), None, None, None),
+        make_task(Some("2026-02-18"), None, None, None),
+        make_task(Some("2026-02-17"), None, None, None),
+    ];
+
+    let groups = group_tasks(tasks, TaskGroupBy::Due, &HashMap::new(), today);
+
+    assert_eq!(
+        group_keys(&groups),
+        vec![
+            "Overdue",
+            "Today",
+            "Tomorrow",
+            "This Week",
+            "Later",
+            "No date"
+        ]
+    );
+    assert!(groups.iter().all(|group| group.tasks.len() == 1));
+}
+
+#[test]
+fn due_bucket_label_uses_existing_week_window() {
+    let sunday = NaiveDate::from_ymd_opt(2026, 2, 22).unwrap();
+    let monday = make_task(Some("2026-02-23"), None, None, None);
+    let tuesday = make_task(Some("2026-02-24"), None, None, None);
+
+    assert_eq!(due_bucket_label(&monday, sunday), "Tomorrow");
+    assert_eq!(due_bucket_label(&tuesday, sunday), "Later");
+}
+
+#[test]
+fn groups_tasks_by_project_name_with_inbox_fallback() {
+    let mut work = make_task(None, None, None, None);
+    work.project_id = Some("project-work".to_string());
+    let mut inbox = make_task(None, None, None, None);
+    inbox.project_id = Some("inbox123".to_string());
+    let mut unknown = make_task(None, None, None, None);
+    unknown.project_id = Some("project-gone".to_string());
+    let names = HashMap::from([("project-work".to_string(), "Work".to_string())]);
+
+    let groups = group_tasks(
+        vec![work.clone(), inbox, work, unknown],
+        TaskGroupBy::Project,
+        &names,
+        NaiveDate::from_ymd_opt(2026, 2, 18).unwrap(),
+    );
+
+    assert_eq!(group_keys(&groups), vec!["Work", "Inbox", "project-gone"]);
+    assert_eq!(groups[0].tasks.len(), 2);
+}
+
+#[test]
+fn groups_tasks_by_priority_from_high_to_none() {
+    let tasks = vec![
+        make_task(None, None, None, Some(1)),
+        make_task(None, None, None, None),
+        make_task(None, None, None, Some(5)),
+        make_task(None, None, None, Some(1)),
+    ];
+
+    let groups = group_tasks(
+        tasks,
+        TaskGroupBy::Priority,
+        &HashMap::new(),
+        NaiveDate::from_ymd_opt(2026, 2, 18).unwrap(),
+    );
+
+    assert_eq!(group_keys(&groups), vec!["High", "Low", "None"]);
+    assert_eq!(groups[1].tasks.len(), 2);
+}
+
+#[test]
+fn groups_multi_tag_tasks_under_each_tag() {
+    let tasks = vec![
+        make_task(None, None, Some(vec!["work", "urgent"]), None),
+        make_task(None, None, Some(vec!["Work"]), None),
+        make_task(None, None, None, None),
+    ];
+
+    let groups = group_tasks(
+        tasks,
+        TaskGroupBy::Tag,
+        &HashMap::new(),
+        NaiveDate::from_ymd_opt(2026, 2, 18).unwrap(),
+    );
+
+    assert_eq!(group_keys(&groups), vec!["urgent", "work", "No tags"]);
+    assert_eq!(groups[0].tasks.len(), 1);
+    assert_eq!(groups[1].tasks.len(), 2);
+    assert_eq!(groups[2].tasks.len(), 1);
+}
+
+fn searchable_task(title: &str, content: Option<&str>, items: &[&str]) -> Task {
+    Task {
+        title: title.to_string(),
+        content: content.map(ToString::to_string),
+        items: (!items.is_empty()).then(|| {
+            items
+                .iter()
+                .map(|item| ChecklistItem {
+                    title: Some(item.to_string()),
+                    ..Default::default()
+                })
+                .collect()
+        }),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn search_matches_title_content_and_checklist_items() {
+    let query = parse_search_query("Passport", false).unwrap();
+
+    assert!(query.matches_task(&searchable_task("Renew passport", None, &[])));
+    assert!(query.matches_task(&searchable_task("Trip", Some("bring PASSPORT"), &[])));
+    assert!(query.matches_task(&searchable_task("Pack", None, &["socks", "passport"])));
+    assert!(!query.matches_task(&searchable_task("Pack", None, &["socks"])));
+}
+
+#[test]
+fn search_supports_phrases_alternatives_and_negation() {
+    let query = parse_search_query("\"weekly review\" notes | agenda -draft", false).unwrap();
+    assert_eq!(query.clauses.len(), 2);
+    assert_eq!(query.clauses[1].len(), 2);
+    assert_eq!(query.excluded.len(), 1);
+
+    assert!(query.matches_task(&searchable_task("Weekly review agenda", None, &[])));
+    assert!(query.matches_task(&searchable_task("weekly review", Some("notes"), &[])));
+    assert!(!query.matches_task(&searchable_task("weekly notes review", None, &[])));
+    assert!(!query.matches_task(&searchable_task("Weekly review notes (draft)", None, &[])));
+
+    let negated_phrase = parse_search_query("report -\"first draft\"", false).unwrap();
+    assert!(negated_phrase.matches_task(&searchable_task("report draft", None, &[])));
+    assert!(!negated_phrase.matches_task(&searchable_task("report first draft", None, &[])));
+}
+
+#[test]
+fn search_regex_mode_is_case_insensitive_and_validates_patterns() {
+    let query = parse_search_query("^inv-\\d+", true).unwrap();
+    assert!(query.matches_task(&searchable_task("INV-204 follow up", None, &[])));
+    assert!(!query.matches_task(&searchable_task("Follow up INV-204", None, &[])));
+
+    let err = parse_search_query("(unclosed", true).unwrap_err();
+    assert!(err.to_string().contains("Invalid regex '(unclosed'"));
+}
+
+#[test]
+fn search_rejects_empty_and_unterminated_queries() {
+    assert!(parse_search_query("   ", false).is_err());
+    assert!(parse_search_query("| |", false).is_err());
+    assert!(parse_search_query("\"open phrase", false)
+        .unwrap_err()
+        .to_string()
+        .contains("Unterminated quote"));
+}
+
+#[test]
+fn last_subtask_flag_wins() {
+    let shown =
+        TaskListArgsCli::try_parse_from(["tt", "--no-subtasks", "--show-subtasks"]).unwrap();
+    assert!(shown.args.show_subtasks && !shown.args.no_subtasks);
+
+    let hidden =
+        TaskListArgsCli::try_parse_from(["tt", "--show-subtasks", "--no-subtasks"]).unwrap();
+    assert!(!hidden.args.show_subtasks && hidden.args.no_subtasks);
+}
+
+fn checklist_item(id: Option<&str>, title: &str, sort_order: Option<i64>) -> ChecklistItem {
+    ChecklistItem {
+        id: id.map(ToString::to_string),
+        title: Some(title.to_string()),
+        sort_order,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn pushing_checklist_items_keeps_existing_ids_and_appends_sort_order() {
+    let mut items = vec![
+        checklist_item(Some("item-1"), "Passport", Some(4)),
+        checklist_item(Some("item-2"), "Chargers", Some(9)),
+    ];
+
+    push_checklist_item(&mut items, "  Snacks ").unwrap();
+
+    assert_eq!(items.len(), 3);
+    assert_eq!(items[0].id.as_deref(), Some("item-1"));
+    assert_eq!(items[1].id.as_deref(), Some("item-2"));
+    assert_eq!(items[2].id, None);
+    assert_eq!(items[2].title.as_deref(), Some("Snacks"));
+    assert_eq!(items[2].sort_order, Some(10));
+    assert!(matches!(items[2].status, Some(TaskStatus::Normal)));
+    assert!(push_checklist_item(&mut items, "   ").is_err());
+}
+
+#[test]
+fn builds_initial_checklist_items_for_task_add() {
+    let items = new_checklist_items(&["a".to_string(), "b".to_string()])
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        items
+            .iter()
+            .map(|item| (item.title.as_deref().unwrap(), item.sort_order.unwrap()))
+            .collect::<Vec<_>>(),
+        vec![("a", 0), ("b", 1)]
+    );
+    assert!(new_checklist_items(&[]).unwrap().is_none());
+    assert!(new_checklist_items(&["".to_string()]).is_err());
+}
+
+#[test]
+fn finds_checklist_items_by_position_id_or_title() {
+    let items = vec![
+        checklist_item(Some("item-1"), "Passport", None),
+        checklist_item(Some("item-2"), "Chargers", None),
+    ];
+
+    assert_eq!(find_checklist_item(&items, "2").unwrap(), 1);
+    assert_eq!(find_checklist_item(&items, "item-1").unwrap(), 0);
+    assert_eq!(find_checklist_item(&items, "chargers").unwrap(), 1);
+    assert!(find_checklist_item(&items, "3")
+        .unwrap_err()
+        .to_string()
+        .contains("Checklist item not found: 3"));
+}
+
+#[test]
+fn completing_and_listing_checklist_items() {
+    let mut items = vec![
+        checklist_item(Some("item-1"), "Passport", None),
+        checklist_item(Some("item-2"), "Chargers", None),
+    ];
+    complete_checklist_item(&mut items[0], "2026-02-18T10:00:00.000+0000".to_string());
+
+    assert!(matches!(items[0].status, Some(TaskStatus::Completed)));
+    assert_eq!(
+        items[0].completed_time.as_deref(),
+        Some("2026-02-18T10:00:00.000+0000")
+    );
+    assert_eq!(
+        format_checklist_lines(&items),
+        "1. [x] Passport\n2. [ ] Chargers\n"
+    );
+    assert_eq!(format_checklist_lines(&[]), "No checklist items.\n");
+}
+
+fn time(hour: u32, minute: u32) -> Option<NaiveTime> {
+    NaiveTime::from_hms_opt(hour, minute, 0)
+}
+
+#[test]
+fn extracts_time_of_day_next_to_dates() {
+    let today = NaiveDate::from_ymd_opt(2026, 2, 18).unwrap();
+    let tomorrow = NaiveDate::from_ymd_opt(2026, 2, 19);
+
+    assert_eq!(
+        extract_due_datetime_from_input("call mom tomorrow at 3pm", today),
+        ("call mom".to_string(), tomorrow, time(15, 0))
+    );
+    assert_eq!(
+        extract_due_datetime_from_input("standup 9:45am friday", today),
+        (
+            "standup".to_string(),
+            NaiveDate::from_ymd_opt(2026, 2, 20),
+            time(9, 45)
+        )
+    );
+    assert_eq!(
+        extract_due_datetime_from_input("deploy tomorrow 15:30", today),
+        ("deploy".to_string(), tomorrow, time(15, 30))
+    );
+    assert_eq!(
+        extract_due_datetime_from_input("review 2 pm tomorrow", today),
+        ("review".to_string(), tomorrow, time(14, 0))
+    );
+}
+
+#[test]
+fn bare_at_hours_use_a_24_hour_clock() {
+    let today = NaiveDate::from_ymd_opt(2026, 2, 18).unwrap();
+
+    assert_eq!(
+        extract_due_datetime_from_input("gym at 9", today).2,
+        time(9, 0)
+    );
+    assert_eq!(
+        extract_due_datetime_from_input("gym at 15", today).2,
+        time(15, 0)
+    );
+    assert_eq!(
+        extract_due_datetime_from_input("lunch at noon", today),
+        ("lunch".to_string(), Some(today), time(12, 0))
+    );
+    assert_eq!(
+        extract_due_datetime_from_input("ship build midnight tomorrow", today).2,
+        time(0, 0)
+    );
+    assert_eq!(
+        extract_due_datetime_from_input("renew 12am", today).2,
+        time(0, 0)
+    );
+}
+
+#[test]
+fn leaves_non_time_numbers_in_the_title() {
+    let today = NaiveDate::from_ymd_opt(2026, 2, 18).unwrap();
+
+    assert_eq!(
+        extract_due_datetime_from_input("buy 3 apples tomorrow", today),
+        (
+            "buy 3 apples".to_string(),
+            NaiveDate::from_ymd_opt(2026, 2, 19),
+            None
+        )
+    );
+    assert_eq!(
+        extract_due_datetime_from_input("meet at cafe", today),
+        ("meet at cafe".to_string(), None, None)
+    );
+    assert_eq!(
+        extract_due_datetime_from_input("read 13pm notes", today),
+        ("read 13pm notes".to_string(), None, None)
+    );
+}
+
+#[test]
+fn colon_numbers_need_a_date_or_at_to_be_times() {
+    let today = NaiveDate::from_ymd_opt(2026, 2, 18).unwrap();
+    let tomorrow = NaiveDate::from_ymd_opt(2026, 2, 19);
+
+    assert_eq!(
+        extract_due_datetime_from_input("Read John 3:16", today),
+        ("Read John 3:16".to_string(), None, None)
+    );
+    assert_eq!(
+        extract_due_datetime_from_input("Read John 3:16 tomorrow", today),
+        ("Read John 3:16".to_string(), tomorrow, None)
+    );
+    assert_eq!(
+        extract_due_datetime_from_input("odds 25:61 tomorrow 7:99", today),
+        ("odds 25:61 7:99".to_string(), tomorrow, None)
+    );
+    assert_eq!(
+        extract_due_datetime_from_input("sermon at 3:16", today),
+        ("sermon".to_string(), Some(today), time(3, 16))
+    );
+}
+
+/// US Eastern time for 2026 only: DST runs from 2026-03-08 02:00 to 2026-11-01 02:00.
+#[derive(Clone, Copy)]
+struct Eastern2026;
+
+impl Eastern2026 {
+    fn standard() -> FixedOffset {
+        FixedOffset::west_opt(5 * 3600).unwrap()
+    }
+
+    fn daylight() -> FixedOffset {
+        FixedOffset::west_opt(4 * 3600).unwrap()
+    }
+
+    fn at(month: u32, day: u32, hour: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2026, month, day)
+            .unwrap()
+            .and_hms_opt(hour, 0, 0)
+            .unwrap()
+    }
+}
+
+impl TimeZone for Eastern2026 {
+    type Offset = FixedOffset;
+
+    fn from_offset(_offset: &FixedOffset) -> Self {
+        Eastern2026
+    }
+
+    fn offset_from_local_date(&self, local: &NaiveDate) -> LocalResult<FixedOffset> {
+        self.offset_from_local_datetime(&local.and_hms_opt(0, 0, 0).unwrap())
+    }
+
+    fn offset_from_local_datetime(&self, local: &NaiveDateTime) -> LocalResult<FixedOffset> {
+        if *local >= Self::at(3, 8, 2) && *local < Self::at(3, 8, 3) {
+            LocalResult::None
+        } else if *local >= Self::at(11, 1, 1) && *local < Self::at(11, 1, 2) {
+            LocalResult::Ambiguous(Self::daylight(), Self::standard())
+        } else if *local >= Self::at(3, 8, 3) && *local < Self::at(11, 1, 1) {
+            LocalResult::Single(Self::daylight())
+        } else {
+            LocalResult::Single(Self::standard())
+        }
+    }
+
+    fn offset_from_utc_date(&self, utc: &NaiveDate) -> FixedOffset {
+        self.offset_from_utc_datetime(&utc.and_hms_opt(0, 0, 0).unwrap())
+    }
+
+    fn offset_from_utc_datetime(&self, utc: &NaiveDateTime) -> FixedOffset {
+        if *utc >= Self::at(3, 8, 7) && *utc < Self::at(11, 1, 6) {
+            Self::daylight()
+        } else {
+            Self::standard()
+        }
+    }
+}
+
+#[test]
+fn local_times_convert_to_utc_across_dst_transitions() {
+    let utc = |local| {
+        local_datetime_to_utc(&Eastern2026, local)
+            .unwrap()
+            .naive_utc()
+    };
+
+    assert_eq!(utc(Eastern2026::at(3, 7, 15)), Eastern2026::at(3, 7, 20));
+    assert_eq!(utc(Eastern2026::at(3, 9, 15)), Eastern2026::at(3, 9, 19));
+    // 02:30 on spring-forward day does not exist; it moves to 03:30 EDT.
+    assert_eq!(
+        utc(Eastern2026::at(3, 8, 2) + chrono::Duration::minutes(30)),
+        Eastern2026::at(3, 8, 7) + chrono::Duration::minutes(30)
+    );
+    // 01:00 on fall-back day happens twice; the first (EDT) occurrence wins.
+    assert_eq!(utc(Eastern2026::at(11, 1, 1)), Eastern2026::at(11, 1, 5));
+    assert_eq!(utc(Eastern2026::at(11, 2, 15)), Eastern2026::at(11, 2, 20));
+}
+
+#[test]
+fn repeat_shorthand_maps_daily_and_weekly_phrases() {
+    assert_eq!(
+        extract_repeat_from_input("water plants every day"),
+        (
+            "water plants".to_string(),
+            Some("RRULE:FREQ=DAILY".to_string())
+        )
+    );
+    assert_eq!(
+        extract_repeat_from_input("every week review goals"),
+        (
+            "review goals".to_string(),
+            Some("RRULE:FREQ=WEEKLY".to_string())
+        )
+    );
+}
+
+#[test]
+fn repeat_shorthand_maps_specific_weekdays() {
+    assert_eq!(
+        extract_repeat_from_input("standup every Monday #work"),
+        (
+            "standup #work".to_string(),
+            Some("RRULE:FREQ=WEEKLY;BYDAY=MO".to_string())
+        )
+    );
+    assert_eq!(
+        extract_repeat_from_input("trash every thursdays")
+            .1
+            .as_deref(),
+        Some("RRULE:FREQ=WEEKLY;BYDAY=TH")
+    );
+}
+
+#[test]
+fn repeat_shorthand_maps_interval_phrases() {
+    assert_eq!(
+        extract_repeat_from_input("payroll every 2 weeks"),
+        (
+            "payroll".to_string(),
+            Some("RRULE:FREQ=WEEKLY;INTERVAL=2".to_string())
+        )
+    );
+    assert_eq!(
+        extract_repeat_from_input("dentist every 6 months")
+            .1
+            .as_deref(),
+        Some("RRULE:FREQ=MONTHLY;INTERVAL=6")
+    );
+    assert_eq!(
+        extract_repeat_from_input("haircut every other week")
+            .1
+            .as_deref(),
+        Some("RRULE:FREQ=WEEKLY;INTERVAL=2")
+    );
+}
+
+#[test]
+fn repeat_shorthand_ignores_unrelated_every() {
+    assert_eq!(
+        extract_repeat_from_input("read every chapter"),
+        ("read every chapter".to_string(), None)
+    );
+    assert_eq!(extract_repeat_from_input("every 0 days").1, None);
+}
+
+#[test]
+fn repeat_phrases_are_removed_before_due_dates_are_inferred() {
+    let today = NaiveDate::from_ymd_opt(2026, 2, 18).unwrap();
+    let (title, repeat) = extract_repeat_from_input("standup every monday");
+    assert_eq!(repeat.as_deref(), Some("RRULE:FREQ=WEEKLY;BYDAY=MO"));
+    assert_eq!(
+        extract_due_date_from_input(&title, today),
+        ("standup".to_string(), None)
+    );
+}
+
+#[test]
+fn repeat_phrases_map_to_expected_rrules() {
+    let cases = [
+        ("daily", "RRULE:FREQ=DAILY"),
+        ("every day", "RRULE:FREQ=DAILY"),
+        ("weekly", "RRULE:FREQ=WEEKLY"),
+        ("every monday", "RRULE:FREQ=WEEKLY;BYDAY=MO"),
+        ("every mon and thu", "RRULE:FREQ=WEEKLY;BYDAY=MO,TH"),
+        ("every fri, mon", "RRULE:FREQ=WEEKLY;BYDAY=MO,FR"),
+        ("every 2 weeks", "RRULE:FREQ=WEEKLY;INTERVAL=2"),
+        ("every weekday", "RRULE:FREQ=WEEKLY;BYDAY=MO,TU,WE,TH,FR"),
+        ("every weekend", "RRULE:FREQ=WEEKLY;BYDAY=SA,SU"),
+        ("monthly", "RRULE:FREQ=MONTHLY"),
+        ("monthly on the 15th", "RRULE:FREQ=MONTHLY;BYMONTHDAY=15"),
+        (
+            "every 3 months on the 1st",
+            "RRULE:FREQ=MONTHLY;INTERVAL=3;BYMONTHDAY=1",
+        ),
+        (
+            "every month on the last day",
+            "RRULE:FREQ=MONTHLY;BYMONTHDAY=-1",
+        ),
+        ("yearly", "RRULE:FREQ=YEARLY"),
+        ("annually", "RRULE:FREQ=YEARLY"),
+    ];
+
+    for (phrase, expected) in cases {
+        let (title, repeat) = extract_repeat_from_input(&format!("renew {} #home", phrase));
+        assert_eq!(title, "renew #home", "phrase: {}", phrase);
+        assert_eq!(repeat.as_deref(), Some(expected), "phrase: {}", phrase);
+        assert_eq!(parse_repeat_value(phrase).as_deref(), Ok(expected));
+    }
+}
+
+#[test]
+fn repeat_flag_accepts_shorthand_without_every_and_raw_rrules() {
+    assert_eq!(
+        parse_repeat_value("2 weeks").as_deref(),
+        Ok("RRULE:FREQ=WEEKLY;INTERVAL=2")
+    );
+    assert_eq!(
+        parse_repeat_value("weekday").as_deref(),
+        Ok("RRULE:FREQ=WEEKLY;BYDAY=MO,TU,WE,TH,FR")
+    );
+    assert_eq!(
+        parse_repeat_value("rrule:freq=daily;interval=3").as_deref(),
+        Ok("RRULE:FREQ=DAILY;INTERVAL=3")
+    );
+    assert!(parse_repeat_value("every blue moon")
+        .unwrap_err()
+        .contains("Unrecognized repeat 'every blue moon'"));
+    assert!(parse_repeat_value("every monday please").is_err());
+}
+
+#[test]
+fn validate_rrule_rejects_rules_ticktick_does_not_accept() {
+    assert!(validate_rrule("RRULE:FREQ=MONTHLY;BYDAY=-1FR").is_ok());
+    assert!(validate_rrule("RRULE:FREQ=DAILY;UNTIL=20261231T000000Z").is_ok());
+
+    let rejected = [
+        ("FREQ=DAILY", "must start with RRULE:"),
+        ("RRULE:INTERVAL=2", "missing FREQ"),
+        ("RRULE:FREQ=HOURLY", "Unsupported FREQ 'HOURLY'"),
+        (
+            "RRULE:FREQ=DAILY;INTERVAL=0",
+            "INTERVAL must be a positive integer",
+        ),
+        ("RRULE:FREQ=WEEKLY;BYDAY=XX", "Invalid BYDAY value 'XX'"),
+        ("RRULE:FREQ=MONTHLY;BYMONTHDAY=32", "BYMONTHDAY must be"),
+        (
+            "RRULE:FREQ=DAILY;COUNT=3;UNTIL=20260101",
+            "both COUNT and UNTIL",
+        ),
+        (
+            "RRULE:FREQ=DAILY;BYHOUR=9",
+            "Unsupported RRULE part 'BYHOUR'",
+        ),
+        ("RRULE:FREQ=DAILY;UNTIL=tomorrow", "UNTIL must look like"),
+    ];
+    for (rule, message) in rejected {
+        let err = validate_rrule(rule).unwrap_err();
+        assert!(err.contains(message), "{} -> {}", rule, err);
+    }
+}
+
+#[test]
+fn describes_repeat_flags_as_human_phrases() {
+    let cases = [
+        ("RRULE:FREQ=DAILY", "every day"),
+        ("RRULE:FREQ=DAILY;INTERVAL=3", "every 3 days"),
+        (
+            "RRULE:FREQ=WEEKLY;INTERVAL=1;BYDAY=MO",
+            "every week on Monday",
+        ),
+        ("RRULE:FREQ=WEEKLY;BYDAY=MO,TU,WE,TH,FR", "every weekday"),
+        (
+            "RRULE:FREQ=MONTHLY;BYMONTHDAY=22",
+            "every month on the 22nd",
+        ),
+        (
+            "RRULE:FREQ=MONTHLY;BYMONTHDAY=-1",
+            "every month on the last day",
+        ),
+        ("RRULE:FREQ=YEARLY;COUNT=1", "every year, 1 time"),
+        (
+            "RRULE:FREQ=WEEKLY;UNTIL=20261231",
+            "every week, until 2026-12-31",
+        ),
+    ];
+    for (rule, expected) in cases {
+        assert_eq!(describe_repeat_flag(rule), expected);
+    }
+
+    assert_eq!(
+        describe_repeat_flag("RRULE:FREQ=MONTHLY;BYDAY=-1FR"),
+        "RRULE:FREQ=MONTHLY;BYDAY=-1FR"
+    );
+    assert_eq!(
+        describe_repeat_flag("ERULE:NAME=CUSTOM"),
+        "ERULE:NAME=CUSTOM"
+    );
+}
+
+#[test]
+fn repeat_and_repeat_flag_are_mutually_exclusive() {
+    let err = TaskUpdateArgsCli::try_parse_from([
+        "tt",
+        "task-1",
+        "--repeat",
+        "daily",
+        "--repeat-flag",
+        "RRULE:FREQ=DAILY",
+    ])
+    .err()
+    .unwrap();
+    assert_eq!(err.kind(), clap::error::ErrorKind::ArgumentConflict);
+
+    let err = TaskUpdateArgsCli::try_parse_from(["tt", "task-1", "--repeat-flag", "FREQ=DAILY"])
+        .err()
+        .unwrap();
+    assert!(err.to_string().contains("must start with RRULE:"));
+}
+
+#[test]
+fn parse_task_import_file_accepts_json_arrays_and_task_objects() {
+    let array = parse_task_import_file(
+        std::path::Path::new("tasks.json"),
+        r#"[{"title": "Pay rent", "due": "2026-03-01", "list": "Home"}]"#,
+    )
+    .unwrap();
+    assert_eq!(array.len(), 1);
+    assert_eq!(array[0].title, "Pay rent");
+    assert_eq!(array[0].list.as_deref(), Some("Home"));
+
+    let object = parse_task_import_file(
+        std::path::Path::new("tasks.json"),
+        r#"{"tasks": [{"title": "One"}, {"title": "Two", "priority": "high"}]}"#,
+    )
+    .unwrap();
+    assert_eq!(object.len(), 2);
+}
+
+#[test]
+fn parse_task_import_file_reads_toml_task_tables() {
+    let tasks = parse_task_import_file(
+        std::path::Path::new("tasks.toml"),
+        r#"
+[[tasks]]
+title = "Renew passport"
+priority = 5
+tags = ["admin"]
+items = ["Photos", "Form"]
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(tasks.len(), 1);
+    assert_eq!(tasks[0].tags, vec!["admin".to_string()]);
+    assert_eq!(tasks[0].items.len(), 2);
+}
+
+#[test]
+fn parse_task_import_file_rejects_yaml_unknown_fields_and_empty_files() {
+    let yaml = parse_task_import_file(std::path::Path::new("tasks.yaml"), "- title: x")
+        .unwrap_err()
+        .to_string();
+    assert!(yaml.contains("JSON or TOML"));
+
+    assert!(parse_task_import_file(
+        std::path::Path::new("tasks.json"),
+        r#"[{"title": "x", "colour": "red"}]"#
+    )
+    .is_err());
+    assert!(parse_task_import_file(std::path::Path::new("tasks.json"), "[]").is_err());
+}
+
+#[test]
+fn task_from_import_maps_dates_priorities_and_items() {
+    let entries = parse_task_import_file(
+        std::path::Path::new("tasks.json"),
+        r#"[{"title": " Pack ", "due": "2026-03-01", "priority": "medium", "items": ["Socks"], "repeat": "weekly"}]"#,
+    )
+    .unwrap();
+    let task = task_from_import(&entries[0], "proj-1").unwrap();
+
+    assert_eq!(task.title, "Pack");
+    assert_eq!(task.project_id.as_deref(), Some("proj-1"));
+    assert_eq!(task.priority, Some(3));
+    assert_eq!(task.is_all_day, Some(true));
+    assert_eq!(task.start_date, task.due_date);
+    assert_eq!(
+        task.due_date.as_deref().and_then(parse_task_date),
+        Some(NaiveDate::from_ymd_opt(2026, 3, 1).unwrap())
+    );
+    assert_eq!(task.repeat_flag.as_deref(), Some("RRULE:FREQ=WEEKLY"));
+    assert_eq!(task.kind.as_deref(), Some("CHECKLIST"));
+    assert_eq!(task.items.as_ref().map(Vec::len), Some(1));
+}
+
+#[test]
+fn task_from_import_reports_invalid_fields() {
+    let entries = parse_task_import_file(
+        std::path::Path::new("tasks.json"),
+        r#"[{"title": "x", "due": "someday"}, {"title": "y", "priority": "urgent"}, {"title": "  "}]"#,
+    )
+    .unwrap();
+
+    let due = task_from_import(&entries[0], "p").unwrap_err().to_string();
+    assert!(due.starts_with("Invalid due:"));
+    assert!(task_from_import(&entries[1], "p").is_err());
+    assert!(task_from_import(&entries[2], "p").is_err());
+}
+
+#[test]
+fn format_import_report_lists_each_task_and_a_summary() {
+    let results = vec![
+        ImportResult {
+            index: 1,
+            title: "Pay rent".to_string(),
+            status: ImportStatus::Created,
+            id: Some("t1".to_string()),
+            list: Some("Home".to_string()),
+            project_id: Some("p1".to_string()),
+            error: None,
+        },
+        ImportResult {
+            index: 2,
+            title: "Broken".to_string(),
+            status: ImportStatus::Failed,
+            id: None,
+            list: None,
+            project_id: None,
+            error: Some("Invalid due: nope".to_string()),
+        },
+    ];
+
+    let human = format_import_report(&results, false, false, &OutputContext::default()).unwrap();
+    assert_eq!(
+        human,
+        "[1/2] created: Pay rent (t1)\n[2/2] failed: Broken: Invalid due: nope\nImported 1 of 2 tasks (1 failed).\n"
+    );
+
+    let json: Value = serde_json::from_str(
+        &format_import_report(&results, true, true, &OutputContext::default()).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(json["dryRun"], true);
+    assert_eq!(json["failed"], 1);
+    assert_eq!(json["results"][0]["projectId"], "p1");
+}
+
+#[test]
+fn parse_task_ids_takes_the_first_field_and_drops_repeats() {
+    let input = "t1|Pay rent\n\n# skipped\n  t2  \nt1\nt3 trailing words\n";
+    assert_eq!(parse_task_ids(input), ["t1", "t2", "t3"]);
+    assert!(parse_task_ids("\n# only comments\n").is_empty());
+}
+
+#[test]
+fn batch_update_reports_only_real_changes() {
+    let plan = BatchUpdatePlan {
+        priority: Some(5),
+        tag_add: vec!["ops".to_string()],
+        tag_remove: vec!["Later".to_string()],
+        to_project: Some(("p2".to_string(), "Home".to_string())),
+    };
+
+    let mut task = make_task(None, None, Some(vec!["work", "later"]), Some(1));
+    task.project_id = Some("p1".to_string());
+    assert_eq!(
+        apply_batch_update(&mut task, &plan),
+        ["priority low -> high", "+#ops", "-#Later", "moved to Home"]
+    );
+    assert_eq!(task.priority, Some(5));
+    assert_eq!(task.tags, Some(vec!["work".to_string(), "ops".to_string()]));
+    assert_eq!(task.project_id.as_deref(), Some("p2"));
+
+    assert!(apply_batch_update(&mut task, &plan).is_empty());
+}
+
+#[test]
+fn batch_update_args_take_comma_separated_ids_and_need_a_change() {
+    #[derive(Parser)]
+    struct Cli {
+        #[command(flatten)]
+        args: TaskBatchUpdateArgs,
+    }
+
+    let cli =
+        Cli::try_parse_from(["tt", "--ids", "t1,t2", "--tag-add", "#ops", "--dry-run"]).unwrap();
+    assert_eq!(cli.args.ids, ["t1", "t2"]);
+    assert_eq!(cli.args.tag_add, ["#ops"]);
+    assert!(cli.args.dry_run);
+    assert!(Cli::try_parse_from(["tt", "--priority", "urgent"]).is_err());
+}
+
+#[test]
+fn format_batch_update_report_summarizes_each_id() {
+    let results = vec![
+        BatchUpdateResult {
+            id: "t1".to_string(),
+            title: Some("Pay rent".to_string()),
+            status: BatchUpdateStatus::Updated,
+            changes: vec!["priority none -> high".to_string(), "+#home".to_string()],
+            error: None,
+        },
+        BatchUpdateResult {
+            id: "t2".to_string(),
+            title: Some("Call bank".to_string()),
+            status: BatchUpdateStatus::Unchanged,
+            changes: Vec::new(),
+            error: None,
+        },
+        BatchUpdateResult {
+            id: "missing".to_string(),
+            title: None,
+            status: BatchUpdateStatus::Failed,
+            changes: Vec::new(),
+            error: Some("Task not found".to_string()),
+        },
+    ];
+
+    assert_eq!(
+        format_batch_update_report(&results, false, false, &OutputContext::default()).unwrap(),
+        "[1/3] updated: Pay rent (priority none -> high, +#home)\n[2/3] unchanged: Call bank\n[3/3] failed: missing: Task not found\nUpdated 1 of 3 tasks (1 unchanged, 1 failed).\n"
+    );
+    let dry_run =
+        format_batch_update_report(&results, true, false, &OutputContext::default()).unwrap();
+    assert!(dry_run.starts_with("[1/3] would update: Pay rent"));
+    assert!(dry_run.ends_with("Nothing was updated.\n"));
+
+    let json: Value = serde_json::from_str(
+        &format_batch_update_report(&results, true, true, &OutputContext::default()).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(json["dryRun"], true);
+    assert_eq!(json["updated"], 1);
+    assert_eq!(json["results"][2]["status"], "failed");
+    assert!(json["results"][1].get("changes").is_none());
+    assert_eq!(batch_update_failed_ids(&results), vec!["missing"]);
+}
+
+#[test]
+fn reminder_values_translate_to_triggers() {
+    assert_eq!(parse_reminder_value("0").unwrap(), "TRIGGER:PT0S");
+    assert_eq!(parse_reminder_value("at-time").unwrap(), "TRIGGER:PT0S");
+    assert_eq!(parse_reminder_value("30m").unwrap(), "TRIGGER:-PT30M");
+    assert_eq!(parse_reminder_value("1h").unwrap(), "TRIGGER:-PT1H");
+    assert_eq!(parse_reminder_value("1d").unwrap(), "TRIGGER:-P1D");
+    assert_eq!(parse_reminder_value("2w").unwrap(), "TRIGGER:-P14D");
+    assert_eq!(parse_reminder_value("1h30m").unwrap(), "TRIGGER:-PT1H30M");
+    assert_eq!(parse_reminder_value("1d 2h").unwrap(), "TRIGGER:-P1DT2H");
+    assert_eq!(parse_reminder_value("15 min").unwrap(), "TRIGGER:-PT15M");
+}
+
+#[test]
+fn reminder_values_round_trip_through_friendly_form() {
+    for friendly in [
+        "at-time", "5m", "30m", "90m", "1h", "2h", "1h30m", "1d", "3d", "1d2h30m",
+    ] {
+        let trigger = parse_reminder_value(friendly).unwrap();
+        assert_eq!(describe_reminder(&trigger), friendly, "trigger {}", trigger);
+    }
+}
+
+#[test]
+fn reminder_values_accept_and_describe_raw_triggers() {
+    assert_eq!(
+        parse_reminder_value("trigger:-pt30m").unwrap(),
+        "TRIGGER:-PT30M"
+    );
+    assert_eq!(
+        parse_reminder_value("TRIGGER:P0DT9H0M0S").unwrap(),
+        "TRIGGER:P0DT9H0M0S"
+    );
+    assert_eq!(describe_reminder("TRIGGER:-P0DT0H45M0S"), "45m");
+    assert_eq!(
+        describe_reminder("TRIGGER:P0DT9H0M0S"),
+        "TRIGGER:P0DT9H0M0S"
+    );
+    assert_eq!(describe_reminder("TRIGGER:-P1W"), "7d");
+}
+
+#[test]
+fn reminder_values_reject_unknown_forms_with_accepted_list() {
+    for value in ["soon", "30", "m30", "1y", "TRIGGER:-PT30X", ""] {
+        let err = parse_reminder_value(value).unwrap_err();
+        assert!(err.contains("at-time"), "{}: {}", value, err);
+        assert!(err.contains("1h30m"), "{}: {}", value, err);
+    }
+}
+
+#[test]
+fn extracts_remind_me_phrases_from_titles() {
+    let (title, trigger) = extract_reminder_from_input("call dentist remind me 30 min before");
+    assert_eq!(title, "call dentist");
+    assert_eq!(trigger.as_deref(), Some("TRIGGER:-PT30M"));
+
+    let (title, trigger) = extract_reminder_from_input("standup remind me 1h before tomorrow");
+    assert_eq!(title, "standup tomorrow");
+    assert_eq!(trigger.as_deref(), Some("TRIGGER:-PT1H"));
+
+    let (title, trigger) = extract_reminder_from_input("pay rent remind me at time");
+    assert_eq!(title, "pay rent");
+    assert_eq!(trigger.as_deref(), Some("TRIGGER:PT0S"));
+
+    let (title, trigger) = extract_reminder_from_input("remind me to breathe");
+    assert_eq!(title, "remind me to breathe");
+    assert_eq!(trigger, None);
+}
+
+#[test]
+fn postpone_targets_parse_offsets_and_dates() {
+    let today = ymd(2026, 2, 18);
+    assert_eq!(
+        parse_postpone_target("1d", today),
+        Ok(PostponeTarget::Days(1))
+    );
+    assert_eq!(
+        parse_postpone_target("+2w", today),
+        Ok(PostponeTarget::Days(14))
+    );
+    assert_eq!(
+        parse_postpone_target("3h", today),
+        Ok(PostponeTarget::Hours(3))
+    );
+    assert_eq!(
+        parse_postpone_target("5 days", today),
+        Ok(PostponeTarget::Days(5))
+    );
+    assert_eq!(
+        parse_postpone_target("tomorrow", today),
+        Ok(PostponeTarget::Date(ymd(2026, 2, 19)))
+    );
+    assert_eq!(
+        parse_postpone_target("next monday", today),
+        Ok(PostponeTarget::Date(ymd(2026, 3, 2)))
+    );
+    assert_eq!(
+        parse_postpone_target("2026-03-01", today),
+        Ok(PostponeTarget::Date(ymd(2026, 3, 1)))
+    );
+
+    for invalid in ["soon", "0d", "1y", ""] {
+        let err = parse_postpone_target(invalid, today).unwrap_err();
+        assert!(err.contains("Invalid postpone target"), "{}", invalid);
+    }
+}
+
+#[test]
+fn shifting_by_days_keeps_wall_clock_time_across_dst() {
+    // 3pm EST on Mar 7 becomes 3pm EDT on Mar 8.
+    assert_eq!(
+        shift_ticktick_datetime(&Eastern2026, "2026-03-07T20:00:00.000+0000", 1, 0).as_deref(),
+        Some("2026-03-08T19:00:00.000+0000")
+    );
+    // And back again in the fall.
+    assert_eq!(
+        shift_ticktick_datetime(&Eastern2026, "2026-10-31T13:00:00.000+0000", 2, 0).as_deref(),
+        Some("2026-11-02T14:00:00.000+0000")
+    );
+}
+
+#[test]
+fn shifting_by_hours_counts_elapsed_time_across_dst() {
+    // 1am EST plus 3 hours is 5am EDT because 2am is skipped.
+    assert_eq!(
+        shift_ticktick_datetime(&Eastern2026, "2026-03-08T06:00:00.000+0000", 0, 3).as_deref(),
+        Some("2026-03-08T09:00:00.000+0000")
+    );
+}
+
+#[test]
+fn shifting_rolls_over_month_and_year_ends() {
+    assert_eq!(
+        shift_ticktick_datetime(&Eastern2026, "2026-01-31T15:00:00.000+0000", 1, 0).as_deref(),
+        Some("2026-02-01T15:00:00.000+0000")
+    );
+    assert_eq!(
+        shift_ticktick_datetime(&Eastern2026, "2026-02-28T15:00:00.000+0000", 1, 0).as_deref(),
+        Some("2026-03-01T15:00:00.000+0000")
+    );
+    assert_eq!(
+        shift_ticktick_datetime(&Eastern2026, "2026-12-31T15:00:00.000+0000", 1, 0).as_deref(),
+        Some("2027-01-01T15:00:00.000+0000")
+    );
+}
+
+#[test]
+fn postpone_to_a_date_moves_due_and_start_by_the_same_delta() {
+    let mut task = make_task(
+        Some("2026-02-27T15:00:00.000+0000"),
+        Some("2026-02-27T14:00:00.000+0000"),
+        None,
+        None,
+    );
+    task.is_all_day = Some(false);
+
+    postpone_task(
+        &mut task,
+        PostponeTarget::Date(ymd(2026, 3, 9)),
+        &Eastern2026,
+    )
+    .unwrap();
+
+    // 10am/9am EST on Feb 27 stay 10am/9am, now EDT, on Mar 9.
+    assert_eq!(
+        task.due_date.as_deref(),
+        Some("2026-03-09T14:00:00.000+0000")
+    );
+    assert_eq!(
+        task.start_date.as_deref(),
+        Some("2026-03-09T13:00:00.000+0000")
+    );
+    assert_eq!(task.is_all_day, Some(false));
+}
+
+#[test]
+fn postpone_uses_the_local_due_day_for_date_targets() {
+    // 11pm EST on Mar 1 is already Mar 2 in UTC; tomorrow still means Mar 2 locally.
+    let mut task = make_task(Some("2026-03-02T04:00:00.000+0000"), None, None, None);
+    postpone_task(
+        &mut task,
+        PostponeTarget::Date(ymd(2026, 3, 2)),
+        &Eastern2026,
+    )
+    .unwrap();
+    assert_eq!(
+        task.due_date.as_deref(),
+        Some("2026-03-03T04:00:00.000+0000")
+    );
+    assert_eq!(task.start_date, None);
+}
+
+#[test]
+fn postpone_without_a_due_date_needs_an_absolute_target() {
+    let mut task = make_task(None, None, None, None);
+    let err = postpone_task(&mut task, PostponeTarget::Days(1), &Eastern2026).unwrap_err();
+    assert!(err.to_string().contains("has no due date"));
+
+    postpone_task(
+        &mut task,
+        PostponeTarget::Date(ymd(2026, 3, 10)),
+        &Eastern2026,
+    )
+    .unwrap();
+    assert_eq!(
+        task.due_date.as_deref(),
+        Some("2026-03-10T04:00:00.000+0000")
+    );
+    assert_eq!(task.start_date, task.due_date);
+    assert_eq!(task.is_all_day, Some(true));
+}
+
+#[test]
+fn completed_since_keeps_local_days_on_or_after_and_sorts_oldest_first() {
+    let completed = |title: &str, time: Option<&str>| Task {
+        title: title.to_string(),
+        completed_time: time.map(ToString::to_string),
+        ..Default::default()
+    };
+    let tasks = vec![
+        completed("late", Some("2026-03-05T15:00:00.000+0000")),
+        // 11pm EST on Mar 1 is Mar 2 in UTC, so it is before a Mar 2 cutoff locally.
+        completed("previous evening", Some("2026-03-02T04:00:00.000+0000")),
+        completed("early", Some("2026-03-02T14:00:00.000+0000")),
+        completed("unknown", None),
+    ];
+
+    let titles: Vec<String> = tasks_completed_since(tasks, ymd(2026, 3, 2), &Eastern2026)
+        .into_iter()
+        .map(|task| task.title)
+        .collect();
+    assert_eq!(titles, vec!["early", "late"]);
+}
+
+#[test]
+fn completed_at_reads_millisecond_epochs_in_local_time() {
+    let task = Task {
+        // 03:00 UTC on Mar 2 is still the evening of Mar 1 in New York.
+        completed_time: Some("1772420400000".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(
+        task_completed_at(&task, &Eastern2026),
+        ymd(2026, 3, 1).and_hms_opt(22, 0, 0)
+    );
+    assert_eq!(task_completed_at(&Task::default(), &Eastern2026), None);
+}
+
+#[test]
+fn newest_first_puts_missing_times_last() {
+    let at = |day: u32| ymd(2026, 3, day).and_hms_opt(9, 0, 0);
+    assert_eq!(compare_newest_first(at(2), at(1)), Ordering::Less);
+    assert_eq!(compare_newest_first(at(1), at(2)), Ordering::Greater);
+    assert_eq!(compare_newest_first(at(1), None), Ordering::Less);
+    assert_eq!(compare_newest_first(None, at(1)), Ordering::Greater);
+    assert_eq!(compare_newest_first(None, None), Ordering::Equal);
+}
+
+#[test]
+fn sort_tasks_orders_by_completed_or_created_time() {
+    let task = |title: &str, created: Option<&str>, completed: Option<&str>| Task {
+        title: title.to_string(),
+        created_time: created.map(ToString::to_string),
+        completed_time: completed.map(ToString::to_string),
+        ..Default::default()
+    };
+    let mut tasks = vec![
+        task("open", Some("2026-03-04T10:00:00.000+0000"), None),
+        task(
+            "older",
+            Some("2026-03-01T10:00:00.000+0000"),
+            Some("1772420400000"),
+        ),
+        task("newer", None, Some("2026-03-05T15:00:00.000+0000")),
+    ];
+    let titles =
+        |tasks: &[Task]| -> Vec<String> { tasks.iter().map(|task| task.title.clone()).collect() };
+
+    sort_tasks(&mut tasks, TaskSort::Completed, &Eastern2026);
+    assert_eq!(titles(&tasks), vec!["newer", "older", "open"]);
+
+    sort_tasks(&mut tasks, TaskSort::Created, &Eastern2026);
+    assert_eq!(titles(&tasks), vec!["open", "older", "newer"]);
+}
+
+#[test]
+fn modified_after_keeps_tasks_changed_on_or_after_the_local_day() {
+    let modified = |title: &str, time: Option<&str>| Task {
+        title: title.to_string(),
+        modified_time: time.map(ToString::to_string),
+        ..Default::default()
+    };
+    let since = parse_modified_after_value("2026-03-02").unwrap();
+    let tasks = [
+        modified("late", Some("2026-03-05T15:00:00.000+0000")),
+        // 11pm EST on Mar 1 is Mar 2 in UTC.
+        modified("previous evening", Some("2026-03-02T04:00:00.000+0000")),
+        modified("same day", Some("2026-03-02T14:00:00.000+0000")),
+        modified("unknown", None),
+    ];
+
+    let titles: Vec<&str> = tasks
+        .iter()
+        .filter(|task| task_modified_since(task, since, &Eastern2026))
+        .map(|task| task.title.as_str())
+        .collect();
+    assert_eq!(titles, vec!["late", "same day"]);
+
+    assert!(parse_modified_after_value("last tuesday").is_err());
+    let parsed = TaskListArgsCli::try_parse_from(["tt", "--since", "2026-03-02"]).unwrap();
+    assert_eq!(parsed.args.modified_after, Some(ymd(2026, 3, 2)));
+}
+
+fn editor_sample_task() -> Task {
+    Task {
+        id: Some("task-1".to_string()),
+        title: "Plan trip".to_string(),
+        content: Some("Book flights\nReserve hotel".to_string()),
+        due_date: Some("2026-03-02T09:30:00.000+0000".to_string()),
+        is_all_day: Some(false),
+        priority: Some(3),
+        tags: Some(vec!["travel".to_string(), "family".to_string()]),
+        items: Some(vec![
+            ChecklistItem {
+                id: Some("item-1".to_string()),
+                title: Some("Passport".to_string()),
+                status: Some(TaskStatus::Completed),
+                ..Default::default()
+            },
+            ChecklistItem {
+                id: Some("item-2".to_string()),
+                title: Some("Chargers".to_string()),
+                status: Some(TaskStatus::Normal),
+                ..Default::default()
+            },
+        ]),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn document_round_trips_tags_dates_and_items() {
+    let document = document_from_task(&editor_sample_task(), &Utc);
+    assert_eq!(document.due.as_deref(), Some("2026-03-02T09:30"));
+    assert_eq!(document.priority, "medium");
+
+    let text = render_document(&document).unwrap();
+    assert!(text.starts_with(DOCUMENT_HEADER));
+    let parsed = parse_document(&text).unwrap().unwrap();
+    assert_eq!(parsed, document);
+    assert_eq!(parsed.tags, vec!["travel", "family"]);
+    assert_eq!(
+        parsed.items,
+        vec![
+            DocumentItem {
+                title: "Passport".to_string(),
+                done: true
+            },
+            DocumentItem {
+                title: "Chargers".to_string(),
+                done: false
+            },
+        ]
+    );
+}
+
+#[test]
+fn unchanged_document_leaves_the_task_untouched() {
+    let task = editor_sample_task();
+    let document = document_from_task(&task, &Utc);
+    let mut updated = task.clone();
+    apply_document(&mut updated, &document, &document, "now").unwrap();
+    assert_eq!(
+        serde_json::to_value(&updated).unwrap(),
+        serde_json::to_value(&task).unwrap()
+    );
+}
+
+#[test]
+fn edited_document_updates_only_changed_fields() {
+    let mut task = editor_sample_task();
+    let original = document_from_task(&task, &Utc);
+    let edited = parse_document(
+        r#"
+title = "Plan trip"
+content = "Book flights\nReserve hotel"
+start = "2026-03-01"
+all_day = false
+priority = "high"
+tags = ["travel"]
+
+[[items]]
+title = "Chargers"
+done = true
+
+[[items]]
+title = "Snacks"
+"#,
+    )
+    .unwrap()
+    .unwrap();
+
+    apply_document(
+        &mut task,
+        &original,
+        &edited,
+        "2026-03-01T10:00:00.000+0000",
+    )
+    .unwrap();
+
+    assert_eq!(task.due_date, None);
+    assert!(task.start_date.is_some());
+    assert_eq!(task.priority, Some(5));
+    assert_eq!(task.tags, Some(vec!["travel".to_string()]));
+    let items = task.items.unwrap();
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[0].id.as_deref(), Some("item-2"));
+    assert_eq!(items[0].status, Some(TaskStatus::Completed));
+    assert_eq!(items[1].id, None);
+    assert_eq!(items[1].title.as_deref(), Some("Snacks"));
+}
+
+#[test]
+fn parse_errors_are_embedded_as_comments_without_stacking() {
+    let text = "title = \"Plan trip\"\npriority = \n";
+    let err = parse_document(text).unwrap_err();
+
+    let annotated = document_with_error(text, &err);
+    assert!(annotated.starts_with(ERROR_PREFIX));
+    assert!(annotated.ends_with(text));
+
+    let again = document_with_error(&annotated, "title cannot be empty");
+    assert_eq!(
+        again,
+        format!("{}title cannot be empty\n{}", ERROR_PREFIX, text)
+    );
+}
+
+#[test]
+fn emptied_document_cancels_the_edit() {
+    assert_eq!(parse_document(DOCUMENT_HEADER), Ok(None));
+    assert_eq!(parse_document("  \n"), Ok(None));
+}
+
+#[test]
+fn batch_input_skips_blank_and_comment_lines() {
+    let input = "Buy milk\n\n# groceries below\n  Call mom tomorrow !high  \n#done\n";
+    assert_eq!(
+        batch_input_lines(input),
+        vec![(1, "Buy milk"), (4, "Call mom tomorrow !high")]
+    );
+}
+
+#[test]
+fn batch_lines_parse_shorthand_independently() {
+    let args = TaskAddArgsCli::try_parse_from(["tt", "--batch", "--list", "Errands"])
+        .unwrap()
+        .args;
+    let today = ymd(2026, 3, 10);
+
+    let mut first = args.clone();
+    let title = apply_task_add_input(&mut first, "Pay rent tomorrow !high #bills", today).unwrap();
+    assert_eq!(title, "Pay rent");
+    assert_eq!(first.priority, Some(5));
+    assert_eq!(first.tags, vec!["bills".to_string()]);
+    assert!(first.due_date.is_some());
+
+    let mut second = args.clone();
+    let title = apply_task_add_input(&mut second, "Water plants", today).unwrap();
+    assert_eq!(title, "Water plants");
+    assert_eq!(second.priority, None);
+    assert!(second.tags.is_empty());
+    assert!(second.due_date.is_none());
+    assert_eq!(second.list.as_deref(), Some("Errands"));
+}
+
+#[test]
+fn literal_add_keeps_the_whole_title_and_explicit_flags() {
+    let today = ymd(2026, 3, 10);
+    let input = "May update every monday !high #ops";
+
+    let mut inferred = TaskAddArgsCli::try_parse_from(["tt"]).unwrap().args;
+    assert_ne!(
+        apply_task_add_input(&mut inferred, input, today).unwrap(),
+        input
+    );
+
+    let mut args = TaskAddArgsCli::try_parse_from([
+        "tt",
+        "--no-infer-date",
+        "--due-date",
+        "2026-05-01",
+        "--repeat",
+        "daily",
+    ])
+    .unwrap()
+    .args;
+    let title = apply_task_add_input(&mut args, &format!("  {}  ", input), today).unwrap();
+    assert_eq!(title, input);
+    assert_eq!(args.priority, None);
+    assert!(args.tags.is_empty());
+    assert!(args.due_date.as_deref().unwrap().starts_with("2026-05-01"));
+    assert_eq!(args.repeat_flag.as_deref(), Some("RRULE:FREQ=DAILY"));
+    assert!(TaskAddArgsCli::try_parse_from(["tt", "--literal", "May update"]).is_ok());
+}
+
+#[test]
+fn batch_conflicts_with_title_arguments() {
+    assert!(TaskAddArgsCli::try_parse_from(["tt", "--batch", "Buy milk"]).is_err());
+}
+
+fn picker_candidate(task_id: &str, label: &str) -> PickerCandidate {
+    PickerCandidate {
+        task_id: task_id.to_string(),
+        project_id: "project-1".to_string(),
+        label: label.to_string(),
+    }
+}
+
+#[test]
+fn fuzzy_score_matches_subsequences_and_prefers_word_starts() {
+    assert!(fuzzy_score("wkrv", "Weekly review (Work)").is_some());
+    assert!(fuzzy_score("xyz", "Weekly review (Work)").is_none());
+    assert_eq!(fuzzy_score("", "anything"), Some(0));
+    assert!(
+        fuzzy_score("rev", "Weekly review").unwrap() > fuzzy_score("rev", "Prepare Evan").unwrap()
+    );
+}
+
+#[test]
+fn filter_candidates_ranks_best_matches_first() {
+    let candidates = vec![
+        picker_candidate("a", "Prepare release notes (Work)"),
+        picker_candidate("b", "Buy milk (Errands)"),
+        picker_candidate("c", "Release party (Personal)"),
+    ];
+
+    let ids: Vec<&str> = filter_candidates(&candidates, "release")
+        .iter()
+        .map(|candidate| candidate.task_id.as_str())
+        .collect();
+    assert_eq!(ids, vec!["c", "a"]);
+    assert_eq!(filter_candidates(&candidates, "").len(), 3);
+}
+
+#[test]
+fn picker_candidates_list_open_tasks_with_their_list() {
+    let mut open = make_task(None, None, None, None);
+    open.id = Some("task-1".to_string());
+    open.project_id = Some("project-1".to_string());
+    let mut inbox = open.clone();
+    inbox.id = Some("task-2".to_string());
+    inbox.project_id = Some("inbox42".to_string());
+    let mut done = open.clone();
+    done.id = Some("task-3".to_string());
+    done.status = Some(TaskStatus::Completed);
+
+    let names = HashMap::from([("project-1".to_string(), "Work".to_string())]);
+    assert_eq!(
+        picker_candidates(&[open, inbox, done], &names),
+        vec![
+            picker_candidate("task-1", "sample (Work)"),
+            PickerCandidate {
+                project_id: "inbox42".to_string(),
+                ..picker_candidate("task-2", "sample (Inbox)")
+            },
+        ]
+    );
+}
+
+#[test]
+fn task_id_is_optional_for_the_picker() {
+    let parsed = TaskUpdateArgsCli::try_parse_from(["tt", "--title", "Renamed"])
+        .unwrap()
+        .args;
+    assert!(parsed.task_id.is_none());
+}
+
+fn template_add_args(argv: &[&str]) -> TaskAddArgs {
+    let mut full = vec!["tt"];
+    full.extend_from_slice(argv);
+    TaskAddArgsCli::try_parse_from(full).unwrap().args
+}
+
+#[test]
+fn note_files_fill_content_and_desc() {
+    let dir = std::env::temp_dir().join(format!("ticktick-cli-note-test-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let content_path = dir.join("notes.md");
+    std::fs::write(&content_path, "# Agenda\n\n- budget\n- hiring\n\n").unwrap();
+    let desc_path = dir.join("desc.txt");
+    std::fs::write(&desc_path, "short\r\n").unwrap();
+
+    let (mut content, mut desc) = (None, Some("kept".to_string()));
+    read_task_note_files(&mut content, Some(&content_path), &mut desc, None).unwrap();
+    assert_eq!(content.as_deref(), Some("# Agenda\n\n- budget\n- hiring"));
+    assert_eq!(desc.as_deref(), Some("kept"));
+
+    read_task_note_files(&mut content, None, &mut desc, Some(&desc_path)).unwrap();
+    assert_eq!(desc.as_deref(), Some("short"));
+
+    let missing = dir.join("missing.md");
+    let err = read_task_note_files(&mut content, Some(&missing), &mut desc, None).unwrap_err();
+    assert!(err.to_string().starts_with("Failed to read "));
+
+    let stdin = std::path::Path::new("-");
+    let err = read_task_note_files(&mut content, Some(stdin), &mut desc, Some(stdin)).unwrap_err();
+    assert!(err.to_string().contains("cannot both read stdin"));
+    assert!(note_file_reads_stdin(None, Some(stdin)));
+    assert!(!note_file_reads_stdin(Some(&content_path), None));
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn note_file_flags_conflict_with_their_inline_forms() {
+    let parsed = TaskAddArgsCli::try_parse_from(["tt", "Plan", "--content-file", "notes.md"])
+        .unwrap()
+        .args;
+    assert_eq!(
+        parsed.content_file.as_deref(),
+        Some(std::path::Path::new("notes.md"))
+    );
+    for argv in [
+        vec!["tt", "Plan", "--content", "x", "--content-file", "notes.md"],
+        vec!["tt", "Plan", "--desc", "x", "--desc-file", "notes.md"],
+        vec!["tt", "--stdin", "--content-file", "-"],
+        vec!["tt", "--batch", "--desc-file", "-"],
+    ] {
+        assert_eq!(
+            TaskAddArgsCli::try_parse_from(argv).err().unwrap().kind(),
+            clap::error::ErrorKind::ArgumentConflict
+        );
+    }
+
+    assert!(TaskUpdateArgsCli::try_parse_from(["tt", "task-1", "--desc-file", "-"]).is_ok());
+    for argv in [
+        vec![
+            "tt",
+            "task-1",
+            "--content",
+            "x",
+            "--content-file",
+            "notes.md",
+        ],
+        vec![
+            "tt",
+            "task-1",
+            "--clear-content",
+            "--content-file",
+            "notes.md",
+        ],
+        vec!["tt", "task-1", "--clear-desc", "--desc-file", "notes.md"],
+    ] {
+        assert!(TaskUpdateArgsCli::try_parse_from(argv).is_err());
+    }
+}
+
+#[test]
+fn template_from_task_drops_instance_fields_and_reopens_items() {
+    let mut task = make_task(
+        Some("2026-03-13T17:00:00+0000"),
+        Some("2026-03-13T17:00:00+0000"),
+        Some(vec!["review"]),
+        Some(3),
+    );
+    task.id = Some("task-1".to_string());
+    task.project_id = Some("project-1".to_string());
+    task.status = Some(TaskStatus::Completed);
+    task.items = Some(vec![ChecklistItem {
+        id: Some("item-1".to_string()),
+        title: Some("Inbox zero".to_string()),
+        status: Some(TaskStatus::Completed),
+        completed_time: Some("2026-03-13T18:00:00+0000".to_string()),
+        ..Default::default()
+    }]);
+
+    let template = template_from_task(&task).unwrap();
+    for key in ["id", "status", "dueDate", "startDate"] {
+        assert!(!template.contains_key(key), "{} kept", key);
+    }
+    assert_eq!(template["projectId"], "project-1");
+    assert_eq!(template["priority"], 3);
+    assert_eq!(template["items"][0]["title"], "Inbox zero");
+    assert!(template["items"][0].get("id").is_none());
+    assert_eq!(
+        template["items"][0]["status"],
+        serde_json::to_value(TaskStatus::Normal).unwrap()
+    );
+}
+
+#[test]
+fn template_dates_are_relative_to_the_day_it_is_used() {
+    let today = ymd(2026, 3, 10);
+    let (due, all_day) = resolve_template_date("tomorrow", today).unwrap();
+    assert_eq!(Some(due), format_ticktick_due_date(ymd(2026, 3, 11)));
+    assert!(all_day);
+    assert!(!resolve_template_date("tomorrow at 9", today).unwrap().1);
+    assert!(resolve_template_date("someday soon", today).is_err());
+}
+
+#[test]
+fn task_from_template_merges_command_line_overrides() {
+    let template = serde_json::json!({
+        "title": "Weekly review",
+        "tags": ["review"],
+        "priority": 3,
+        "items": [{ "title": "Inbox zero", "status": 0 }],
+        "kind": "CHECKLIST",
+        "due": "tomorrow",
+        "list": "Work",
+        "futureField": { "kept": true },
+    });
+    let today = ymd(2026, 3, 10);
+    let mut args = template_add_args(&["--item", "Plan next week", "--priority", "high"]);
+    let title = apply_task_add_input(&mut args, "Quarterly review #planning", today).unwrap();
+
+    let (task, list) = task_value_from_template(&template, &args, &title, today).unwrap();
+    assert_eq!(list.as_deref(), Some("Work"));
+    assert_eq!(task["title"], "Quarterly review");
+    assert_eq!(task["priority"], 5);
+    assert_eq!(task["tags"], serde_json::json!(["review", "planning"]));
+    assert_eq!(task["items"].as_array().unwrap().len(), 2);
+    assert_eq!(task["futureField"]["kept"], true);
+    let expected_due = format_ticktick_due_date(ymd(2026, 3, 11)).unwrap();
+    assert_eq!(task["dueDate"], expected_due.as_str());
+    assert_eq!(task["startDate"], expected_due.as_str());
+    assert_eq!(task["isAllDay"], true);
+    assert!(!task.contains_key("due") && !task.contains_key("list"));
+}
+
+#[test]
+fn task_from_template_keeps_the_template_title_without_an_override() {
+    let template = serde_json::json!({ "title": "Weekly review" });
+    let args = template_add_args(&["--template", "weekly"]);
+    let (task, _) = task_value_from_template(&template, &args, "", ymd(2026, 3, 10)).unwrap();
+    assert_eq!(task["title"], "Weekly review");
+    assert!(!task.contains_key("dueDate"));
+}
+
+#[test]
+fn task_stats_count_by_list_priority_and_due_bucket() {
+    let today = ymd(2026, 3, 10);
+    let mut overdue = make_task(Some("2026-03-08T12:00:00+0000"), None, None, Some(5));
+    overdue.project_id = Some("project-1".to_string());
+    let mut tomorrow = make_task(Some("2026-03-11T12:00:00+0000"), None, None, Some(3));
+    tomorrow.project_id = Some("project-1".to_string());
+    let mut undated = make_task(None, None, None, None);
+    undated.project_id = Some("inbox42".to_string());
+    let names = HashMap::from([("project-1".to_string(), "Work".to_string())]);
+
+    let stats = task_stats(&[overdue, tomorrow, undated], &names, today);
+    assert_eq!(stats.total, 3);
+    assert_eq!(
+        stats.by_list,
+        BTreeMap::from([("Inbox".to_string(), 1), ("Work".to_string(), 2)])
+    );
+    assert_eq!(
+        stats.by_priority,
+        PriorityCounts {
+            none: 1,
+            low: 0,
+            medium: 1,
+            high: 1
+        }
+    );
+    assert_eq!(
+        stats.by_due,
+        DueCounts {
+            overdue: 1,
+            today: 0,
+            this_week: 1,
+            later: 0,
+            none: 1
+        }
+    );
+}
+
+#[test]
+fn task_stats_with_no_matches_report_zeros() {
+    let stats = task_stats(&[], &HashMap::new(), ymd(2026, 3, 10));
+    let human = format_task_stats(&stats, MessageFormat::Human, &OutputContext::default()).unwrap();
+    assert!(human.starts_with("Tasks: 0\n"));
+    assert!(human.contains("  (none)\n"));
+    assert!(!human.contains("No items found"));
+
+    let json: Value = serde_json::from_str(
+        &format_task_stats(&stats, MessageFormat::Json, &OutputContext::default()).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(json["total"], 0);
+    assert_eq!(json["byDue"]["thisWeek"], 0);
+    assert_eq!(json["byPriority"]["high"], 0);
+}
+
+#[test]
+fn project_stats_summarize_open_tasks_for_a_weekly_review() {
+    let tz = FixedOffset::west_opt(5 * 3600).unwrap();
+    let today = ymd(2026, 10, 14);
+    let task = |title: &str, due: Option<&str>, created: &str, priority: i32| Task {
+        title: title.to_string(),
+        due_date: due.map(str::to_string),
+        created_time: Some(created.to_string()),
+        priority: Some(priority),
+        ..Default::default()
+    };
+    let mut overdue = task(
+        "File taxes",
+        Some("2026-10-10T12:00:00+0000"),
+        "2026-09-01T12:00:00.000+0000",
+        5,
+    );
+    overdue.tags = Some(vec!["Admin".to_string(), "money".to_string()]);
+    let mut soon = task(
+        "Book dentist",
+        Some("2026-10-16T12:00:00+0000"),
+        "2026-10-01T12:00:00.000+0000",
+        1,
+    );
+    soon.tags = Some(vec!["admin".to_string()]);
+    soon.items = Some(vec![
+        ChecklistItem {
+            status: Some(TaskStatus::Completed),
+            ..Default::default()
+        },
+        ChecklistItem::default(),
+        ChecklistItem::default(),
+    ]);
+    let later = task(
+        "Plan trip",
+        Some("2026-11-20T12:00:00+0000"),
+        "2026-10-12T12:00:00.000+0000",
+        0,
+    );
+    let done = [Task::default(), Task::default()];
+
+    let stats = summarize_project("Errands", &[overdue, later, soon], Some(&done), today, &tz);
+    let human =
+        format_project_stats(&stats, MessageFormat::Human, &OutputContext::default()).unwrap();
+    assert!(human.starts_with(
+        "Errands\n  Open       3\n  Completed  2\n  Overdue    1\n  Next due   Book dentist (2026-10-16)\n  Oldest     File taxes (2026-09-01)\n  Checklist  1/3 items done (33%)\n"
+    ));
+    assert!(human.contains("  admin  2\n"));
+
+    let json: Value = serde_json::from_str(
+        &format_project_stats(&stats, MessageFormat::Json, &OutputContext::default()).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(json["byPriority"]["high"], 1);
+    assert_eq!(json["byTag"]["money"], 1);
+    assert_eq!(json["nextDue"]["title"], "Book dentist");
+    assert_eq!(json["checklist"]["percent"], 33);
+}
+
+#[test]
+fn project_stats_for_an_empty_list_render_without_gaps() {
+    let stats = summarize_project("Empty", &[], None, ymd(2026, 10, 14), &Utc);
+    let human =
+        format_project_stats(&stats, MessageFormat::Human, &OutputContext::default()).unwrap();
+    assert!(human.contains("  Completed  unavailable\n"));
+    assert!(human.contains("  Next due   -\n"));
+    assert!(!human.contains("Checklist"));
+    assert!(human.contains("By tag:\n  (none)\n"));
+
+    let json: Value = serde_json::from_str(
+        &format_project_stats(&stats, MessageFormat::Json, &OutputContext::default()).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(json["open"], 0);
+    assert!(json["completed"].is_null());
+    assert!(json["checklist"].is_null());
+}
+
+fn sample_snapshot() -> SyncSnapshot {
+    let project = |id: &str, name: &str, closed: bool| Project {
+        id: Some(id.to_string()),
+        name: name.to_string(),
+        closed: Some(closed),
+        ..Default::default()
+    };
+    let task = |id: &str, project_id: &str| Task {
+        id: Some(id.to_string()),
+        project_id: Some(project_id.to_string()),
+        title: format!("task {}", id),
+        ..Default::default()
+    };
+    SyncSnapshot {
+        synced_at: 1_000,
+        projects: vec![
+            project("work", "Work", false),
+            project("archive", "Archive", true),
+        ],
+        tasks: vec![
+            task("t1", "work"),
+            task("t2", "archive"),
+            task("t3", "inbox123"),
+        ],
+    }
+}
+
+fn task_ids(tasks: &[Task]) -> Vec<&str> {
+    tasks.iter().filter_map(|task| task.id.as_deref()).collect()
+}
+
+#[test]
+fn snapshot_scopes_tasks_by_project_inbox_or_open_lists() {
+    let snapshot = sample_snapshot();
+    assert_eq!(
+        task_ids(&snapshot_tasks_in_scope(&snapshot, Some("work"))),
+        ["t1"]
+    );
+    assert_eq!(
+        task_ids(&snapshot_tasks_in_scope(&snapshot, Some(""))),
+        ["t3"]
+    );
+    assert_eq!(
+        task_ids(&snapshot_tasks_in_scope(&snapshot, None)),
+        ["t1", "t3"]
+    );
+}
+
+#[test]
+fn snapshot_resolves_lists_and_tasks_by_name_and_id() {
+    let snapshot = sample_snapshot();
+    assert_eq!(snapshot_list_project_id(&snapshot, "work").unwrap(), "work");
+    assert!(snapshot_list_project_id(&snapshot, "Errands")
+        .unwrap_err()
+        .to_string()
+        .contains("List not found in offline data: Errands"));
+
+    assert_eq!(snapshot_task(&snapshot, "t2").unwrap().title, "task t2");
+    assert!(snapshot_task(&snapshot, "missing")
+        .unwrap_err()
+        .to_string()
+        .contains("Run 'tt sync'"));
+}
+
+#[test]
+fn snapshot_age_notice_warns_once_stale() {
+    let snapshot = sample_snapshot();
+    assert_eq!(
+        snapshot_age_notice(&snapshot, 1_000 + 2 * 60 * 60),
+        "Using offline data synced 2h ago."
+    );
+    assert_eq!(
+        snapshot_age_notice(&snapshot, 1_000 + SNAPSHOT_STALE_AFTER_SECS + 60),
+        "Warning: offline data was synced 1d ago; run 'tt sync' to refresh it."
+    );
+}
+
+#[test]
+fn watch_interval_defaults_to_a_minute_and_needs_equals_for_a_value() {
+    let default = TaskListArgsCli::try_parse_from(["tt", "--watch", "today"]).unwrap();
+    assert_eq!(default.args.watch, Some(std::time::Duration::from_secs(60)));
+    assert_eq!(default.args.query, vec!["today".to_string()]);
+
+    let custom = TaskListArgsCli::try_parse_from(["tt", "--watch=5m"]).unwrap();
+    assert_eq!(custom.args.watch, Some(std::time::Duration::from_secs(300)));
+
+    let none = TaskListArgsCli::try_parse_from(["tt"]).unwrap();
+    assert_eq!(none.args.watch, None);
+}
+
+#[test]
+fn parses_watch_intervals_in_seconds_minutes_and_hours() {
+    assert_eq!(parse_watch_interval("45").unwrap().as_secs(), 45);
+    assert_eq!(parse_watch_interval("30s").unwrap().as_secs(), 30);
+    assert_eq!(parse_watch_interval("2m").unwrap().as_secs(), 120);
+    assert_eq!(parse_watch_interval("1h").unwrap().as_secs(), 3600);
+    assert!(parse_watch_interval("0").is_err());
+    assert!(parse_watch_interval("soon").is_err());
+}
+
+#[test]
+fn watch_diff_reports_new_and_vanished_tasks_by_title() {
+    let task = |id: &str, title: &str| Task {
+        id: Some(id.to_string()),
+        title: title.to_string(),
+        ..Default::default()
+    };
+    let previous = vec![task("a", "Keep"), task("b", "Finish report")];
+    let current = vec![task("a", "Keep"), task("c", "Call Sam")];
+
+    let diff = diff_task_lists(&previous, &current);
+    assert_eq!(diff.added, vec!["Call Sam".to_string()]);
+    assert_eq!(diff.removed, vec!["Finish report".to_string()]);
+
+    let footer = render_watch_footer(&diff, "09:30", std::time::Duration::from_secs(60), true);
+    assert!(footer.contains("\x1b[32m+ Call Sam\x1b[0m"));
+    assert!(footer.contains("- Finish report (done or moved)"));
+    assert!(footer.contains("Last updated 09:30 · every 1m · Ctrl-C to quit"));
+
+    let quiet = render_watch_footer(
+        &WatchDiff::default(),
+        "09:31",
+        std::time::Duration::from_secs(30),
+        false,
+    );
+    assert_eq!(quiet.lines().filter(|line| !line.is_empty()).count(), 1);
+    assert!(!quiet.contains('\x1b'));
+}
+
+#[test]
+fn duplicate_due_moves_a_matching_start_and_drops_a_range_start() {
+    let mut single = make_task(
+        Some("2026-03-01T00:00:00.000+0000"),
+        Some("2026-03-01T00:00:00.000+0000"),
+        None,
+        None,
+    );
+    apply_duplicate_due(
+        &mut single,
+        "2026-03-08T00:00:00.000+0000".to_string(),
+        true,
+    );
+    assert_eq!(
+        single.due_date.as_deref(),
+        Some("2026-03-08T00:00:00.000+0000")
+    );
+    assert_eq!(
+        single.start_date.as_deref(),
+        Some("2026-03-08T00:00:00.000+0000")
+    );
+    assert_eq!(single.is_all_day, Some(true));
+
+    let mut range = make_task(
+        Some("2026-03-05T00:00:00.000+0000"),
+        Some("2026-03-01T00:00:00.000+0000"),
+        None,
+        None,
+    );
+    apply_duplicate_due(&mut range, "2026-02-20T00:00:00.000+0000".to_string(), true);
+    assert_eq!(range.start_date, None);
+
+    let mut undated = make_task(None, None, None, None);
+    apply_duplicate_due(
+        &mut undated,
+        "2026-03-08T00:00:00.000+0000".to_string(),
+        true,
+    );
+    assert_eq!(undated.start_date, None);
+}
+
+#[test]
+fn parse_duplicate_due_accepts_dates_datetimes_and_phrases() {
+    let (date, all_day) = parse_duplicate_due("2026-03-08").unwrap();
+    assert_eq!(Some(date), format_ticktick_due_date(ymd(2026, 3, 8)));
+    assert!(all_day);
+
+    let (_, all_day) = parse_duplicate_due("2026-03-08T09:30").unwrap();
+    assert!(!all_day);
+
+    assert!(parse_duplicate_due("next friday").unwrap().1);
+    assert!(parse_duplicate_due("whenever").is_err());
+}
+
+#[test]
+fn defer_specs_parse_offsets_tomorrow_and_weekdays() {
+    assert_eq!(parse_defer_spec("1d"), Ok(DeferSpec::Days(1)));
+    assert_eq!(parse_defer_spec("1w"), Ok(DeferSpec::Days(7)));
+    assert_eq!(parse_defer_spec("tomorrow"), Ok(DeferSpec::Days(1)));
+    assert_eq!(parse_defer_spec("next week"), Ok(DeferSpec::Days(7)));
+    assert_eq!(
+        parse_defer_spec("Friday"),
+        Ok(DeferSpec::Weekday(chrono::Weekday::Fri))
+    );
+    assert_eq!(
+        parse_defer_spec("next mon"),
+        Ok(DeferSpec::Weekday(chrono::Weekday::Mon))
+    );
+
+    assert!(parse_defer_spec("3h")
+        .unwrap_err()
+        .contains("tt task postpone"));
+    assert!(parse_defer_spec("someday")
+        .unwrap_err()
+        .contains("Invalid defer spec"));
+}
+
+#[test]
+fn defer_targets_add_days_and_weeks_and_find_the_next_weekday() {
+    // 2026-03-06 is a Friday.
+    let friday = ymd(2026, 3, 6);
+    assert_eq!(defer_target(friday, DeferSpec::Days(1)), ymd(2026, 3, 7));
+    assert_eq!(defer_target(friday, DeferSpec::Days(14)), ymd(2026, 3, 20));
+    assert_eq!(
+        defer_target(friday, DeferSpec::Weekday(chrono::Weekday::Fri)),
+        ymd(2026, 3, 13)
+    );
+    assert_eq!(
+        defer_target(friday, DeferSpec::Weekday(chrono::Weekday::Mon)),
+        ymd(2026, 3, 9)
+    );
+}
+
+#[test]
+fn defer_counts_from_the_due_date_and_keeps_the_time_of_day() {
+    // 10am EST on Friday Feb 27; "friday" means the next one, Mar 6, still at 10am.
+    let mut task = make_task(
+        Some("2026-02-27T15:00:00.000+0000"),
+        Some("2026-02-27T15:00:00.000+0000"),
+        None,
+        None,
+    );
+    task.is_all_day = Some(false);
+    let before = describe_task_due(&task, &Eastern2026);
+
+    defer_task(
+        &mut task,
+        DeferSpec::Weekday(chrono::Weekday::Fri),
+        ymd(2026, 2, 10),
+        &Eastern2026,
+    )
+    .unwrap();
+    assert_eq!(
+        task.due_date.as_deref(),
+        Some("2026-03-06T15:00:00.000+0000")
+    );
+    assert_eq!(task.start_date, task.due_date);
+    assert_eq!(before, "2026-02-27 10:00");
+    assert_eq!(describe_task_due(&task, &Eastern2026), "2026-03-06 10:00");
+}
+
+#[test]
+fn defer_without_a_due_date_counts_from_today() {
+    let mut task = make_task(None, None, None, None);
+    assert_eq!(describe_task_due(&task, &Eastern2026), "no due date");
+
+    defer_task(&mut task, DeferSpec::Days(7), ymd(2026, 3, 3), &Eastern2026).unwrap();
+    assert_eq!(
+        task.due_date.as_deref(),
+        Some("2026-03-10T04:00:00.000+0000")
+    );
+    assert_eq!(task.is_all_day, Some(true));
+    assert_eq!(describe_task_due(&task, &Eastern2026), "2026-03-10");
+}
+
+#[test]
+fn tag_changes_keep_tags_that_are_not_mentioned() {
+    let mut task = make_task(None, None, Some(vec!["Work", "errands", "Later"]), None);
+    apply_tag_changes(
+        &mut task,
+        vec!["work".to_string(), "waiting".to_string()],
+        &["LATER".to_string()],
+    );
+    assert_eq!(
+        task.tags,
+        Some(vec![
+            "Work".to_string(),
+            "errands".to_string(),
+            "waiting".to_string()
+        ])
+    );
+
+    let mut untagged = make_task(None, None, None, None);
+    apply_tag_changes(
+        &mut untagged,
+        vec!["new".to_string()],
+        &["absent".to_string()],
+    );
+    assert_eq!(untagged.tags, Some(vec!["new".to_string()]));
+}
+
+#[test]
+fn parses_tag_change_tokens() {
+    assert_eq!(
+        parse_tag_change("+#foo"),
+        Ok(TagChange::Add("foo".to_string()))
+    );
+    assert_eq!(
+        parse_tag_change("#foo"),
+        Ok(TagChange::Add("foo".to_string()))
+    );
+    assert_eq!(
+        parse_tag_change("-#bar"),
+        Ok(TagChange::Remove("bar".to_string()))
+    );
+    assert_eq!(
+        parse_tag_change("-bar"),
+        Ok(TagChange::Remove("bar".to_string()))
+    );
+    assert!(parse_tag_change("+#").is_err());
+    assert!(parse_tag_change("two words").is_err());
+}
+
+#[test]
+fn tag_flags_parse_on_update_and_task_tag_accepts_removals() {
+    let parsed = TaskUpdateArgsCli::try_parse_from([
+        "tt",
+        "task-1",
+        "--add-tag",
+        "a",
+        "--add-tag",
+        "b",
+        "--remove-tag",
+        "c",
+    ])
+    .unwrap()
+    .args;
+    assert_eq!(parsed.add_tag, vec!["a", "b"]);
+    assert_eq!(parsed.remove_tag, vec!["c"]);
+    assert!(
+        TaskUpdateArgsCli::try_parse_from(["tt", "task-1", "--tags", "x", "--add-tag", "y"])
+            .is_err()
+    );
+
+    #[derive(Parser)]
+    struct TaskTagArgsCli {
+        #[command(flatten)]
+        args: TaskTagArgs,
+    }
+    let parsed = TaskTagArgsCli::try_parse_from(["tt", "task-1", "+#foo", "-#bar"])
+        .unwrap()
+        .args;
+    assert_eq!(
+        parsed.changes,
+        vec![
+            TagChange::Add("foo".to_string()),
+            TagChange::Remove("bar".to_string())
+        ]
+    );
+    assert!(TaskTagArgsCli::try_parse_from(["tt", "task-1"]).is_err());
+}
+
+#[test]
+fn task_format_renders_fields_like_the_table() {
+    let format =
+        parse_task_format("{due} [{priority}] {title} ({list}) {tags} {{id={id}}}").unwrap();
+    let mut task = make_task(
+        Some("2026-03-01T14:00:00.000+0000"),
+        None,
+        Some(vec!["work", "q1"]),
+        Some(3),
+    );
+    task.id = Some("task-1".to_string());
+    task.project_id = Some("project-1".to_string());
+    let names = HashMap::from([("project-1".to_string(), "Work".to_string())]);
+
+    assert!(format.uses_list());
+    assert_eq!(
+        render_formatted_tasks(&[task], &format, &names),
+        "2026-03-01 [Medium] sample (Work) #work #q1 {id=task-1}\n"
+    );
+
+    let bare = make_task(None, None, None, None);
+    assert_eq!(
+        parse_task_format("{title}|{due}|{priority}|{list}")
+            .unwrap()
+            .render(&bare, &HashMap::new()),
+        "sample|||Inbox"
+    );
+}
+
+#[test]
+fn task_format_rejects_unknown_placeholders_and_stray_braces() {
+    let err = parse_task_format("{title} {owner}").unwrap_err();
+    assert!(err.contains("Unknown placeholder '{owner}'"), "{}", err);
+    assert!(
+        err.contains("{id}, {title}, {due}, {priority}, {list}"),
+        "{}",
+        err
+    );
+
+    assert!(parse_task_format("{title")
+        .unwrap_err()
+        .contains("Unclosed"));
+    assert!(parse_task_format("title}")
+        .unwrap_err()
+        .contains("Unmatched"));
+    assert!(!parse_task_format("{TITLE}").unwrap().uses_list());
+
+    let err = TaskListArgsCli::try_parse_from(["tt", "--format", "{nope}"])
+        .err()
+        .unwrap()
+        .to_string();
+    assert!(err.contains("Valid fields"), "{}", err);
+    assert!(
+        TaskListArgsCli::try_parse_from(["tt", "--format", "{title}", "--group-by", "tag"])
+            .is_err()
+    );
+}
+
+fn agenda_task(title: &str, due: &str, all_day: bool, priority: i32) -> Task {
+    Task {
+        id: Some(title.to_lowercase()),
+        title: title.to_string(),
+        due_date: Some(due.to_string()),
+        is_all_day: Some(all_day),
+        priority: Some(priority),
+        project_id: Some("project-1".to_string()),
+        ..Default::default()
+    }
+}
+
+fn agenda_sample() -> Vec<Task> {
+    vec![
+        agenda_task("Standup", "2026-10-14T13:30:00.000+0000", false, 0),
+        // 01:00 UTC on the 15th is still the evening of the 14th in UTC-5.
+        agenda_task("Late call", "2026-10-15T01:00:00.000+0000", false, 3),
+        agenda_task("Rent", "2026-10-12T05:00:00.000+0000", true, 5),
+        agenda_task("Groceries", "2026-10-14T05:00:00.000+0000", true, 1),
+        agenda_task("Dentist", "2026-10-16T14:00:00.000+0000", false, 0),
+        agenda_task("Far off", "2026-11-01T05:00:00.000+0000", true, 0),
+    ]
+}
+
+fn group_titles(groups: &[crate::output::TaskGroup]) -> Vec<(String, Vec<String>)> {
+    groups
+        .iter()
+        .map(|group| {
+            (
+                group.key.clone(),
+                group.tasks.iter().map(|task| task.title.clone()).collect(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn today_groups_overdue_then_today_by_local_date() {
+    let tz = FixedOffset::west_opt(5 * 3600).unwrap();
+    let groups = today_groups(agenda_sample(), ymd(2026, 10, 14), &tz);
+
+    assert_eq!(
+        group_titles(&groups),
+        vec![
+            ("Overdue".to_string(), vec!["Rent".to_string()]),
+            (
+                "Today".to_string(),
+                vec![
+                    "Groceries".to_string(),
+                    "Standup".to_string(),
+                    "Late call".to_string()
+                ]
+            ),
+        ]
+    );
+    assert!(today_groups(Vec::new(), ymd(2026, 10, 14), &tz).is_empty());
+}
+
+#[test]
+fn upcoming_groups_by_day_within_the_window() {
+    let tz = FixedOffset::west_opt(5 * 3600).unwrap();
+    let groups = upcoming_groups(agenda_sample(), ymd(2026, 10, 14), 3, &tz);
+
+    assert_eq!(
+        group_titles(&groups),
+        vec![
+            (
+                "Today · Wed Oct 14".to_string(),
+                vec![
+                    "Groceries".to_string(),
+                    "Standup".to_string(),
+                    "Late call".to_string()
+                ]
+            ),
+            ("Fri Oct 16".to_string(), vec!["Dentist".to_string()]),
+        ]
+    );
+    assert_eq!(
+        upcoming_groups(agenda_sample(), ymd(2026, 10, 14), 30, &tz).len(),
+        3
+    );
+}
+
+#[test]
+fn agenda_lines_show_local_times_priority_and_list() {
+    let tz = FixedOffset::west_opt(5 * 3600).unwrap();
+    let today = ymd(2026, 10, 14);
+    let groups = today_groups(agenda_sample(), today, &tz);
+    let names = HashMap::from([("project-1".to_string(), "Work".to_string())]);
+
+    let rendered = render_agenda(
+        &groups,
+        "Nothing due today.",
+        OutputFormat::Human,
+        today,
+        Some(&names),
+        &tz,
+        &OutputContext::default(),
+    );
+    assert!(rendered.starts_with("Overdue (1)\n  Oct 12  Rent  !high  Work\n"));
+    assert!(rendered.contains("\n          Groceries  !low  Work\n"));
+    assert!(rendered.contains("  20:00   Late call  !medium  Work\n"));
+    assert!(!rendered.contains('\x1b'));
+
+    let colored = render_agenda(
+        &groups,
+        "",
+        OutputFormat::Human,
+        today,
+        None,
+        &tz,
+        &terminal(),
+    );
+    assert!(colored.contains("\x1b[1;31m!high\x1b[0m"));
+    assert!(!colored.contains("Work"));
+
+    assert_eq!(
+        render_agenda(
+            &[],
+            "Nothing due today.",
+            OutputFormat::Human,
+            today,
+            None,
+            &tz,
+            &OutputContext::default()
+        ),
+        "Nothing due today.\n"
+    );
+    let json: serde_json::Value = serde_json::from_str(&render_agenda(
+        &groups,
+        "",
+        OutputFormat::Json,
+        today,
+        None,
+        &tz,
+        &OutputContext::default(),
+    ))
+    .unwrap();
+    assert_eq!(json["groups"][1]["key"], "Today");
+}
+
+#[test]
+fn project_task_counts_count_open_tasks_and_those_due_before_today() {
+    let tz = FixedOffset::west_opt(5 * 3600).unwrap();
+    let today = ymd(2026, 10, 14);
+    let tasks = vec![
+        make_task(Some("2026-10-12T12:00:00+0000"), None, None, None),
+        // 02:00 UTC on the 14th is still the 13th five hours west.
+        make_task(Some("2026-10-14T02:00:00+0000"), None, None, None),
+        make_task(Some("2026-10-14T12:00:00+0000"), None, None, None),
+        make_task(None, Some("2026-10-01T12:00:00+0000"), None, None),
+        make_task(None, None, None, None),
+    ];
+
+    let counts = count_project_tasks(&tasks, today, &tz);
+    assert_eq!(counts.open, 5);
+    assert_eq!(counts.overdue, 2);
+}
+
+#[test]
+fn dedupe_tasks_by_id_keeps_the_first_copy_and_tasks_without_ids() {
+    let task = |id: Option<&str>, project_id: &str| Task {
+        id: id.map(str::to_string),
+        project_id: Some(project_id.to_string()),
+        title: "sample".to_string(),
+        ..Default::default()
+    };
+    let mut tasks = vec![
+        task(Some("task-1"), "project-1"),
+        task(Some("task-2"), "project-1"),
+        task(None, "project-1"),
+        task(Some("task-1"), "inbox123"),
+        task(None, "inbox123"),
+        task(Some("task-2"), "project-2"),
+    ];
+
+    dedupe_tasks_by_id(&mut tasks);
+
+    let survivors: Vec<(Option<&str>, Option<&str>)> = tasks
+        .iter()
+        .map(|task| (task.id.as_deref(), task.project_id.as_deref()))
+        .collect();
+    assert_eq!(
+        survivors,
+        vec![
+            (Some("task-1"), Some("project-1")),
+            (Some("task-2"), Some("project-1")),
+            (None, Some("project-1")),
+            (None, Some("inbox123")),
+        ]
+    );
+}
+
+#[test]
+fn parses_reminder_at_times_and_datetimes() {
+    for value in ["9am", "9:00", "at 9", "09:00"] {
+        assert!(parse_reminder_at(value).is_ok(), "{}", value);
+    }
+    assert_eq!(
+        parse_reminder_at("2026-10-14T08:00"),
+        parse_reminder_at("2026-10-14 8am")
+    );
+    assert_ne!(
+        parse_reminder_at("8am"),
+        parse_reminder_at("2026-10-14 8am")
+    );
+
+    for value in ["2026-10-14", "soon", "25:00", "9am tomorrow"] {
+        let err = parse_reminder_at(value).unwrap_err();
+        assert!(err.contains("Invalid reminder time"), "{}", err);
+    }
+}
+
+#[test]
+fn reminder_at_becomes_a_trigger_relative_to_the_due_date() {
+    let tz = FixedOffset::west_opt(5 * 3600).unwrap();
+    let all_day_due = "2026-10-14T05:00:00.000+0000";
+    let timed_due = "2026-10-14T19:00:00.000+0000";
+    let trigger = |at: &str, due: &str| {
+        reminder_at_trigger(parse_reminder_at(at).unwrap(), Some(due), &tz).unwrap()
+    };
+
+    assert_eq!(trigger("9am", all_day_due), "TRIGGER:P0DT9H0M0S");
+    assert_eq!(trigger("1:30pm", timed_due), "TRIGGER:-PT30M");
+    assert_eq!(trigger("14:00", timed_due), "TRIGGER:PT0S");
+    assert_eq!(trigger("2026-10-13T20:00", timed_due), "TRIGGER:-PT18H");
+    assert_eq!(trigger("2026-10-12 1pm", timed_due), "TRIGGER:-P2DT1H");
+    assert!(parse_reminder_value(&trigger("9am", all_day_due)).is_ok());
+}
+
+#[test]
+fn reminder_at_without_a_due_date_explains_what_is_missing() {
+    let tz = FixedOffset::west_opt(5 * 3600).unwrap();
+    let err = reminder_at_trigger(parse_reminder_at("9am").unwrap(), None, &tz).unwrap_err();
+    assert!(err.to_string().contains("needs a due date"), "{}", err);
+
+    let err =
+        reminder_at_trigger(parse_reminder_at("2026-10-14T09:00").unwrap(), None, &tz).unwrap_err();
+    assert!(err.to_string().contains("set --due-date"), "{}", err);
+
+    assert_eq!(
+        TaskUpdateArgsCli::try_parse_from([
+            "tt",
+            "task-1",
+            "--reminder-at",
+            "9am",
+            "--clear-reminders"
+        ])
+        .unwrap_err()
+        .kind(),
+        clap::error::ErrorKind::ArgumentConflict
+    );
+}
+
+#[test]
+fn column_flag_parses_on_add_and_update_but_not_with_batch() {
+    let add = TaskAddArgsCli::try_parse_from(["tt", "Ship it", "--column", "Doing"]).unwrap();
+    assert_eq!(add.args.column.as_deref(), Some("Doing"));
+
+    let update = TaskUpdateArgsCli::try_parse_from(["tt", "task-1", "--column", "Done"]).unwrap();
+    assert_eq!(update.args.column.as_deref(), Some("Done"));
+
+    assert_eq!(
+        TaskAddArgsCli::try_parse_from(["tt", "--batch", "--column", "Doing"])
+            .err()
+            .unwrap()
+            .kind(),
+        clap::error::ErrorKind::ArgumentConflict
+    );
+}
+
+#[test]
+fn inbox_shorthand_and_flag_target_the_inbox_on_add() {
+    let today = NaiveDate::from_ymd_opt(2026, 3, 13).unwrap();
+    let mut args = template_add_args(&[]);
+    let title = apply_task_add_input(&mut args, "Call mom ~inbox", today).unwrap();
+    assert_eq!(title, "Call mom");
+    assert!(args.inbox);
+    assert_eq!(args.list, None);
+
+    let mut args = template_add_args(&["--list", "Work"]);
+    apply_task_add_input(&mut args, "Call mom ~inbox", today).unwrap();
+    assert!(!args.inbox);
+    assert_eq!(args.list.as_deref(), Some("Work"));
+
+    assert!(template_add_args(&["Call mom", "--inbox"]).inbox);
+    assert_eq!(
+        TaskAddArgsCli::try_parse_from(["tt", "--inbox", "--list", "Work"])
+            .err()
+            .unwrap()
+            .kind(),
+        clap::error::ErrorKind::ArgumentConflict
+    );
+}
+
+/// Serves canned JSON bodies by `METHOD /path` on localhost and reports each request
+/// (`METHOD /path` and body) on the returned channel.
+fn mock_api(
+    routes: Vec<(&'static str, Value)>,
+) -> (String, std::sync::mpsc::Receiver<(String, String)>) {
+    let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", server.server_addr().to_ip().unwrap());
+    let (sender, receiver) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        for mut request in server.incoming_requests() {
+            let route = format!("{} {}", request.method(), request.url());
+            let mut body = String::new();
+            let _ = request.as_reader().read_to_string(&mut body);
+            let response = match routes.iter().find(|(key, _)| *key == route) {
+                Some((_, value)) => tiny_http::Response::from_string(value.to_string()),
+                None => tiny_http::Response::from_string("not found").with_status_code(404),
+            };
+            let _ = sender.send((route, body));
+            let _ = request.respond(response);
+        }
+    });
+    (base_url, receiver)
+}
+
+fn mock_client(base_url: &str) -> TickTickClient {
+    TickTickClient::new(crate::config::Config {
+        access_token: "token".to_string(),
+        refresh_token: String::new(),
+        expires_at: i64::MAX,
+    })
+    .unwrap()
+    .with_base_url(base_url)
+}
+
+#[tokio::test]
+async fn inbox_tasks_are_created_with_the_inbox_project_id() {
+    let (base_url, requests) = mock_api(vec![
+        (
+            "GET /project",
+            serde_json::json!([{ "id": "p1", "name": "Work" }]),
+        ),
+        (
+            "GET /project/inbox/data",
+            serde_json::json!({ "project": { "id": "inbox42", "name": "Inbox" }, "tasks": [] }),
+        ),
+        (
+            "POST /task",
+            serde_json::json!({ "id": "t1", "title": "Call mom", "projectId": "inbox42" }),
+        ),
+    ]);
+    let client = mock_client(&base_url);
+
+    for (inbox, list) in [(true, None), (false, Some("inbox".to_string()))] {
+        let project_id = resolve_create_project_id(&client, None, None, inbox, None, list)
+            .await
+            .unwrap();
+        assert_eq!(project_id.as_deref(), Some("inbox42"));
+    }
+
+    let args = template_add_args(&["--inbox"]);
+    let task = task_from_add_args(args, "Call mom".to_string(), "inbox42").unwrap();
+    let created = client.create_task(&task).await.unwrap();
+    assert_eq!(created.project_id.as_deref(), Some("inbox42"));
+
+    let (route, body) = requests.try_iter().last().unwrap();
+    assert_eq!(route, "POST /task");
+    let sent: Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(sent["projectId"], "inbox42");
+}
+
+#[tokio::test]
+async fn parent_flag_finds_the_parents_list_and_sends_parent_id() {
+    let parent =
+        serde_json::json!({ "id": "parent1", "title": "Plan offsite", "projectId": "work" });
+    let (base_url, requests) = mock_api(vec![
+        (
+            "GET /project",
+            serde_json::json!([{ "id": "work", "name": "Work" }]),
+        ),
+        (
+            "GET /project/work/data",
+            serde_json::json!({ "project": { "id": "work", "name": "Work" }, "tasks": [parent] }),
+        ),
+        ("GET /project/work/task/parent1", parent.clone()),
+        (
+            "POST /task",
+            serde_json::json!({
+                "id": "child1",
+                "title": "Book venue",
+                "projectId": "work",
+                "parentId": "parent1"
+            }),
+        ),
+    ]);
+    let client = mock_client(&base_url);
+
+    let (found, project_id) = fetch_task_with_project(&client, None, "parent1", None, None)
+        .await
+        .unwrap();
+    assert_eq!(project_id, "work");
+    assert_eq!(
+        format_subtask_of(&found),
+        "Subtask of: Plan offsite (parent1)\n"
+    );
+
+    let args = template_add_args(&["--parent", "parent1"]);
+    let task = task_from_add_args(args, "Book venue".to_string(), &project_id).unwrap();
+    let created = client.create_task(&task).await.unwrap();
+    assert_eq!(created.parent_id.as_deref(), Some("parent1"));
+
+    let (route, body) = requests.try_iter().last().unwrap();
+    assert_eq!(route, "POST /task");
+    let sent: Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(sent["parentId"], "parent1");
+    assert_eq!(sent["projectId"], "work");
+
+    assert!(TaskAddArgsCli::try_parse_from(["tt", "--parent", "parent1", "--batch"]).is_err());
+}
+
+#[tokio::test]
+async fn inbox_project_id_comes_from_the_project_list_when_present() {
+    let (base_url, requests) = mock_api(vec![(
+        "GET /project",
+        serde_json::json!([
+            { "id": "p1", "name": "Work" },
+            { "id": "inbox7", "name": "Inbox", "kind": "INBOX" }
+        ]),
+    )]);
+    let client = mock_client(&base_url);
+
+    let project_id = resolve_create_project_id(&client, None, None, true, None, None)
+        .await
+        .unwrap();
+    assert_eq!(project_id.as_deref(), Some("inbox7"));
+    let routes: Vec<String> = requests.try_iter().map(|(route, _)| route).collect();
+    assert_eq!(routes, vec!["GET /project".to_string()]);
+}
+
+#[test]
+fn default_project_prefers_the_inbox_then_an_open_list() {
+    let project = |id: &str, name: &str, kind: Option<&str>, closed: bool| Project {
+        id: Some(id.to_string()),
+        name: name.to_string(),
+        kind: kind.map(str::to_string),
+        closed: Some(closed),
+        ..Default::default()
+    };
+
+    let projects = vec![
+        project("p1", "Work", None, false),
+        project("inbox1", "Inbox", Some("INBOX"), false),
+    ];
+    assert_eq!(pick_default_project_id(&projects).unwrap(), "inbox1");
+
+    let projects = vec![
+        project("old", "Old", None, true),
+        project("p2", "Home", None, false),
+    ];
+    assert_eq!(pick_default_project_id(&projects).unwrap(), "p2");
+    assert!(pick_default_project_id(&[]).is_err());
+}
+
+#[tokio::test]
+async fn default_project_ids_are_cached_in_the_config_file() {
+    let (base_url, requests) = mock_api(vec![(
+        "GET /project",
+        serde_json::json!([
+            { "id": "p1", "name": "Work" },
+            { "id": "inbox7", "name": "Inbox", "kind": "INBOX" }
+        ]),
+    )]);
+    let client = mock_client(&base_url);
+    let dir = std::env::temp_dir().join(format!(
+        "ticktick-cli-default-project-test-{}",
+        std::process::id()
+    ));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("config.toml");
+    std::fs::write(&path, "expires_at = 100\n").unwrap();
+    let defaults = crate::config::AppConfig::with_config_file(path);
+
+    for _ in 0..2 {
+        let project_id = infer_default_project_id(&client, None, Some(&defaults))
+            .await
+            .unwrap();
+        assert_eq!(project_id, "inbox7");
+    }
+    let inbox = resolve_create_project_id(&client, None, Some(&defaults), true, None, None)
+        .await
+        .unwrap();
+    assert_eq!(inbox.as_deref(), Some("inbox7"));
+    let routes: Vec<String> = requests.try_iter().map(|(route, _)| route).collect();
+    assert_eq!(routes, vec!["GET /project".to_string()]);
+
+    assert!(!forget_default_project_id(Some(&defaults), "p1"));
+    assert!(forget_default_project_id(Some(&defaults), "inbox7"));
+    assert_eq!(defaults.load_default_project_ids().unwrap(), None);
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn inbox_flag_on_task_list_scopes_to_the_inbox() {
+    let parsed = TaskListArgsCli::try_parse_from(["tt", "--inbox"]).unwrap();
+    assert!(parsed.args.inbox);
+    assert_eq!(
+        TaskListArgsCli::try_parse_from(["tt", "--inbox", "--project-id", "p1"])
+            .err()
+            .unwrap()
+            .kind(),
+        clap::error::ErrorKind::ArgumentConflict
+    );
+}
@@ -0,0 +1,165 @@
+use super::offline::TaskSource;
+use super::{render_task_list, TaskListArgs};
+use crate::cli::undo::describe_age;
+use crate::models::Task;
+use crate::output::{paint, OutputContext, OutputFormat, ANSI_DIM, ANSI_GREEN, ANSI_YELLOW};
+use anyhow::{anyhow, Result};
+use chrono::Local;
+use std::collections::HashSet;
+use std::io::{self, Write};
+use std::time::Duration;
+
+const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+/// Parses a refresh interval: plain seconds, or a number with `s`, `m`, or `h`.
+pub(super) fn parse_watch_interval(value: &str) -> std::result::Result<Duration, String> {
+    let trimmed = value.trim().to_ascii_lowercase();
+    let split = trimmed
+        .find(|ch: char| !ch.is_ascii_digit())
+        .unwrap_or(trimmed.len());
+    let (amount, unit) = trimmed.split_at(split);
+    let multiplier = match unit.trim() {
+        "" | "s" | "sec" | "secs" => Some(1),
+        "m" | "min" | "mins" => Some(60),
+        "h" | "hr" | "hrs" => Some(60 * 60),
+        _ => None,
+    };
+
+    amount
+        .parse::<u64>()
+        .ok()
+        .zip(multiplier)
+        .and_then(|(amount, multiplier)| amount.checked_mul(multiplier))
+        .filter(|secs| *secs > 0)
+        .map(Duration::from_secs)
+        .ok_or_else(|| {
+            format!(
+                "Invalid interval '{}'. Use seconds or a value like 30s, 5m, or 1h.",
+                value
+            )
+        })
+}
+
+/// Tasks that appeared or dropped out of the list between two refreshes, by title.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(super) struct WatchDiff {
+    pub(super) added: Vec<String>,
+    pub(super) removed: Vec<String>,
+}
+
+pub(super) fn diff_task_lists(previous: &[Task], current: &[Task]) -> WatchDiff {
+    let ids = |tasks: &[Task]| -> HashSet<String> {
+        tasks.iter().filter_map(|task| task.id.clone()).collect()
+    };
+    let (previous_ids, current_ids) = (ids(previous), ids(current));
+    let titles_missing_from = |tasks: &[Task], other: &HashSet<String>| -> Vec<String> {
+        tasks
+            .iter()
+            .filter(|task| task.id.as_ref().is_some_and(|id| !other.contains(id)))
+            .map(|task| task.title.clone())
+            .collect()
+    };
+
+    WatchDiff {
+        added: titles_missing_from(current, &previous_ids),
+        removed: titles_missing_from(previous, &current_ids),
+    }
+}
+
+/// The lines under the list: what changed since the last refresh, then when it ran.
+pub(super) fn render_watch_footer(
+    diff: &WatchDiff,
+    last_updated: &str,
+    interval: Duration,
+    color: bool,
+) -> String {
+    let mut footer = String::from("\n");
+    for title in &diff.added {
+        footer.push_str(&paint(&format!("+ {}", title), ANSI_GREEN, color));
+        footer.push('\n');
+    }
+    for title in &diff.removed {
+        footer.push_str(&paint(
+            &format!("- {} (done or moved)", title),
+            ANSI_DIM,
+            color,
+        ));
+        footer.push('\n');
+    }
+    let status = format!(
+        "Last updated {} · every {} · Ctrl-C to quit",
+        last_updated,
+        describe_age(interval.as_secs() as i64)
+    );
+    footer.push_str(&paint(&status, ANSI_DIM, color));
+    footer.push('\n');
+    footer
+}
+
+pub(super) fn render_refresh_warning(err: &anyhow::Error, at: &str, color: bool) -> String {
+    let warning = format!(
+        "Warning: refresh at {} failed ({}); showing the previous list.",
+        at, err
+    );
+    format!("{}\n\n", paint(&warning, ANSI_YELLOW, color))
+}
+
+/// `tt task list --watch`: redraws the list every `interval` until Ctrl-C. A failed
+/// refresh keeps the last good list on screen under a warning; only the first fetch
+/// failing ends the command.
+pub(super) async fn watch_task_list(
+    args: TaskListArgs,
+    interval: Duration,
+    context: &OutputContext,
+) -> Result<()> {
+    if matches!(args.output, OutputFormat::Json) {
+        return Err(anyhow!("--watch only works with human output"));
+    }
+    let source = TaskSource::open()?;
+    let interrupted = tokio::signal::ctrl_c();
+    tokio::pin!(interrupted);
+
+    let mut previous: Option<(Vec<Task>, String, String)> = None;
+    let mut stdout = io::stdout();
+    loop {
+        let now = Local::now().format("%H:%M").to_string();
+        let screen = match render_task_list(&source, &mut args.clone(), context).await {
+            Ok((tasks, rendered)) => {
+                let diff = previous
+                    .as_ref()
+                    .map(|(previous_tasks, _, _)| diff_task_lists(previous_tasks, &tasks))
+                    .unwrap_or_default();
+                let screen = format!(
+                    "{}{}",
+                    rendered,
+                    render_watch_footer(&diff, &now, interval, context.color)
+                );
+                previous = Some((tasks, rendered, now));
+                screen
+            }
+            Err(err) => {
+                let Some((_, rendered, last_updated)) = previous.as_ref() else {
+                    return Err(err);
+                };
+                format!(
+                    "{}{}{}",
+                    render_refresh_warning(&err, &now, context.color),
+                    rendered,
+                    render_watch_footer(
+                        &WatchDiff::default(),
+                        last_updated,
+                        interval,
+                        context.color
+                    )
+                )
+            }
+        };
+
+        let _ = write!(stdout, "{}{}", CLEAR_SCREEN, screen);
+        let _ = stdout.flush();
+        tokio::select! {
+            _ = &mut interrupted => return Ok(()),
+            _ = tokio::time::sleep(interval) => {}
+        }
+    }
+}
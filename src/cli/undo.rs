@@ -0,0 +1,223 @@
+use super::task::{recreate_task, reopen_task, restore_task, TaskSource};
+use crate::cache::{CacheStore, JournalEntry, JournalOperation};
+use crate::models::Project;
+use crate::output::{MessageFormat, OutputContext, Verbosity};
+use anyhow::{anyhow, Context, Result};
+use chrono::Utc;
+use clap::Args;
+use serde_json::{json, Value};
+
+#[derive(Args)]
+pub struct UndoArgs {
+    #[arg(
+        long,
+        default_value = "24h",
+        value_parser = parse_max_age,
+        help = "Refuse to undo operations older than this (30m, 12h, 7d)"
+    )]
+    max_age: i64,
+    #[arg(long, default_value = "human")]
+    output: MessageFormat,
+}
+
+/// Parses `30m`, `12h`, or `7d` into seconds.
+fn parse_max_age(value: &str) -> std::result::Result<i64, String> {
+    let trimmed = value.trim().to_ascii_lowercase();
+    let split = trimmed
+        .find(|ch: char| !ch.is_ascii_digit())
+        .unwrap_or(trimmed.len());
+    let (amount, unit) = trimmed.split_at(split);
+    let amount = amount.parse::<i64>().ok();
+    let multiplier = match unit.trim() {
+        "s" | "sec" | "secs" => Some(1),
+        "m" | "min" | "mins" => Some(60),
+        "h" | "hr" | "hrs" | "hour" | "hours" => Some(60 * 60),
+        "d" | "day" | "days" => Some(24 * 60 * 60),
+        _ => None,
+    };
+
+    amount
+        .zip(multiplier)
+        .and_then(|(amount, multiplier)| amount.checked_mul(multiplier))
+        .ok_or_else(|| format!("Invalid age '{}'. Use a value like 30m, 12h, or 7d.", value))
+}
+
+pub(super) fn describe_age(secs: i64) -> String {
+    match secs {
+        secs if secs < 60 => format!("{}s", secs),
+        secs if secs < 60 * 60 => format!("{}m", secs / 60),
+        secs if secs < 24 * 60 * 60 => format!("{}h", secs / (60 * 60)),
+        secs => format!("{}d", secs / (24 * 60 * 60)),
+    }
+}
+
+fn operation_label(operation: &JournalOperation) -> &'static str {
+    match operation {
+        JournalOperation::TaskDeleted { .. } => "task delete",
+        JournalOperation::TaskCompleted { .. } => "task complete",
+        JournalOperation::TaskUpdated { .. } => "task update",
+        JournalOperation::ProjectDeleted { .. } => "project delete",
+    }
+}
+
+fn check_entry_age(entry: &JournalEntry, now: i64, max_age: i64) -> Result<()> {
+    let age = now.saturating_sub(entry.recorded_at).max(0);
+    if age > max_age {
+        return Err(anyhow!(
+            "Last operation ({}) was {} ago, older than --max-age {}; not undoing it",
+            operation_label(&entry.operation),
+            describe_age(age),
+            describe_age(max_age)
+        ));
+    }
+    Ok(())
+}
+
+/// Undo result: a human summary plus the JSON fields for `--output json`.
+struct UndoOutcome {
+    message: String,
+    details: Value,
+}
+
+fn format_undo_output(
+    outcome: &UndoOutcome,
+    format: MessageFormat,
+    context: &OutputContext,
+) -> Result<String> {
+    match format {
+        MessageFormat::Json => Ok(format!("{}\n", context.to_json_string(&outcome.details)?)),
+        MessageFormat::Human => Ok(format!("{}\n", outcome.message)),
+    }
+}
+
+/// Replays the inverse of the newest journal entry and drops it once that succeeds.
+pub async fn undo(args: UndoArgs, verbosity: Verbosity, context: &OutputContext) -> Result<()> {
+    let cache = CacheStore::new()?;
+    let entry = cache
+        .last_operation()?
+        .ok_or_else(|| anyhow!("Nothing to undo"))?;
+    check_entry_age(&entry, Utc::now().timestamp(), args.max_age)?;
+
+    let (client, _) = TaskSource::online("undo")?;
+    let outcome = match entry.operation {
+        JournalOperation::TaskDeleted { task, project_id } => {
+            let created = recreate_task(&client, Some(&cache), &task, &project_id).await?;
+            let new_id = created.id.clone().unwrap_or_default();
+            let old_id = task.id.clone().unwrap_or_default();
+            UndoOutcome {
+                message: format!(
+                    "Restored deleted task '{}' with new ID {} (was {})",
+                    created.title, new_id, old_id
+                ),
+                details: json!({
+                    "undone": "task_deleted",
+                    "taskId": new_id,
+                    "previousTaskId": old_id,
+                    "projectId": project_id,
+                }),
+            }
+        }
+        JournalOperation::TaskCompleted {
+            task_id,
+            project_id,
+        } => {
+            let (task, project_id, reopened) =
+                reopen_task(&client, Some(&cache), &task_id, Some(project_id), None).await?;
+            let state = if reopened {
+                "Reopened"
+            } else {
+                "Already open:"
+            };
+            UndoOutcome {
+                message: format!("{} task '{}'", state, task.title),
+                details: json!({
+                    "undone": "task_completed",
+                    "taskId": task_id,
+                    "projectId": project_id,
+                    "reopened": reopened,
+                }),
+            }
+        }
+        JournalOperation::TaskUpdated {
+            previous,
+            project_id,
+        } => {
+            let restored = restore_task(&client, Some(&cache), &previous, &project_id).await?;
+            UndoOutcome {
+                message: format!("Restored previous fields of task '{}'", restored.title),
+                details: json!({
+                    "undone": "task_updated",
+                    "taskId": restored.id,
+                    "projectId": project_id,
+                }),
+            }
+        }
+        JournalOperation::ProjectDeleted { project } => {
+            let recreated = client
+                .create_project(&Project {
+                    id: None,
+                    ..project.clone()
+                })
+                .await
+                .context("Failed to recreate project")?;
+            let _ = cache.invalidate_projects();
+            let new_id = recreated.id.clone().unwrap_or_default();
+            UndoOutcome {
+                message: format!(
+                    "Recreated project '{}' with new ID {} (was {}); its tasks were not restored",
+                    recreated.name,
+                    new_id,
+                    project.id.clone().unwrap_or_default()
+                ),
+                details: json!({
+                    "undone": "project_deleted",
+                    "projectId": new_id,
+                    "previousProjectId": project.id,
+                }),
+            }
+        }
+    };
+
+    cache.remove_last_operation()?;
+    verbosity.confirm(
+        &format_undo_output(&outcome, args.output, context)?,
+        args.output,
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(recorded_at: i64) -> JournalEntry {
+        JournalEntry {
+            recorded_at,
+            operation: JournalOperation::TaskCompleted {
+                task_id: "task-1".to_string(),
+                project_id: "project-1".to_string(),
+            },
+        }
+    }
+
+    #[test]
+    fn parse_max_age_accepts_minutes_hours_and_days() {
+        assert_eq!(parse_max_age("30m"), Ok(30 * 60));
+        assert_eq!(parse_max_age("12h"), Ok(12 * 60 * 60));
+        assert_eq!(parse_max_age(" 7d "), Ok(7 * 24 * 60 * 60));
+        assert!(parse_max_age("soon").is_err());
+        assert!(parse_max_age("5").is_err());
+    }
+
+    #[test]
+    fn entries_older_than_max_age_are_refused() {
+        let now = 1_800_000_000;
+        assert!(check_entry_age(&entry(now - 60), now, 3600).is_ok());
+
+        let err = check_entry_age(&entry(now - 2 * 3600), now, 3600).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Last operation (task complete) was 2h ago, older than --max-age 1h; not undoing it"
+        );
+    }
+}
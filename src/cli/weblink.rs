@@ -0,0 +1,43 @@
+use crate::output::{MessageFormat, Verbosity};
+
+const WEB_APP_URL: &str = "https://ticktick.com/webapp/#p";
+
+pub(super) fn project_web_url(project_id: &str) -> String {
+    format!("{}/{}/tasks", WEB_APP_URL, project_id)
+}
+
+pub(super) fn task_web_url(project_id: &str, task_id: &str) -> String {
+    format!("{}/{}/tasks/{}", WEB_APP_URL, project_id, task_id)
+}
+
+/// With `--print`, writes the URL to stdout; otherwise opens it in the default browser,
+/// falling back to printing it when no browser can be started.
+pub(super) fn open_or_print(url: &str, print: bool, verbosity: Verbosity) {
+    if print {
+        println!("{}", url);
+        return;
+    }
+    if webbrowser::open(url).is_err() {
+        println!("Open this URL in your browser:");
+        println!("{}", url);
+        return;
+    }
+    verbosity.confirm(&format!("Opened {}\n", url), MessageFormat::Human);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_web_app_urls_for_projects_and_tasks() {
+        assert_eq!(
+            project_web_url("p1"),
+            "https://ticktick.com/webapp/#p/p1/tasks"
+        );
+        assert_eq!(
+            task_web_url("p1", "t1"),
+            "https://ticktick.com/webapp/#p/p1/tasks/t1"
+        );
+    }
+}
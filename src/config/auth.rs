@@ -1,8 +1,9 @@
+use crate::api::proxy::{http_client_builder, send_error};
 use anyhow::{anyhow, Context, Result};
 use oauth2::{
     basic::BasicClient, AuthUrl, AuthorizationCode, ClientId, ClientSecret, CsrfToken,
-    EndpointNotSet, EndpointSet, PkceCodeChallenge, PkceCodeVerifier, RedirectUrl, RefreshToken,
-    Scope, TokenResponse, TokenUrl,
+    EndpointNotSet, EndpointSet, ErrorResponse, HttpClientError, PkceCodeChallenge,
+    PkceCodeVerifier, RedirectUrl, RefreshToken, RequestTokenError, Scope, TokenResponse, TokenUrl,
 };
 use serde::{Deserialize, Serialize};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
@@ -85,6 +86,17 @@ impl AuthSettings {
         self.broker_url.is_some()
     }
 
+    pub fn broker_url(&self) -> Option<&str> {
+        self.broker_url.as_deref()
+    }
+
+    /// A `TICKTICK_CLIENT_ID` of your own sent through the shared broker, which only
+    /// holds the secret for the shared client.
+    pub fn custom_client_on_shared_broker(&self) -> bool {
+        self.client_id != DEFAULT_SHARED_CLIENT_ID
+            && self.broker_url.as_deref() == Some(DEFAULT_BROKER_URL)
+    }
+
     pub async fn exchange_code(
         &self,
         code: AuthorizationCode,
@@ -175,7 +187,7 @@ impl TickTickOAuth {
             client = client.set_client_secret(ClientSecret::new(client_secret));
         }
 
-        let http_client = reqwest::ClientBuilder::new()
+        let http_client = http_client_builder()?
             .redirect(reqwest::redirect::Policy::none())
             .build()
             .context("Failed to build OAuth HTTP client")?;
@@ -211,7 +223,8 @@ impl TickTickOAuth {
             .exchange_code(code)
             .set_pkce_verifier(pkce_verifier)
             .request_async(&self.http_client)
-            .await?;
+            .await
+            .map_err(token_request_error)?;
 
         token_response_data(&token)
     }
@@ -221,7 +234,8 @@ impl TickTickOAuth {
             .client
             .exchange_refresh_token(&RefreshToken::new(refresh_token.to_string()))
             .request_async(&self.http_client)
-            .await?;
+            .await
+            .map_err(token_request_error)?;
 
         token_response_data(&token)
     }
@@ -255,6 +269,38 @@ impl TickTickOAuth {
     }
 }
 
+/// Keeps an unreachable proxy from surfacing as a token error.
+fn token_request_error<RE: ErrorResponse + Send + Sync + 'static>(
+    err: RequestTokenError<HttpClientError<reqwest::Error>, RE>,
+) -> anyhow::Error {
+    match err {
+        RequestTokenError::Request(HttpClientError::Reqwest(err)) => {
+            send_error(*err, "Failed to reach the TickTick token endpoint")
+        }
+        other => other.into(),
+    }
+}
+
+/// Checks that the broker answers its `GET /health` route.
+pub async fn check_broker_health(broker_url: &str) -> Result<()> {
+    let endpoint = format!("{}/health", broker_url.trim_end_matches('/'));
+    let client = http_client_builder()?
+        .timeout(Duration::from_secs(10))
+        .build()
+        .context("Failed to build OAuth broker HTTP client")?;
+    let response = client
+        .get(endpoint)
+        .send()
+        .await
+        .map_err(|err| send_error(err, "Failed to reach OAuth broker"))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        return Err(anyhow!("OAuth broker health check returned {}", status));
+    }
+    Ok(())
+}
+
 async fn send_broker_token_request<T: Serialize>(
     broker_url: &str,
     path: &str,
@@ -262,7 +308,9 @@ async fn send_broker_token_request<T: Serialize>(
     broker_api_key: Option<&str>,
 ) -> Result<TokenResponseData> {
     let endpoint = format!("{}{}", broker_url.trim_end_matches('/'), path);
-    let client = reqwest::Client::new();
+    let client = http_client_builder()?
+        .build()
+        .context("Failed to build OAuth broker HTTP client")?;
     let mut request = client.post(endpoint).json(payload);
     if let Some(key) = broker_api_key.filter(|value| !value.trim().is_empty()) {
         request = request.header("x-broker-key", key);
@@ -271,7 +319,7 @@ async fn send_broker_token_request<T: Serialize>(
     let response = request
         .send()
         .await
-        .context("Failed to call OAuth broker")?;
+        .map_err(|err| send_error(err, "Failed to call OAuth broker"))?;
 
     let status = response.status();
     if !status.is_success() {
@@ -279,11 +327,7 @@ async fn send_broker_token_request<T: Serialize>(
             .text()
             .await
             .unwrap_or_else(|_| "No response body".to_string());
-        return Err(anyhow!(
-            "OAuth broker returned {}: {}",
-            status.as_u16(),
-            details
-        ));
+        return Err(anyhow!(broker_error_message(status.as_u16(), &details)));
     }
 
     let token = response
@@ -294,6 +338,34 @@ async fn send_broker_token_request<T: Serialize>(
     TokenResponseData::from_token_endpoint_response(token)
 }
 
+/// Structured error body returned by the auth broker.
+#[derive(Debug, Deserialize)]
+struct BrokerErrorBody {
+    error: String,
+    error_description: Option<String>,
+    upstream_status: Option<u16>,
+    request_id: Option<String>,
+}
+
+fn broker_error_message(status: u16, body: &str) -> String {
+    let Ok(parsed) = serde_json::from_str::<BrokerErrorBody>(body) else {
+        return format!("OAuth broker returned {}: {}", status, body);
+    };
+
+    let mut message = match parsed.upstream_status {
+        Some(upstream) => format!("TickTick rejected the token request ({})", upstream),
+        None => format!("OAuth broker returned {}", status),
+    };
+    message.push_str(&format!(": {}", parsed.error));
+    if let Some(description) = parsed.error_description.filter(|value| !value.is_empty()) {
+        message.push_str(&format!(" - {}", description));
+    }
+    if let Some(request_id) = parsed.request_id.filter(|value| value != "-") {
+        message.push_str(&format!(" (request {})", request_id));
+    }
+    message
+}
+
 #[derive(Debug, Serialize)]
 struct BrokerExchangeRequest {
     code: String,
@@ -433,4 +505,26 @@ mod tests {
         assert!(!pkce_verifier.secret().is_empty());
         assert!(!csrf_token.secret().is_empty());
     }
+
+    #[test]
+    fn broker_error_message_surfaces_upstream_oauth_errors() {
+        let body = r#"{"error":"invalid_grant","error_description":"Authorization code expired","upstream_status":400,"request_id":"8a1b"}"#;
+        assert_eq!(
+            broker_error_message(400, body),
+            "TickTick rejected the token request (400): invalid_grant - Authorization code expired (request 8a1b)"
+        );
+    }
+
+    #[test]
+    fn broker_error_message_handles_broker_and_plain_errors() {
+        let body = r#"{"error":"unauthorized","error_description":"Missing or invalid x-broker-key","request_id":"-"}"#;
+        assert_eq!(
+            broker_error_message(401, body),
+            "OAuth broker returned 401: unauthorized - Missing or invalid x-broker-key"
+        );
+        assert_eq!(
+            broker_error_message(502, "Bad Gateway"),
+            "OAuth broker returned 502: Bad Gateway"
+        );
+    }
 }
@@ -29,6 +29,26 @@ impl Config {
     }
 }
 
+/// The list `tt add` files tasks under when none is given, and the Inbox ID, as last
+/// looked up. Kept in `config.toml` so adding a task does not fetch every list first.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DefaultProjectIds {
+    pub default_project_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inbox_id: Option<String>,
+    pub updated_at: i64,
+}
+
+impl DefaultProjectIds {
+    pub fn is_fresh(&self, now: i64, max_age_secs: i64) -> bool {
+        now >= self.updated_at && now - self.updated_at < max_age_secs
+    }
+
+    pub fn contains(&self, project_id: &str) -> bool {
+        self.default_project_id == project_id || self.inbox_id.as_deref() == Some(project_id)
+    }
+}
+
 #[derive(Clone)]
 pub struct AppConfig {
     config_file: PathBuf,
@@ -43,22 +63,27 @@ impl std::fmt::Debug for AppConfig {
     }
 }
 
-impl AppConfig {
-    pub fn new() -> Result<Self> {
-        let proj_dirs = ProjectDirs::from("", "", "ticktick-cli")
-            .context("Failed to get project directories")?;
+/// The per-user config directory, created on first use.
+pub fn config_dir() -> Result<PathBuf> {
+    let proj_dirs =
+        ProjectDirs::from("", "", "ticktick-cli").context("Failed to get project directories")?;
 
-        let config_dir = proj_dirs.config_dir().to_path_buf();
+    let config_dir = proj_dirs.config_dir().to_path_buf();
 
-        if !config_dir.exists() {
-            fs::create_dir_all(&config_dir).context("Failed to create config directory")?;
-        }
+    if !config_dir.exists() {
+        fs::create_dir_all(&config_dir).context("Failed to create config directory")?;
+    }
 
-        let config_file = config_dir.join("config.toml");
+    Ok(config_dir)
+}
+
+impl AppConfig {
+    pub fn new() -> Result<Self> {
+        let config_file = config_dir()?.join("config.toml");
 
         Ok(Self::with_token_store(
             config_file,
-            Arc::new(KeyringTokenStore::default()),
+            Arc::new(KeyringTokenStore),
         ))
     }
 
@@ -81,7 +106,7 @@ impl AppConfig {
 
                 return Err(err).context("Failed to migrate credentials to secure storage");
             }
-            self.write_metadata(ConfigMetadata::from_config(&config))
+            self.write_metadata(stored.metadata())
                 .context("Failed to rewrite config file without credentials")?;
             return Ok(Some(config));
         }
@@ -105,7 +130,9 @@ impl AppConfig {
             .save(&tokens)
             .context("Failed to save credentials to secure storage")?;
 
-        if let Err(err) = self.write_metadata(ConfigMetadata::from_config(config)) {
+        let mut metadata = ConfigMetadata::from_config(config);
+        metadata.default_projects = self.load_default_project_ids().unwrap_or_default();
+        if let Err(err) = self.write_metadata(metadata) {
             let _ = self.token_store.clear();
             return Err(err);
         }
@@ -131,10 +158,41 @@ impl AppConfig {
         Ok(())
     }
 
+    /// The cached default list and Inbox IDs, without touching secure storage.
+    pub fn load_default_project_ids(&self) -> Result<Option<DefaultProjectIds>> {
+        Ok(self
+            .read_stored()?
+            .and_then(|stored| stored.default_projects))
+    }
+
+    /// Replaces (or with `None`, drops) the cached IDs. Does nothing when logged out, so
+    /// an environment-only token never creates a config file.
+    pub fn save_default_project_ids(&self, ids: Option<&DefaultProjectIds>) -> Result<()> {
+        let Some(mut stored) = self.read_stored()? else {
+            return Ok(());
+        };
+        stored.default_projects = ids.cloned();
+
+        let contents =
+            toml::to_string_pretty(&stored).context("Failed to serialize config metadata")?;
+        fs::write(&self.config_file, contents).context("Failed to write config file")
+    }
+
     pub fn config_file_path(&self) -> &PathBuf {
         &self.config_file
     }
 
+    /// Where `tt sync` keeps its snapshot, next to `config.toml`.
+    pub fn snapshot_path(&self) -> PathBuf {
+        self.config_file.with_file_name("sync.json")
+    }
+
+    /// Points at another `config.toml`, for tests that only touch the file.
+    #[cfg(test)]
+    pub(crate) fn with_config_file(config_file: PathBuf) -> Self {
+        Self::with_token_store(config_file, Arc::new(KeyringTokenStore))
+    }
+
     fn with_token_store(config_file: PathBuf, token_store: Arc<dyn TokenStore>) -> Self {
         Self {
             config_file,
@@ -150,27 +208,36 @@ impl AppConfig {
     }
 
     fn has_legacy_plaintext_config(&self) -> Result<bool> {
+        Ok(self
+            .read_stored()?
+            .is_some_and(|stored| stored.legacy_config().is_some()))
+    }
+
+    fn read_stored(&self) -> Result<Option<StoredConfig>> {
         if !self.config_file.exists() {
-            return Ok(false);
+            return Ok(None);
         }
 
         let contents =
             fs::read_to_string(&self.config_file).context("Failed to read config file")?;
-        let stored: StoredConfig =
-            toml::from_str(&contents).context("Failed to parse config file")?;
-        Ok(stored.legacy_config().is_some())
+        toml::from_str(&contents)
+            .context("Failed to parse config file")
+            .map(Some)
     }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct ConfigMetadata {
     expires_at: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    default_projects: Option<DefaultProjectIds>,
 }
 
 impl ConfigMetadata {
     fn from_config(config: &Config) -> Self {
         Self {
             expires_at: config.expires_at,
+            default_projects: None,
         }
     }
 }
@@ -178,16 +245,19 @@ impl ConfigMetadata {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct StoredConfig {
     expires_at: i64,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     access_token: Option<String>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     refresh_token: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    default_projects: Option<DefaultProjectIds>,
 }
 
 impl StoredConfig {
     fn metadata(self) -> ConfigMetadata {
         ConfigMetadata {
             expires_at: self.expires_at,
+            default_projects: self.default_projects,
         }
     }
 
@@ -340,8 +410,7 @@ mod tests {
 
     impl ErrorTokenStore {
         fn secure_storage_error(message: &str) -> anyhow::Error {
-            anyhow::Error::new(KeyringError::NoStorageAccess(Box::new(io::Error::new(
-                io::ErrorKind::Other,
+            anyhow::Error::new(KeyringError::NoStorageAccess(Box::new(io::Error::other(
                 message.to_string(),
             ))))
         }
@@ -484,6 +553,86 @@ expires_at = 987654321
         assert!(!path.exists());
     }
 
+    #[test]
+    fn default_project_ids_survive_token_saves_and_skip_missing_config() {
+        let path = temp_config_path();
+        let app_config = test_app_config(path.clone());
+        let ids = DefaultProjectIds {
+            default_project_id: "inbox1".to_string(),
+            inbox_id: Some("inbox1".to_string()),
+            updated_at: 1_000,
+        };
+
+        app_config.save_default_project_ids(Some(&ids)).unwrap();
+        assert!(!path.exists());
+
+        let mut config = Config {
+            access_token: "access-token".to_string(),
+            refresh_token: "refresh-token".to_string(),
+            expires_at: 100,
+        };
+        app_config.save(&config).unwrap();
+        app_config.save_default_project_ids(Some(&ids)).unwrap();
+        assert_eq!(
+            app_config.load_default_project_ids().unwrap(),
+            Some(ids.clone())
+        );
+
+        config.expires_at = 200;
+        app_config.save(&config).unwrap();
+        assert_eq!(app_config.load_default_project_ids().unwrap(), Some(ids));
+        assert_eq!(app_config.load().unwrap().unwrap().expires_at, 200);
+
+        app_config.save_default_project_ids(None).unwrap();
+        assert_eq!(app_config.load_default_project_ids().unwrap(), None);
+        assert!(!fs::read_to_string(&path)
+            .unwrap()
+            .contains("default_projects"));
+    }
+
+    #[test]
+    fn default_project_ids_keep_legacy_plaintext_credentials() {
+        let path = temp_config_path();
+        let app_config = test_app_config(path.clone());
+        fs::write(
+            &path,
+            r#"
+access_token = "legacy-access"
+refresh_token = "legacy-refresh"
+expires_at = 987654321
+"#,
+        )
+        .unwrap();
+
+        let ids = DefaultProjectIds {
+            default_project_id: "p1".to_string(),
+            inbox_id: None,
+            updated_at: 1_000,
+        };
+        app_config.save_default_project_ids(Some(&ids)).unwrap();
+
+        let contents = fs::read_to_string(&path).unwrap();
+        assert!(contents.contains("legacy-access"));
+        assert!(contents.contains("default_project_id = \"p1\""));
+    }
+
+    #[test]
+    fn default_project_ids_are_fresh_for_a_window() {
+        let ids = DefaultProjectIds {
+            default_project_id: "p1".to_string(),
+            inbox_id: Some("inbox1".to_string()),
+            updated_at: 1_000,
+        };
+
+        assert!(ids.is_fresh(1_000, 60));
+        assert!(ids.is_fresh(1_059, 60));
+        assert!(!ids.is_fresh(1_060, 60));
+        assert!(!ids.is_fresh(999, 60));
+        assert!(ids.contains("p1"));
+        assert!(ids.contains("inbox1"));
+        assert!(!ids.contains("p2"));
+    }
+
     #[test]
     fn access_token_expiration_check_uses_expires_at() {
         let config = Config {
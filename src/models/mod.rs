@@ -80,6 +80,47 @@ pub struct Task {
     pub time_zone: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub kind: Option<String>,
+    /// The kanban column the task sits in, for lists shown as boards.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column_id: Option<String>,
+    /// The task this one is a subtask of (`parentId`). Checklist items are `items`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<String>,
+    /// When the task was first saved to TickTick (`createdTime`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, deserialize_with = "deserialize_opt_string")]
+    pub created_time: Option<String>,
+    /// When TickTick last saved a change to the task (`modifiedTime`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, deserialize_with = "deserialize_opt_string")]
+    pub modified_time: Option<String>,
+}
+
+impl Task {
+    /// Marks the task as open again; the completion timestamp is dropped so it is not sent
+    /// back to TickTick.
+    pub fn reopen(&mut self) {
+        self.status = Some(TaskStatus::Normal);
+        self.completed_time = None;
+    }
+
+    /// A copy TickTick will create as a new open task: the ID, sort position, and
+    /// completion state are dropped, and checklist items keep their titles and order but
+    /// lose their IDs and are reopened.
+    pub fn duplicate(&self) -> Task {
+        let mut copy = self.clone();
+        copy.id = None;
+        copy.sort_order = None;
+        copy.created_time = None;
+        copy.modified_time = None;
+        copy.reopen();
+        for item in copy.items.iter_mut().flatten() {
+            item.id = None;
+            item.status = Some(TaskStatus::Normal);
+            item.completed_time = None;
+        }
+        copy
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -175,6 +216,101 @@ pub struct ProjectData {
     pub columns: Option<Vec<Column>>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Habit {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(alias = "name")]
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(alias = "encouragement")]
+    pub content: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub goal: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unit: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub days: Option<Vec<i32>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remind: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(alias = "repeatRule")]
+    pub repeat: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repeated: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort_order: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_check_ins: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current_streak: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archived_time: Option<String>,
+}
+
+impl Habit {
+    /// TickTick marks archived habits with status 1; active ones have 0 or no status.
+    pub fn is_archived(&self) -> bool {
+        self.status == Some(1)
+    }
+
+    /// Archives the habit as of `now` (a TickTick timestamp), or makes it active again.
+    pub fn set_archived(&mut self, archived: bool, now: &str) {
+        if archived {
+            self.status = Some(1);
+            self.archived_time = Some(now.to_string());
+        } else {
+            self.status = Some(0);
+            self.archived_time = None;
+        }
+    }
+}
+
+/// One day's record for a habit; `checkin_stamp` is the local date as `YYYYMMDD`.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct HabitCheckin {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub habit_id: Option<String>,
+    pub checkin_stamp: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub goal: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<i32>,
+}
+
+impl HabitCheckin {
+    /// Status 2 marks a completed day; numeric habits also count once the goal is met.
+    pub fn is_completed(&self) -> bool {
+        if self.status == Some(2) {
+            return true;
+        }
+        matches!((self.value, self.goal), (Some(value), Some(goal)) if goal > 0.0 && value >= goal)
+    }
+}
+
+/// What TickTick sends back after a check-in. The endpoint may reply with an empty body or
+/// only some of these fields, so everything is optional.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct HabitCheckinReceipt {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub goal: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current_streak: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_check_ins: Option<i64>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -224,6 +360,89 @@ mod tests {
         );
     }
 
+    #[test]
+    fn task_reads_modified_time_from_an_api_response() {
+        let task: Task = serde_json::from_str(
+            r#"{
+                "id": "6502f8fce4b0a1b2c3d4e5f6",
+                "projectId": "6502f8fce4b0a1b2c3d4e000",
+                "sortOrder": -1099511627776,
+                "title": "Renew passport",
+                "content": "",
+                "timeZone": "America/New_York",
+                "isAllDay": true,
+                "priority": 0,
+                "status": 0,
+                "tags": [],
+                "columnId": "6502f8fce4b0a1b2c3d4e111",
+                "etag": "k2x9q1ab",
+                "kind": "TEXT",
+                "createdTime": "2026-09-30T18:22:05.000+0000",
+                "modifiedTime": "2026-10-12T14:03:11.000+0000"
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            task.modified_time.as_deref(),
+            Some("2026-10-12T14:03:11.000+0000")
+        );
+
+        let value = serde_json::to_value(&task).unwrap();
+        assert_eq!(value["modifiedTime"], "2026-10-12T14:03:11.000+0000");
+        assert!(task.duplicate().modified_time.is_none());
+    }
+
+    #[test]
+    fn task_reads_created_time_from_an_api_response() {
+        let task: Task = serde_json::from_str(
+            r#"{
+                "id": "6502f8fce4b0a1b2c3d4e5f6",
+                "projectId": "6502f8fce4b0a1b2c3d4e000",
+                "title": "Renew passport",
+                "createdTime": "2026-09-30T18:22:05.000+0000"
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            task.created_time.as_deref(),
+            Some("2026-09-30T18:22:05.000+0000")
+        );
+
+        let value = serde_json::to_value(&task).unwrap();
+        assert_eq!(value["createdTime"], "2026-09-30T18:22:05.000+0000");
+        assert!(task.duplicate().created_time.is_none());
+    }
+
+    #[test]
+    fn task_reads_and_writes_parent_id_like_the_api() {
+        let subtask: Task = serde_json::from_str(
+            r#"{
+                "id": "6502f8fce4b0a1b2c3d4e777",
+                "projectId": "6502f8fce4b0a1b2c3d4e000",
+                "sortOrder": -1099511627776,
+                "title": "Book venue",
+                "timeZone": "America/New_York",
+                "isAllDay": false,
+                "priority": 0,
+                "status": 0,
+                "parentId": "6502f8fce4b0a1b2c3d4e5f6",
+                "etag": "p7m2c0de",
+                "kind": "TEXT"
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            subtask.parent_id.as_deref(),
+            Some("6502f8fce4b0a1b2c3d4e5f6")
+        );
+
+        let value = serde_json::to_value(&subtask).unwrap();
+        assert_eq!(value["parentId"], "6502f8fce4b0a1b2c3d4e5f6");
+        assert!(value.get("parent_id").is_none());
+        let top_level = serde_json::to_value(Task::default()).unwrap();
+        assert!(top_level.get("parentId").is_none());
+    }
+
     #[test]
     fn task_status_rejects_unsupported_values() {
         let err = serde_json::from_value::<TaskStatus>(json!(3))
@@ -271,4 +490,131 @@ mod tests {
         assert_eq!(task.start_date.as_deref(), Some("{\"seconds\":30}"));
         assert_eq!(task.status, Some(TaskStatus::Normal));
     }
+
+    #[test]
+    fn reopened_task_serializes_as_normal_without_completed_time() {
+        let mut task: Task = serde_json::from_value(json!({
+            "id": "task-1",
+            "title": "Ship release",
+            "status": 2,
+            "completedTime": "2026-03-01T10:00:00.000+0000"
+        }))
+        .unwrap();
+
+        task.reopen();
+        let value = serde_json::to_value(&task).unwrap();
+
+        assert_eq!(value["status"], json!(0));
+        assert!(value.get("completedTime").is_none());
+    }
+
+    #[test]
+    fn duplicated_task_drops_identity_and_completion_but_keeps_content() {
+        let task: Task = serde_json::from_value(json!({
+            "id": "task-1",
+            "projectId": "project-1",
+            "title": "Pack for trip",
+            "content": "Carry-on only",
+            "tags": ["travel"],
+            "sortOrder": 42,
+            "status": 2,
+            "completedTime": "2026-03-01T10:00:00.000+0000",
+            "items": [
+                {"id": "item-1", "title": "Passport", "status": 1, "sortOrder": 0,
+                 "completedTime": "2026-03-01T09:00:00.000+0000"},
+                {"id": "item-2", "title": "Chargers", "status": 0, "sortOrder": 1}
+            ]
+        }))
+        .unwrap();
+
+        let copy = serde_json::to_value(task.duplicate()).unwrap();
+
+        for key in ["id", "sortOrder", "completedTime"] {
+            assert!(copy.get(key).is_none(), "{} should be stripped", key);
+        }
+        assert_eq!(copy["status"], json!(0));
+        assert_eq!(copy["projectId"], json!("project-1"));
+        assert_eq!(copy["title"], json!("Pack for trip"));
+        assert_eq!(copy["content"], json!("Carry-on only"));
+        assert_eq!(copy["tags"], json!(["travel"]));
+        assert_eq!(
+            copy["items"],
+            json!([
+                {"title": "Passport", "status": 0, "sortOrder": 0},
+                {"title": "Chargers", "status": 0, "sortOrder": 1}
+            ])
+        );
+    }
+
+    #[test]
+    fn habit_deserialization_accepts_api_field_names() {
+        let habit: Habit = serde_json::from_value(json!({
+            "id": "habit-1",
+            "name": "Drink water",
+            "goal": 8.0,
+            "unit": "Cup",
+            "repeatRule": "RRULE:FREQ=DAILY",
+            "currentStreak": 4
+        }))
+        .unwrap();
+
+        assert_eq!(habit.title, "Drink water");
+        assert_eq!(habit.goal, Some(8.0));
+        assert_eq!(habit.repeat.as_deref(), Some("RRULE:FREQ=DAILY"));
+        assert_eq!(habit.current_streak, Some(4));
+    }
+
+    #[test]
+    fn habit_archived_state_round_trips() {
+        let mut habit: Habit = serde_json::from_value(json!({
+            "id": "habit-1",
+            "name": "Read",
+            "status": 1,
+            "archivedTime": "2026-03-01T08:00:00.000+0000"
+        }))
+        .unwrap();
+        assert!(habit.is_archived());
+
+        let value = serde_json::to_value(&habit).unwrap();
+        assert_eq!(value["status"], json!(1));
+        assert_eq!(value["archivedTime"], json!("2026-03-01T08:00:00.000+0000"));
+
+        habit.set_archived(false, "2026-03-02T08:00:00.000+0000");
+        assert!(!habit.is_archived());
+        let value = serde_json::to_value(&habit).unwrap();
+        assert_eq!(value["status"], json!(0));
+        assert!(value.get("archivedTime").is_none());
+
+        habit.set_archived(true, "2026-03-02T08:00:00.000+0000");
+        let restored: Habit =
+            serde_json::from_value(serde_json::to_value(&habit).unwrap()).unwrap();
+        assert!(restored.is_archived());
+        assert_eq!(
+            restored.archived_time.as_deref(),
+            Some("2026-03-02T08:00:00.000+0000")
+        );
+    }
+
+    #[test]
+    fn habit_checkin_counts_status_or_reached_goal_as_completed() {
+        let done = HabitCheckin {
+            checkin_stamp: 20260301,
+            status: Some(2),
+            ..Default::default()
+        };
+        let partial = HabitCheckin {
+            checkin_stamp: 20260301,
+            value: Some(3.0),
+            goal: Some(8.0),
+            ..Default::default()
+        };
+        let reached = HabitCheckin {
+            value: Some(8.0),
+            ..partial.clone()
+        };
+
+        assert!(done.is_completed());
+        assert!(!partial.is_completed());
+        assert!(reached.is_completed());
+    }
 }
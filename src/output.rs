@@ -1,21 +1,437 @@
-use crate::models::{Project, Task};
+use crate::cli::priority_title;
+use crate::models::{ChecklistItem, Column, Habit, Project, Task, TaskStatus};
 use atty::Stream;
+use chrono::{DateTime, Local, NaiveDate, TimeZone};
 use serde::Serialize;
 use std::io;
+use terminal_size::{terminal_size, Width};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// `--color`: `auto` colors a terminal unless `NO_COLOR` is set; `always` and `never`
+/// override both checks.
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ColorChoice {
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+/// Whether output going to a terminal (`is_tty`) gets ANSI colors; `no_color` is whether
+/// the `NO_COLOR` environment variable is set to something non-empty.
+pub fn should_colorize(choice: ColorChoice, is_tty: bool, no_color: bool) -> bool {
+    match choice {
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+        ColorChoice::Auto => is_tty && !no_color,
+    }
+}
+
+/// Where output is headed and how it is shaped: `is_tty` picks tables over `id|title`
+/// lines and `color` whether they carry ANSI styling; the rest come from `--compact`,
+/// `--fields` and `--no-header`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct OutputContext {
+    pub is_tty: bool,
+    pub color: bool,
+    /// JSON on a single line instead of pretty-printed.
+    pub compact_json: bool,
+    /// CSV/TSV columns, in order; empty means every field. Names are checked with
+    /// [`parse_field_name`].
+    pub fields: Vec<String>,
+    /// Leaves the header row out of CSV/TSV output.
+    pub no_header: bool,
+}
+
+impl OutputContext {
+    /// Stdout under the `--color` choice; worked out once per run and handed to commands.
+    pub fn stdout(choice: ColorChoice) -> Self {
+        let is_tty = atty::is(Stream::Stdout);
+        let no_color = std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+        Self {
+            is_tty,
+            color: should_colorize(choice, is_tty, no_color),
+            ..Self::default()
+        }
+    }
+
+    /// Serializes `--output json` data: pretty-printed, or one line under `--compact`.
+    /// Either way the caller ends it with a newline.
+    pub fn to_json_string<T: Serialize + ?Sized>(&self, value: &T) -> serde_json::Result<String> {
+        if self.compact_json {
+            serde_json::to_string(value)
+        } else {
+            serde_json::to_string_pretty(value)
+        }
+    }
+}
+
+/// `text` between `ansi` and a reset when `color` is on, otherwise unchanged.
+pub fn paint(text: &str, ansi: &str, color: bool) -> String {
+    if color {
+        format!("{}{}{}", ansi, text, ANSI_RESET)
+    } else {
+        text.to_string()
+    }
+}
 
 #[derive(clap::ValueEnum, Clone, Copy, Debug, Default)]
 pub enum OutputFormat {
     #[default]
     Human,
     Json,
+    Csv,
+    Tsv,
+    Markdown,
+}
+
+/// `--output` for commands that report an outcome or a summary rather than rows: there is
+/// nothing to lay out as CSV, TSV or Markdown, so only human text and JSON are offered.
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MessageFormat {
+    #[default]
+    Human,
+    Json,
+}
+
+impl MessageFormat {
+    /// For a command that shares its `--output` with a listing (`tt task count` takes the
+    /// `tt task list` flags); CSV, TSV and Markdown are refused.
+    pub fn for_command(format: OutputFormat, command: &str) -> anyhow::Result<Self> {
+        match format {
+            OutputFormat::Human => Ok(Self::Human),
+            OutputFormat::Json => Ok(Self::Json),
+            OutputFormat::Csv | OutputFormat::Tsv | OutputFormat::Markdown => {
+                let name = clap::ValueEnum::to_possible_value(&format)
+                    .map(|value| value.get_name().to_string())
+                    .unwrap_or_default();
+                Err(anyhow::anyhow!(
+                    "'tt {}' has no {} output; use --output human or json",
+                    command,
+                    name
+                ))
+            }
+        }
+    }
+}
+
+/// How chatty commands are about success. `--quiet` drops human confirmation lines so
+/// scripts can rely on the exit code; data and JSON output are always printed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Verbosity {
+    Quiet,
+    #[default]
+    Normal,
+}
+
+impl Verbosity {
+    pub fn is_quiet(self) -> bool {
+        self == Verbosity::Quiet
+    }
+
+    /// Prints a rendered confirmation unless it is human text in quiet mode.
+    pub fn confirm(self, rendered: &str, format: MessageFormat) {
+        if self.is_quiet() && format == MessageFormat::Human {
+            return;
+        }
+        print!("{}", rendered);
+    }
+}
+
+/// Human-mode presentation switches for task listings; JSON output ignores them.
+/// `due_cell`, when set, renders the Due column in place of [`task_date_cell`];
+/// `completed_cell` adds a Completed column after it.
+#[derive(Clone, Copy, Default)]
+pub struct TaskListOptions<'a> {
+    pub show_subtasks: bool,
+    pub show_status: bool,
+    pub table: TableLayout,
+    pub due_cell: Option<&'a dyn Fn(&Task) -> String>,
+    pub completed_cell: Option<&'a dyn Fn(&Task) -> String>,
+}
+
+/// Used when stdout is not a terminal or its size cannot be read.
+const DEFAULT_TERMINAL_WIDTH: usize = 80;
+/// The Title (or Name) column keeps at least this many characters before other columns
+/// are narrowed to make room.
+const MIN_FLEX_COLUMN_WIDTH: usize = 10;
+
+/// Width budget for human tables. Without a `max_width` columns grow to fit their
+/// widest cell; with one, long cells are truncated with an ellipsis or, with `wrap`,
+/// continued on extra lines.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TableLayout {
+    pub max_width: Option<usize>,
+    pub wrap: bool,
+}
+
+impl TableLayout {
+    pub fn for_terminal(wrap: bool) -> Self {
+        Self {
+            max_width: Some(terminal_width()),
+            wrap,
+        }
+    }
+}
+
+fn terminal_width() -> usize {
+    if !atty::is(Stream::Stdout) {
+        return DEFAULT_TERMINAL_WIDTH;
+    }
+    terminal_size()
+        .map(|(Width(columns), _)| usize::from(columns))
+        .filter(|columns| *columns > 0)
+        .or_else(|| {
+            std::env::var("COLUMNS")
+                .ok()
+                .and_then(|value| value.trim().parse::<usize>().ok())
+                .filter(|columns| *columns > 0)
+        })
+        .unwrap_or(DEFAULT_TERMINAL_WIDTH)
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskGroup {
+    pub key: String,
+    pub tasks: Vec<Task>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskSearchResult {
+    pub project_name: String,
+    #[serde(flatten)]
+    pub task: Task,
+}
+
+/// A habit plus the check-in stats `tt habit list` derives; stats stay `None` when the
+/// API returned no check-in data. `schedule` is the weekday label for the Days column.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HabitSummary {
+    #[serde(flatten)]
+    pub habit: Habit,
+    #[serde(skip)]
+    pub schedule: String,
+    pub scheduled_today: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub streak: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checked_in_today: Option<bool>,
+}
+
+/// The result of `tt habit checkin`: the day's value after the check-in, plus the streak
+/// and total when TickTick reported them.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HabitCheckinOutcome {
+    pub habit_id: String,
+    pub title: String,
+    pub date: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub goal: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unit: Option<String>,
+    pub completed: bool,
+    pub already_checked_in: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current_streak: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_check_ins: Option<i64>,
+}
+
+/// Completed scheduled days out of scheduled days over the last `days` days.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HabitRate {
+    pub days: i64,
+    pub completed: u32,
+    pub scheduled: u32,
+}
+
+impl HabitRate {
+    fn percent(&self) -> Option<u32> {
+        (self.scheduled > 0).then(|| (self.completed * 100 + self.scheduled / 2) / self.scheduled)
+    }
 }
 
+/// What `tt habit stats` shows; `recent` is the last two weeks, oldest first.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HabitStats {
+    pub habit_id: String,
+    pub title: String,
+    pub current_streak: u32,
+    pub longest_streak: u32,
+    pub last_30_days: HabitRate,
+    pub last_90_days: HabitRate,
+    pub recent: String,
+    pub done_today: bool,
+}
+
+/// A project plus the task counts `tt project list --counts` adds; counts are `None`
+/// when they were not requested or that project's tasks could not be fetched.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectSummary {
+    #[serde(flatten)]
+    pub project: Project,
+    pub open_count: Option<usize>,
+    pub overdue_count: Option<usize>,
+}
+
+impl ProjectSummary {
+    pub fn without_counts(project: Project) -> Self {
+        Self {
+            project,
+            open_count: None,
+            overdue_count: None,
+        }
+    }
+}
+
+/// A folder and its lists for `tt project list --tree`. `folder_id` is `None` for the
+/// lists that are in no folder; `--counts` fills in each list's task counts.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectFolder {
+    pub folder_id: Option<String>,
+    pub name: String,
+    pub projects: Vec<ProjectSummary>,
+}
+
+/// One stdin line from `tt task add --batch`: the created task's ID, or why it failed.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchAddResult {
+    pub line: usize,
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl BatchAddResult {
+    fn status_cell(&self) -> String {
+        match self.error.as_deref() {
+            Some(error) => format!("failed: {}", error),
+            None => "created".to_string(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ErrorDocument {
+    error: String,
+    context: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct TaskGroupsDocument<'a> {
+    groups: &'a [TaskGroup],
+}
+
+#[derive(Serialize)]
+struct ProjectTreeDocument<'a> {
+    folders: &'a [ProjectFolder],
+}
+
+/// A listing row: table columns for human output, and named fields for CSV/TSV. Fields
+/// hold full values where the table shortens them (IDs, notes).
 trait Tabular {
     fn headers() -> Vec<String>;
     fn rows(&self) -> Vec<String>;
+    /// Every field name, in the order CSV/TSV prints them without `--fields`.
+    fn field_names() -> &'static [&'static str];
+    fn field(&self, name: &str) -> Option<String>;
+}
+
+const TASK_FIELDS: &[&str] = &[
+    "id",
+    "title",
+    "status",
+    "priority",
+    "due",
+    "start",
+    "project",
+    "tags",
+    "content",
+    "completed",
+    "created",
+];
+const TASK_SEARCH_FIELDS: &[&str] = &[
+    "id",
+    "title",
+    "list",
+    "status",
+    "priority",
+    "due",
+    "start",
+    "project",
+    "tags",
+    "content",
+    "completed",
+    "created",
+];
+const BATCH_ADD_FIELDS: &[&str] = &["line", "id", "title", "status"];
+const PROJECT_FIELDS: &[&str] = &["id", "name", "color", "view", "kind", "group", "closed"];
+const PROJECT_SUMMARY_FIELDS: &[&str] = &[
+    "id", "name", "color", "view", "kind", "group", "closed", "open", "overdue",
+];
+const COLUMN_FIELDS: &[&str] = &["id", "name", "project"];
+const TAG_COUNT_FIELDS: &[&str] = &["tag", "open", "total"];
+const HABIT_FIELDS: &[&str] = &[
+    "id", "title", "days", "goal", "unit", "streak", "today", "archived",
+];
+
+/// `--fields` value parser: a field of at least one listing, lowercased.
+pub fn parse_field_name(value: &str) -> Result<String, String> {
+    let name = value.trim().to_ascii_lowercase();
+    let lists = [
+        TASK_SEARCH_FIELDS,
+        BATCH_ADD_FIELDS,
+        PROJECT_SUMMARY_FIELDS,
+        COLUMN_FIELDS,
+        TAG_COUNT_FIELDS,
+        HABIT_FIELDS,
+    ];
+    if lists.iter().any(|fields| fields.contains(&name.as_str())) {
+        return Ok(name);
+    }
+    let mut known: Vec<&str> = lists.concat();
+    known.sort_unstable();
+    known.dedup();
+    Err(format!(
+        "Unknown field '{}'. Fields: {}",
+        value,
+        known.join(", ")
+    ))
+}
+
+fn task_status_field(task: &Task) -> String {
+    if matches!(task.status, Some(TaskStatus::Completed)) {
+        "completed".to_string()
+    } else {
+        "open".to_string()
+    }
+}
+
+fn task_content_field(task: &Task) -> String {
+    task.content
+        .as_deref()
+        .filter(|value| !value.trim().is_empty())
+        .or(task.desc.as_deref())
+        .unwrap_or_default()
+        .to_string()
 }
 
-fn task_date_cell(task: &Task) -> String {
+/// The date shown in the Due column: the due date (or start date), without the time.
+pub fn task_date_cell(task: &Task) -> String {
     task.due_date
         .as_ref()
         .or(task.start_date.as_ref())
@@ -54,6 +470,14 @@ fn task_note_cell(task: &Task) -> String {
         .unwrap_or_default()
 }
 
+/// The Priority column label; no priority is left blank.
+pub fn task_priority_label(priority: i32) -> String {
+    match priority {
+        0 => String::new(),
+        p => priority_title(p),
+    }
+}
+
 impl Tabular for Task {
     fn headers() -> Vec<String> {
         vec![
@@ -66,23 +490,102 @@ impl Tabular for Task {
     }
 
     fn rows(&self) -> Vec<String> {
-        let priority = match self.priority.unwrap_or(0) {
-            0 => "".to_string(),
-            1 => "Low".to_string(),
-            3 => "Medium".to_string(),
-            5 => "High".to_string(),
-            p => p.to_string(),
-        };
         let id = self.id.clone().unwrap_or_default();
 
         vec![
             id,
             self.title.clone(),
-            priority,
+            task_priority_label(self.priority.unwrap_or(0)),
             task_date_cell(self),
             task_note_cell(self),
         ]
     }
+
+    fn field_names() -> &'static [&'static str] {
+        TASK_FIELDS
+    }
+
+    fn field(&self, name: &str) -> Option<String> {
+        let date = |value: &Option<String>| {
+            value
+                .as_deref()
+                .map(|date| date.split('T').next().unwrap_or(date).to_string())
+                .unwrap_or_default()
+        };
+        Some(match name {
+            "id" => self.id.clone().unwrap_or_default(),
+            "title" => self.title.clone(),
+            "status" => task_status_field(self),
+            "priority" => task_priority_label(self.priority.unwrap_or(0)),
+            "due" => task_date_cell(self),
+            "start" => date(&self.start_date),
+            "project" => self.project_id.clone().unwrap_or_default(),
+            "tags" => self.tags.as_deref().unwrap_or_default().join(","),
+            "content" => task_content_field(self),
+            "completed" => self.completed_time.clone().unwrap_or_default(),
+            "created" => self.created_time.clone().unwrap_or_default(),
+            _ => return None,
+        })
+    }
+}
+
+impl Tabular for TaskSearchResult {
+    fn headers() -> Vec<String> {
+        let mut headers = Task::headers();
+        headers.insert(2, "List".to_string());
+        headers
+    }
+
+    fn rows(&self) -> Vec<String> {
+        let mut rows = self.task.rows();
+        rows.insert(2, self.project_name.clone());
+        rows
+    }
+
+    fn field_names() -> &'static [&'static str] {
+        TASK_SEARCH_FIELDS
+    }
+
+    fn field(&self, name: &str) -> Option<String> {
+        match name {
+            "list" => Some(self.project_name.clone()),
+            _ => self.task.field(name),
+        }
+    }
+}
+
+impl Tabular for BatchAddResult {
+    fn headers() -> Vec<String> {
+        vec![
+            "Line".to_string(),
+            "ID".to_string(),
+            "Title".to_string(),
+            "Status".to_string(),
+        ]
+    }
+
+    fn rows(&self) -> Vec<String> {
+        vec![
+            self.line.to_string(),
+            self.id.clone().unwrap_or_default(),
+            self.title.clone(),
+            self.status_cell(),
+        ]
+    }
+
+    fn field_names() -> &'static [&'static str] {
+        BATCH_ADD_FIELDS
+    }
+
+    fn field(&self, name: &str) -> Option<String> {
+        Some(match name {
+            "line" => self.line.to_string(),
+            "id" => self.id.clone().unwrap_or_default(),
+            "title" => self.title.clone(),
+            "status" => self.status_cell(),
+            _ => return None,
+        })
+    }
 }
 
 impl Tabular for Project {
@@ -97,170 +600,1505 @@ impl Tabular for Project {
 
     fn rows(&self) -> Vec<String> {
         let id = self.id.clone().unwrap_or_default();
+        let name = if self.closed.unwrap_or(false) {
+            format!("{} (closed)", self.name)
+        } else {
+            self.name.clone()
+        };
         vec![
             format!("{}...", &id[..8.min(id.len())]),
-            self.name.clone(),
+            name,
             self.color.clone().unwrap_or_default(),
             self.view_mode.clone().unwrap_or_default(),
         ]
     }
-}
 
-fn render_table<T: Tabular>(items: &[T]) -> String {
-    if items.is_empty() {
-        return "No items found.\n".to_string();
+    fn field_names() -> &'static [&'static str] {
+        PROJECT_FIELDS
     }
 
-    let headers = T::headers();
-    let rows: Vec<Vec<String>> = items.iter().map(|i| i.rows()).collect();
-
-    let col_widths: Vec<usize> = headers
-        .iter()
-        .enumerate()
-        .map(|(i, header)| {
-            let max_width = rows
-                .iter()
-                .map(|row| row.get(i).map_or(0, |c| c.len()))
-                .max()
-                .unwrap_or(0);
-            header.len().max(max_width)
+    fn field(&self, name: &str) -> Option<String> {
+        Some(match name {
+            "id" => self.id.clone().unwrap_or_default(),
+            "name" => self.name.clone(),
+            "color" => self.color.clone().unwrap_or_default(),
+            "view" => self.view_mode.clone().unwrap_or_default(),
+            "kind" => self.kind.clone().unwrap_or_default(),
+            "group" => self.group_id.clone().unwrap_or_default(),
+            "closed" => self.closed.unwrap_or(false).to_string(),
+            _ => return None,
         })
-        .collect();
+    }
+}
 
-    let separator: String = col_widths
-        .iter()
-        .map(|w| "-".repeat(*w + 2))
-        .collect::<Vec<_>>()
-        .join("+");
-    let header_row: String = col_widths
-        .iter()
-        .enumerate()
-        .map(|(i, w)| {
-            format!(
-                " {:width$} ",
-                headers.get(i).unwrap_or(&String::new()),
-                width = *w
-            )
-        })
-        .collect::<Vec<_>>()
-        .join("|");
+impl Tabular for Column {
+    fn headers() -> Vec<String> {
+        vec!["ID".to_string(), "Name".to_string()]
+    }
 
-    let mut output = String::new();
-    output.push_str(&format!("|{}|\n", header_row));
-    output.push_str(&format!("|{}|\n", separator));
+    fn rows(&self) -> Vec<String> {
+        vec![self.id.clone(), self.name.clone()]
+    }
 
-    for row in rows {
-        let row_str: String = col_widths
-            .iter()
-            .enumerate()
-            .map(|(i, w)| {
-                format!(
-                    " {:width$} ",
-                    row.get(i).unwrap_or(&String::new()),
-                    width = *w
-                )
-            })
-            .collect::<Vec<_>>()
-            .join("|");
-        output.push_str(&format!("|{}|\n", row_str));
+    fn field_names() -> &'static [&'static str] {
+        COLUMN_FIELDS
     }
 
-    output
+    fn field(&self, name: &str) -> Option<String> {
+        Some(match name {
+            "id" => self.id.clone(),
+            "name" => self.name.clone(),
+            "project" => self.project_id.clone(),
+            _ => return None,
+        })
+    }
 }
 
-fn render_json<T: Serialize>(items: &[T]) -> String {
-    let mut output = serde_json::to_string_pretty(items).unwrap_or_else(|_| "[]".to_string());
-    output.push('\n');
-    output
+fn count_cell(count: Option<usize>) -> String {
+    count
+        .map(|count| count.to_string())
+        .unwrap_or_else(|| "?".to_string())
 }
 
-fn render_task_lines(tasks: &[Task]) -> String {
-    let mut output = tasks
-        .iter()
-        .map(|task| {
-            let id = task.id.clone().unwrap_or_default();
-            format!("{}|{}", id, task.title)
-        })
-        .collect::<Vec<_>>()
-        .join("\n");
-    if !output.is_empty() {
-        output.push('\n');
+impl Tabular for ProjectSummary {
+    fn headers() -> Vec<String> {
+        let mut headers = Project::headers();
+        headers.extend(["Open".to_string(), "Overdue".to_string()]);
+        headers
     }
-    output
-}
 
-fn render_project_lines(projects: &[Project]) -> String {
-    let mut output = projects
-        .iter()
-        .map(|project| {
-            let id = project.id.clone().unwrap_or_default();
-            format!("{}|{}", id, project.name)
-        })
-        .collect::<Vec<_>>()
-        .join("\n");
-    if !output.is_empty() {
-        output.push('\n');
+    fn rows(&self) -> Vec<String> {
+        let mut row = self.project.rows();
+        row.extend([count_cell(self.open_count), count_cell(self.overdue_count)]);
+        row
     }
-    output
-}
 
-fn render_tasks(tasks: &[Task], format: OutputFormat, is_tty: bool) -> String {
-    match format {
-        OutputFormat::Json => render_json(tasks),
-        OutputFormat::Human => {
-            if is_tty {
-                render_table(tasks)
-            } else {
-                render_task_lines(tasks)
-            }
+    fn field_names() -> &'static [&'static str] {
+        PROJECT_SUMMARY_FIELDS
+    }
+
+    fn field(&self, name: &str) -> Option<String> {
+        let count = |count: Option<usize>| count.map(|count| count.to_string()).unwrap_or_default();
+        match name {
+            "open" => Some(count(self.open_count)),
+            "overdue" => Some(count(self.overdue_count)),
+            _ => self.project.field(name),
         }
     }
 }
 
-fn render_projects(projects: &[Project], format: OutputFormat, is_tty: bool) -> String {
-    match format {
-        OutputFormat::Json => render_json(projects),
-        OutputFormat::Human => {
-            if is_tty {
-                render_table(projects)
-            } else {
-                render_project_lines(projects)
-            }
-        }
+fn format_habit_goal(goal: f64) -> String {
+    if goal.fract() == 0.0 {
+        format!("{}", goal as i64)
+    } else {
+        goal.to_string()
     }
 }
 
-pub fn print_tasks(tasks: &[Task], format: OutputFormat) {
-    let _ = io::Write::write_all(
-        &mut io::stdout(),
-        render_tasks(tasks, format, atty::is(Stream::Stdout)).as_bytes(),
-    );
+fn habit_today_cell(checked_in_today: Option<bool>) -> String {
+    match checked_in_today {
+        Some(true) => "✓".to_string(),
+        Some(false) => "·".to_string(),
+        None => String::new(),
+    }
 }
 
-pub fn print_projects(projects: &[Project], format: OutputFormat) {
-    let _ = io::Write::write_all(
-        &mut io::stdout(),
-        render_projects(projects, format, atty::is(Stream::Stdout)).as_bytes(),
-    );
+/// Tasks carrying one tag: `open` excludes completed tasks, `total` includes them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TagCount {
+    pub tag: String,
+    pub open: usize,
+    pub total: usize,
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+impl Tabular for TagCount {
+    fn headers() -> Vec<String> {
+        vec!["Tag".to_string(), "Open".to_string(), "Total".to_string()]
+    }
 
-    #[test]
-    fn task_rows_format_priority_due_date_and_missing_id() {
-        let task = Task {
-            title: "Ship release".to_string(),
-            priority: Some(5),
-            due_date: Some("2026-03-08T09:00:00Z".to_string()),
-            ..Default::default()
-        };
+    fn rows(&self) -> Vec<String> {
+        vec![
+            format!("#{}", self.tag),
+            self.open.to_string(),
+            self.total.to_string(),
+        ]
+    }
 
-        assert_eq!(
-            task.rows(),
-            vec![
-                "".to_string(),
+    fn field_names() -> &'static [&'static str] {
+        TAG_COUNT_FIELDS
+    }
+
+    fn field(&self, name: &str) -> Option<String> {
+        Some(match name {
+            "tag" => self.tag.clone(),
+            "open" => self.open.to_string(),
+            "total" => self.total.to_string(),
+            _ => return None,
+        })
+    }
+}
+
+impl Tabular for HabitSummary {
+    fn headers() -> Vec<String> {
+        vec![
+            "ID".to_string(),
+            "Title".to_string(),
+            "Days".to_string(),
+            "Goal".to_string(),
+            "Unit".to_string(),
+            "Streak".to_string(),
+            "Today".to_string(),
+        ]
+    }
+
+    fn rows(&self) -> Vec<String> {
+        vec![
+            self.habit.id.clone().unwrap_or_default(),
+            if self.habit.is_archived() {
+                format!("{} (archived)", self.habit.title)
+            } else {
+                self.habit.title.clone()
+            },
+            self.schedule.clone(),
+            self.habit.goal.map(format_habit_goal).unwrap_or_default(),
+            self.habit.unit.clone().unwrap_or_default(),
+            self.streak
+                .map(|streak| streak.to_string())
+                .unwrap_or_default(),
+            habit_today_cell(self.checked_in_today),
+        ]
+    }
+
+    fn field_names() -> &'static [&'static str] {
+        HABIT_FIELDS
+    }
+
+    fn field(&self, name: &str) -> Option<String> {
+        Some(match name {
+            "id" => self.habit.id.clone().unwrap_or_default(),
+            "title" => self.habit.title.clone(),
+            "days" => self.schedule.clone(),
+            "goal" => self.habit.goal.map(format_habit_goal).unwrap_or_default(),
+            "unit" => self.habit.unit.clone().unwrap_or_default(),
+            "streak" => self
+                .streak
+                .map(|streak| streak.to_string())
+                .unwrap_or_default(),
+            "today" => self
+                .checked_in_today
+                .map(|checked| checked.to_string())
+                .unwrap_or_default(),
+            "archived" => self.habit.is_archived().to_string(),
+            _ => return None,
+        })
+    }
+}
+
+fn render_table<T: Tabular>(items: &[T], layout: TableLayout) -> String {
+    if items.is_empty() {
+        return "No items found.\n".to_string();
+    }
+
+    let rows: Vec<Vec<String>> = items.iter().map(|i| i.rows()).collect();
+    render_table_rows(&T::headers(), &rows, layout)
+}
+
+/// One CSV or TSV value. CSV quotes values holding a comma, quote, or line break (quotes
+/// doubled); TSV has no quoting, so tabs, line breaks, and backslashes are escaped.
+fn delimited_cell(value: &str, format: OutputFormat) -> String {
+    if matches!(format, OutputFormat::Tsv) {
+        return value
+            .replace('\\', "\\\\")
+            .replace('\t', "\\t")
+            .replace('\n', "\\n")
+            .replace('\r', "\\r");
+    }
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// CSV/TSV rows for `items` with the `--fields` columns (every field by default), headed
+/// by the field names unless `--no-header`.
+fn render_delimited<T: Tabular>(
+    items: &[T],
+    format: OutputFormat,
+    context: &OutputContext,
+) -> String {
+    let fields: Vec<&str> = if context.fields.is_empty() {
+        T::field_names().to_vec()
+    } else {
+        context.fields.iter().map(String::as_str).collect()
+    };
+    render_delimited_fields(items, format, &fields, !context.no_header)
+}
+
+/// A field this listing lacks is left empty, so columns line up with `fields`.
+fn render_delimited_fields<T: Tabular>(
+    items: &[T],
+    format: OutputFormat,
+    fields: &[&str],
+    header: bool,
+) -> String {
+    let separator = if matches!(format, OutputFormat::Tsv) {
+        "\t"
+    } else {
+        ","
+    };
+
+    let mut output = String::new();
+    if header {
+        output.push_str(&fields.join(separator));
+        output.push('\n');
+    }
+    for item in items {
+        let cells: Vec<String> = fields
+            .iter()
+            .map(|name| delimited_cell(&item.field(name).unwrap_or_default(), format))
+            .collect();
+        output.push_str(&cells.join(separator));
+        output.push('\n');
+    }
+    output
+}
+
+/// A piece of a table cell: a color escape, or one character as it is drawn, together
+/// with the accents, variation selectors, and joined emoji that take no columns of their own.
+enum CellPiece<'a> {
+    Escape(&'a str),
+    Cluster(&'a str, usize),
+}
+
+fn cell_pieces(cell: &str) -> Vec<CellPiece<'_>> {
+    let mut pieces = Vec::new();
+    let mut chars = cell.char_indices().peekable();
+    while let Some((start, ch)) = chars.next() {
+        let mut end = start + ch.len_utf8();
+        if ch == '\x1b' {
+            for (index, next) in chars.by_ref() {
+                end = index + next.len_utf8();
+                if next == 'm' {
+                    break;
+                }
+            }
+            pieces.push(CellPiece::Escape(&cell[start..end]));
+            continue;
+        }
+
+        while let Some(&(index, next)) = chars.peek() {
+            let extended = &cell[start..index + next.len_utf8()];
+            let joins = next.width() == Some(0) || extended.width() <= cell[start..end].width();
+            if next == '\x1b' || !joins {
+                break;
+            }
+            end = index + next.len_utf8();
+            chars.next();
+        }
+        let cluster = &cell[start..end];
+        pieces.push(CellPiece::Cluster(cluster, cluster.width()));
+    }
+    pieces
+}
+
+/// Terminal columns a cell takes, ignoring color escapes.
+fn visible_width(cell: &str) -> usize {
+    cell_pieces(cell)
+        .iter()
+        .map(|piece| match piece {
+            CellPiece::Escape(_) => 0,
+            CellPiece::Cluster(_, width) => *width,
+        })
+        .sum()
+}
+
+fn pad_cell(cell: &str, width: usize) -> String {
+    let padding = width.saturating_sub(visible_width(cell));
+    format!("{}{}", cell, " ".repeat(padding))
+}
+
+/// Cuts a cell to at most `width` columns, ending in `…`, without splitting a character
+/// from its accents or an emoji sequence. Color codes are kept and reset before the
+/// ellipsis.
+fn truncate_cell(cell: &str, width: usize) -> String {
+    if visible_width(cell) <= width {
+        return cell.to_string();
+    }
+
+    let mut truncated = String::new();
+    let mut visible = 0;
+    let mut colored = false;
+    for piece in cell_pieces(cell) {
+        match piece {
+            CellPiece::Escape(code) => {
+                colored = true;
+                truncated.push_str(code);
+            }
+            CellPiece::Cluster(text, cluster_width) => {
+                if visible + cluster_width >= width {
+                    break;
+                }
+                truncated.push_str(text);
+                visible += cluster_width;
+            }
+        }
+    }
+    if colored {
+        truncated.push_str(ANSI_RESET);
+    }
+    if width > 0 {
+        truncated.push('…');
+    }
+    truncated
+}
+
+/// Splits `word` after as many whole characters as fit in `width` columns (at least one).
+fn split_word_at_width(word: &str, width: usize) -> (String, String) {
+    let mut head = String::new();
+    let mut used = 0;
+    let mut rest = word;
+    for piece in cell_pieces(word) {
+        let CellPiece::Cluster(text, cluster_width) = piece else {
+            continue;
+        };
+        if !head.is_empty() && used + cluster_width > width {
+            break;
+        }
+        head.push_str(text);
+        used += cluster_width;
+        rest = &rest[text.len()..];
+    }
+    (head, rest.to_string())
+}
+
+/// Splits a cell into lines of at most `width` columns, breaking at spaces where it can.
+/// Colored cells are truncated instead since escapes cannot span lines.
+fn wrap_cell(cell: &str, width: usize) -> Vec<String> {
+    if visible_width(cell) <= width || width == 0 {
+        return vec![cell.to_string()];
+    }
+    if cell.contains('\x1b') {
+        return vec![truncate_cell(cell, width)];
+    }
+
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    for word in cell.split(' ') {
+        let mut word = word.to_string();
+        let line_width = visible_width(&line);
+        if line_width > 0 && line_width + 1 + visible_width(&word) <= width {
+            line.push(' ');
+            line.push_str(&word);
+            continue;
+        }
+        if line_width > 0 {
+            lines.push(std::mem::take(&mut line));
+        }
+        while visible_width(&word) > width {
+            let (head, rest) = split_word_at_width(&word, width);
+            lines.push(head);
+            word = rest;
+        }
+        line = word;
+    }
+    if !line.is_empty() || lines.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+/// Shrinks column widths so the table fits in `max_width`. The Title/Name column takes
+/// whatever the other columns leave; ID columns are never narrowed.
+fn fit_column_widths(headers: &[String], natural: &[usize], max_width: usize) -> Vec<usize> {
+    let chrome = 3 * natural.len() + 1;
+    if natural.iter().sum::<usize>() + chrome <= max_width {
+        return natural.to_vec();
+    }
+
+    let is_id = |index: usize| headers.get(index).is_some_and(|header| header == "ID");
+    let Some(flex) = headers
+        .iter()
+        .position(|header| header == "Title" || header == "Name")
+        .or_else(|| {
+            (0..natural.len())
+                .filter(|index| !is_id(*index))
+                .max_by_key(|index| natural[*index])
+        })
+    else {
+        return natural.to_vec();
+    };
+
+    let available = max_width.saturating_sub(chrome);
+    let flex_floor = MIN_FLEX_COLUMN_WIDTH.min(natural[flex]);
+    let mut widths = natural.to_vec();
+    loop {
+        let others: usize = widths
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| *index != flex)
+            .map(|(_, width)| *width)
+            .sum();
+        let budget = available.saturating_sub(others);
+        if budget >= flex_floor {
+            widths[flex] = budget.min(natural[flex]);
+            return widths;
+        }
+
+        let shrinkable = (0..widths.len())
+            .filter(|index| *index != flex && !is_id(*index))
+            .filter(|index| widths[*index] > visible_width(&headers[*index]).max(1))
+            .max_by_key(|index| widths[*index]);
+        match shrinkable {
+            Some(index) => widths[index] -= 1,
+            None => {
+                widths[flex] = flex_floor;
+                return widths;
+            }
+        }
+    }
+}
+
+fn render_table_rows(headers: &[String], rows: &[Vec<String>], layout: TableLayout) -> String {
+    let natural_widths: Vec<usize> = headers
+        .iter()
+        .enumerate()
+        .map(|(i, header)| {
+            let max_width = rows
+                .iter()
+                .map(|row| row.get(i).map_or(0, |c| visible_width(c)))
+                .max()
+                .unwrap_or(0);
+            visible_width(header).max(max_width)
+        })
+        .collect();
+    let col_widths = match layout.max_width {
+        Some(max_width) => fit_column_widths(headers, &natural_widths, max_width),
+        None => natural_widths,
+    };
+
+    let separator: String = col_widths
+        .iter()
+        .map(|w| "-".repeat(*w + 2))
+        .collect::<Vec<_>>()
+        .join("+");
+    let header_row: String = col_widths
+        .iter()
+        .enumerate()
+        .map(|(i, w)| {
+            format!(
+                " {} ",
+                pad_cell(
+                    &truncate_cell(headers.get(i).map_or("", String::as_str), *w),
+                    *w
+                )
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("|");
+
+    let mut output = String::new();
+    output.push_str(&format!("|{}|\n", header_row));
+    output.push_str(&format!("|{}|\n", separator));
+
+    for row in rows {
+        let cells: Vec<Vec<String>> = col_widths
+            .iter()
+            .enumerate()
+            .map(|(i, w)| {
+                let cell = row.get(i).map_or("", String::as_str);
+                if layout.wrap {
+                    wrap_cell(cell, *w)
+                } else {
+                    vec![truncate_cell(cell, *w)]
+                }
+            })
+            .collect();
+        let line_count = cells.iter().map(Vec::len).max().unwrap_or(1);
+
+        for line in 0..line_count {
+            let row_str: String = col_widths
+                .iter()
+                .enumerate()
+                .map(|(i, w)| {
+                    let cell = cells[i].get(line).map_or("", String::as_str);
+                    format!(" {} ", pad_cell(cell, *w))
+                })
+                .collect::<Vec<_>>()
+                .join("|");
+            output.push_str(&format!("|{}|\n", row_str));
+        }
+    }
+
+    output
+}
+
+fn subtask_label(item: &ChecklistItem) -> String {
+    let mark = if matches!(item.status, Some(TaskStatus::Completed)) {
+        "[x]"
+    } else {
+        "[ ]"
+    };
+    format!("  {} {}", mark, item.title.as_deref().unwrap_or_default())
+}
+
+fn task_subtask_labels(task: &Task) -> Vec<String> {
+    task.items.iter().flatten().map(subtask_label).collect()
+}
+
+fn task_status_cell(task: &Task) -> String {
+    if matches!(task.status, Some(TaskStatus::Completed)) {
+        "✓ done".to_string()
+    } else {
+        "open".to_string()
+    }
+}
+
+/// Colors one task's table row: completed tasks are dimmed and struck through, open ones
+/// get [`priority_ansi`] on the Priority cell and [`due_ansi`] on the Due cell.
+fn paint_task_row(
+    row: &mut [String],
+    task: &Task,
+    priority_index: Option<usize>,
+    due_index: Option<usize>,
+    today: NaiveDate,
+) {
+    if matches!(task.status, Some(TaskStatus::Completed)) {
+        for cell in row.iter_mut().filter(|cell| !cell.is_empty()) {
+            *cell = paint(cell, ANSI_DONE, true);
+        }
+        return;
+    }
+    let styles = [
+        (priority_index, priority_ansi(task.priority.unwrap_or(0))),
+        (due_index, due_ansi(task, today, &Local)),
+    ];
+    for (index, ansi) in styles {
+        if let (Some(index), Some(ansi)) = (index, ansi) {
+            row[index] = paint(&row[index], ansi, true);
+        }
+    }
+}
+
+fn render_task_table(tasks: &[Task], options: TaskListOptions<'_>, color: bool) -> String {
+    if tasks.is_empty() {
+        return "No items found.\n".to_string();
+    }
+
+    let mut headers = Task::headers();
+    if options.show_status {
+        headers.insert(1, "Status".to_string());
+    }
+    let title_index = headers
+        .iter()
+        .position(|header| header == "Title")
+        .unwrap_or(1);
+    let priority_index = headers.iter().position(|header| header == "Priority");
+    let due_index = headers.iter().position(|header| header == "Due");
+    let completed_index = due_index.map(|index| index + 1);
+    let today = Local::now().date_naive();
+    if let (Some(_), Some(completed_index)) = (options.completed_cell, completed_index) {
+        headers.insert(completed_index, "Completed".to_string());
+    }
+
+    let mut rows = Vec::new();
+    for task in tasks {
+        let mut row = task.rows();
+        if options.show_status {
+            row.insert(1, task_status_cell(task));
+        }
+        if let (Some(due_cell), Some(due_index)) = (options.due_cell, due_index) {
+            row[due_index] = due_cell(task);
+        }
+        if let (Some(completed_cell), Some(completed_index)) =
+            (options.completed_cell, completed_index)
+        {
+            row.insert(completed_index, completed_cell(task));
+        }
+        if color {
+            paint_task_row(&mut row, task, priority_index, due_index, today);
+        }
+        rows.push(row);
+
+        if options.show_subtasks {
+            for label in task_subtask_labels(task) {
+                let mut row = vec![String::new(); headers.len()];
+                row[title_index] = label;
+                rows.push(row);
+            }
+        }
+    }
+    render_table_rows(&headers, &rows, options.table)
+}
+
+fn render_json<T: Serialize>(items: &[T], context: &OutputContext) -> String {
+    let mut output = context
+        .to_json_string(items)
+        .unwrap_or_else(|_| "[]".to_string());
+    output.push('\n');
+    output
+}
+
+fn render_task_lines(tasks: &[Task], options: TaskListOptions<'_>) -> String {
+    let mut output = tasks
+        .iter()
+        .flat_map(|task| {
+            let id = task.id.clone().unwrap_or_default();
+            let mut lines = vec![format!("{}|{}", id, task.title)];
+            if options.show_subtasks {
+                lines.extend(task_subtask_labels(task));
+            }
+            lines
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    if !output.is_empty() {
+        output.push('\n');
+    }
+    output
+}
+
+fn render_project_lines(projects: &[Project]) -> String {
+    let mut output = projects
+        .iter()
+        .map(|project| {
+            let id = project.id.clone().unwrap_or_default();
+            format!("{}|{}", id, project.name)
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    if !output.is_empty() {
+        output.push('\n');
+    }
+    output
+}
+
+fn render_project_summary_lines(summaries: &[ProjectSummary]) -> String {
+    let mut output = summaries
+        .iter()
+        .map(|summary| {
+            format!(
+                "{}|{}|{}|{}",
+                summary.project.id.as_deref().unwrap_or_default(),
+                summary.project.name,
+                count_cell(summary.open_count),
+                count_cell(summary.overdue_count)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    if !output.is_empty() {
+        output.push('\n');
+    }
+    output
+}
+
+/// Backslash-escapes characters that would start emphasis, code, links, HTML, tables,
+/// or tags when a title is pasted into Markdown.
+pub fn escape_markdown(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for ch in value.chars() {
+        if matches!(
+            ch,
+            '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '#' | '|' | '~'
+        ) {
+            escaped.push('\\');
+        }
+        escaped.push(ch);
+    }
+    escaped
+}
+
+fn markdown_checkbox(status: Option<TaskStatus>) -> &'static str {
+    if matches!(status, Some(TaskStatus::Completed)) {
+        "[x]"
+    } else {
+        "[ ]"
+    }
+}
+
+/// `- [ ] Title (due Mar 3) #tag`, checked when the task is completed. The date is the
+/// one in the Due column.
+fn markdown_task_line(task: &Task) -> String {
+    let mut line = format!(
+        "- {} {}",
+        markdown_checkbox(task.status),
+        escape_markdown(&task.title)
+    );
+    let date = task_date_cell(task);
+    if !date.is_empty() {
+        let label = NaiveDate::parse_from_str(&date, "%Y-%m-%d")
+            .map(|date| date.format("%b %-d").to_string())
+            .unwrap_or(date);
+        let kind = if task.due_date.is_some() {
+            "due"
+        } else {
+            "starts"
+        };
+        line.push_str(&format!(" ({} {})", kind, label));
+    }
+    for tag in task.tags.iter().flatten() {
+        line.push_str(&format!(" #{}", tag));
+    }
+    line.push('\n');
+    line
+}
+
+/// A task as a Markdown list item, with its checklist items as nested checkboxes when
+/// `show_subtasks` is set.
+pub fn render_task_markdown(task: &Task, show_subtasks: bool) -> String {
+    let mut output = markdown_task_line(task);
+    if show_subtasks {
+        for item in task.items.iter().flatten() {
+            output.push_str(&format!(
+                "  - {} {}\n",
+                markdown_checkbox(item.status),
+                escape_markdown(item.title.as_deref().unwrap_or_default())
+            ));
+        }
+    }
+    output
+}
+
+fn render_task_markdown_list(tasks: &[Task], options: TaskListOptions<'_>) -> String {
+    tasks
+        .iter()
+        .map(|task| render_task_markdown(task, options.show_subtasks))
+        .collect()
+}
+
+/// `- Work (3 open, 1 overdue)`; the counts are left out when they were not fetched.
+fn markdown_project_line(project: &Project, open: Option<usize>, overdue: Option<usize>) -> String {
+    let closed = if project.closed.unwrap_or(false) {
+        ", closed"
+    } else {
+        ""
+    };
+    let details = match (open, overdue) {
+        (Some(open), Some(overdue)) if overdue > 0 => {
+            format!(" ({} open, {} overdue{})", open, overdue, closed)
+        }
+        (Some(open), _) => format!(" ({} open{})", open, closed),
+        _ if project.closed.unwrap_or(false) => " (closed)".to_string(),
+        _ => String::new(),
+    };
+    format!("- {}{}\n", escape_markdown(&project.name), details)
+}
+
+pub fn render_tasks(
+    tasks: &[Task],
+    format: OutputFormat,
+    options: TaskListOptions<'_>,
+    context: &OutputContext,
+) -> String {
+    match format {
+        OutputFormat::Json => render_json(tasks, context),
+        OutputFormat::Csv | OutputFormat::Tsv => render_delimited(tasks, format, context),
+        OutputFormat::Markdown => render_task_markdown_list(tasks, options),
+        OutputFormat::Human => {
+            if context.is_tty {
+                render_task_table(tasks, options, context.color)
+            } else {
+                render_task_lines(tasks, options)
+            }
+        }
+    }
+}
+
+pub fn render_task_groups(
+    groups: &[TaskGroup],
+    format: OutputFormat,
+    options: TaskListOptions<'_>,
+    context: &OutputContext,
+) -> String {
+    match format {
+        OutputFormat::Json => {
+            let mut output = context
+                .to_json_string(&TaskGroupsDocument { groups })
+                .unwrap_or_else(|_| "{\"groups\":[]}".to_string());
+            output.push('\n');
+            output
+        }
+        // One table for every group, in group order.
+        OutputFormat::Csv | OutputFormat::Tsv => {
+            let tasks: Vec<Task> = groups
+                .iter()
+                .flat_map(|group| group.tasks.iter().cloned())
+                .collect();
+            render_delimited(&tasks, format, context)
+        }
+        OutputFormat::Markdown => groups
+            .iter()
+            .map(|group| {
+                format!(
+                    "## {} ({})\n\n{}",
+                    escape_markdown(&group.key),
+                    group.tasks.len(),
+                    render_task_markdown_list(&group.tasks, options)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        OutputFormat::Human => {
+            if groups.is_empty() {
+                return "No items found.\n".to_string();
+            }
+
+            groups
+                .iter()
+                .map(|group| {
+                    format!(
+                        "# {} ({})\n{}",
+                        group.key,
+                        group.tasks.len(),
+                        render_tasks(&group.tasks, format, options, context)
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        }
+    }
+}
+
+fn render_task_search_results(
+    results: &[TaskSearchResult],
+    format: OutputFormat,
+    context: &OutputContext,
+    layout: TableLayout,
+) -> String {
+    match format {
+        OutputFormat::Json => render_json(results, context),
+        OutputFormat::Csv | OutputFormat::Tsv => render_delimited(results, format, context),
+        OutputFormat::Markdown => results
+            .iter()
+            .map(|result| markdown_task_line(&result.task))
+            .collect(),
+        OutputFormat::Human => {
+            if context.is_tty {
+                render_table(results, layout)
+            } else {
+                let mut output = results
+                    .iter()
+                    .map(|result| {
+                        let id = result.task.id.clone().unwrap_or_default();
+                        format!("{}|{}|{}", id, result.task.title, result.project_name)
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                if !output.is_empty() {
+                    output.push('\n');
+                }
+                output
+            }
+        }
+    }
+}
+
+fn render_batch_add_results(
+    results: &[BatchAddResult],
+    format: OutputFormat,
+    context: &OutputContext,
+    layout: TableLayout,
+) -> String {
+    match format {
+        OutputFormat::Json => render_json(results, context),
+        OutputFormat::Csv | OutputFormat::Tsv => render_delimited(results, format, context),
+        OutputFormat::Human | OutputFormat::Markdown => {
+            if context.is_tty {
+                render_table(results, layout)
+            } else {
+                results
+                    .iter()
+                    .map(|result| {
+                        format!(
+                            "{}|{}|{}|{}\n",
+                            result.line,
+                            result.id.as_deref().unwrap_or_default(),
+                            result.title,
+                            result.status_cell()
+                        )
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+fn render_projects(
+    projects: &[Project],
+    format: OutputFormat,
+    context: &OutputContext,
+    layout: TableLayout,
+) -> String {
+    match format {
+        OutputFormat::Json => render_json(projects, context),
+        OutputFormat::Csv | OutputFormat::Tsv => render_delimited(projects, format, context),
+        OutputFormat::Markdown => projects
+            .iter()
+            .map(|project| markdown_project_line(project, None, None))
+            .collect(),
+        OutputFormat::Human => {
+            if context.is_tty {
+                render_table(projects, layout)
+            } else {
+                render_project_lines(projects)
+            }
+        }
+    }
+}
+
+fn render_project_tree(
+    folders: &[ProjectFolder],
+    format: OutputFormat,
+    context: &OutputContext,
+) -> String {
+    match format {
+        OutputFormat::Json => {
+            let mut output = context
+                .to_json_string(&ProjectTreeDocument { folders })
+                .unwrap_or_else(|_| "{\"folders\":[]}".to_string());
+            output.push('\n');
+            output
+        }
+        // Flat rows in tree order; the `group` field names the folder.
+        OutputFormat::Csv | OutputFormat::Tsv => {
+            let summaries: Vec<ProjectSummary> = folders
+                .iter()
+                .flat_map(|folder| folder.projects.iter().cloned())
+                .collect();
+            render_delimited(&summaries, format, context)
+        }
+        OutputFormat::Markdown => folders
+            .iter()
+            .map(|folder| {
+                let mut section = format!("## {}\n\n", escape_markdown(&folder.name));
+                for summary in &folder.projects {
+                    section.push_str(&markdown_project_line(
+                        &summary.project,
+                        summary.open_count,
+                        summary.overdue_count,
+                    ));
+                }
+                section
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        OutputFormat::Human => {
+            if folders.is_empty() {
+                return "No items found.\n".to_string();
+            }
+
+            folders
+                .iter()
+                .map(|folder| {
+                    let mut section = format!("{} ({})\n", folder.name, folder.projects.len());
+                    for summary in &folder.projects {
+                        let project = &summary.project;
+                        let closed = if project.closed.unwrap_or(false) {
+                            " (closed)"
+                        } else {
+                            ""
+                        };
+                        let counts = match (summary.open_count, summary.overdue_count) {
+                            (Some(open), Some(overdue)) if overdue > 0 => {
+                                format!("  {} open, {} overdue", open, overdue)
+                            }
+                            (Some(open), _) => format!("  {} open", open),
+                            _ => String::new(),
+                        };
+                        section.push_str(&format!(
+                            "  {}{}  {}{}\n",
+                            project.name,
+                            closed,
+                            project.id.as_deref().unwrap_or_default(),
+                            counts
+                        ));
+                    }
+                    section
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        }
+    }
+}
+
+fn render_columns(
+    columns: &[Column],
+    format: OutputFormat,
+    context: &OutputContext,
+    layout: TableLayout,
+) -> String {
+    match format {
+        OutputFormat::Json => render_json(columns, context),
+        OutputFormat::Csv | OutputFormat::Tsv => render_delimited(columns, format, context),
+        OutputFormat::Human | OutputFormat::Markdown if context.is_tty => {
+            render_table(columns, layout)
+        }
+        OutputFormat::Human | OutputFormat::Markdown => columns
+            .iter()
+            .map(|column| format!("{}|{}\n", column.id, column.name))
+            .collect(),
+    }
+}
+
+fn render_project_summaries(
+    summaries: &[ProjectSummary],
+    format: OutputFormat,
+    context: &OutputContext,
+    layout: TableLayout,
+) -> String {
+    match format {
+        OutputFormat::Json => render_json(summaries, context),
+        OutputFormat::Csv | OutputFormat::Tsv => render_delimited(summaries, format, context),
+        OutputFormat::Markdown => summaries
+            .iter()
+            .map(|summary| {
+                markdown_project_line(&summary.project, summary.open_count, summary.overdue_count)
+            })
+            .collect(),
+        OutputFormat::Human => {
+            if context.is_tty {
+                render_table(summaries, layout)
+            } else {
+                render_project_summary_lines(summaries)
+            }
+        }
+    }
+}
+
+pub const ANSI_RED: &str = "\x1b[31m";
+pub const ANSI_GREEN: &str = "\x1b[32m";
+pub const ANSI_YELLOW: &str = "\x1b[33m";
+pub const ANSI_BLUE: &str = "\x1b[34m";
+pub const ANSI_DIM: &str = "\x1b[2m";
+pub const ANSI_RESET: &str = "\x1b[0m";
+pub const ANSI_BOLD_RED: &str = "\x1b[1;31m";
+/// Completed tasks: dimmed and struck through.
+pub const ANSI_DONE: &str = "\x1b[2;9m";
+
+/// The color for a priority, used by the task table, `tt task show`, and the agenda: high
+/// stands out in bold red, medium is yellow, low is blue.
+pub fn priority_ansi(priority: i32) -> Option<&'static str> {
+    match priority {
+        5 => Some(ANSI_BOLD_RED),
+        3 => Some(ANSI_YELLOW),
+        1 => Some(ANSI_BLUE),
+        _ => None,
+    }
+}
+
+/// The color for an open task's due date: red once it is before `today`, yellow on
+/// `today`, both as local days in `tz`. Completed tasks and later dates stay plain.
+pub fn due_ansi<Tz: TimeZone>(task: &Task, today: NaiveDate, tz: &Tz) -> Option<&'static str> {
+    if matches!(task.status, Some(TaskStatus::Completed)) {
+        return None;
+    }
+    let value = task.due_date.as_deref()?;
+    let due = match DateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f%z")
+        .or_else(|_| DateTime::parse_from_rfc3339(value))
+    {
+        Ok(due) => due.with_timezone(tz).date_naive(),
+        Err(_) => NaiveDate::parse_from_str(value.get(..10)?, "%Y-%m-%d").ok()?,
+    };
+    match due.cmp(&today) {
+        std::cmp::Ordering::Less => Some(ANSI_RED),
+        std::cmp::Ordering::Equal => Some(ANSI_YELLOW),
+        std::cmp::Ordering::Greater => None,
+    }
+}
+
+fn render_habit_table(habits: &[HabitSummary], layout: TableLayout, color: bool) -> String {
+    if habits.is_empty() {
+        return "No items found.\n".to_string();
+    }
+
+    let today_index = HabitSummary::headers().len() - 1;
+    let rows: Vec<Vec<String>> = habits
+        .iter()
+        .map(|habit| {
+            let mut row = habit.rows();
+            let ansi = match habit.checked_in_today {
+                Some(true) => Some(ANSI_GREEN),
+                Some(false) => Some(ANSI_DIM),
+                None => None,
+            };
+            if let Some(ansi) = ansi {
+                row[today_index] = paint(&row[today_index], ansi, color);
+            }
+            row
+        })
+        .collect();
+    render_table_rows(&HabitSummary::headers(), &rows, layout)
+}
+
+fn render_habit_lines(habits: &[HabitSummary]) -> String {
+    let mut output = habits
+        .iter()
+        .map(|summary| {
+            format!(
+                "{}|{}|{}|{}",
+                summary.habit.id.as_deref().unwrap_or_default(),
+                summary.habit.title,
+                summary
+                    .streak
+                    .map(|streak| streak.to_string())
+                    .unwrap_or_default(),
+                habit_today_cell(summary.checked_in_today)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    if !output.is_empty() {
+        output.push('\n');
+    }
+    output
+}
+
+fn render_habits(
+    habits: &[HabitSummary],
+    format: OutputFormat,
+    context: &OutputContext,
+    layout: TableLayout,
+) -> String {
+    match format {
+        OutputFormat::Json => render_json(habits, context),
+        OutputFormat::Csv | OutputFormat::Tsv => render_delimited(habits, format, context),
+        OutputFormat::Human | OutputFormat::Markdown => {
+            if context.is_tty {
+                render_habit_table(habits, layout, context.color)
+            } else {
+                render_habit_lines(habits)
+            }
+        }
+    }
+}
+
+fn render_habit_checkin(
+    outcome: &HabitCheckinOutcome,
+    format: MessageFormat,
+    context: &OutputContext,
+) -> String {
+    if format == MessageFormat::Json {
+        let mut output = context
+            .to_json_string(outcome)
+            .unwrap_or_else(|_| "{}".to_string());
+        output.push('\n');
+        return output;
+    }
+
+    let verb = if outcome.already_checked_in {
+        "Already checked in"
+    } else {
+        "Checked in"
+    };
+    let mut line = format!("{}: {} ({})", verb, outcome.title, outcome.date);
+    // Plain yes/no habits have a goal of 1; progress only means something for real goals.
+    if let (Some(value), Some(goal)) = (outcome.value, outcome.goal) {
+        if goal != 1.0 || outcome.unit.is_some() {
+            line.push_str(&format!(
+                ", {}/{}",
+                format_habit_goal(value),
+                format_habit_goal(goal)
+            ));
+            if let Some(unit) = outcome.unit.as_deref() {
+                line.push_str(&format!(" {}", unit));
+            }
+        }
+    }
+    if let Some(streak) = outcome.current_streak {
+        let days = if streak == 1 { "day" } else { "days" };
+        line.push_str(&format!(", streak {} {}", streak, days));
+    }
+    line.push('\n');
+    line
+}
+
+fn format_streak_days(days: u32) -> String {
+    format!("{} {}", days, if days == 1 { "day" } else { "days" })
+}
+
+fn render_habit_stats(
+    stats: &HabitStats,
+    format: MessageFormat,
+    context: &OutputContext,
+) -> String {
+    if format == MessageFormat::Json {
+        let mut output = context
+            .to_json_string(stats)
+            .unwrap_or_else(|_| "{}".to_string());
+        output.push('\n');
+        return output;
+    }
+
+    let rate_line = |rate: &HabitRate| match rate.percent() {
+        Some(percent) => format!(
+            "  Last {} days:   {}% ({}/{})\n",
+            rate.days, percent, rate.completed, rate.scheduled
+        ),
+        None => format!("  Last {} days:   -\n", rate.days),
+    };
+    let mut output = format!("{}\n", stats.title);
+    output.push_str(&format!(
+        "  Current streak: {}\n",
+        format_streak_days(stats.current_streak)
+    ));
+    output.push_str(&format!(
+        "  Longest streak: {}\n",
+        format_streak_days(stats.longest_streak)
+    ));
+    output.push_str(&rate_line(&stats.last_30_days));
+    output.push_str(&rate_line(&stats.last_90_days));
+    output.push_str(&format!("  Recent:         {}\n", stats.recent));
+    output
+}
+
+/// Serializes an error and its causes as `{"error": ..., "context": [...]}` for stderr.
+pub fn render_error_json(err: &anyhow::Error) -> String {
+    let document = ErrorDocument {
+        error: err.to_string(),
+        context: err.chain().skip(1).map(ToString::to_string).collect(),
+    };
+    let mut output = serde_json::to_string(&document)
+        .unwrap_or_else(|_| "{\"error\":\"unknown error\",\"context\":[]}".to_string());
+    output.push('\n');
+    output
+}
+
+pub fn print_task_search_results(
+    results: &[TaskSearchResult],
+    format: OutputFormat,
+    context: &OutputContext,
+) {
+    let _ = io::Write::write_all(
+        &mut io::stdout(),
+        render_task_search_results(results, format, context, TableLayout::for_terminal(false))
+            .as_bytes(),
+    );
+}
+
+pub fn print_batch_add_results(
+    results: &[BatchAddResult],
+    format: OutputFormat,
+    context: &OutputContext,
+) {
+    let _ = io::Write::write_all(
+        &mut io::stdout(),
+        render_batch_add_results(results, format, context, TableLayout::for_terminal(false))
+            .as_bytes(),
+    );
+}
+
+pub fn print_projects(projects: &[Project], format: OutputFormat, context: &OutputContext) {
+    let _ = io::Write::write_all(
+        &mut io::stdout(),
+        render_projects(projects, format, context, TableLayout::for_terminal(false)).as_bytes(),
+    );
+}
+
+pub fn print_habit_stats(stats: &HabitStats, format: MessageFormat, context: &OutputContext) {
+    let _ = io::Write::write_all(
+        &mut io::stdout(),
+        render_habit_stats(stats, format, context).as_bytes(),
+    );
+}
+
+pub fn print_habit_checkin(
+    outcome: &HabitCheckinOutcome,
+    format: MessageFormat,
+    context: &OutputContext,
+) {
+    let _ = io::Write::write_all(
+        &mut io::stdout(),
+        render_habit_checkin(outcome, format, context).as_bytes(),
+    );
+}
+
+pub fn print_project_tree(
+    folders: &[ProjectFolder],
+    format: OutputFormat,
+    context: &OutputContext,
+) {
+    let _ = io::Write::write_all(
+        &mut io::stdout(),
+        render_project_tree(folders, format, context).as_bytes(),
+    );
+}
+
+pub fn print_columns(columns: &[Column], format: OutputFormat, context: &OutputContext) {
+    let _ = io::Write::write_all(
+        &mut io::stdout(),
+        render_columns(columns, format, context, TableLayout::for_terminal(false)).as_bytes(),
+    );
+}
+
+pub fn print_project_summaries(
+    summaries: &[ProjectSummary],
+    format: OutputFormat,
+    context: &OutputContext,
+) {
+    let _ = io::Write::write_all(
+        &mut io::stdout(),
+        render_project_summaries(summaries, format, context, TableLayout::for_terminal(false))
+            .as_bytes(),
+    );
+}
+
+fn render_tag_counts(
+    counts: &[TagCount],
+    format: OutputFormat,
+    context: &OutputContext,
+    layout: TableLayout,
+) -> String {
+    match format {
+        OutputFormat::Json => render_json(counts, context),
+        OutputFormat::Csv | OutputFormat::Tsv => render_delimited(counts, format, context),
+        OutputFormat::Human | OutputFormat::Markdown if context.is_tty => {
+            render_table(counts, layout)
+        }
+        OutputFormat::Human | OutputFormat::Markdown => counts
+            .iter()
+            .map(|count| format!("{}|{}|{}\n", count.tag, count.open, count.total))
+            .collect(),
+    }
+}
+
+pub fn print_tag_counts(counts: &[TagCount], format: OutputFormat, context: &OutputContext) {
+    let _ = io::Write::write_all(
+        &mut io::stdout(),
+        render_tag_counts(counts, format, context, TableLayout::for_terminal(false)).as_bytes(),
+    );
+}
+
+pub fn print_habits(habits: &[HabitSummary], format: OutputFormat, context: &OutputContext) {
+    let _ = io::Write::write_all(
+        &mut io::stdout(),
+        render_habits(habits, format, context, TableLayout::for_terminal(false)).as_bytes(),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A terminal with colors off, so tables can be compared as plain text.
+    fn terminal() -> OutputContext {
+        OutputContext {
+            is_tty: true,
+            color: false,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn due_and_priority_colors_follow_one_set_of_rules() {
+        use chrono::Utc;
+
+        let today = NaiveDate::from_ymd_opt(2026, 3, 8).unwrap();
+        let due = |due_date: &str| Task {
+            due_date: Some(due_date.to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            due_ansi(&due("2026-03-07T12:00:00.000+0000"), today, &Utc),
+            Some(ANSI_RED)
+        );
+        assert_eq!(
+            due_ansi(&due("2026-03-08T23:00:00.000+0000"), today, &Utc),
+            Some(ANSI_YELLOW)
+        );
+        assert_eq!(
+            due_ansi(&due("2026-03-09T00:00:00.000+0000"), today, &Utc),
+            None
+        );
+        // 23:00 UTC on the 7th is already the 8th in UTC+2.
+        let east = chrono::FixedOffset::east_opt(2 * 3600).unwrap();
+        assert_eq!(
+            due_ansi(&due("2026-03-07T23:00:00.000+0000"), today, &east),
+            Some(ANSI_YELLOW)
+        );
+        let done = Task {
+            status: Some(TaskStatus::Completed),
+            ..due("2026-03-01T00:00:00.000+0000")
+        };
+        assert_eq!(due_ansi(&done, today, &Utc), None);
+
+        assert_eq!(priority_ansi(5), Some(ANSI_BOLD_RED));
+        assert_eq!(priority_ansi(3), Some(ANSI_YELLOW));
+        assert_eq!(priority_ansi(1), Some(ANSI_BLUE));
+        assert_eq!(priority_ansi(0), None);
+    }
+
+    #[test]
+    fn task_table_colors_overdue_priority_and_completed_rows_only_with_color() {
+        let tasks = vec![
+            Task {
+                id: Some("task-1".to_string()),
+                title: "Overdue".to_string(),
+                priority: Some(5),
+                due_date: Some("2020-01-01T00:00:00.000+0000".to_string()),
+                ..Default::default()
+            },
+            Task {
+                id: Some("task-2".to_string()),
+                title: "Finished".to_string(),
+                status: Some(TaskStatus::Completed),
+                ..Default::default()
+            },
+        ];
+        let options = TaskListOptions {
+            table: TableLayout {
+                max_width: None,
+                wrap: false,
+            },
+            ..Default::default()
+        };
+
+        let color_terminal = OutputContext {
+            is_tty: true,
+            color: true,
+            ..Default::default()
+        };
+        let colored = render_tasks(&tasks, OutputFormat::Human, options, &color_terminal);
+        assert!(colored.contains("\x1b[1;31mHigh\x1b[0m"));
+        assert!(colored.contains("\x1b[31m2020-01-01\x1b[0m"));
+        assert!(colored.contains("\x1b[2;9mFinished\x1b[0m"));
+        let widths: Vec<usize> = colored.lines().map(visible_width).collect();
+        assert!(widths.iter().all(|width| *width == widths[0]));
+
+        let plain = render_tasks(&tasks, OutputFormat::Human, options, &terminal());
+        assert!(!plain.contains('\x1b'));
+        assert_eq!(
+            plain.lines().map(visible_width).collect::<Vec<_>>(),
+            colored.lines().map(visible_width).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn should_colorize_lets_color_choice_override_tty_and_no_color() {
+        assert!(should_colorize(ColorChoice::Auto, true, false));
+        assert!(!should_colorize(ColorChoice::Auto, false, false));
+        assert!(!should_colorize(ColorChoice::Auto, true, true));
+        assert!(should_colorize(ColorChoice::Always, false, true));
+        assert!(!should_colorize(ColorChoice::Never, true, false));
+
+        assert_eq!(paint("done", ANSI_GREEN, true), "\x1b[32mdone\x1b[0m");
+        assert_eq!(paint("done", ANSI_GREEN, false), "done");
+    }
+
+    #[test]
+    fn task_rows_format_priority_due_date_and_missing_id() {
+        let task = Task {
+            title: "Ship release".to_string(),
+            priority: Some(5),
+            due_date: Some("2026-03-08T09:00:00Z".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            task.rows(),
+            vec![
+                "".to_string(),
                 "Ship release".to_string(),
                 "High".to_string(),
                 "2026-03-08".to_string(),
@@ -270,86 +2108,928 @@ mod tests {
     }
 
     #[test]
-    fn task_rows_fall_back_to_start_date_and_note_preview() {
-        let task = Task {
-            title: "Review notes".to_string(),
-            start_date: Some("2026-03-09T00:00:00.000+0000".to_string()),
-            content: Some(
-                "This is a long note that should be truncated once it exceeds the preview width."
-                    .to_string(),
-            ),
+    fn task_rows_fall_back_to_start_date_and_note_preview() {
+        let task = Task {
+            title: "Review notes".to_string(),
+            start_date: Some("2026-03-09T00:00:00.000+0000".to_string()),
+            content: Some(
+                "This is a long note that should be truncated once it exceeds the preview width."
+                    .to_string(),
+            ),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            task.rows(),
+            vec![
+                "".to_string(),
+                "Review notes".to_string(),
+                "".to_string(),
+                "2026-03-09".to_string(),
+                "This is a long note that should be trunc...".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn task_note_prefers_content_then_desc() {
+        let with_desc_only = Task {
+            title: "One".to_string(),
+            desc: Some("Description".to_string()),
+            ..Default::default()
+        };
+        let with_both = Task {
+            title: "Two".to_string(),
+            content: Some("Content".to_string()),
+            desc: Some("Description".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(task_note_cell(&with_desc_only), "Description");
+        assert_eq!(task_note_cell(&with_both), "Content");
+    }
+
+    #[test]
+    fn project_rows_truncate_long_ids() {
+        let project = Project {
+            id: Some("1234567890abcdef".to_string()),
+            name: "Inbox".to_string(),
+            color: Some("#ff0000".to_string()),
+            view_mode: Some("list".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            project.rows(),
+            vec![
+                "12345678...".to_string(),
+                "Inbox".to_string(),
+                "#ff0000".to_string(),
+                "list".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn render_table_handles_empty_lists() {
+        let tasks: Vec<Task> = Vec::new();
+        assert_eq!(
+            render_table(&tasks, TableLayout::default()),
+            "No items found.\n"
+        );
+    }
+
+    fn long_title_task() -> Task {
+        Task {
+            id: Some("64f1a2b3c4d5e6f708192a3b".to_string()),
+            title: "Draft the quarterly planning document for the whole team".to_string(),
+            priority: Some(5),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn narrow_tables_truncate_the_title_and_keep_the_id() {
+        let layout = TableLayout {
+            max_width: Some(70),
+            wrap: false,
+        };
+        let output = render_table(&[long_title_task()], layout);
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert!(lines.iter().all(|line| visible_width(line) <= 70));
+        assert!(lines[2].contains("64f1a2b3c4d5e6f708192a3b"));
+        assert!(lines[2].contains("Draft the quar…"));
+        assert!(lines[2].contains("High"));
+    }
+
+    #[test]
+    fn display_width_counts_wide_and_zero_width_characters() {
+        assert_eq!(visible_width("🚀 Launch plan"), 14);
+        assert_eq!(visible_width("会議の準備"), 10);
+        assert_eq!(visible_width("Cafe\u{301}"), 4);
+        assert_eq!(visible_width("👩\u{200D}💻"), 2);
+        assert_eq!(visible_width("👍🏽 ok"), 5);
+        assert_eq!(visible_width("❤\u{FE0F} x"), 4);
+        assert_eq!(visible_width("ก\u{0E49}\u{0E33}"), 2);
+        assert_eq!(visible_width("\x1b[31m会議\x1b[0m"), 4);
+
+        // Cuts fall between whole characters: accents and joined emoji stay together, and
+        // a wide character that would overflow is left out.
+        assert_eq!(truncate_cell("Cafe\u{301} menu", 5), "Cafe\u{301}…");
+        assert_eq!(truncate_cell("👩\u{200D}💻 Pair", 4), "👩\u{200D}💻 …");
+        assert_eq!(truncate_cell("会議の準備", 4), "会…");
+        assert_eq!(
+            wrap_cell("会議の準備をする", 5),
+            vec!["会議", "の準", "備を", "する"]
+        );
+    }
+
+    #[test]
+    fn tables_with_emoji_combining_marks_and_cjk_stay_aligned() {
+        let titles = [
+            "🚀 Launch plan",
+            "Cafe\u{301} menu review",
+            "会議の準備",
+            "👩\u{200D}💻 Pair on parser",
+            "Plain ASCII title",
+        ];
+        let tasks: Vec<Task> = titles
+            .iter()
+            .map(|title| Task {
+                id: Some("task-1".to_string()),
+                title: title.to_string(),
+                tags: Some(vec!["✨".to_string()]),
+                ..Default::default()
+            })
+            .collect();
+
+        for max_width in [None, Some(51), Some(47)] {
+            for wrap in [false, true] {
+                let layout = TableLayout { max_width, wrap };
+                let output = render_table(&tasks, layout);
+                let widths: Vec<usize> = output.lines().map(visible_width).collect();
+                assert!(
+                    widths.iter().all(|width| *width == widths[0]),
+                    "{:?} {} {:?}\n{}",
+                    max_width,
+                    wrap,
+                    widths,
+                    output
+                );
+                if let Some(max_width) = max_width {
+                    assert!(widths[0] <= max_width, "{}", output);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn wrapped_tables_continue_long_titles_on_extra_lines() {
+        let layout = TableLayout {
+            max_width: Some(70),
+            wrap: true,
+        };
+        let output = render_table(&[long_title_task()], layout);
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert!(lines.iter().all(|line| visible_width(line) <= 70));
+        assert!(lines.len() > 3);
+        assert!(lines[3].starts_with("|                          | "));
+        let title: Vec<&str> = lines[2..]
+            .iter()
+            .map(|line| line.split('|').nth(2).unwrap().trim())
+            .collect();
+        assert_eq!(
+            title.join(" "),
+            "Draft the quarterly planning document for the whole team"
+        );
+    }
+
+    #[test]
+    fn batch_add_results_show_created_ids_and_failures() {
+        let results = vec![
+            BatchAddResult {
+                line: 1,
+                title: "Buy milk".to_string(),
+                id: Some("task-1".to_string()),
+                project_id: Some("inbox".to_string()),
+                error: None,
+            },
+            BatchAddResult {
+                line: 3,
+                title: "Call ~Nowhere".to_string(),
+                id: None,
+                project_id: None,
+                error: Some("List not found: Nowhere".to_string()),
+            },
+        ];
+
+        assert_eq!(
+            render_batch_add_results(
+                &results,
+                OutputFormat::Human,
+                &OutputContext::default(),
+                TableLayout::default()
+            ),
+            "1|task-1|Buy milk|created\n3||Call ~Nowhere|failed: List not found: Nowhere\n"
+        );
+        let json: serde_json::Value = serde_json::from_str(&render_batch_add_results(
+            &results,
+            OutputFormat::Json,
+            &OutputContext::default(),
+            TableLayout::default(),
+        ))
+        .unwrap();
+        assert_eq!(json[0]["id"], "task-1");
+        assert_eq!(json[1]["error"], "List not found: Nowhere");
+        assert!(json[1].get("id").is_none());
+    }
+
+    #[test]
+    fn truncating_colored_cells_resets_the_color() {
+        let cell = format!("{}Done with it all{}", ANSI_GREEN, ANSI_RESET);
+        let truncated = truncate_cell(&cell, 6);
+        assert_eq!(visible_width(&truncated), 6);
+        assert_eq!(truncated, format!("{}Done {}…", ANSI_GREEN, ANSI_RESET));
+    }
+
+    #[test]
+    fn render_tasks_uses_pipe_output_for_non_tty_human_mode() {
+        let tasks = vec![Task {
+            id: Some("task-1".to_string()),
+            title: "Write tests".to_string(),
+            ..Default::default()
+        }];
+
+        assert_eq!(
+            render_tasks(
+                &tasks,
+                OutputFormat::Human,
+                TaskListOptions::default(),
+                &OutputContext::default()
+            ),
+            "task-1|Write tests\n"
+        );
+    }
+
+    fn task_with_checklist() -> Task {
+        Task {
+            id: Some("task-1".to_string()),
+            title: "Pack".to_string(),
+            items: Some(vec![
+                ChecklistItem {
+                    title: Some("Passport".to_string()),
+                    status: Some(TaskStatus::Completed),
+                    ..Default::default()
+                },
+                ChecklistItem {
+                    title: Some("Chargers".to_string()),
+                    ..Default::default()
+                },
+            ]),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn render_tasks_shows_subtasks_only_when_requested() {
+        let tasks = vec![task_with_checklist()];
+        let options = TaskListOptions {
+            show_subtasks: true,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            render_tasks(
+                &tasks,
+                OutputFormat::Human,
+                TaskListOptions::default(),
+                &OutputContext::default()
+            ),
+            "task-1|Pack\n"
+        );
+        assert_eq!(
+            render_tasks(
+                &tasks,
+                OutputFormat::Human,
+                options,
+                &OutputContext::default()
+            ),
+            "task-1|Pack\n  [x] Passport\n  [ ] Chargers\n"
+        );
+
+        let json = render_tasks(
+            &tasks,
+            OutputFormat::Json,
+            options,
+            &OutputContext::default(),
+        );
+        assert_eq!(json, render_json(&tasks, &OutputContext::default()));
+    }
+
+    #[test]
+    fn render_task_table_keeps_columns_aligned_for_subtask_rows() {
+        let table = render_tasks(
+            &[task_with_checklist()],
+            OutputFormat::Human,
+            TaskListOptions {
+                show_subtasks: true,
+                ..Default::default()
+            },
+            &terminal(),
+        );
+        let lines: Vec<&str> = table.lines().collect();
+
+        assert_eq!(lines.len(), 5);
+        assert!(lines[3].contains("|   [x] Passport |"));
+        assert!(lines[4].contains("|   [ ] Chargers |"));
+        let widths: Vec<usize> = lines.iter().map(|line| line.len()).collect();
+        assert!(widths.iter().all(|width| *width == widths[0]));
+    }
+
+    #[test]
+    fn csv_output_quotes_and_tsv_output_escapes_special_characters() {
+        let tasks = vec![Task {
+            id: Some("task-1".to_string()),
+            title: "Milk, eggs".to_string(),
+            content: Some("Say \"hi\"\nthen\tleave".to_string()),
+            priority: Some(5),
+            tags: Some(vec!["shop".to_string(), "home".to_string()]),
             ..Default::default()
-        };
+        }];
 
+        let csv = render_tasks(
+            &tasks,
+            OutputFormat::Csv,
+            TaskListOptions::default(),
+            &terminal(),
+        );
+        let mut lines = csv.lines();
         assert_eq!(
-            task.rows(),
-            vec![
-                "".to_string(),
-                "Review notes".to_string(),
-                "".to_string(),
-                "2026-03-09".to_string(),
-                "This is a long note that should be trunc...".to_string(),
-            ]
+            lines.next(),
+            Some("id,title,status,priority,due,start,project,tags,content,completed,created")
+        );
+        assert_eq!(
+            csv.split_once('\n').unwrap().1,
+            "task-1,\"Milk, eggs\",open,High,,,,\"shop,home\",\"Say \"\"hi\"\"\nthen\tleave\",,\n"
         );
+
+        let tsv = render_delimited_fields(
+            &tasks,
+            OutputFormat::Tsv,
+            &["title", "content", "tags"],
+            false,
+        );
+        assert_eq!(tsv, "Milk, eggs\tSay \"hi\"\\nthen\\tleave\tshop,home\n");
+    }
+
+    fn markdown_sample_tasks() -> Vec<Task> {
+        vec![
+            Task {
+                id: Some("task-1".to_string()),
+                title: "Fix *bold* [link] #1 | `code`".to_string(),
+                due_date: Some("2026-03-03T09:00:00.000+0000".to_string()),
+                tags: Some(vec!["work".to_string(), "urgent".to_string()]),
+                items: Some(vec![
+                    ChecklistItem {
+                        title: Some("Write_test".to_string()),
+                        status: Some(TaskStatus::Completed),
+                        ..Default::default()
+                    },
+                    ChecklistItem {
+                        title: Some("Ship".to_string()),
+                        ..Default::default()
+                    },
+                ]),
+                ..Default::default()
+            },
+            Task {
+                id: Some("task-2".to_string()),
+                title: "Book flights".to_string(),
+                start_date: Some("2026-12-24T00:00:00.000+0000".to_string()),
+                status: Some(TaskStatus::Completed),
+                ..Default::default()
+            },
+        ]
     }
 
     #[test]
-    fn task_note_prefers_content_then_desc() {
-        let with_desc_only = Task {
-            title: "One".to_string(),
-            desc: Some("Description".to_string()),
+    fn markdown_tasks_render_as_escaped_checkbox_lists() {
+        let tasks = markdown_sample_tasks();
+
+        assert_eq!(
+            render_tasks(
+                &tasks,
+                OutputFormat::Markdown,
+                TaskListOptions::default(),
+                &terminal()
+            ),
+            "- [ ] Fix \\*bold\\* \\[link\\] \\#1 \\| \\`code\\` (due Mar 3) #work #urgent\n\
+             - [x] Book flights (starts Dec 24)\n"
+        );
+        assert_eq!(
+            render_task_markdown(&tasks[0], true),
+            "- [ ] Fix \\*bold\\* \\[link\\] \\#1 \\| \\`code\\` (due Mar 3) #work #urgent\n\
+             \x20 - [x] Write\\_test\n\
+             \x20 - [ ] Ship\n"
+        );
+
+        let groups = vec![
+            TaskGroup {
+                key: "Work".to_string(),
+                tasks: tasks[..1].to_vec(),
+            },
+            TaskGroup {
+                key: "Personal".to_string(),
+                tasks: tasks[1..].to_vec(),
+            },
+        ];
+        assert_eq!(
+            render_task_groups(
+                &groups,
+                OutputFormat::Markdown,
+                TaskListOptions::default(),
+                &OutputContext::default()
+            ),
+            "## Work (1)\n\n\
+             - [ ] Fix \\*bold\\* \\[link\\] \\#1 \\| \\`code\\` (due Mar 3) #work #urgent\n\
+             \n\
+             ## Personal (1)\n\n\
+             - [x] Book flights (starts Dec 24)\n"
+        );
+    }
+
+    #[test]
+    fn markdown_projects_render_as_bullets_with_counts() {
+        let project = |name: &str, closed: bool| Project {
+            name: name.to_string(),
+            closed: Some(closed),
             ..Default::default()
         };
-        let with_both = Task {
-            title: "Two".to_string(),
-            content: Some("Content".to_string()),
-            desc: Some("Description".to_string()),
+        let summaries = vec![
+            ProjectSummary {
+                project: project("Work", false),
+                open_count: Some(3),
+                overdue_count: Some(1),
+            },
+            ProjectSummary {
+                project: project("Side_projects", false),
+                open_count: Some(0),
+                overdue_count: Some(0),
+            },
+            ProjectSummary {
+                project: project("Archive", true),
+                open_count: Some(2),
+                overdue_count: Some(0),
+            },
+        ];
+
+        assert_eq!(
+            render_project_summaries(
+                &summaries,
+                OutputFormat::Markdown,
+                &terminal(),
+                TableLayout::default()
+            ),
+            "- Work (3 open, 1 overdue)\n\
+             - Side\\_projects (0 open)\n\
+             - Archive (2 open, closed)\n"
+        );
+        assert_eq!(
+            render_projects(
+                &[project("Work", false), project("Archive", true)],
+                OutputFormat::Markdown,
+                &terminal(),
+                TableLayout::default()
+            ),
+            "- Work\n- Archive (closed)\n"
+        );
+
+        let folders = vec![ProjectFolder {
+            folder_id: Some("g1".to_string()),
+            name: "Clients".to_string(),
+            projects: summaries[..1].to_vec(),
+        }];
+        assert_eq!(
+            render_project_tree(&folders, OutputFormat::Markdown, &OutputContext::default()),
+            "## Clients\n\n- Work (3 open, 1 overdue)\n"
+        );
+    }
+
+    #[test]
+    fn delimited_fields_pick_columns_and_leave_unknown_ones_empty() {
+        let projects = vec![Project {
+            id: Some("0123456789abcdef".to_string()),
+            name: "Work".to_string(),
+            closed: Some(true),
             ..Default::default()
-        };
+        }];
 
-        assert_eq!(task_note_cell(&with_desc_only), "Description");
-        assert_eq!(task_note_cell(&with_both), "Content");
+        assert_eq!(
+            render_delimited_fields(
+                &projects,
+                OutputFormat::Csv,
+                &["name", "id", "title", "closed"],
+                true
+            ),
+            "name,id,title,closed\nWork,0123456789abcdef,,true\n"
+        );
+        assert_eq!(
+            render_delimited_fields(&Vec::<Project>::new(), OutputFormat::Csv, &["id"], true),
+            "id\n"
+        );
+
+        assert_eq!(parse_field_name(" Due "), Ok("due".to_string()));
+        assert_eq!(parse_field_name("overdue"), Ok("overdue".to_string()));
+        let err = parse_field_name("colour").unwrap_err();
+        assert!(
+            err.starts_with("Unknown field 'colour'. Fields: archived, closed, color,"),
+            "{}",
+            err
+        );
     }
 
     #[test]
-    fn project_rows_truncate_long_ids() {
-        let project = Project {
-            id: Some("1234567890abcdef".to_string()),
-            name: "Inbox".to_string(),
-            color: Some("#ff0000".to_string()),
-            view_mode: Some("list".to_string()),
+    fn message_format_refuses_formats_without_rows() {
+        assert_eq!(
+            MessageFormat::for_command(OutputFormat::Json, "task count").unwrap(),
+            MessageFormat::Json
+        );
+        let err = MessageFormat::for_command(OutputFormat::Tsv, "task count").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "'tt task count' has no tsv output; use --output human or json"
+        );
+    }
+
+    #[test]
+    fn output_context_carries_compact_json_fields_and_no_header() {
+        let projects = vec![Project {
+            id: Some("project-1".to_string()),
+            name: "Work".to_string(),
+            ..Default::default()
+        }];
+        let context = OutputContext {
+            compact_json: true,
+            fields: vec!["name".to_string(), "id".to_string()],
+            no_header: true,
             ..Default::default()
         };
 
+        let json = render_json(&projects, &context);
+        assert_eq!(json.lines().count(), 1);
+        assert!(json.starts_with("[{\"id\":\"project-1\""));
         assert_eq!(
-            project.rows(),
-            vec![
-                "12345678...".to_string(),
-                "Inbox".to_string(),
-                "#ff0000".to_string(),
-                "list".to_string(),
-            ]
+            render_delimited(&projects, OutputFormat::Csv, &context),
+            "Work,project-1\n"
         );
+        assert!(render_json(&projects, &OutputContext::default()).contains("\n  "));
     }
 
     #[test]
-    fn render_table_handles_empty_lists() {
-        let tasks: Vec<Task> = Vec::new();
-        assert_eq!(render_table(&tasks), "No items found.\n");
+    fn render_task_table_uses_due_cell_override() {
+        let tasks = vec![Task {
+            id: Some("task-1".to_string()),
+            title: "Ship".to_string(),
+            due_date: Some("2026-03-01T09:30:00.000+0000".to_string()),
+            ..Default::default()
+        }];
+        let due_cell = |_: &Task| "Mar 01".to_string();
+
+        let default = render_tasks(
+            &tasks,
+            OutputFormat::Human,
+            TaskListOptions::default(),
+            &terminal(),
+        );
+        assert!(default.contains("2026-03-01"));
+
+        let custom = render_tasks(
+            &tasks,
+            OutputFormat::Human,
+            TaskListOptions {
+                show_status: true,
+                due_cell: Some(&due_cell),
+                ..Default::default()
+            },
+            &terminal(),
+        );
+        assert!(custom.contains("Mar 01"));
+        assert!(!custom.contains("2026-03-01"));
     }
 
     #[test]
-    fn render_tasks_uses_pipe_output_for_non_tty_human_mode() {
+    fn render_task_table_adds_completed_column_after_due() {
         let tasks = vec![Task {
             id: Some("task-1".to_string()),
-            title: "Write tests".to_string(),
+            title: "Ship".to_string(),
+            status: Some(TaskStatus::Completed),
+            ..Default::default()
+        }];
+        let completed_cell = |_: &Task| "2026-03-05".to_string();
+
+        let table = render_tasks(
+            &tasks,
+            OutputFormat::Human,
+            TaskListOptions {
+                show_status: true,
+                completed_cell: Some(&completed_cell),
+                ..Default::default()
+            },
+            &terminal(),
+        );
+        let lines: Vec<&str> = table.lines().collect();
+        assert!(
+            lines[0].contains("| Due | Completed  | Note |"),
+            "{}",
+            lines[0]
+        );
+        assert!(lines[2].contains("|     | 2026-03-05 |"), "{}", lines[2]);
+    }
+
+    #[test]
+    fn render_task_table_adds_status_column_when_requested() {
+        let tasks = vec![
+            Task {
+                id: Some("task-1".to_string()),
+                title: "Ship".to_string(),
+                status: Some(TaskStatus::Completed),
+                ..Default::default()
+            },
+            Task {
+                id: Some("task-2".to_string()),
+                title: "Plan".to_string(),
+                ..Default::default()
+            },
+        ];
+        let options = TaskListOptions {
+            show_status: true,
             ..Default::default()
+        };
+
+        let table = render_tasks(&tasks, OutputFormat::Human, options, &terminal());
+        let lines: Vec<&str> = table.lines().collect();
+        assert!(lines[0].starts_with("| ID     | Status | Title |"));
+        assert!(lines[2].starts_with("| task-1 | ✓ done | Ship  |"));
+        assert!(lines[3].starts_with("| task-2 | open   | Plan  |"));
+
+        let plain = render_tasks(
+            &tasks,
+            OutputFormat::Human,
+            TaskListOptions::default(),
+            &terminal(),
+        );
+        assert!(!plain.contains("Status"));
+    }
+
+    fn grouped_sample() -> Vec<TaskGroup> {
+        vec![
+            TaskGroup {
+                key: "Work".to_string(),
+                tasks: vec![
+                    Task {
+                        id: Some("task-1".to_string()),
+                        title: "Ship release".to_string(),
+                        ..Default::default()
+                    },
+                    Task {
+                        id: Some("task-2".to_string()),
+                        title: "Review PR".to_string(),
+                        ..Default::default()
+                    },
+                ],
+            },
+            TaskGroup {
+                key: "Home".to_string(),
+                tasks: vec![Task {
+                    id: Some("task-3".to_string()),
+                    title: "Water plants".to_string(),
+                    ..Default::default()
+                }],
+            },
+        ]
+    }
+
+    #[test]
+    fn render_task_groups_prints_section_headers_with_counts() {
+        assert_eq!(
+            render_task_groups(
+                &grouped_sample(),
+                OutputFormat::Human,
+                TaskListOptions::default(),
+                &OutputContext::default()
+            ),
+            "# Work (2)\ntask-1|Ship release\ntask-2|Review PR\n\n# Home (1)\ntask-3|Water plants\n"
+        );
+    }
+
+    #[test]
+    fn render_task_groups_nests_tasks_in_json() {
+        let json = render_task_groups(
+            &grouped_sample(),
+            OutputFormat::Json,
+            TaskListOptions::default(),
+            &OutputContext::default(),
+        );
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(value["groups"][0]["key"], "Work");
+        assert_eq!(value["groups"][0]["tasks"][1]["id"], "task-2");
+        assert_eq!(value["groups"][1]["key"], "Home");
+        assert_eq!(value["groups"][1]["tasks"][0]["title"], "Water plants");
+    }
+
+    #[test]
+    fn render_task_groups_handles_empty_groups() {
+        assert_eq!(
+            render_task_groups(
+                &[],
+                OutputFormat::Human,
+                TaskListOptions::default(),
+                &terminal()
+            ),
+            "No items found.\n"
+        );
+        assert_eq!(
+            render_task_groups(
+                &[],
+                OutputFormat::Json,
+                TaskListOptions::default(),
+                &OutputContext::default()
+            ),
+            "{\n  \"groups\": []\n}\n"
+        );
+    }
+
+    #[test]
+    fn render_habit_checkin_shows_goal_progress_and_streak() {
+        let mut outcome = HabitCheckinOutcome {
+            habit_id: "h1".to_string(),
+            title: "Drink water".to_string(),
+            date: "2026-03-02".to_string(),
+            value: Some(3.0),
+            goal: Some(8.0),
+            unit: Some("glasses".to_string()),
+            completed: false,
+            already_checked_in: false,
+            current_streak: None,
+            total_check_ins: None,
+        };
+        assert_eq!(
+            render_habit_checkin(&outcome, MessageFormat::Human, &OutputContext::default()),
+            "Checked in: Drink water (2026-03-02), 3/8 glasses\n"
+        );
+
+        outcome.title = "Read".to_string();
+        outcome.value = Some(1.0);
+        outcome.goal = Some(1.0);
+        outcome.unit = None;
+        outcome.current_streak = Some(5);
+        assert_eq!(
+            render_habit_checkin(&outcome, MessageFormat::Human, &OutputContext::default()),
+            "Checked in: Read (2026-03-02), streak 5 days\n"
+        );
+
+        outcome.already_checked_in = true;
+        outcome.current_streak = None;
+        assert_eq!(
+            render_habit_checkin(&outcome, MessageFormat::Human, &OutputContext::default()),
+            "Already checked in: Read (2026-03-02)\n"
+        );
+
+        let json: serde_json::Value = serde_json::from_str(&render_habit_checkin(
+            &outcome,
+            MessageFormat::Json,
+            &OutputContext::default(),
+        ))
+        .unwrap();
+        assert_eq!(json["habitId"], "h1");
+        assert_eq!(json["alreadyCheckedIn"], true);
+        assert!(json.get("currentStreak").is_none());
+    }
+
+    #[test]
+    fn render_habit_stats_lines_up_rates_and_the_recent_strip() {
+        let stats = HabitStats {
+            habit_id: "h1".to_string(),
+            title: "Read".to_string(),
+            current_streak: 1,
+            longest_streak: 12,
+            last_30_days: HabitRate {
+                days: 30,
+                completed: 20,
+                scheduled: 30,
+            },
+            last_90_days: HabitRate {
+                days: 90,
+                completed: 0,
+                scheduled: 0,
+            },
+            recent: "✓✓·-✓".to_string(),
+            done_today: true,
+        };
+        assert_eq!(
+            render_habit_stats(&stats, MessageFormat::Human, &OutputContext::default()),
+            "Read\n  Current streak: 1 day\n  Longest streak: 12 days\n  Last 30 days:   67% (20/30)\n  Last 90 days:   -\n  Recent:         ✓✓·-✓\n"
+        );
+
+        let json: serde_json::Value = serde_json::from_str(&render_habit_stats(
+            &stats,
+            MessageFormat::Json,
+            &OutputContext::default(),
+        ))
+        .unwrap();
+        assert_eq!(json["longestStreak"], 12);
+        assert_eq!(json["last30Days"]["completed"], 20);
+        assert_eq!(json["recent"], "✓✓·-✓");
+    }
+
+    #[test]
+    fn render_project_tree_indents_lists_under_folders() {
+        let project = |id: &str, name: &str| Project {
+            id: Some(id.to_string()),
+            name: name.to_string(),
+            ..Default::default()
+        };
+        let folders = vec![
+            ProjectFolder {
+                folder_id: Some("g1".to_string()),
+                name: "g1".to_string(),
+                projects: vec![
+                    ProjectSummary::without_counts(project("p1", "Work")),
+                    ProjectSummary::without_counts(project("p2", "Side")),
+                ],
+            },
+            ProjectFolder {
+                folder_id: None,
+                name: "(no folder)".to_string(),
+                projects: vec![ProjectSummary::without_counts(project("p3", "Home"))],
+            },
+        ];
+
+        assert_eq!(
+            render_project_tree(&folders, OutputFormat::Human, &OutputContext::default()),
+            "g1 (2)\n  Work  p1\n  Side  p2\n\n(no folder) (1)\n  Home  p3\n"
+        );
+
+        let value: serde_json::Value = serde_json::from_str(&render_project_tree(
+            &folders,
+            OutputFormat::Json,
+            &OutputContext::default(),
+        ))
+        .unwrap();
+        assert_eq!(value["folders"][0]["folderId"], "g1");
+        assert_eq!(value["folders"][0]["projects"][1]["name"], "Side");
+        assert!(value["folders"][1]["folderId"].is_null());
+
+        let mut counted = folders;
+        counted[0].projects[0].open_count = Some(4);
+        counted[0].projects[0].overdue_count = Some(1);
+        counted[0].projects[1].open_count = Some(2);
+        counted[0].projects[1].overdue_count = Some(0);
+        assert!(
+            render_project_tree(&counted, OutputFormat::Human, &OutputContext::default())
+                .starts_with("g1 (2)\n  Work  p1  4 open, 1 overdue\n  Side  p2  2 open\n")
+        );
+        let value: serde_json::Value = serde_json::from_str(&render_project_tree(
+            &counted,
+            OutputFormat::Json,
+            &OutputContext::default(),
+        ))
+        .unwrap();
+        assert_eq!(value["folders"][0]["projects"][0]["openCount"], 4);
+    }
+
+    #[test]
+    fn task_search_results_include_list_column_and_project_name() {
+        let results = vec![TaskSearchResult {
+            project_name: "Work".to_string(),
+            task: Task {
+                id: Some("task-1".to_string()),
+                title: "Ship release".to_string(),
+                ..Default::default()
+            },
         }];
 
+        let table = render_task_search_results(
+            &results,
+            OutputFormat::Human,
+            &terminal(),
+            TableLayout::default(),
+        );
+        assert!(table.starts_with("| ID     | Title        | List |"));
         assert_eq!(
-            render_tasks(&tasks, OutputFormat::Human, false),
-            "task-1|Write tests\n"
+            render_task_search_results(
+                &results,
+                OutputFormat::Human,
+                &OutputContext::default(),
+                TableLayout::default()
+            ),
+            "task-1|Ship release|Work\n"
+        );
+
+        let json = render_task_search_results(
+            &results,
+            OutputFormat::Json,
+            &OutputContext::default(),
+            TableLayout::default(),
         );
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value[0]["projectName"], "Work");
+        assert_eq!(value[0]["id"], "task-1");
+    }
+
+    #[test]
+    fn render_error_json_includes_message_and_causes() {
+        let err = anyhow::anyhow!("connection refused").context("Failed to fetch projects");
+        let value: serde_json::Value = serde_json::from_str(&render_error_json(&err)).unwrap();
+
+        assert_eq!(value["error"], "Failed to fetch projects");
+        assert_eq!(value["context"], serde_json::json!(["connection refused"]));
+
+        let plain = render_error_json(&anyhow::anyhow!("Task not found"));
+        assert_eq!(plain, "{\"error\":\"Task not found\",\"context\":[]}\n");
     }
 
     #[test]
@@ -362,12 +3042,231 @@ mod tests {
             ..Default::default()
         }];
 
-        let json = render_projects(&projects, OutputFormat::Json, false);
+        let json = render_projects(
+            &projects,
+            OutputFormat::Json,
+            &OutputContext::default(),
+            TableLayout::default(),
+        );
         assert!(json.contains("\"name\": \"Inbox\""));
 
-        let table = render_projects(&projects, OutputFormat::Human, true);
+        let table = render_projects(
+            &projects,
+            OutputFormat::Human,
+            &terminal(),
+            TableLayout::default(),
+        );
         assert!(table.contains("| ID"));
         assert!(table.contains("12345678..."));
         assert!(table.contains("kanban"));
+
+        let closed = Project {
+            closed: Some(true),
+            ..projects[0].clone()
+        };
+        assert_eq!(closed.rows()[1], "Inbox (closed)");
+    }
+
+    #[test]
+    fn render_tag_counts_supports_table_lines_and_json() {
+        let counts = vec![TagCount {
+            tag: "errands".to_string(),
+            open: 2,
+            total: 5,
+        }];
+
+        let table = render_tag_counts(
+            &counts,
+            OutputFormat::Human,
+            &terminal(),
+            TableLayout::default(),
+        );
+        assert!(table.contains("| Tag"));
+        assert!(table.contains("#errands"));
+        assert_eq!(
+            render_tag_counts(
+                &counts,
+                OutputFormat::Human,
+                &OutputContext::default(),
+                TableLayout::default()
+            ),
+            "errands|2|5\n"
+        );
+
+        let json: serde_json::Value = serde_json::from_str(&render_tag_counts(
+            &counts,
+            OutputFormat::Json,
+            &OutputContext::default(),
+            TableLayout::default(),
+        ))
+        .unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!([{ "tag": "errands", "open": 2, "total": 5 }])
+        );
+    }
+
+    #[test]
+    fn render_columns_prints_id_and_name() {
+        let columns = vec![Column {
+            id: "c1".to_string(),
+            project_id: "p1".to_string(),
+            name: "Doing".to_string(),
+            sort_order: Some(1),
+        }];
+
+        let lines = render_columns(
+            &columns,
+            OutputFormat::Human,
+            &OutputContext::default(),
+            TableLayout::default(),
+        );
+        assert_eq!(lines, "c1|Doing\n");
+        let table = render_columns(
+            &columns,
+            OutputFormat::Human,
+            &terminal(),
+            TableLayout::default(),
+        );
+        assert!(table.contains("| Name"));
+        let json = render_columns(
+            &columns,
+            OutputFormat::Json,
+            &OutputContext::default(),
+            TableLayout::default(),
+        );
+        assert!(json.contains("\"projectId\": \"p1\""));
+    }
+
+    #[test]
+    fn project_summaries_add_counts_and_mark_unknown_ones() {
+        let summaries = vec![
+            ProjectSummary {
+                project: Project {
+                    id: Some("p1".to_string()),
+                    name: "Work".to_string(),
+                    ..Default::default()
+                },
+                open_count: Some(12),
+                overdue_count: Some(3),
+            },
+            ProjectSummary {
+                project: Project {
+                    id: Some("p2".to_string()),
+                    name: "Home".to_string(),
+                    ..Default::default()
+                },
+                open_count: None,
+                overdue_count: None,
+            },
+        ];
+
+        let table = render_project_summaries(
+            &summaries,
+            OutputFormat::Human,
+            &terminal(),
+            TableLayout::default(),
+        );
+        assert!(table.contains("| Open"));
+        assert!(table.contains("| Overdue"));
+
+        let lines = render_project_summaries(
+            &summaries,
+            OutputFormat::Human,
+            &OutputContext::default(),
+            TableLayout::default(),
+        );
+        assert_eq!(lines, "p1|Work|12|3\np2|Home|?|?\n");
+
+        let json = render_project_summaries(
+            &summaries,
+            OutputFormat::Json,
+            &OutputContext::default(),
+            TableLayout::default(),
+        );
+        assert!(json.contains("\"openCount\": 12"));
+        assert!(json.contains("\"overdueCount\": null"));
+    }
+
+    fn habit_summary(checked_in_today: Option<bool>, streak: Option<u32>) -> HabitSummary {
+        HabitSummary {
+            habit: Habit {
+                id: Some("h1".to_string()),
+                title: "Drink water".to_string(),
+                goal: Some(8.0),
+                unit: Some("Cup".to_string()),
+                ..Default::default()
+            },
+            schedule: "daily".to_string(),
+            scheduled_today: true,
+            streak,
+            checked_in_today,
+        }
+    }
+
+    #[test]
+    fn habit_rows_show_goal_unit_streak_and_today_marker() {
+        assert_eq!(
+            habit_summary(Some(true), Some(5)).rows(),
+            vec!["h1", "Drink water", "daily", "8", "Cup", "5", "✓"]
+        );
+        assert_eq!(habit_summary(Some(false), Some(0)).rows()[6], "·");
+
+        let blank = habit_summary(None, None).rows();
+        assert_eq!(blank[5], "");
+        assert_eq!(blank[6], "");
+
+        let mut archived = habit_summary(None, None);
+        archived.habit.status = Some(1);
+        assert_eq!(archived.rows()[1], "Drink water (archived)");
+    }
+
+    #[test]
+    fn habit_table_colors_today_marker_without_breaking_alignment() {
+        let output = render_habits(
+            &[
+                habit_summary(Some(true), Some(5)),
+                habit_summary(None, None),
+            ],
+            OutputFormat::Human,
+            &OutputContext {
+                is_tty: true,
+                color: true,
+                ..Default::default()
+            },
+            TableLayout::default(),
+        );
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert!(lines[2].contains("\x1b[32m✓\x1b[0m"));
+        assert_eq!(visible_width(lines[2]), visible_width(lines[0]));
+        assert_eq!(visible_width(lines[3]), visible_width(lines[0]));
+    }
+
+    #[test]
+    fn habit_lines_are_plain_when_piped() {
+        let output = render_habits(
+            &[habit_summary(Some(false), Some(2))],
+            OutputFormat::Human,
+            &OutputContext::default(),
+            TableLayout::default(),
+        );
+        assert_eq!(output, "h1|Drink water|2|·\n");
+    }
+
+    #[test]
+    fn habit_json_includes_stats_only_when_known() {
+        let output = render_habits(
+            &[habit_summary(None, Some(3))],
+            OutputFormat::Json,
+            &OutputContext::default(),
+            TableLayout::default(),
+        );
+        let value: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(value[0]["title"], "Drink water");
+        assert_eq!(value[0]["streak"], 3);
+        assert_eq!(value[0]["scheduledToday"], true);
+        assert!(value[0].get("checkedInToday").is_none());
+        assert!(value[0].get("schedule").is_none());
     }
 }
@@ -87,7 +87,7 @@ impl CliSandbox {
             self.config_file(),
             format!(
                 concat!(
-                    "access_token = \"12345678abcdefgh\"\n",
+                    "access_token = \"12345678-middle-abcdefgh\"\n",
                     "refresh_token = \"refresh-token\"\n",
                     "expires_at = {}\n"
                 ),
@@ -113,7 +113,7 @@ fn help_lists_core_commands() {
     let sandbox = CliSandbox::new();
 
     sandbox.command().arg("--help").assert().success().stdout(
-        predicate::str::contains("Usage: tt <COMMAND>")
+        predicate::str::contains("Usage: tt [OPTIONS] <COMMAND>")
             .and(predicate::str::contains("login"))
             .and(predicate::str::contains("projects")),
     );
@@ -135,11 +135,28 @@ fn status_masks_saved_access_token() {
     let sandbox = CliSandbox::new();
     sandbox.write_config(4_102_444_800);
 
-    sandbox.command().arg("status").assert().success().stdout(
-        predicate::str::contains("Status: Authenticated").and(predicate::str::contains(
-            "Access Token: 12345678...abcdefgh",
-        )),
-    );
+    sandbox
+        .command()
+        .args(["status", "--no-verify"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("Status: Authenticated").and(predicate::str::contains(
+                "Access Token: 12345678...abcdefgh",
+            )),
+        );
+}
+
+#[test]
+fn quiet_status_exits_non_zero_without_auth() {
+    let sandbox = CliSandbox::new();
+
+    sandbox
+        .command()
+        .args(["status", "--quiet"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::is_empty());
 }
 
 #[test]
@@ -174,6 +191,109 @@ fn list_requires_authentication_before_network_requests() {
         ));
 }
 
+#[test]
+fn offline_refuses_commands_that_need_the_network() {
+    let sandbox = CliSandbox::new();
+
+    for (args, command) in [
+        (
+            ["task", "update", "t1", "--title", "Renamed"].as_slice(),
+            "task update",
+        ),
+        (["task", "complete", "t1"].as_slice(), "task complete"),
+        (["task", "delete", "t1"].as_slice(), "task delete"),
+        (["task", "add", "Milk"].as_slice(), "task add"),
+        (["task", "prio", "t1", "high"].as_slice(), "task prio"),
+        (
+            ["task", "items", "add", "t1", "Eggs"].as_slice(),
+            "task items add",
+        ),
+        (["project", "add", "Errands"].as_slice(), "project add"),
+        (
+            ["project", "archive", "Errands"].as_slice(),
+            "project archive",
+        ),
+        (
+            ["project", "column", "list", "Errands"].as_slice(),
+            "project column list",
+        ),
+        (
+            ["tag", "rename", "errand", "errands"].as_slice(),
+            "tag rename",
+        ),
+        (["habit", "list"].as_slice(), "habit list"),
+        (["export"].as_slice(), "export"),
+    ] {
+        sandbox
+            .command()
+            .arg("--offline")
+            .args(args)
+            .assert()
+            .failure()
+            .stdout(predicate::str::is_empty())
+            .stderr(predicate::str::contains(format!(
+                "'tt {}' needs the network; drop --offline",
+                command
+            )));
+    }
+}
+
+#[test]
+fn offline_project_reads_come_from_the_snapshot() {
+    let sandbox = CliSandbox::new();
+    fs::create_dir_all(sandbox.config_dir()).unwrap();
+    fs::write(
+        sandbox.config_dir().join("sync.json"),
+        r#"{"synced_at":4102444800,"projects":[{"id":"p1","name":"Errands"}],"tasks":[{"id":"t1","projectId":"p1","title":"Milk"},{"id":"t2","projectId":"p2","title":"Elsewhere"}]}"#,
+    )
+    .unwrap();
+
+    sandbox
+        .command()
+        .args([
+            "--offline",
+            "project",
+            "data",
+            "Errands",
+            "--output",
+            "json",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"title\": \"Milk\""))
+        .stdout(predicate::str::contains("Elsewhere").not());
+}
+
+#[test]
+fn json_output_reports_errors_as_json_on_stderr() {
+    let sandbox = CliSandbox::new();
+
+    sandbox
+        .command()
+        .args(["ls", "--output", "json"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::eq(
+            "{\"error\":\"Not authenticated. Run 'tt auth login' first.\",\"context\":[]}\n",
+        ));
+}
+
+#[test]
+fn quick_add_parse_errors_use_the_json_error_envelope() {
+    let sandbox = CliSandbox::new();
+
+    sandbox
+        .command()
+        .args(["buy", "milk", "--output", "json", "--bogus"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::starts_with(
+            "{\"error\":\"unexpected argument '--bogus'",
+        ));
+}
+
 #[test]
 fn login_defaults_to_shared_broker_when_env_is_missing() {
     let sandbox = CliSandbox::new();
@@ -191,3 +311,43 @@ fn login_defaults_to_shared_broker_when_env_is_missing() {
             "TICKTICK_REDIRECT_URI must use http for the local callback server",
         ));
 }
+
+#[test]
+fn compact_flag_prints_json_on_one_line() {
+    let sandbox = CliSandbox::new();
+    sandbox
+        .command()
+        .args(["template", "save", "standup", "--title", "Standup notes"])
+        .assert()
+        .success();
+
+    sandbox
+        .command()
+        .args(["template", "list", "--output", "json", "--compact"])
+        .assert()
+        .success()
+        .stdout(predicate::eq(
+            "[{\"name\":\"standup\",\"template\":{\"title\":\"Standup notes\"}}]\n",
+        ));
+
+    sandbox
+        .command()
+        .args(["template", "list", "--output", "json"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::starts_with("[\n  {\n    \"name\": \"standup\",")
+                .and(predicate::str::ends_with("}\n]\n")),
+        );
+}
+
+#[test]
+fn fields_flag_rejects_unknown_field_names() {
+    let sandbox = CliSandbox::new();
+    sandbox
+        .command()
+        .args(["template", "list", "--fields", "id,bogus"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unknown field 'bogus'"));
+}